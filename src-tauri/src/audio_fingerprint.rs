@@ -0,0 +1,718 @@
+//! audio_fingerprint — Empreinte du signal d'un fichier audio + vérification (V2-D).
+//!
+//! Portée STRICTE : lire un WAV, un FLAC ou un MP3 et en tirer une
+//! `evidence_kernel::AudioFingerprint` dont l'empreinte ne dépend QUE du signal,
+//! jamais des tags (BWF `bext`, LIST, Vorbis comments, ID3v2/ID3v1, APEv2) :
+//! - WAV (PCM entier ou flottant) : SHA256 du PCM décodé — les octets du chunk
+//!   `data`, précédés du format (`PCM_SCHEME`) ;
+//! - FLAC : SHA256 des paramètres du flux (STREAMINFO : fréquence, canaux,
+//!   profondeur, nombre d'échantillons) et des trames hors tags
+//!   (`FRAMES_SCHEME`). Le MD5 que STREAMINFO déclare n'est PAS repris : écrit
+//!   par le fichier lui-même, jamais contrôlé ici, il survivrait à une
+//!   retouche des trames ;
+//! - MP3 : SHA256 des trames MPEG hors tags (`FRAMES_SCHEME`). Le PCM décodé
+//!   d'un MP3 n'est pas identique au bit près d'un décodeur à l'autre : le flux
+//!   de trames est la seule référence stable.
+//!
+//! Puis comparer un fichier candidat à cette référence (`SignalIdentical` /
+//! `Different`). Le SHA256 du fichier reste la preuve ; cette empreinte sert à
+//! reconnaître le même enregistrement après ré-étiquetage. AUCUNE écriture,
+//! AUCUN décodage de trames compressées, AUCUNE commande Tauri.
+//!
+//! Garde-fous :
+//! - format détecté par signature (magic), pas par extension ;
+//! - lecture en flux (`Read + Seek`) : seuls les en-têtes sont chargés, le
+//!   signal est haché par blocs (`file_hash::stream_reader`) ;
+//! - tout chunk / bloc / tag déclarant une taille hors fichier -> `Err` ;
+//! - WAV compressé (ni PCM ni flottant) -> `Err` ;
+//! - références de schémas différents (ex. WAV contre FLAC) -> `Err` (jamais de
+//!   faux « identique »).
+
+// Fondation (V2-D) : consommé par `work_audio` (sidecar + tags) ; verifier sans UI.
+#![allow(dead_code)]
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::Path;
+
+use crate::evidence_kernel::{AudioFingerprint, AudioSignalDigest};
+
+/// SHA256 du PCM décodé (WAV) : domaine + format + octets du chunk `data`.
+pub(crate) const PCM_SCHEME: &str = "HO-PCM-SHA256-V1";
+/// SHA256 du flux de trames hors tags (FLAC, MP3).
+pub(crate) const FRAMES_SCHEME: &str = "HO-AUDIO-FRAMES-SHA256-V1";
+
+const PCM_DOMAIN: &[u8] = b"HO-PCM-V1\0";
+const FRAMES_DOMAIN: &[u8] = b"HO-AUDIO-FRAMES-V1\0";
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// Type de bloc FLAC STREAMINFO (toujours le premier).
+pub(crate) const FLAC_STREAMINFO: u8 = 0;
+const FLAC_STREAMINFO_LEN: u64 = 34;
+/// STREAMINFO : fréquence, canaux, profondeur et nombre total d'échantillons.
+const FLAC_STREAM_PARAMS: Range<usize> = 10..18;
+
+/// Fenêtre de recherche de la première trame MPEG.
+const MPEG_SCAN_WINDOW: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AudioFormat {
+    Wav,
+    Flac,
+    Mp3,
+}
+
+impl AudioFormat {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Mp3 => "mp3",
+        }
+    }
+
+    pub(crate) fn mime_type(self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "audio/wav",
+            AudioFormat::Flac => "audio/flac",
+            AudioFormat::Mp3 => "audio/mpeg",
+        }
+    }
+}
+
+/// Format d'un fichier audio d'après sa signature (magic).
+pub(crate) fn detect_audio_format(bytes: &[u8]) -> Option<AudioFormat> {
+    if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE" {
+        Some(AudioFormat::Wav)
+    } else if bytes.starts_with(b"fLaC") {
+        Some(AudioFormat::Flac)
+    } else if bytes.starts_with(b"ID3") || is_mpeg_frame_header(bytes) {
+        Some(AudioFormat::Mp3)
+    } else {
+        None
+    }
+}
+
+/// Format d'un flux audio d'après ses premiers octets.
+pub(crate) fn detect_audio_stream<R: Read + Seek>(
+    r: &mut R,
+) -> Result<Option<AudioFormat>, String> {
+    Ok(detect_audio_format(&read_head(r, 12)?))
+}
+
+/// Résultat de la comparaison d'un fichier candidat à une référence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "result")]
+pub(crate) enum AudioMatch {
+    SignalIdentical,
+    Different,
+}
+
+impl AudioMatch {
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            AudioMatch::SignalIdentical => "signal-identical",
+            AudioMatch::Different => "different",
+        }
+    }
+}
+
+fn u16_le(b: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([b[at], b[at + 1]])
+}
+
+fn u32_le(b: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([b[at], b[at + 1], b[at + 2], b[at + 3]])
+}
+
+// --- LECTURE EN FLUX ----------------------------------------------------------
+
+/// Taille totale du flux.
+pub(crate) fn stream_len<R: Seek>(r: &mut R) -> Result<u64, String> {
+    r.seek(SeekFrom::End(0)).map_err(|e| e.to_string())
+}
+
+/// Octets `range` du flux (étendue déjà validée contre sa taille). Réservé
+/// aux en-têtes et métadonnées : le signal passe par `hash_range`.
+pub(crate) fn read_range<R: Read + Seek>(r: &mut R, range: Range<u64>) -> Result<Vec<u8>, String> {
+    let len = usize::try_from(range.end - range.start)
+        .map_err(|_| "étendue trop grande pour être chargée".to_string())?;
+    r.seek(SeekFrom::Start(range.start))
+        .map_err(|e| e.to_string())?;
+    let mut buf = vec![0u8; len];
+    r.read_exact(&mut buf).map_err(|e| e.to_string())?;
+    Ok(buf)
+}
+
+/// Au plus `n` premiers octets du flux.
+fn read_head<R: Read + Seek>(r: &mut R, n: u64) -> Result<Vec<u8>, String> {
+    let len = stream_len(r)?;
+    read_range(r, 0..n.min(len))
+}
+
+/// Hache l'étendue `range` du flux par blocs (mémoire constante).
+fn hash_range<R: Read + Seek>(
+    r: &mut R,
+    range: Range<u64>,
+    hasher: &mut Sha256,
+) -> Result<(), String> {
+    r.seek(SeekFrom::Start(range.start))
+        .map_err(|e| e.to_string())?;
+    let mut taken = r.take(range.end - range.start);
+    crate::file_hash::stream_reader(&mut taken, |b| hasher.update(b)).map_err(|e| e.to_string())?;
+    if taken.limit() != 0 {
+        return Err("flux audio tronqué".to_string());
+    }
+    Ok(())
+}
+
+// --- WAV (RIFF) ---------------------------------------------------------------
+
+/// Chunk RIFF : identifiant, étendue complète (en-tête + données + octet de
+/// bourrage éventuel) et étendue des données.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RiffChunk {
+    pub id: [u8; 4],
+    pub span: Range<u64>,
+    pub data: Range<u64>,
+}
+
+/// Chunks d'un fichier `RIFF/WAVE`, dans l'ordre du fichier.
+pub(crate) fn riff_chunks<R: Read + Seek>(r: &mut R) -> Result<Vec<RiffChunk>, String> {
+    if detect_audio_stream(r)? != Some(AudioFormat::Wav) {
+        return Err("pas un fichier RIFF/WAVE".to_string());
+    }
+    let len = stream_len(r)?;
+    let mut chunks = Vec::new();
+    let mut off = 12;
+    while off + 8 <= len {
+        let header = read_range(r, off..off + 8)?;
+        let id = [header[0], header[1], header[2], header[3]];
+        let size = u64::from(u32_le(&header, 4));
+        let data = off + 8..off + 8 + size;
+        if data.end > len {
+            return Err(format!("chunk {} tronqué", String::from_utf8_lossy(&id)));
+        }
+        let end = (data.end + (size & 1)).min(len);
+        chunks.push(RiffChunk {
+            id,
+            span: off..end,
+            data,
+        });
+        off = end;
+    }
+    Ok(chunks)
+}
+
+fn wav_fingerprint<R: Read + Seek>(r: &mut R) -> Result<AudioFingerprint, String> {
+    let chunks = riff_chunks(r)?;
+    let fmt = chunks
+        .iter()
+        .find(|c| &c.id == b"fmt ")
+        .ok_or_else(|| "WAV sans chunk fmt".to_string())?;
+    if fmt.data.end - fmt.data.start < 16 {
+        return Err("chunk fmt trop court".to_string());
+    }
+    // Les champs utiles tiennent dans les 26 premiers octets (extensible).
+    let f = read_range(r, fmt.data.start..fmt.data.end.min(fmt.data.start + 26))?;
+    let mut format_code = u16_le(&f, 0);
+    if format_code == WAVE_FORMAT_EXTENSIBLE {
+        if f.len() < 26 {
+            return Err("chunk fmt extensible trop court".to_string());
+        }
+        // Premier champ du GUID de sous-format = code de format effectif.
+        format_code = u16_le(&f, 24);
+    }
+    if format_code != WAVE_FORMAT_PCM && format_code != WAVE_FORMAT_IEEE_FLOAT {
+        return Err(format!(
+            "WAV compressé non supporté (format {format_code:#06x})"
+        ));
+    }
+    let channels = u16_le(&f, 2);
+    let sample_rate = u32_le(&f, 4);
+    let bits = u16_le(&f, 14);
+
+    let mut data_chunks = chunks.iter().filter(|c| &c.id == b"data");
+    let data = data_chunks
+        .next()
+        .ok_or_else(|| "WAV sans chunk data".to_string())?;
+    if data_chunks.next().is_some() {
+        return Err("WAV avec plusieurs chunks data".to_string());
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(PCM_DOMAIN);
+    hasher.update(format_code.to_le_bytes());
+    hasher.update(channels.to_le_bytes());
+    hasher.update(sample_rate.to_le_bytes());
+    hasher.update(bits.to_le_bytes());
+    hash_range(r, data.data.clone(), &mut hasher)?;
+    Ok(AudioFingerprint {
+        container: AudioFormat::Wav.label().to_string(),
+        sample_rate,
+        channels,
+        bits_per_sample: Some(bits),
+        signal: AudioSignalDigest {
+            scheme: PCM_SCHEME.to_string(),
+            value: format!("{:x}", hasher.finalize()),
+        },
+    })
+}
+
+// --- FLAC ---------------------------------------------------------------------
+
+/// Bloc de métadonnées FLAC : type, drapeau « dernier », étendue complète
+/// (en-tête de 4 octets inclus) et étendue des données.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FlacBlock {
+    pub kind: u8,
+    pub is_last: bool,
+    pub span: Range<u64>,
+    pub data: Range<u64>,
+}
+
+/// Blocs de métadonnées d'un FLAC + position du début des trames audio.
+pub(crate) fn flac_metadata_blocks<R: Read + Seek>(
+    r: &mut R,
+) -> Result<(Vec<FlacBlock>, u64), String> {
+    if detect_audio_stream(r)? != Some(AudioFormat::Flac) {
+        return Err("pas un fichier FLAC".to_string());
+    }
+    let len = stream_len(r)?;
+    let mut blocks = Vec::new();
+    let mut off = 4;
+    loop {
+        if off + 4 > len {
+            return Err("métadonnées FLAC tronquées".to_string());
+        }
+        let h = read_range(r, off..off + 4)?;
+        let kind = h[0] & 0x7F;
+        if kind == 127 {
+            return Err("bloc FLAC invalide".to_string());
+        }
+        let block_len = (u64::from(h[1]) << 16) | (u64::from(h[2]) << 8) | u64::from(h[3]);
+        let data = off + 4..off + 4 + block_len;
+        if data.end > len {
+            return Err("bloc FLAC tronqué".to_string());
+        }
+        let is_last = h[0] & 0x80 != 0;
+        blocks.push(FlacBlock {
+            kind,
+            is_last,
+            span: off..data.end,
+            data: data.clone(),
+        });
+        off = data.end;
+        if is_last {
+            break;
+        }
+    }
+    match blocks.first() {
+        Some(b)
+            if b.kind == FLAC_STREAMINFO && b.data.end - b.data.start == FLAC_STREAMINFO_LEN => {}
+        _ => return Err("FLAC sans STREAMINFO en tête".to_string()),
+    }
+    Ok((blocks, off))
+}
+
+fn flac_fingerprint<R: Read + Seek>(r: &mut R) -> Result<AudioFingerprint, String> {
+    let (blocks, audio_start) = flac_metadata_blocks(r)?;
+    let si = read_range(r, blocks[0].data.clone())?;
+    let sample_rate =
+        (u32::from(si[10]) << 12) | (u32::from(si[11]) << 4) | (u32::from(si[12]) >> 4);
+    let channels = u16::from((si[12] >> 1) & 0x07) + 1;
+    let bits = ((u16::from(si[12] & 0x01) << 4) | u16::from(si[13] >> 4)) + 1;
+
+    // Paramètres du flux + trames hors tags : toute retouche des trames change
+    // l'empreinte, quel que soit le MD5 déclaré par STREAMINFO.
+    let end = trailing_tags_start(r, audio_start)?;
+    let mut hasher = Sha256::new();
+    hasher.update(FRAMES_DOMAIN);
+    hasher.update(&si[FLAC_STREAM_PARAMS]);
+    hash_range(r, audio_start..end, &mut hasher)?;
+    Ok(AudioFingerprint {
+        container: AudioFormat::Flac.label().to_string(),
+        sample_rate,
+        channels,
+        bits_per_sample: Some(bits),
+        signal: AudioSignalDigest {
+            scheme: FRAMES_SCHEME.to_string(),
+            value: format!("{:x}", hasher.finalize()),
+        },
+    })
+}
+
+// --- MP3 ----------------------------------------------------------------------
+
+/// Taille totale d'un tag ID3v2 de tête (en-tête et pied inclus), 0 si absent.
+pub(crate) fn id3v2_len<R: Read + Seek>(r: &mut R) -> Result<u64, String> {
+    let head = read_head(r, 10)?;
+    if !head.starts_with(b"ID3") {
+        return Ok(0);
+    }
+    if head.len() < 10 || head[6..10].iter().any(|&b| b & 0x80 != 0) {
+        return Err("en-tête ID3v2 invalide".to_string());
+    }
+    let size = head[6..10]
+        .iter()
+        .fold(0u64, |acc, &b| (acc << 7) | u64::from(b));
+    let footer = if head[5] & 0x10 != 0 { 10 } else { 0 };
+    let total = 10 + size + footer;
+    if total > stream_len(r)? {
+        return Err("tag ID3v2 tronqué".to_string());
+    }
+    Ok(total)
+}
+
+/// Fin du flux audio : avant un tag ID3v1 (`TAG`, 128 octets) et/ou APEv2 de
+/// queue.
+fn trailing_tags_start<R: Read + Seek>(r: &mut R, from: u64) -> Result<u64, String> {
+    let mut end = stream_len(r)?;
+    if end - from >= 128 && read_range(r, end - 128..end - 125)? == b"TAG" {
+        end -= 128;
+    }
+    if end - from >= 32 {
+        let footer = read_range(r, end - 32..end)?;
+        if &footer[..8] == b"APETAGEX" {
+            let size = u64::from(u32_le(&footer, 12));
+            let has_header = u32_le(&footer, 20) & 0x8000_0000 != 0;
+            let total = size + if has_header { 32 } else { 0 };
+            if total > end - from {
+                return Err("tag APEv2 tronqué".to_string());
+            }
+            end -= total;
+        }
+    }
+    Ok(end)
+}
+
+fn is_mpeg_frame_header(b: &[u8]) -> bool {
+    b.len() >= 4
+        && b[0] == 0xFF
+        && b[1] & 0xE0 == 0xE0
+        && (b[1] >> 3) & 0x03 != 1
+        && (b[1] >> 1) & 0x03 != 0
+        && b[2] >> 4 != 0x0F
+        && (b[2] >> 2) & 0x03 != 3
+}
+
+/// Étendue du flux de trames MPEG (de la première trame à la fin, hors tags).
+pub(crate) fn mp3_audio_range<R: Read + Seek>(r: &mut R) -> Result<Range<u64>, String> {
+    let start = id3v2_len(r)?;
+    let end = trailing_tags_start(r, start)?;
+    // Recherche par fenêtres ; 3 octets de recouvrement pour un en-tête à cheval.
+    let mut pos = start;
+    while pos + 4 <= end {
+        let window_end = (pos + MPEG_SCAN_WINDOW).min(end);
+        let window = read_range(r, pos..window_end)?;
+        if let Some(i) = (0..window.len() - 3).find(|&i| is_mpeg_frame_header(&window[i..])) {
+            return Ok(pos + i as u64..end);
+        }
+        if window_end == end {
+            break;
+        }
+        pos = window_end - 3;
+    }
+    Err("aucune trame MPEG".to_string())
+}
+
+fn mp3_fingerprint<R: Read + Seek>(r: &mut R) -> Result<AudioFingerprint, String> {
+    let range = mp3_audio_range(r)?;
+    let h = read_range(r, range.start..range.start + 4)?;
+    let version = (h[1] >> 3) & 0x03;
+    let rate_index = usize::from((h[2] >> 2) & 0x03);
+    let base = [44_100u32, 48_000, 32_000][rate_index];
+    let sample_rate = match version {
+        3 => base,     // MPEG-1
+        2 => base / 2, // MPEG-2
+        _ => base / 4, // MPEG-2.5
+    };
+    let channels = if h[3] >> 6 == 3 { 1 } else { 2 };
+
+    let mut hasher = Sha256::new();
+    hasher.update(FRAMES_DOMAIN);
+    hash_range(r, range, &mut hasher)?;
+    Ok(AudioFingerprint {
+        container: AudioFormat::Mp3.label().to_string(),
+        sample_rate,
+        channels,
+        bits_per_sample: None,
+        signal: AudioSignalDigest {
+            scheme: FRAMES_SCHEME.to_string(),
+            value: format!("{:x}", hasher.finalize()),
+        },
+    })
+}
+
+// --- EMPREINTE / VÉRIFICATION -------------------------------------------------
+
+/// Empreinte du signal d'un flux audio (WAV, FLAC, MP3), lu en flux.
+pub(crate) fn fingerprint_audio<R: Read + Seek>(r: &mut R) -> Result<AudioFingerprint, String> {
+    match detect_audio_stream(r)? {
+        Some(AudioFormat::Wav) => wav_fingerprint(r),
+        Some(AudioFormat::Flac) => flac_fingerprint(r),
+        Some(AudioFormat::Mp3) => mp3_fingerprint(r),
+        None => Err("format audio non supporté (WAV/FLAC/MP3)".to_string()),
+    }
+}
+
+/// Empreinte du signal d'un fichier audio en mémoire (WAV, FLAC, MP3).
+pub(crate) fn fingerprint_audio_bytes(bytes: &[u8]) -> Result<AudioFingerprint, String> {
+    fingerprint_audio(&mut Cursor::new(bytes))
+}
+
+pub(crate) fn fingerprint_audio_file(path: &Path) -> Result<AudioFingerprint, String> {
+    let mut file = File::open(path).map_err(|e| format!("lecture {} : {e}", path.display()))?;
+    fingerprint_audio(&mut file)
+}
+
+/// Compare deux empreintes. Schémas différents -> `Err` (incomparables).
+pub(crate) fn compare_fingerprints(
+    reference: &AudioFingerprint,
+    candidate: &AudioFingerprint,
+) -> Result<AudioMatch, String> {
+    if reference.signal.scheme != candidate.signal.scheme {
+        return Err(format!(
+            "schéma de signal différent : {} / {}",
+            reference.signal.scheme, candidate.signal.scheme
+        ));
+    }
+    if reference == candidate {
+        Ok(AudioMatch::SignalIdentical)
+    } else {
+        Ok(AudioMatch::Different)
+    }
+}
+
+/// Vérifie un fichier candidat (en mémoire) contre une empreinte de référence.
+pub(crate) fn verify_audio_bytes(
+    reference: &AudioFingerprint,
+    candidate: &[u8],
+) -> Result<AudioMatch, String> {
+    compare_fingerprints(reference, &fingerprint_audio_bytes(candidate)?)
+}
+
+pub(crate) fn verify_audio_file(
+    reference: &AudioFingerprint,
+    candidate: &Path,
+) -> Result<AudioMatch, String> {
+    compare_fingerprints(reference, &fingerprint_audio_file(candidate)?)
+}
+
+// --- TESTS UNITAIRES ----------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// WAV PCM 16 bits stéréo 8 kHz, `frames` échantillons déterministes.
+    fn sample_wav(frames: u32, seed: u8) -> Vec<u8> {
+        let mut data = Vec::new();
+        for i in 0..frames {
+            let v = ((i as i32 * 97 + i32::from(seed)) % 2000 - 1000) as i16;
+            data.extend_from_slice(&v.to_le_bytes());
+            data.extend_from_slice(&(-v).to_le_bytes());
+        }
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&WAVE_FORMAT_PCM.to_le_bytes());
+        fmt.extend_from_slice(&2u16.to_le_bytes());
+        fmt.extend_from_slice(&8_000u32.to_le_bytes());
+        fmt.extend_from_slice(&32_000u32.to_le_bytes());
+        fmt.extend_from_slice(&4u16.to_le_bytes());
+        fmt.extend_from_slice(&16u16.to_le_bytes());
+        let mut out = b"RIFF\0\0\0\0WAVE".to_vec();
+        for (id, body) in [(b"fmt ", fmt), (b"data", data)] {
+            out.extend_from_slice(id);
+            out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+            out.extend_from_slice(&body);
+        }
+        let riff = (out.len() - 8) as u32;
+        out[4..8].copy_from_slice(&riff.to_le_bytes());
+        out
+    }
+
+    /// FLAC minimal : STREAMINFO (44,1 kHz, stéréo, 16 bits, MD5 donné) +
+    /// trames factices (jamais décodées ici).
+    fn sample_flac(md5: [u8; 16]) -> Vec<u8> {
+        let mut si = vec![0u8; FLAC_STREAMINFO_LEN as usize];
+        si[0..2].copy_from_slice(&4096u16.to_be_bytes());
+        si[2..4].copy_from_slice(&4096u16.to_be_bytes());
+        // 44100 Hz (20 bits) | canaux-1 = 1 (3 bits) | bps-1 = 15 (5 bits).
+        si[10] = (44_100u32 >> 12) as u8;
+        si[11] = (44_100u32 >> 4) as u8;
+        si[12] = ((44_100u32 & 0x0F) as u8) << 4 | (1 << 1);
+        si[13] = 0xF0;
+        si[18..34].copy_from_slice(&md5);
+        let mut out = b"fLaC".to_vec();
+        out.push(0x80 | FLAC_STREAMINFO);
+        out.extend_from_slice(&(FLAC_STREAMINFO_LEN as u32).to_be_bytes()[1..]);
+        out.extend_from_slice(&si);
+        out.extend_from_slice(&[0xFF, 0xF8, 0x69, 0x08, 1, 2, 3, 4, 5, 6, 7, 8]);
+        out
+    }
+
+    /// MP3 : ID3v2.3 (TIT2) + 3 trames MPEG-1 Layer III 44,1 kHz + ID3v1.
+    fn sample_mp3() -> Vec<u8> {
+        let mut frame = b"TIT2".to_vec();
+        frame.extend_from_slice(&6u32.to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 0]);
+        frame.extend_from_slice(b"Titre");
+        let mut out = b"ID3\x03\x00\x00".to_vec();
+        let size = frame.len() as u32;
+        out.extend_from_slice(&[
+            (size >> 21) as u8 & 0x7F,
+            (size >> 14) as u8 & 0x7F,
+            (size >> 7) as u8 & 0x7F,
+            size as u8 & 0x7F,
+        ]);
+        out.extend_from_slice(&frame);
+        for i in 0..3u8 {
+            out.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x64]);
+            out.resize(out.len() + 413, i + 1);
+        }
+        let mut v1 = b"TAG".to_vec();
+        v1.resize(128, b' ');
+        out.extend_from_slice(&v1);
+        out
+    }
+
+    #[test]
+    fn test_1_detection_par_signature() {
+        assert_eq!(
+            detect_audio_format(&sample_wav(4, 0)),
+            Some(AudioFormat::Wav)
+        );
+        assert_eq!(
+            detect_audio_format(&sample_flac([1; 16])),
+            Some(AudioFormat::Flac)
+        );
+        assert_eq!(detect_audio_format(&sample_mp3()), Some(AudioFormat::Mp3));
+        assert_eq!(detect_audio_format(b"%PDF-1.7"), None);
+        assert!(fingerprint_audio_bytes(b"%PDF-1.7").is_err());
+    }
+
+    #[test]
+    fn test_2_wav_pcm_insensible_aux_chunks_de_tags() {
+        let wav = sample_wav(64, 3);
+        let fp = fingerprint_audio_bytes(&wav).unwrap();
+        assert_eq!(fp.signal.scheme, PCM_SCHEME);
+        assert_eq!((fp.sample_rate, fp.channels), (8_000, 2));
+        assert_eq!(fp.bits_per_sample, Some(16));
+
+        // Chunk LIST (tags) ajouté, de taille impaire (octet de bourrage) : même signal.
+        let mut tagged = wav.clone();
+        tagged.extend_from_slice(b"LIST\x05\x00\x00\x00INFOx\0");
+        let riff = (tagged.len() - 8) as u32;
+        tagged[4..8].copy_from_slice(&riff.to_le_bytes());
+        assert_eq!(
+            verify_audio_bytes(&fp, &tagged).unwrap(),
+            AudioMatch::SignalIdentical
+        );
+        // Un échantillon modifié : signal différent.
+        assert_eq!(
+            verify_audio_bytes(&fp, &sample_wav(64, 4)).unwrap(),
+            AudioMatch::Different
+        );
+    }
+
+    #[test]
+    fn test_3_flac_trames_et_non_md5_streaminfo() {
+        let flac = sample_flac([0xAB; 16]);
+        let fp = fingerprint_audio_bytes(&flac).unwrap();
+        assert_eq!(fp.signal.scheme, FRAMES_SCHEME);
+        assert_eq!((fp.sample_rate, fp.channels), (44_100, 2));
+        assert_eq!(fp.bits_per_sample, Some(16));
+
+        // Trames retouchées, STREAMINFO (MD5 compris) inchangé : différent.
+        let mut forged = flac.clone();
+        let last = forged.len() - 1;
+        forged[last] ^= 0xFF;
+        assert_eq!(
+            verify_audio_bytes(&fp, &forged).unwrap(),
+            AudioMatch::Different
+        );
+        // Seul le MD5 déclaré change (jamais contrôlé) : même signal.
+        assert_eq!(
+            verify_audio_bytes(&fp, &sample_flac([0; 16])).unwrap(),
+            AudioMatch::SignalIdentical
+        );
+        // Schémas différents (WAV contre FLAC) : incomparables, jamais « identiques ».
+        let wav = fingerprint_audio_bytes(&sample_wav(4, 0)).unwrap();
+        assert!(compare_fingerprints(&fp, &wav).is_err());
+    }
+
+    #[test]
+    fn test_4_mp3_trames_hors_id3() {
+        let mp3 = sample_mp3();
+        let fp = fingerprint_audio_bytes(&mp3).unwrap();
+        assert_eq!(fp.signal.scheme, FRAMES_SCHEME);
+        assert_eq!((fp.sample_rate, fp.channels), (44_100, 2));
+        assert_eq!(fp.bits_per_sample, None);
+
+        // Sans aucun tag : même signal.
+        let start = id3v2_len(&mut Cursor::new(&mp3)).unwrap() as usize;
+        let bare = mp3[start..mp3.len() - 128].to_vec();
+        assert_eq!(
+            verify_audio_bytes(&fp, &bare).unwrap(),
+            AudioMatch::SignalIdentical
+        );
+        // Un octet de trame modifié : différent.
+        let mut altered = mp3.clone();
+        altered[start + 100] ^= 0xFF;
+        assert_eq!(
+            verify_audio_bytes(&fp, &altered).unwrap(),
+            AudioMatch::Different
+        );
+    }
+
+    #[test]
+    fn test_5_tailles_hors_fichier_refusees() {
+        let mut wav = sample_wav(8, 0);
+        let len = wav.len();
+        wav.truncate(len - 4);
+        assert!(
+            fingerprint_audio_bytes(&wav).is_err(),
+            "chunk data tronqué accepté"
+        );
+
+        let mut flac = sample_flac([1; 16]);
+        flac[7] = 0xFF; // longueur STREAMINFO incohérente
+        assert!(fingerprint_audio_bytes(&flac).is_err());
+
+        let mut mp3 = sample_mp3();
+        mp3[6] = 0x7F; // tag ID3v2 plus grand que le fichier
+        assert!(fingerprint_audio_bytes(&mp3).is_err());
+    }
+
+    #[test]
+    fn test_6_lecture_fichier_en_flux_et_trame_a_cheval() {
+        // Première trame MPEG à cheval sur deux fenêtres de recherche.
+        let mp3 = sample_mp3();
+        let start = id3v2_len(&mut Cursor::new(&mp3)).unwrap() as usize;
+        let mut shifted = mp3[..start].to_vec();
+        shifted.resize(start + MPEG_SCAN_WINDOW as usize - 2, 0);
+        shifted.extend_from_slice(&mp3[start..]);
+        let range = mp3_audio_range(&mut Cursor::new(&shifted)).unwrap();
+        assert_eq!(range.start, (start + MPEG_SCAN_WINDOW as usize - 2) as u64);
+        assert_eq!(
+            verify_audio_bytes(&fingerprint_audio_bytes(&mp3).unwrap(), &shifted).unwrap(),
+            AudioMatch::SignalIdentical
+        );
+
+        // Fichier lu en flux : même empreinte qu'en mémoire.
+        let path = std::env::temp_dir().join(format!("ho_audio_fp_{}.wav", uuid::Uuid::new_v4()));
+        let wav = sample_wav(4096, 9);
+        std::fs::write(&path, &wav).unwrap();
+        assert_eq!(
+            fingerprint_audio_file(&path).unwrap(),
+            fingerprint_audio_bytes(&wav).unwrap()
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -10,6 +10,9 @@
 //! à une structure signée (sérialisé en `null` faute de `skip_serializing_if`),
 //! les octets canoniques changent et ces tests ÉCHOUENT — c'est le garde-fou.
 //!
+//! Les enveloppes HO-JSON (v1 frontend, v1 Work, V2) sont figées de même et
+//! vérifiées par le chemin natif (`ho_json_v1::verify_envelope`).
+//!
 //! Les fixtures sont FIGÉES (const &str). Elles ne sont pas régénérées à chaque
 //! test : c'est ce qui les rend protectrices contre les régressions futures.
 
@@ -19,6 +22,14 @@ use base64::{engine::general_purpose, Engine as _};
 use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
 use sha2::{Digest, Sha256};
 
+use crate::evidence_kernel::{
+    AudioFingerprint, AudioSignalDigest, DocumentArtifactKind, MediaKind, MediaSpecificMetadata,
+    ObjectVersionRef, ObservedObjectRef, RenderedArtifactRef,
+};
+use crate::ho_json_v1::{
+    parse_ho_json_v1, work_certificate_from_ho_json_v1, work_certificate_to_ho_json_v1,
+};
+use crate::ho_json_v2::{build_ho_json_v2, parse_ho_json_v2, verify_ho_json_v2, HoJsonV2Document};
 use crate::work_certificate::{
     verify_certificate, CertificateSignatureMetadata, CertificateVersion, Continuity,
     ContinuityKind, IncludedPeriod, ProofVerdict, PublicCoreEvidence, PublicDocumentRef,
@@ -76,6 +87,7 @@ fn build_public_core_evidence() -> PublicCoreEvidence {
         document: PublicDocumentRef {
             hash_current: "a".repeat(64),
             size_current: 1234,
+            members: None,
         },
         included_period_summaries: vec![IncludedPeriod {
             period_id: "period-0001".to_string(),
@@ -87,6 +99,8 @@ fn build_public_core_evidence() -> PublicCoreEvidence {
             net_document_change: true,
             gate_passed: true,
             qualifying: true,
+            change_magnitude: None,
+            member_changes: None,
             period_record_sha256: "d".repeat(64),
         }],
         new_period_ids: vec!["period-0001".to_string()],
@@ -101,6 +115,7 @@ fn build_public_core_evidence() -> PublicCoreEvidence {
             total_active_seconds: 120,
             continuity: ContinuityKind::Full,
             verdict: ProofVerdict::ObservedWorkConsistent,
+            flags: vec![],
         },
         previous_certificate_id: None,
         previous_core_evidence_sha256: None,
@@ -132,6 +147,7 @@ fn mint_certificate_json() -> String {
             signing_key_id: key_id,
             identity_status: "LOCAL_DEVICE".to_string(),
             schema_version: CERTIFICATE_SCHEMA_VERSION,
+            canonicalization: None,
         },
         signature: String::new(),
     };
@@ -160,11 +176,17 @@ fn mint_manifest_json() -> String {
                 filename: "certificate.json".to_string(),
                 sha256: "e".repeat(64),
             },
-            labeled_pdf: FileRef {
-                filename: "labeled_document.pdf".to_string(),
-                sha256: "f".repeat(64),
-            },
+            labeled_artifact: Some(RenderedArtifactRef {
+                artifact_name: "labeled_document.pdf".to_string(),
+                media_type: DocumentArtifactKind::Pdf,
+                fingerprint_sha256: Some("f".repeat(64)),
+                size_bytes: None,
+                mime_type: None,
+            }),
+            original_document: None,
+            labeled_sidecar: None,
         },
+        derived_rendition: None,
         signature_metadata: ManifestSignatureMetadata {
             signature_algorithm: "ed25519".to_string(),
             public_key: pk,
@@ -180,6 +202,61 @@ fn mint_manifest_json() -> String {
     serde_json::to_string(&man).unwrap()
 }
 
+/// Document HO-JSON V2 frappé SUR le certificat V1 figé (même clé de test).
+fn mint_ho_json_v2(
+    media_type: MediaKind,
+    mime_type: Option<&str>,
+    media_specific: Option<MediaSpecificMetadata>,
+    verify_url: Option<&str>,
+) -> String {
+    let cert: WorkCertificate = serde_json::from_str(CERT_V1_JSON).unwrap();
+    let object = ObservedObjectRef::new(cert.work_id.as_str(), media_type);
+    let version = ObjectVersionRef {
+        fingerprint_sha256: cert.public_core_evidence.document.hash_current.clone(),
+        size_bytes: Some(cert.public_core_evidence.document.size_current),
+        mime_type: mime_type.map(str::to_string),
+        chunked_fingerprint: None,
+        media_specific,
+    };
+    let doc = build_ho_json_v2(&cert, &object, &version, verify_url, &test_key()).unwrap();
+    serde_json::to_string(&doc).unwrap()
+}
+
+fn mint_ho_json_v2_pdf() -> String {
+    mint_ho_json_v2(
+        MediaKind::Pdf,
+        Some("application/pdf"),
+        None,
+        Some("https://verify.humanorigin.app/r/cert-0001"),
+    )
+}
+
+fn mint_ho_json_v2_audio() -> String {
+    let audio = MediaSpecificMetadata::Audio(AudioFingerprint {
+        container: "flac".to_string(),
+        sample_rate: 48_000,
+        channels: 2,
+        bits_per_sample: Some(24),
+        signal: AudioSignalDigest {
+            scheme: "FLAC-STREAMINFO-MD5".to_string(),
+            value: "0123456789abcdef0123456789abcdef".to_string(),
+        },
+    });
+    mint_ho_json_v2(MediaKind::Audio, None, Some(audio), None)
+}
+
+/// Enveloppe HO-JSON v1 d'un certificat Work, frappée sur le certificat V1 figé.
+fn mint_ho_json_v1_work() -> String {
+    let cert: WorkCertificate = serde_json::from_str(CERT_V1_JSON).unwrap();
+    let env = work_certificate_to_ho_json_v1(
+        &cert,
+        Some("https://verify.humanorigin.app/r/cert-0001"),
+        &test_key(),
+    )
+    .unwrap();
+    serde_json::to_string(&env).unwrap()
+}
+
 /// Générateur one-shot : imprime les fixtures à figer. Marqué #[ignore] pour ne
 /// PAS tourner en CI (les fixtures FIGÉES ci-dessous sont la source de vérité).
 /// Lancer manuellement : `cargo test compat_v1::mint_fixtures -- --ignored --nocapture`
@@ -192,6 +269,15 @@ fn mint_fixtures() {
     println!("=====MANIFEST_V1_JSON_BEGIN=====");
     println!("{}", mint_manifest_json());
     println!("=====MANIFEST_V1_JSON_END=====");
+    println!("=====HO_JSON_V1_WORK_BEGIN=====");
+    println!("{}", mint_ho_json_v1_work());
+    println!("=====HO_JSON_V1_WORK_END=====");
+    println!("=====HO_JSON_V2_PDF_BEGIN=====");
+    println!("{}", mint_ho_json_v2_pdf());
+    println!("=====HO_JSON_V2_PDF_END=====");
+    println!("=====HO_JSON_V2_AUDIO_BEGIN=====");
+    println!("{}", mint_ho_json_v2_audio());
+    println!("=====HO_JSON_V2_AUDIO_END=====");
 }
 
 // ===========================================================================
@@ -205,6 +291,21 @@ const CERT_V1_JSON: &str = r#"{"schema_version":1,"certificate_id":"cert-0001","
 
 const MANIFEST_V1_JSON: &str = r#"{"schema_version":1,"package_id":"package-0001","work_id":"00000000-0000-4000-8000-000000000001","certificate_id":"cert-0001","certificate_sequence":1,"created_at":"2026-01-01T00:00:00Z","certificate_version":"V1","verdict":"OBSERVED_WORK_CONSISTENT","files":{"certificate":{"filename":"certificate.json","sha256":"eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"},"labeled_pdf":{"filename":"labeled_document.pdf","sha256":"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"}},"signature_metadata":{"signature_algorithm":"ed25519","public_key":"GX9rI+FshTLGq8g4+s1ep4m+DHaykgM0A5v6iz02jWE=","signing_key_id":"201713b33c99236b2b5799a8b1ee5149b67265539960f6f9096a8c4af77b3c39","identity_status":"LOCAL_DEVICE","schema_version":1},"signature":"dhCLuMUruw+AUOQDe9Vw8gBbm8EpVkRXW6QYDUzL4fJvpyCcfmONemou3JMYwaR0zoeNvf3BQq+l/1eM5xr0Aw=="}"#;
 
+// FIXTURES FIGÉES HO-JSON v1 — enveloppe `CERTIFICAT_FINAL.v1.ho.json`.
+// `HO_JSON_V1_JS_JSON` est frappée HORS Rust (Node, `canonicalize` + `sha256Hex`
+// de src/main.js, même clé de test) : elle prouve que la vérification native
+// accepte exactement ce que produit le frontend. `HO_JSON_V1_WORK_JSON` est
+// frappée par `mint_ho_json_v1_work` sur `CERT_V1_JSON`.
+const HO_JSON_V1_JS_JSON: &str = r#"{"format":"humanorigin-hojson","version":"1.0","payload":{"certificate_type":"final_project_certificate","certificate_id":"HO-TEST-0001","issued_at":"2026-01-01T00:00:00.000Z","issuer":{"product":"HumanOrigin","issuer_mode":"local","app_version":"0.0.0-test","security_schema_version":"2026-06-p0","proof_trust_level":"local_self_signed","key_trust":"local_unregistered_key"},"project":{"name":"Mémoire — test é"},"document":{"binding_mode":"final_only","filename":null,"mime":"application/pdf","sha256":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","delta_bytes_ratio":0.125,"delta_significant":true,"contribution_flags":[]},"process_summary":{"verdict":"OBSERVED","scp_score":72.5,"evidence_score":0,"active_ms":120000},"label_eligibility":{"visible_verdict":"OBSERVED","binding_cap_reason":null},"verification":{"verify_url":null,"verification_method":"ed25519_payload_sha256"}},"payload_sha256":"17ada88796b62ae4479ce3bb0a60b10e0c5cb9f2a012925176e51b63c43c3ab5","signatures":[{"role":"issuer","algorithm":"ed25519","signed_field":"payload_sha256","public_key":"GX9rI+FshTLGq8g4+s1ep4m+DHaykgM0A5v6iz02jWE=","signature":"4cfqS2Kpwxqh96VRcg2Htl6ZBJdN5yuBJ69den5hWk76bLGaHq8x9KrA4g8PReMPJiPoowUITdC33MAMmv3yBQ=="}]}"#;
+
+const HO_JSON_V1_WORK_JSON: &str = r#"{"format":"humanorigin-hojson","version":"1.0","payload":{"certificate_id":"cert-0001","certificate_type":"work_certificate","document":{"binding_mode":"work_certificate","final_size_bytes":1234,"sha256":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"},"issued_at":"2026-01-01T00:00:00Z","issuer":{"app_version":"0.1.0","identity_status":"LOCAL_DEVICE","issuer_mode":"local","key_trust":"local_unregistered_key","product":"HumanOrigin","proof_trust_level":"local_self_signed","security_schema_version":"2026-06-p0","signing_key_id":"201713b33c99236b2b5799a8b1ee5149b67265539960f6f9096a8c4af77b3c39"},"label_eligibility":{"visible_verdict":"OBSERVED_WORK_CONSISTENT"},"process_summary":{"active_ms":120000,"valid_sessions_count":1,"verdict":"OBSERVED_WORK_CONSISTENT"},"verification":{"verification_method":"ed25519_payload_sha256","verify_url":"https://verify.humanorigin.app/r/cert-0001"},"work_certificate":{"certificate_id":"cert-0001","certificate_sequence":1,"certificate_version":"V1","core_evidence_sha256":"08d442447bcab04523647084232faa2d4232deed4fa83f2da6aaf8d415210f65","created_at":"2026-01-01T00:00:00Z","previous_certificate_id":null,"previous_core_evidence_sha256":null,"public_core_evidence":{"certificate_version":"V1","continuity":{"gaps":[],"kind":"FULL"},"document":{"hash_current":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","size_current":1234},"included_period_summaries":[{"gate_passed":true,"hash_end":"cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc","hash_start":"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb","net_document_change":true,"period_id":"period-0001","period_record_sha256":"dddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddd","qualifying":true,"sequence_number":0,"size_end":1234,"size_start":1000}],"new_period_ids":["period-0001"],"previous_certificate_id":null,"previous_core_evidence_sha256":null,"qualifying_new_period_count":1,"schema_version":1,"verdict":{"continuity":"FULL","qualifying_periods":1,"total_active_seconds":120,"total_periods":1,"verdict":"OBSERVED_WORK_CONSISTENT"},"work_id":"00000000-0000-4000-8000-000000000001"},"schema_version":1,"signature":"MYoSbnb+hjagA75FW1/Mv5C030NA4rvNfC2vgVCK2GSNqUYUHmwaevTzjCJJHMuxebMOLRU4pnfgAtcPfJK5Ag==","signature_metadata":{"identity_status":"LOCAL_DEVICE","public_key":"GX9rI+FshTLGq8g4+s1ep4m+DHaykgM0A5v6iz02jWE=","schema_version":1,"signature_algorithm":"ed25519","signing_key_id":"201713b33c99236b2b5799a8b1ee5149b67265539960f6f9096a8c4af77b3c39"},"work_id":"00000000-0000-4000-8000-000000000001"}},"payload_sha256":"6e3d97a33746e6f44bf24acc860b882e841a2f3543c24a0c02e5f5744506f512","signatures":[{"role":"issuer","algorithm":"ed25519","signed_field":"payload_sha256","public_key":"GX9rI+FshTLGq8g4+s1ep4m+DHaykgM0A5v6iz02jWE=","signature":"ynM8KF+hrHcczCQSMvWwFqJmw7cst8vvnvhnWWZTO7pw1q+ytDIgHm8n+Mm7SomwxlKZdq2ohCLCIubihVH6AA=="}]}"#;
+
+// FIXTURES FIGÉES V2 — mêmes règles : frappées sur `CERT_V1_JSON`, NE PAS
+// régénérer. Un verifier v1 (payload_sha256 + Ed25519) doit les accepter.
+const HO_JSON_V2_PDF_JSON: &str = r#"{"format":"humanorigin-hojson","version":"2.0","payload":{"schema_version":2,"certificate_type":"work_certificate","certificate_id":"cert-0001","issued_at":"2026-01-01T00:00:00Z","issuer":{"product":"HumanOrigin","identity_status":"LOCAL_DEVICE","signing_key_id":"201713b33c99236b2b5799a8b1ee5149b67265539960f6f9096a8c4af77b3c39"},"verdict":"OBSERVED_WORK_CONSISTENT","document":{"sha256":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","mime":"application/pdf"},"observed_object":{"object_id":"00000000-0000-4000-8000-000000000001","media_type":"pdf","content_private":true},"final_version":{"fingerprint_sha256":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","size_bytes":1234,"mime_type":"application/pdf"},"verification":{"verify_url":"https://verify.humanorigin.app/r/cert-0001","verification_method":"ed25519_payload_sha256"},"work_certificate":{"schema_version":1,"certificate_id":"cert-0001","certificate_sequence":1,"created_at":"2026-01-01T00:00:00Z","work_id":"00000000-0000-4000-8000-000000000001","certificate_version":"V1","public_core_evidence":{"schema_version":1,"work_id":"00000000-0000-4000-8000-000000000001","certificate_version":"V1","document":{"hash_current":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","size_current":1234},"included_period_summaries":[{"period_id":"period-0001","sequence_number":0,"hash_start":"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb","hash_end":"cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc","size_start":1000,"size_end":1234,"net_document_change":true,"gate_passed":true,"qualifying":true,"period_record_sha256":"dddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddd"}],"new_period_ids":["period-0001"],"qualifying_new_period_count":1,"continuity":{"kind":"FULL","gaps":[]},"verdict":{"total_periods":1,"qualifying_periods":1,"total_active_seconds":120,"continuity":"FULL","verdict":"OBSERVED_WORK_CONSISTENT"},"previous_certificate_id":null,"previous_core_evidence_sha256":null},"core_evidence_sha256":"08d442447bcab04523647084232faa2d4232deed4fa83f2da6aaf8d415210f65","previous_certificate_id":null,"previous_core_evidence_sha256":null,"signature_metadata":{"signature_algorithm":"ed25519","public_key":"GX9rI+FshTLGq8g4+s1ep4m+DHaykgM0A5v6iz02jWE=","signing_key_id":"201713b33c99236b2b5799a8b1ee5149b67265539960f6f9096a8c4af77b3c39","identity_status":"LOCAL_DEVICE","schema_version":1},"signature":"MYoSbnb+hjagA75FW1/Mv5C030NA4rvNfC2vgVCK2GSNqUYUHmwaevTzjCJJHMuxebMOLRU4pnfgAtcPfJK5Ag=="}},"payload_sha256":"24fb7a97bcbd3c40d68222a751ea43838dc8d20cef4b389b8008230894653bff","signatures":[{"role":"issuer","algorithm":"ed25519","signed_field":"payload_sha256","public_key":"GX9rI+FshTLGq8g4+s1ep4m+DHaykgM0A5v6iz02jWE=","signature":"9AVc8LhB7Dfj5jLj7YfnS+p3AG6njlymWtM1FmSN560V/2nrJmIYZnTXf02Orq9LMhbX7ddJrRJ09VknMijhCw=="}]}"#;
+
+const HO_JSON_V2_AUDIO_JSON: &str = r#"{"format":"humanorigin-hojson","version":"2.0","payload":{"schema_version":2,"certificate_type":"work_certificate","certificate_id":"cert-0001","issued_at":"2026-01-01T00:00:00Z","issuer":{"product":"HumanOrigin","identity_status":"LOCAL_DEVICE","signing_key_id":"201713b33c99236b2b5799a8b1ee5149b67265539960f6f9096a8c4af77b3c39"},"verdict":"OBSERVED_WORK_CONSISTENT","document":{"sha256":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"},"observed_object":{"object_id":"00000000-0000-4000-8000-000000000001","media_type":"audio","content_private":true},"final_version":{"fingerprint_sha256":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","size_bytes":1234,"media_specific":{"audio":{"container":"flac","sample_rate":48000,"channels":2,"bits_per_sample":24,"signal":{"scheme":"FLAC-STREAMINFO-MD5","value":"0123456789abcdef0123456789abcdef"}}}},"verification":{"verification_method":"ed25519_payload_sha256"},"work_certificate":{"schema_version":1,"certificate_id":"cert-0001","certificate_sequence":1,"created_at":"2026-01-01T00:00:00Z","work_id":"00000000-0000-4000-8000-000000000001","certificate_version":"V1","public_core_evidence":{"schema_version":1,"work_id":"00000000-0000-4000-8000-000000000001","certificate_version":"V1","document":{"hash_current":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","size_current":1234},"included_period_summaries":[{"period_id":"period-0001","sequence_number":0,"hash_start":"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb","hash_end":"cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc","size_start":1000,"size_end":1234,"net_document_change":true,"gate_passed":true,"qualifying":true,"period_record_sha256":"dddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddd"}],"new_period_ids":["period-0001"],"qualifying_new_period_count":1,"continuity":{"kind":"FULL","gaps":[]},"verdict":{"total_periods":1,"qualifying_periods":1,"total_active_seconds":120,"continuity":"FULL","verdict":"OBSERVED_WORK_CONSISTENT"},"previous_certificate_id":null,"previous_core_evidence_sha256":null},"core_evidence_sha256":"08d442447bcab04523647084232faa2d4232deed4fa83f2da6aaf8d415210f65","previous_certificate_id":null,"previous_core_evidence_sha256":null,"signature_metadata":{"signature_algorithm":"ed25519","public_key":"GX9rI+FshTLGq8g4+s1ep4m+DHaykgM0A5v6iz02jWE=","signing_key_id":"201713b33c99236b2b5799a8b1ee5149b67265539960f6f9096a8c4af77b3c39","identity_status":"LOCAL_DEVICE","schema_version":1},"signature":"MYoSbnb+hjagA75FW1/Mv5C030NA4rvNfC2vgVCK2GSNqUYUHmwaevTzjCJJHMuxebMOLRU4pnfgAtcPfJK5Ag=="}},"payload_sha256":"d6be0bff47cdb6d180208068fb6cde8a146c330027f378e36b076be033d77fca","signatures":[{"role":"issuer","algorithm":"ed25519","signed_field":"payload_sha256","public_key":"GX9rI+FshTLGq8g4+s1ep4m+DHaykgM0A5v6iz02jWE=","signature":"dfnxAbJCaXvTlOzH4LdySnwhXhKbJFlFs516LkQMniy/79b0XbJ1tHJw6kh8WGQuiwWJ8BdO2hElfWaMK1SnCA=="}]}"#;
+
 // ---------------------------------------------------------------------------
 // GARDE-FOUS : n'utilisent QUE les fixtures FIGÉES (jamais une régénération).
 // ---------------------------------------------------------------------------
@@ -335,3 +436,116 @@ fn ho_json_v2_skip_none_rule_is_enforced() {
         "ajouter un champ SANS skip change les octets -> casse V1"
     );
 }
+
+// ---------------------------------------------------------------------------
+// HO-JSON V2 : fixtures figées (superset non-rupture de l'enveloppe v1).
+// ---------------------------------------------------------------------------
+
+const HO_JSON_V2_FIXTURES: [&str; 2] = [HO_JSON_V2_PDF_JSON, HO_JSON_V2_AUDIO_JSON];
+
+#[test]
+fn ho_json_v2_fixtures_parse_and_verify() {
+    let cert: WorkCertificate = serde_json::from_str(CERT_V1_JSON).unwrap();
+    for raw in HO_JSON_V2_FIXTURES {
+        let doc = parse_ho_json_v2(raw).expect("le HO-JSON V2 figé doit rester vérifiable");
+        assert_eq!(doc.payload.work_certificate, cert);
+        assert_eq!(doc.payload.schema_version, 2);
+    }
+    let audio = parse_ho_json_v2(HO_JSON_V2_AUDIO_JSON).unwrap();
+    assert!(matches!(
+        audio.payload.final_version.media_specific,
+        Some(MediaSpecificMetadata::Audio(_))
+    ));
+}
+
+#[test]
+fn ho_json_v2_fixtures_reminted_byte_identical() {
+    // Writer déterministe : mêmes entrées -> mêmes octets (Ed25519 déterministe).
+    assert_eq!(mint_ho_json_v2_pdf(), HO_JSON_V2_PDF_JSON);
+    assert_eq!(mint_ho_json_v2_audio(), HO_JSON_V2_AUDIO_JSON);
+}
+
+#[test]
+fn ho_json_v2_envelope_verifies_as_v1() {
+    // Chemin d'un verifier v1 : SHA256(HO-CANON-V1(payload)) == payload_sha256,
+    // puis Ed25519 sur les 32 octets du hash. Aucune connaissance des champs V2.
+    for raw in HO_JSON_V2_FIXTURES {
+        let v: serde_json::Value = serde_json::from_str(raw).unwrap();
+        let body = canonical_bytes_excluding(&v["payload"], &[]).unwrap();
+        let digest = format!("{:x}", Sha256::digest(&body));
+        assert_eq!(v["payload_sha256"].as_str().unwrap(), digest);
+        assert_eq!(
+            v["payload"]["document"]["sha256"].as_str().unwrap(),
+            "a".repeat(64)
+        );
+        let sig = &v["signatures"][0];
+        let vk = VerifyingKey::from_bytes(&decode32(sig["public_key"].as_str().unwrap())).unwrap();
+        let sig = Signature::from_bytes(&decode64(sig["signature"].as_str().unwrap()));
+        let hash: Vec<u8> = (0..32)
+            .map(|i| u8::from_str_radix(&digest[2 * i..2 * i + 2], 16).unwrap())
+            .collect();
+        vk.verify_strict(&hash, &sig)
+            .expect("l'enveloppe V2 doit se vérifier comme une v1");
+    }
+}
+
+#[test]
+fn ho_json_v2_tamper_breaks_verification() {
+    let doc: HoJsonV2Document = serde_json::from_str(HO_JSON_V2_AUDIO_JSON).unwrap();
+    let mut d1 = doc.clone();
+    d1.payload.observed_object.media_type = MediaKind::Image;
+    assert!(verify_ho_json_v2(&d1).is_err());
+    let mut d2 = doc.clone();
+    d2.payload.work_certificate.certificate_sequence += 1;
+    assert!(verify_ho_json_v2(&d2).is_err());
+    let mut d3 = doc;
+    d3.signatures[0].signature = b64(&[0u8; 64]);
+    assert!(verify_ho_json_v2(&d3).is_err());
+}
+
+#[test]
+fn ho_json_v2_null_instead_of_absent_is_rejected() {
+    // Règle skip-none côté lecture : `"mime": null` n'est PAS un champ absent.
+    let mut v: serde_json::Value = serde_json::from_str(HO_JSON_V2_AUDIO_JSON).unwrap();
+    v["payload"]["document"]["mime"] = serde_json::Value::Null;
+    assert!(parse_ho_json_v2(&v.to_string()).is_err());
+    let mut v: serde_json::Value = serde_json::from_str(HO_JSON_V2_AUDIO_JSON).unwrap();
+    v["payload"]["verification"]["verify_url"] = serde_json::Value::Null;
+    assert!(parse_ho_json_v2(&v.to_string()).is_err());
+}
+
+// ---------------------------------------------------------------------------
+// HO-JSON v1 : vérification native des enveloppes JS et du pont Work.
+// ---------------------------------------------------------------------------
+
+#[test]
+fn ho_json_v1_js_fixture_verifies_natively() {
+    let env = parse_ho_json_v1(HO_JSON_V1_JS_JSON)
+        .expect("l'enveloppe v1 produite par le frontend doit se vérifier en Rust");
+    assert_eq!(
+        env.signatures[0].public_key,
+        b64(&test_key().verifying_key().to_bytes())
+    );
+    assert_eq!(env.payload["project"]["name"], "Mémoire — test é");
+    // Payload JS : pas de certificat Work embarqué.
+    assert!(work_certificate_from_ho_json_v1(&env).is_err());
+}
+
+#[test]
+fn ho_json_v1_js_fixture_tamper_breaks_verification() {
+    let mut v: serde_json::Value = serde_json::from_str(HO_JSON_V1_JS_JSON).unwrap();
+    v["payload"]["process_summary"]["scp_score"] = serde_json::json!(99.5);
+    assert!(parse_ho_json_v1(&v.to_string()).is_err());
+    let mut v: serde_json::Value = serde_json::from_str(HO_JSON_V1_JS_JSON).unwrap();
+    v["payload"]["document"]["filename"] = serde_json::json!("autre.pdf");
+    assert!(parse_ho_json_v1(&v.to_string()).is_err());
+}
+
+#[test]
+fn ho_json_v1_work_fixture_bridges_to_certificate() {
+    let env = parse_ho_json_v1(HO_JSON_V1_WORK_JSON).unwrap();
+    let cert = work_certificate_from_ho_json_v1(&env).unwrap();
+    let frozen: WorkCertificate = serde_json::from_str(CERT_V1_JSON).unwrap();
+    assert_eq!(cert, frozen);
+    assert_eq!(env.payload["document"]["sha256"], "a".repeat(64));
+}
@@ -0,0 +1,451 @@
+//! document_set — Work multi-fichiers : dossier ou ensemble de fichiers (V2).
+//!
+//! Portée STRICTE : un livre s'écrit en plusieurs fichiers (chapitres, dossier
+//! de ressources). Un Work peut donc se lier à un ENSEMBLE : soit un dossier
+//! (`Folder`, membres relevés à chaque capture), soit une liste explicite de
+//! fichiers (`Files`, membres fixés à la création). Chaque membre est haché
+//! (SHA256) et l'ensemble réduit à une empreinte de manifeste
+//! (`DOCUMENT_SET_SCHEME`) qui joue le rôle du « SHA256 du document » :
+//! périodes, continuité et certificat s'y lient sans changement de règle.
+//!
+//! Empreinte = SHA256 de `DOCUMENT_SET_SCHEME\n` puis, par chemin (octets,
+//! ordre croissant), `"{sha256} {taille} {chemin}\n"`. Chemins RELATIFS à la
+//! racine de l'ensemble, séparateur `/` (jamais de chemin absolu exposé).
+//!
+//! Garde-fous :
+//! - `Folder` : entrées cachées (`.` de tête) et liens symboliques ignorés,
+//!   dossier sans fichier -> `Err` ;
+//! - `Files` : membre manquant ou non régulier -> `Err` (document indisponible,
+//!   jamais un ensemble silencieusement réduit) ; doublon -> `Err` ;
+//! - chemin non UTF-8 ou contenant un saut de ligne -> `Err` ;
+//! - au plus `MAX_SET_MEMBERS` membres.
+//!
+//! AUCUNE écriture, AUCUNE commande Tauri, AUCUNE UI.
+
+// Fondation (V2 multi-fichiers) : consommé par `work_commands`, `work_engine`
+// et `work_certificate`.
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::file_hash;
+
+/// Identifiant du format des lignes du manifeste. Tout changement = nouveau schéma.
+pub(crate) const DOCUMENT_SET_SCHEME: &str = "HO-DOCSET-SHA256-V1";
+
+/// MIME d'un document de type ensemble de fichiers.
+pub(crate) const DOCUMENT_SET_MIME: &str = "application/vnd.humanorigin.document-set";
+
+/// Nombre maximal de membres d'un ensemble.
+pub(crate) const MAX_SET_MEMBERS: usize = 10_000;
+
+/// Nature de l'ensemble.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DocumentSetKind {
+    /// Tout fichier (non caché) du dossier, relevé à chaque capture : un
+    /// chapitre ajouté ou supprimé est un changement documentaire.
+    Folder,
+    /// Liste explicite de fichiers, fixée à la création du Work.
+    Files,
+}
+
+/// Définition persistée d'un ensemble (dans `WorkDocumentMetadata`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DocumentSetSpec {
+    pub kind: DocumentSetKind,
+    /// Racine normalisée (dossier ; ancêtre commun des fichiers pour `Files`).
+    pub root: String,
+    /// Chemins relatifs à `root` (`Files` uniquement ; vide pour `Folder`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<String>,
+}
+
+/// Membre capturé : chemin RELATIF, SHA256, taille.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DocumentSetMember {
+    pub path: String,
+    pub sha256: String,
+    pub size_bytes: u64,
+}
+
+/// Capture complète d'un ensemble à un instant donné.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DocumentSetDigest {
+    pub manifest_sha256: String,
+    pub total_bytes: u64,
+    pub members: Vec<DocumentSetMember>,
+}
+
+/// Nature du changement d'un membre entre deux captures.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MemberChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Changement d'un membre au cours d'une période (signé avec la période).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MemberChange {
+    pub path: String,
+    pub change: MemberChangeKind,
+    pub hash_start: Option<String>,
+    pub hash_end: Option<String>,
+}
+
+// --- DÉFINITION ---------------------------------------------------------------
+
+fn canonical(path: &Path) -> Result<PathBuf, String> {
+    fs::canonicalize(path).map_err(|e| format!("Chemin introuvable : {} ({e})", path.display()))
+}
+
+/// Chemin relatif `/`-séparé, refusé s'il est ambigu dans le manifeste.
+fn relative_path(root: &Path, path: &Path) -> Result<String, String> {
+    let rel = path
+        .strip_prefix(root)
+        .map_err(|_| format!("Hors de la racine de l'ensemble : {}", path.display()))?;
+    let mut parts = Vec::new();
+    for c in rel.components() {
+        let part = c
+            .as_os_str()
+            .to_str()
+            .ok_or_else(|| format!("Nom non UTF-8 : {}", path.display()))?;
+        if part.contains('\n') {
+            return Err(format!("Nom avec saut de ligne : {}", path.display()));
+        }
+        parts.push(part);
+    }
+    if parts.is_empty() {
+        return Err(format!("Membre vide : {}", path.display()));
+    }
+    Ok(parts.join("/"))
+}
+
+/// Ensemble « dossier » : refuse un chemin qui n'est pas un dossier.
+pub(crate) fn folder_spec(dir: &Path) -> Result<DocumentSetSpec, String> {
+    let root = canonical(dir)?;
+    if !root.is_dir() {
+        return Err(format!("Pas un dossier : {}", dir.display()));
+    }
+    Ok(DocumentSetSpec {
+        kind: DocumentSetKind::Folder,
+        root: root.to_string_lossy().to_string(),
+        members: Vec::new(),
+    })
+}
+
+/// Ensemble « fichiers » : racine = plus proche ancêtre commun des fichiers.
+pub(crate) fn files_spec(paths: &[PathBuf]) -> Result<DocumentSetSpec, String> {
+    if paths.is_empty() {
+        return Err("Ensemble de fichiers vide".to_string());
+    }
+    if paths.len() > MAX_SET_MEMBERS {
+        return Err(format!(
+            "Ensemble trop grand (> {MAX_SET_MEMBERS} fichiers)"
+        ));
+    }
+    let mut files = Vec::with_capacity(paths.len());
+    for p in paths {
+        let canon = canonical(p)?;
+        if !canon.is_file() {
+            return Err(format!(
+                "Le chemin n'est pas un fichier régulier : {}",
+                p.display()
+            ));
+        }
+        files.push(canon);
+    }
+    let mut root = files[0]
+        .parent()
+        .ok_or_else(|| "Fichier sans dossier parent".to_string())?
+        .to_path_buf();
+    for f in &files[1..] {
+        while !f.starts_with(&root) {
+            root = root
+                .parent()
+                .ok_or_else(|| "Aucune racine commune aux fichiers".to_string())?
+                .to_path_buf();
+        }
+    }
+    let mut members = files
+        .iter()
+        .map(|f| relative_path(&root, f))
+        .collect::<Result<Vec<_>, _>>()?;
+    members.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+    if members.windows(2).any(|w| w[0] == w[1]) {
+        return Err("Fichier présent deux fois dans l'ensemble".to_string());
+    }
+    Ok(DocumentSetSpec {
+        kind: DocumentSetKind::Files,
+        root: root.to_string_lossy().to_string(),
+        members,
+    })
+}
+
+// --- CAPTURE ------------------------------------------------------------------
+
+/// Empreinte de manifeste de membres déjà triés par chemin.
+pub(crate) fn manifest_sha256(members: &[DocumentSetMember]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(DOCUMENT_SET_SCHEME.as_bytes());
+    hasher.update(b"\n");
+    for m in members {
+        hasher.update(format!("{} {} {}\n", m.sha256, m.size_bytes, m.path).as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn capture_member(root: &Path, rel: &str) -> Result<DocumentSetMember, String> {
+    let path = root.join(rel);
+    let meta =
+        fs::symlink_metadata(&path).map_err(|e| format!("Membre introuvable : {rel} ({e})"))?;
+    if !meta.is_file() {
+        return Err(format!("Membre non régulier : {rel}"));
+    }
+    Ok(DocumentSetMember {
+        path: rel.to_string(),
+        sha256: file_hash::sha256_file(&path)?,
+        size_bytes: meta.len(),
+    })
+}
+
+fn walk_folder(dir: &Path, rel_dir: &str, out: &mut Vec<String>) -> Result<(), String> {
+    let mut children: Vec<_> = fs::read_dir(dir)
+        .map_err(|e| format!("Dossier illisible : {} ({e})", dir.display()))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+    children.sort_by_key(|e| e.file_name());
+
+    for child in children {
+        let name = child.file_name();
+        let name = name
+            .to_str()
+            .ok_or_else(|| format!("Nom non UTF-8 dans {}", dir.display()))?;
+        if name.starts_with('.') {
+            continue;
+        }
+        if name.contains('\n') {
+            return Err(format!("Nom avec saut de ligne dans {}", dir.display()));
+        }
+        let rel = if rel_dir.is_empty() {
+            name.to_string()
+        } else {
+            format!("{rel_dir}/{name}")
+        };
+        let file_type = fs::symlink_metadata(child.path())
+            .map_err(|e| e.to_string())?
+            .file_type();
+        if file_type.is_dir() {
+            walk_folder(&child.path(), &rel, out)?;
+        } else if file_type.is_file() {
+            if out.len() >= MAX_SET_MEMBERS {
+                return Err(format!(
+                    "Dossier trop volumineux (> {MAX_SET_MEMBERS} fichiers)"
+                ));
+            }
+            out.push(rel);
+        }
+    }
+    Ok(())
+}
+
+/// Capture l'ensemble : hache chaque membre et calcule l'empreinte de manifeste.
+pub(crate) fn capture_set(spec: &DocumentSetSpec) -> Result<DocumentSetDigest, String> {
+    let root = Path::new(&spec.root);
+    let mut paths = match spec.kind {
+        DocumentSetKind::Folder => {
+            let mut out = Vec::new();
+            walk_folder(root, "", &mut out)?;
+            if out.is_empty() {
+                return Err(format!("Dossier sans fichier : {}", root.display()));
+            }
+            out
+        }
+        DocumentSetKind::Files => {
+            if spec.members.is_empty() {
+                return Err("Ensemble de fichiers vide".to_string());
+            }
+            spec.members.clone()
+        }
+    };
+    paths.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+    let members = paths
+        .iter()
+        .map(|rel| capture_member(root, rel))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(DocumentSetDigest {
+        manifest_sha256: manifest_sha256(&members),
+        total_bytes: members.iter().map(|m| m.size_bytes).sum(),
+        members,
+    })
+}
+
+/// Changements membre à membre entre deux captures (ordre des chemins).
+/// Vide si les deux captures sont identiques.
+pub(crate) fn member_changes(
+    start: &[DocumentSetMember],
+    end: &[DocumentSetMember],
+) -> Vec<MemberChange> {
+    let mut all: BTreeMap<&str, (Option<&str>, Option<&str>)> = BTreeMap::new();
+    for m in start {
+        all.entry(&m.path).or_default().0 = Some(&m.sha256);
+    }
+    for m in end {
+        all.entry(&m.path).or_default().1 = Some(&m.sha256);
+    }
+    all.into_iter()
+        .filter_map(|(path, (a, b))| {
+            let change = match (a, b) {
+                (None, Some(_)) => MemberChangeKind::Added,
+                (Some(_), None) => MemberChangeKind::Removed,
+                (Some(x), Some(y)) if x != y => MemberChangeKind::Modified,
+                _ => return None,
+            };
+            Some(MemberChange {
+                path: path.to_string(),
+                change,
+                hash_start: a.map(str::to_string),
+                hash_end: b.map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+// --- TESTS UNITAIRES ----------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn tmp_dir() -> PathBuf {
+        let d = std::env::temp_dir().join(format!("ho_document_set_test_{}", Uuid::new_v4()));
+        fs::create_dir_all(&d).unwrap();
+        d
+    }
+
+    fn cleanup(p: &Path) {
+        let _ = fs::remove_dir_all(p);
+    }
+
+    fn write(root: &Path, rel: &str, content: &[u8]) -> PathBuf {
+        let p = root.join(rel);
+        fs::create_dir_all(p.parent().unwrap()).unwrap();
+        fs::write(&p, content).unwrap();
+        p
+    }
+
+    fn paths(d: &DocumentSetDigest) -> Vec<&str> {
+        d.members.iter().map(|m| m.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_1_dossier_empreinte_deterministe_caches_ignores() {
+        let root = tmp_dir();
+        write(&root, "chapitres/01.md", b"# Un\n");
+        write(&root, "chapitres/02.md", b"# Deux\n");
+        write(&root, ".DS_Store", b"x");
+        write(&root, ".brouillons/notes.md", b"y");
+        let spec = folder_spec(&root).unwrap();
+        let a = capture_set(&spec).unwrap();
+        assert_eq!(a, capture_set(&spec).unwrap());
+        assert_eq!(paths(&a), vec!["chapitres/01.md", "chapitres/02.md"]);
+        assert_eq!(a.total_bytes, 12);
+        assert_eq!(a.manifest_sha256, manifest_sha256(&a.members));
+        // Un fichier caché ne change pas l'empreinte.
+        write(&root, ".DS_Store", b"autre");
+        assert_eq!(
+            capture_set(&spec).unwrap().manifest_sha256,
+            a.manifest_sha256
+        );
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_2_modification_ajout_suppression_par_fichier() {
+        let root = tmp_dir();
+        write(&root, "01.md", b"un");
+        write(&root, "02.md", b"deux");
+        let spec = folder_spec(&root).unwrap();
+        let a = capture_set(&spec).unwrap();
+        write(&root, "01.md", b"un, revu");
+        write(&root, "03.md", b"trois");
+        fs::remove_file(root.join("02.md")).unwrap();
+        let b = capture_set(&spec).unwrap();
+        assert_ne!(a.manifest_sha256, b.manifest_sha256);
+
+        let changes = member_changes(&a.members, &b.members);
+        let kinds: Vec<_> = changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.change))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("01.md", MemberChangeKind::Modified),
+                ("02.md", MemberChangeKind::Removed),
+                ("03.md", MemberChangeKind::Added),
+            ]
+        );
+        assert!(changes[1].hash_end.is_none());
+        assert!(changes[2].hash_start.is_none());
+        assert!(member_changes(&b.members, &b.members).is_empty());
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_3_fichiers_racine_commune_et_chemins_relatifs() {
+        let root = tmp_dir();
+        let c1 = write(&root, "livre/partie1/ch1.docx", b"c1");
+        let c2 = write(&root, "livre/partie2/ch2.docx", b"c2");
+        write(&root, "livre/partie2/hors_ensemble.txt", b"z");
+        let spec = files_spec(&[c2.clone(), c1.clone()]).unwrap();
+        assert_eq!(spec.kind, DocumentSetKind::Files);
+        assert!(spec.root.ends_with("livre"));
+        assert_eq!(spec.members, vec!["partie1/ch1.docx", "partie2/ch2.docx"]);
+        let d = capture_set(&spec).unwrap();
+        assert_eq!(paths(&d), vec!["partie1/ch1.docx", "partie2/ch2.docx"]);
+        // Un fichier hors ensemble ne compte pas.
+        write(&root, "livre/partie2/hors_ensemble.txt", b"zz");
+        assert_eq!(capture_set(&spec).unwrap(), d);
+        // Doublon refusé.
+        assert!(files_spec(&[c1.clone(), c1]).is_err());
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_4_membre_manquant_ou_dossier_vide_refuse() {
+        let root = tmp_dir();
+        let c1 = write(&root, "ch1.md", b"c1");
+        let c2 = write(&root, "ch2.md", b"c2");
+        let spec = files_spec(&[c1, c2.clone()]).unwrap();
+        fs::remove_file(&c2).unwrap();
+        assert!(capture_set(&spec).is_err(), "membre manquant accepté");
+
+        let empty = root.join("vide");
+        fs::create_dir_all(empty.join(".cache")).unwrap();
+        assert!(capture_set(&folder_spec(&empty).unwrap()).is_err());
+        assert!(folder_spec(&root.join("ch1.md")).is_err());
+        assert!(files_spec(&[]).is_err());
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_5_renommage_change_l_empreinte() {
+        let root = tmp_dir();
+        write(&root, "a.md", b"texte");
+        let spec = folder_spec(&root).unwrap();
+        let a = capture_set(&spec).unwrap();
+        fs::rename(root.join("a.md"), root.join("b.md")).unwrap();
+        let b = capture_set(&spec).unwrap();
+        assert_eq!(a.members[0].sha256, b.members[0].sha256);
+        assert_ne!(a.manifest_sha256, b.manifest_sha256);
+        cleanup(&root);
+    }
+}
@@ -0,0 +1,609 @@
+//! docx_metrics — métriques structurelles d'un DOCX (sans exposer le contenu).
+//!
+//! Portée STRICTE : lire les parties OOXML d'un `.docx` et en tirer des
+//! COMPTES et des EMPREINTES, jamais du texte :
+//!   - corps (`word/document.xml`) : longueur, mots, SHA256 du texte normalisé,
+//!     nombre de paragraphes ;
+//!   - en-têtes / pieds de page / notes de bas de page / notes de fin : mêmes
+//!     métriques, par famille de parties ;
+//!   - révisions suivies (`w:ins` / `w:del`) : comptes, caractères, et par
+//!     auteur la première / dernière date ;
+//!   - commentaires (`word/comments.xml`) : nombre ;
+//!   - propriétés (`docProps/core.xml` : révision, dates ; `docProps/app.xml` :
+//!     `TotalTime`, temps d'édition total en minutes).
+//!
+//! Révisions et temps d'édition sont des signaux de CORROBORATION d'un travail
+//! observé ; ils sont déclaratifs (écrits par le traitement de texte) et ne
+//! prouvent rien seuls.
+//!
+//! Garde-fous :
+//! - vrai parseur XML (`roxmltree`), DTD refusée (pas d'entités externes) ;
+//! - chaque partie lue est bornée (`MAX_PART_BYTES`) : pas de zip bomb ;
+//! - texte du corps = texte VISIBLE (`w:t`, insertions suivies comprises) ;
+//!   `w:delText` et codes de champ (`w:instrText`) exclus ;
+//! - aucun texte, nom de fichier ou chemin n'est renvoyé, seuls les auteurs
+//!   de révisions (métadonnée déjà publique du document) ;
+//! - `metrics_version` (`DOCX_METRICS_VERSION`) accompagne `word_count` /
+//!   `text_extract_hash` : deux mesures de versions différentes ne sont PAS
+//!   comparables (l'extraction a changé), le frontend ne les compare pas.
+//!
+//! AUCUNE écriture, AUCUNE commande Tauri ici (exposé par `main.rs`).
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::path::Path;
+
+const W_NS: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+const W_NS_STRICT: &str = "http://purl.oclc.org/ooxml/wordprocessingml/main";
+const DOCUMENT_PART: &str = "word/document.xml";
+const COMMENTS_PART: &str = "word/comments.xml";
+const FOOTNOTES_PART: &str = "word/footnotes.xml";
+const ENDNOTES_PART: &str = "word/endnotes.xml";
+const CORE_PART: &str = "docProps/core.xml";
+const APP_PART: &str = "docProps/app.xml";
+const MAX_PART_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Version de l'extraction du corps. 1 (implicite, champ absent) : ancien
+/// extracteur de `main.rs`, tout le texte hors balises (`w:delText` et codes
+/// de champ compris). 2 : texte visible via `roxmltree` (voir Garde-fous).
+pub(crate) const DOCX_METRICS_VERSION: u32 = 2;
+
+// --- TYPES --------------------------------------------------------------------
+
+/// Texte réduit à des métriques : mots séparés par un espace, puis SHA256.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TextMetrics {
+    pub text_length: usize,
+    pub word_count: usize,
+    pub text_extract_hash: String,
+}
+
+/// Famille de parties (en-têtes, pieds) ou de notes : `count` = parties
+/// (en-têtes/pieds) ou notes réelles (séparateurs exclus).
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PartsText {
+    pub count: usize,
+    #[serde(flatten)]
+    pub text: TextMetrics,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub(crate) struct RevisionAuthor {
+    pub author: String,
+    pub insertions: usize,
+    pub deletions: usize,
+    pub first_change_at: Option<String>,
+    pub last_change_at: Option<String>,
+}
+
+/// Révisions suivies, toutes parties confondues. `authors` trié par nom.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub(crate) struct TrackedChanges {
+    pub insertion_count: usize,
+    pub deletion_count: usize,
+    pub inserted_chars: usize,
+    pub deleted_chars: usize,
+    pub authors: Vec<RevisionAuthor>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub(crate) struct CoreProperties {
+    pub revision: Option<u64>,
+    pub total_editing_time_minutes: Option<u64>,
+    pub created_at: Option<String>,
+    pub modified_at: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DocxStructure {
+    pub paragraph_count: usize,
+    pub headers: PartsText,
+    pub footers: PartsText,
+    pub footnotes: PartsText,
+    pub endnotes: PartsText,
+    pub tracked_changes: TrackedChanges,
+    pub comments_count: usize,
+    pub core_properties: CoreProperties,
+}
+
+/// Réponse de `extract_docx_text_metrics` : champs historiques à plat
+/// (corps) + `metrics_version` + `docx_structure`.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DocxMetrics {
+    #[serde(flatten)]
+    pub body: TextMetrics,
+    pub metrics_version: u32,
+    pub extraction_status: String,
+    pub docx_structure: DocxStructure,
+}
+
+// --- HELPERS ------------------------------------------------------------------
+
+fn text_metrics(raw: &str) -> TextMetrics {
+    let normalized = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    TextMetrics {
+        text_length: normalized.chars().count(),
+        word_count: normalized.split_whitespace().count(),
+        text_extract_hash: format!("{:x}", Sha256::digest(normalized.as_bytes())),
+    }
+}
+
+fn is_w(node: roxmltree::Node, local: &str) -> bool {
+    node.is_element()
+        && node.tag_name().name() == local
+        && matches!(node.tag_name().namespace(), Some(W_NS) | Some(W_NS_STRICT))
+}
+
+/// Attribut `w:<local>` (même espace de noms que l'élément).
+fn w_attr<'a>(node: roxmltree::Node<'a, '_>, local: &str) -> Option<&'a str> {
+    let ns = node.tag_name().namespace();
+    node.attributes()
+        .find(|a| a.name() == local && a.namespace() == ns)
+        .map(|a| a.value())
+}
+
+/// Texte visible sous `node` : `w:t`, tabulations/sauts = espace, un espace
+/// entre paragraphes. `w:delText` / `w:instrText` ignorés.
+fn visible_text(node: roxmltree::Node, out: &mut String) {
+    for n in node.descendants() {
+        if is_w(n, "t") {
+            out.push_str(n.text().unwrap_or(""));
+        } else if is_w(n, "p") || is_w(n, "tab") || is_w(n, "br") || is_w(n, "cr") {
+            out.push(' ');
+        }
+    }
+}
+
+fn chars_under(node: roxmltree::Node, local: &str) -> usize {
+    node.descendants()
+        .filter(|n| is_w(*n, local))
+        .map(|n| n.text().unwrap_or("").chars().count())
+        .sum()
+}
+
+fn read_part<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<Option<String>, String> {
+    let part = match archive.by_name(name) {
+        Ok(p) => p,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    let mut buf = Vec::new();
+    part.take(MAX_PART_BYTES + 1)
+        .read_to_end(&mut buf)
+        .map_err(|e| e.to_string())?;
+    if buf.len() as u64 > MAX_PART_BYTES {
+        return Err(format!("part_too_large:{name}"));
+    }
+    String::from_utf8(buf)
+        .map(Some)
+        .map_err(|_| format!("part_not_utf8:{name}"))
+}
+
+fn parse<'x>(xml: &'x str, name: &str) -> Result<roxmltree::Document<'x>, String> {
+    roxmltree::Document::parse(xml).map_err(|e| format!("xml_invalid:{name}:{e}"))
+}
+
+/// Noms `word/<prefix>N.xml` (en-têtes, pieds), triés.
+fn part_names<R: Read + Seek>(archive: &zip::ZipArchive<R>, prefix: &str) -> Vec<String> {
+    let mut names: Vec<String> = archive
+        .file_names()
+        .filter(|n| {
+            n.strip_prefix("word/")
+                .and_then(|rest| rest.strip_prefix(prefix))
+                .and_then(|rest| rest.strip_suffix(".xml"))
+                .map(|digits| digits.chars().all(|c| c.is_ascii_digit()))
+                .unwrap_or(false)
+        })
+        .map(str::to_string)
+        .collect();
+    names.sort();
+    names
+}
+
+#[derive(Default)]
+struct RevisionTally {
+    changes: TrackedChanges,
+    by_author: BTreeMap<String, RevisionAuthor>,
+}
+
+impl RevisionTally {
+    fn scan(&mut self, doc: &roxmltree::Document) {
+        for n in doc.descendants() {
+            let inserted = is_w(n, "ins");
+            if !inserted && !is_w(n, "del") {
+                continue;
+            }
+            let author = w_attr(n, "author").unwrap_or("").to_string();
+            let entry = self
+                .by_author
+                .entry(author.clone())
+                .or_insert_with(|| RevisionAuthor {
+                    author,
+                    ..RevisionAuthor::default()
+                });
+            if inserted {
+                self.changes.insertion_count += 1;
+                self.changes.inserted_chars += chars_under(n, "t");
+                entry.insertions += 1;
+            } else {
+                self.changes.deletion_count += 1;
+                self.changes.deleted_chars += chars_under(n, "delText");
+                entry.deletions += 1;
+            }
+            // Dates ISO 8601 (UTC) : l'ordre lexical est l'ordre chronologique.
+            if let Some(date) = w_attr(n, "date") {
+                if entry.first_change_at.as_deref().is_none_or(|d| date < d) {
+                    entry.first_change_at = Some(date.to_string());
+                }
+                if entry.last_change_at.as_deref().is_none_or(|d| date > d) {
+                    entry.last_change_at = Some(date.to_string());
+                }
+            }
+        }
+    }
+
+    fn finish(mut self) -> TrackedChanges {
+        self.changes.authors = self.by_author.into_values().collect();
+        self.changes
+    }
+}
+
+/// En-têtes / pieds : une entrée par partie, texte concaténé.
+fn parts_text<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    prefix: &str,
+    tally: &mut RevisionTally,
+) -> Result<PartsText, String> {
+    let names = part_names(archive, prefix);
+    let mut text = String::new();
+    for name in &names {
+        if let Some(xml) = read_part(archive, name)? {
+            let doc = parse(&xml, name)?;
+            visible_text(doc.root(), &mut text);
+            text.push(' ');
+            tally.scan(&doc);
+        }
+    }
+    Ok(PartsText {
+        count: names.len(),
+        text: text_metrics(&text),
+    })
+}
+
+/// Notes (`w:footnote` / `w:endnote`) hors séparateurs techniques.
+fn notes_text<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    part: &str,
+    element: &str,
+    tally: &mut RevisionTally,
+) -> Result<PartsText, String> {
+    let mut count = 0;
+    let mut text = String::new();
+    if let Some(xml) = read_part(archive, part)? {
+        let doc = parse(&xml, part)?;
+        for note in doc.descendants().filter(|n| is_w(*n, element)) {
+            if matches!(
+                w_attr(note, "type"),
+                Some("separator") | Some("continuationSeparator") | Some("continuationNotice")
+            ) {
+                continue;
+            }
+            count += 1;
+            visible_text(note, &mut text);
+            text.push(' ');
+        }
+        tally.scan(&doc);
+    }
+    Ok(PartsText {
+        count,
+        text: text_metrics(&text),
+    })
+}
+
+fn core_properties<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> Result<CoreProperties, String> {
+    // Propriétés déclaratives : une partie illisible est ignorée, pas fatale.
+    let child_text = |doc: &roxmltree::Document, local: &str| {
+        doc.descendants()
+            .find(|n| n.is_element() && n.tag_name().name() == local)
+            .and_then(|n| n.text())
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+    };
+    let mut props = CoreProperties::default();
+    if let Some(xml) = read_part(archive, CORE_PART)? {
+        if let Ok(doc) = parse(&xml, CORE_PART) {
+            props.revision = child_text(&doc, "revision").and_then(|v| v.parse().ok());
+            props.created_at = child_text(&doc, "created");
+            props.modified_at = child_text(&doc, "modified");
+        }
+    }
+    if let Some(xml) = read_part(archive, APP_PART)? {
+        if let Ok(doc) = parse(&xml, APP_PART) {
+            props.total_editing_time_minutes =
+                child_text(&doc, "TotalTime").and_then(|v| v.parse().ok());
+        }
+    }
+    Ok(props)
+}
+
+// --- EXTRACTION ---------------------------------------------------------------
+
+fn analyze_docx<R: Read + Seek>(reader: R) -> Result<DocxMetrics, String> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(|e| e.to_string())?;
+    let document_xml =
+        read_part(&mut archive, DOCUMENT_PART)?.ok_or_else(|| "no_document_xml".to_string())?;
+    let document = parse(&document_xml, DOCUMENT_PART)?;
+
+    let mut tally = RevisionTally::default();
+    tally.scan(&document);
+
+    let body = document
+        .descendants()
+        .find(|n| is_w(*n, "body"))
+        .ok_or_else(|| "no_document_body".to_string())?;
+    let mut body_text = String::new();
+    visible_text(body, &mut body_text);
+    let paragraph_count = body.descendants().filter(|n| is_w(*n, "p")).count();
+
+    let headers = parts_text(&mut archive, "header", &mut tally)?;
+    let footers = parts_text(&mut archive, "footer", &mut tally)?;
+    let footnotes = notes_text(&mut archive, FOOTNOTES_PART, "footnote", &mut tally)?;
+    let endnotes = notes_text(&mut archive, ENDNOTES_PART, "endnote", &mut tally)?;
+
+    let comments_count = match read_part(&mut archive, COMMENTS_PART)? {
+        Some(xml) => parse(&xml, COMMENTS_PART)?
+            .descendants()
+            .filter(|n| is_w(*n, "comment"))
+            .count(),
+        None => 0,
+    };
+    let core_properties = core_properties(&mut archive)?;
+
+    Ok(DocxMetrics {
+        body: text_metrics(&body_text),
+        metrics_version: DOCX_METRICS_VERSION,
+        extraction_status: "ok".to_string(),
+        docx_structure: DocxStructure {
+            paragraph_count,
+            headers,
+            footers,
+            footnotes,
+            endnotes,
+            tracked_changes: tally.finish(),
+            comments_count,
+            core_properties,
+        },
+    })
+}
+
+pub(crate) fn extract_docx_metrics(path: &Path) -> Result<DocxMetrics, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    analyze_docx(BufReader::new(file))
+}
+
+/// Paragraphes visibles du corps (espaces normalisés, paragraphes vides
+/// omis), pour la comparaison début/fin de période. Reste en mémoire.
+fn body_paragraphs<R: Read + Seek>(reader: R) -> Result<Vec<String>, String> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(|e| e.to_string())?;
+    let document_xml =
+        read_part(&mut archive, DOCUMENT_PART)?.ok_or_else(|| "no_document_xml".to_string())?;
+    let document = parse(&document_xml, DOCUMENT_PART)?;
+    let body = document
+        .descendants()
+        .find(|n| is_w(*n, "body"))
+        .ok_or_else(|| "no_document_body".to_string())?;
+    let paragraphs = body
+        .descendants()
+        // Paragraphes de premier niveau : ceux d'une zone de texte sont déjà
+        // compris dans le paragraphe qui la porte.
+        .filter(|n| is_w(*n, "p") && !n.ancestors().skip(1).any(|a| is_w(a, "p")))
+        .map(|p| {
+            let mut text = String::new();
+            visible_text(p, &mut text);
+            text.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .filter(|t| !t.is_empty())
+        .collect();
+    Ok(paragraphs)
+}
+
+/// Paragraphes du corps d'un DOCX déjà en mémoire (document courant ou
+/// snapshot de début de période).
+pub(crate) fn docx_body_paragraphs(bytes: &[u8]) -> Result<Vec<String>, String> {
+    body_paragraphs(Cursor::new(bytes))
+}
+
+// --- TESTS --------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const W: &str = r#"xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main""#;
+
+    fn docx(parts: &[(&str, String)]) -> Cursor<Vec<u8>> {
+        let mut zw = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            zw.start_file(*name, zip::write::FileOptions::default())
+                .unwrap();
+            zw.write_all(content.as_bytes()).unwrap();
+        }
+        let mut cursor = zw.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    fn document(body: &str) -> (&'static str, String) {
+        (
+            DOCUMENT_PART,
+            format!(r#"<?xml version="1.0"?><w:document {W}><w:body>{body}</w:body></w:document>"#),
+        )
+    }
+
+    #[test]
+    fn test_1_corps_texte_et_paragraphes() {
+        let d = docx(&[document(
+            r#"<w:p><w:r><w:t>Bon</w:t></w:r><w:r><w:t>jour le</w:t></w:r></w:p>
+               <w:p><w:r><w:t>monde</w:t><w:tab/><w:t>&amp; co</w:t></w:r></w:p>
+               <w:p/>"#,
+        )]);
+        let m = analyze_docx(d).unwrap();
+        // Les runs d'un même mot ne sont PAS coupés (contrairement au strip naïf).
+        assert_eq!(m.body.word_count, 5);
+        assert_eq!(m.body.text_length, "Bonjour le monde & co".len());
+        assert_eq!(
+            m.body.text_extract_hash,
+            text_metrics("Bonjour le monde & co").text_extract_hash
+        );
+        assert_eq!(m.docx_structure.paragraph_count, 3);
+        assert_eq!(m.extraction_status, "ok");
+    }
+
+    #[test]
+    fn test_2_revisions_suivies_par_auteur() {
+        let d = docx(&[document(
+            r#"<w:p>
+                 <w:ins w:id="1" w:author="Alice" w:date="2026-03-02T10:00:00Z"><w:r><w:t>ajout</w:t></w:r></w:ins>
+                 <w:del w:id="2" w:author="Alice" w:date="2026-03-01T09:00:00Z"><w:r><w:delText>retiré</w:delText></w:r></w:del>
+               </w:p>
+               <w:p>
+                 <w:ins w:id="3" w:author="Bob" w:date="2026-03-05T08:00:00Z"><w:r><w:t>x</w:t></w:r></w:ins>
+               </w:p>"#,
+        )]);
+        let m = analyze_docx(d).unwrap();
+        let tc = &m.docx_structure.tracked_changes;
+        assert_eq!((tc.insertion_count, tc.deletion_count), (2, 1));
+        assert_eq!((tc.inserted_chars, tc.deleted_chars), (6, 6));
+        assert_eq!(tc.authors.len(), 2);
+        let alice = &tc.authors[0];
+        assert_eq!(alice.author, "Alice");
+        assert_eq!((alice.insertions, alice.deletions), (1, 1));
+        assert_eq!(
+            alice.first_change_at.as_deref(),
+            Some("2026-03-01T09:00:00Z")
+        );
+        assert_eq!(
+            alice.last_change_at.as_deref(),
+            Some("2026-03-02T10:00:00Z")
+        );
+        // Texte supprimé exclu du texte visible.
+        assert_eq!(m.body.word_count, 2);
+    }
+
+    #[test]
+    fn test_3_entetes_pieds_notes_commentaires() {
+        let d = docx(&[
+            document("<w:p><w:r><w:t>corps</w:t></w:r></w:p>"),
+            (
+                "word/header1.xml",
+                format!("<w:hdr {W}><w:p><w:r><w:t>Titre courant</w:t></w:r></w:p></w:hdr>"),
+            ),
+            (
+                "word/footer1.xml",
+                format!("<w:ftr {W}><w:p><w:r><w:t>Page</w:t></w:r></w:p></w:ftr>"),
+            ),
+            (
+                "word/footer2.xml",
+                format!("<w:ftr {W}><w:p><w:r><w:t>Confidentiel</w:t></w:r></w:p></w:ftr>"),
+            ),
+            (
+                FOOTNOTES_PART,
+                format!(
+                    r#"<w:footnotes {W}>
+                         <w:footnote w:type="separator" w:id="-1"><w:p><w:r><w:separator/></w:r></w:p></w:footnote>
+                         <w:footnote w:id="1"><w:p><w:r><w:t>Une note utile</w:t></w:r></w:p></w:footnote>
+                       </w:footnotes>"#
+                ),
+            ),
+            (
+                COMMENTS_PART,
+                format!(
+                    r#"<w:comments {W}><w:comment w:id="0"/><w:comment w:id="1"/></w:comments>"#
+                ),
+            ),
+        ]);
+        let s = analyze_docx(d).unwrap().docx_structure;
+        assert_eq!((s.headers.count, s.headers.text.word_count), (1, 2));
+        assert_eq!((s.footers.count, s.footers.text.word_count), (2, 2));
+        assert_eq!((s.footnotes.count, s.footnotes.text.word_count), (1, 3));
+        assert_eq!(s.endnotes.count, 0);
+        assert_eq!(s.comments_count, 2);
+    }
+
+    #[test]
+    fn test_4_proprietes_revision_et_temps_d_edition() {
+        let d = docx(&[
+            document("<w:p/>"),
+            (
+                CORE_PART,
+                r#"<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dcterms="http://purl.org/dc/terms/">
+                     <cp:revision>42</cp:revision>
+                     <dcterms:created>2026-01-10T08:00:00Z</dcterms:created>
+                     <dcterms:modified>2026-03-05T18:30:00Z</dcterms:modified>
+                   </cp:coreProperties>"#
+                    .to_string(),
+            ),
+            (
+                APP_PART,
+                r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties"><TotalTime>315</TotalTime></Properties>"#
+                    .to_string(),
+            ),
+        ]);
+        let p = analyze_docx(d).unwrap().docx_structure.core_properties;
+        assert_eq!(p.revision, Some(42));
+        assert_eq!(p.total_editing_time_minutes, Some(315));
+        assert_eq!(p.created_at.as_deref(), Some("2026-01-10T08:00:00Z"));
+        assert_eq!(p.modified_at.as_deref(), Some("2026-03-05T18:30:00Z"));
+    }
+
+    #[test]
+    fn test_5_sans_document_xml_ou_dtd_refuse() {
+        let d = docx(&[("word/other.xml", "<x/>".to_string())]);
+        assert_eq!(analyze_docx(d).err(), Some("no_document_xml".to_string()));
+
+        let d = docx(&[(
+            DOCUMENT_PART,
+            r#"<!DOCTYPE d [<!ENTITY x SYSTEM "file:///etc/passwd">]><d>&x;</d>"#.to_string(),
+        )]);
+        assert!(analyze_docx(d).is_err());
+    }
+
+    #[test]
+    fn test_6_json_compatible_avec_l_ancien_format() {
+        let d = docx(&[document("<w:p><w:r><w:t>un deux</w:t></w:r></w:p>")]);
+        let v = serde_json::to_value(analyze_docx(d).unwrap()).unwrap();
+        assert_eq!(v["word_count"], 2);
+        assert_eq!(v["text_length"], 7);
+        assert!(v["text_extract_hash"].is_string());
+        assert_eq!(v["metrics_version"], DOCX_METRICS_VERSION);
+        assert_eq!(v["extraction_status"], "ok");
+        assert_eq!(v["docx_structure"]["tracked_changes"]["insertion_count"], 0);
+        assert!(v["docx_structure"]["core_properties"]["revision"].is_null());
+        // Aucun texte du document dans la sortie.
+        assert!(!v.to_string().contains("deux"));
+    }
+
+    #[test]
+    fn test_7_paragraphes_du_corps_pour_comparaison() {
+        let d = docx(&[document(
+            r#"<w:p><w:r><w:t>Un   </w:t></w:r><w:r><w:t>début</w:t></w:r></w:p>
+               <w:p/>
+               <w:p><w:r><w:t>suite</w:t></w:r>
+                 <w:r><w:txbxContent><w:p><w:r><w:t>encadré</w:t></w:r></w:p></w:txbxContent></w:r>
+               </w:p>"#,
+        )]);
+        assert_eq!(
+            body_paragraphs(d).unwrap(),
+            vec!["Un début".to_string(), "suite encadré".to_string()]
+        );
+    }
+}
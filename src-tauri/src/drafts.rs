@@ -18,6 +18,7 @@ const KEYCHAIN_ACCOUNT: &str = "ho_draft_key_v1";
 const DRAFTS_DIR: &str = "ho_drafts";
 const VERSION: u8 = 1;
 const NONCE_LEN: usize = 12;
+pub(crate) const KEY_LEN: usize = 32;
 
 // Métadonnées lisibles sans déchiffrer
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,17 +72,56 @@ fn get_or_create_key_b64() -> Result<String, String> {
     }
 }
 
-fn cipher_from_key() -> Result<Aes256Gcm, String> {
+/// Clé locale (Keychain) partagée par les drafts et les snapshots de période.
+pub(crate) fn local_key() -> Result<[u8; KEY_LEN], String> {
     let key_b64 = get_or_create_key_b64()?;
     let key_bytes = general_purpose::STANDARD
         .decode(key_b64.trim())
         .map_err(|e| e.to_string())?;
 
-    if key_bytes.len() != 32 {
-        return Err("La clé Draft doit faire 32 bytes".into());
+    key_bytes
+        .try_into()
+        .map_err(|_| "La clé Draft doit faire 32 bytes".to_string())
+}
+
+/// Chiffre `msg` lié à `aad` (anti swap).
+/// Format: [VERSION 1o][NONCE 12o][CIPHERTEXT...]
+pub(crate) fn seal(key: &[u8; KEY_LEN], aad: &[u8], msg: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| e.to_string())?;
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, Payload { msg, aad })
+        .map_err(|_| "Erreur chiffrement".to_string())?;
+
+    let mut out = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+    out.push(VERSION);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Inverse de `seal` : refuse un format inconnu, une autre clé ou un autre `aad`.
+pub(crate) fn open(key: &[u8; KEY_LEN], aad: &[u8], bytes: &[u8]) -> Result<Vec<u8>, String> {
+    if bytes.len() < 1 + NONCE_LEN {
+        return Err("Fichier chiffré corrompu (trop court)".into());
+    }
+    if bytes[0] != VERSION {
+        return Err("Version de fichier chiffré incompatible".into());
     }
 
-    Aes256Gcm::new_from_slice(&key_bytes).map_err(|e| e.to_string())
+    let nonce_bytes = &bytes[1..1 + NONCE_LEN];
+    let ciphertext = &bytes[1 + NONCE_LEN..];
+
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| e.to_string())?;
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    cipher
+        .decrypt(nonce, Payload { msg: ciphertext, aad })
+        .map_err(|_| "Erreur déchiffrement (Clé invalide ou fichier altéré)".to_string())
 }
 
 pub fn save_draft(
@@ -94,25 +134,12 @@ pub fn save_draft(
     let dir = drafts_dir(app_data_dir);
     ensure_dir(&dir)?;
 
-    let cipher = cipher_from_key()?;
-
-    let mut nonce_bytes = [0u8; NONCE_LEN];
-    OsRng.fill_bytes(&mut nonce_bytes);
-    let nonce = Nonce::from_slice(&nonce_bytes);
+    let key = local_key()?;
 
     // Associated Data = session_id (anti swap)
-    let aad = session_id.as_bytes();
-
-    let ciphertext = cipher
-        .encrypt(nonce, Payload { msg: snapshot_json.as_bytes(), aad })
+    let out = seal(&key, session_id.as_bytes(), snapshot_json.as_bytes())
         .map_err(|_| "Erreur chiffrement draft".to_string())?;
 
-    // Format: [VERSION 1o][NONCE 12o][CIPHERTEXT...]
-    let mut out = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
-    out.push(VERSION);
-    out.extend_from_slice(&nonce_bytes);
-    out.extend_from_slice(&ciphertext);
-
     let bin_path = draft_bin_path(app_data_dir, session_id);
     fs::write(&bin_path, out).map_err(|e| e.to_string())?;
 
@@ -133,23 +160,8 @@ pub fn load_draft(app_data_dir: &Path, session_id: &str) -> Result<String, Strin
     let path = draft_bin_path(app_data_dir, session_id);
     let bytes = fs::read(&path).map_err(|e| format!("Fichier introuvable ou illisible: {}", e))?;
 
-    if bytes.len() < 1 + NONCE_LEN {
-        return Err("Fichier draft corrompu (trop court)".into());
-    }
-    if bytes[0] != VERSION {
-        return Err("Version draft incompatible".into());
-    }
-
-    let nonce_bytes = &bytes[1..1 + NONCE_LEN];
-    let ciphertext = &bytes[1 + NONCE_LEN..];
-
-    let cipher = cipher_from_key()?;
-    let nonce = Nonce::from_slice(nonce_bytes);
-    let aad = session_id.as_bytes();
-
-    let plaintext = cipher
-        .decrypt(nonce, Payload { msg: ciphertext, aad })
-        .map_err(|_| "Erreur déchiffrement (Clé invalide ou fichier altéré)".to_string())?;
+    let key = local_key()?;
+    let plaintext = open(&key, session_id.as_bytes(), &bytes)?;
 
     String::from_utf8(plaintext).map_err(|e| e.to_string())
 }
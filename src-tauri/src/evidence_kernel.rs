@@ -1,26 +1,36 @@
-//! evidence_kernel.rs — Vocabulaire V2 media-agnostic (READ-ONLY, NON câblé au runtime).
+//! evidence_kernel.rs — Vocabulaire V2 media-agnostic.
 //!
-//! Objectif (V2-M2) : figer des *types de vocabulaire* pour un futur noyau de preuve
+//! Objectif (V2-M2) : figer des *types de vocabulaire* pour un noyau de preuve
 //! généralisable (PDF, image, vidéo, audio, code) SANS toucher au moteur V1.
 //!
 //! Garanties de ce module :
-//! - Il ne modifie AUCUNE structure signée existante (WorkCertificate, PublicCoreEvidence,
-//!   ObservationPeriod, PackageManifest ne sont pas importés ni touchés).
-//! - Il n'est appelé par AUCUNE commande Tauri, AUCUNE UI, AUCUN chemin de publication.
-//! - Le simple `mod evidence_kernel;` ne fait que compiler ce fichier : aucun code ne
-//!   s'exécute tant qu'aucun appelant ne l'invoque → comportement runtime inchangé.
-//! - `serde` n'est dérivé QUE sur ces nouveaux types non branchés.
+//! - Il n'importe AUCUNE structure signée (WorkCertificate, PublicCoreEvidence,
+//!   ObservationPeriod, PackageManifest) : ce sont elles qui référencent ses types.
+//! - Il n'est appelé par AUCUNE commande Tauri, AUCUNE UI ; il ne fait aucune I/O.
+//! - Types câblés au runtime (tous optionnels ou au format V1 inchangé) :
+//!   - V2-C : `ChunkedFingerprint`, produit par `file_chunks` et porté (absent si
+//!     `None`) par la frontière d'une période ;
+//!   - V2-B : `MediaSpecificMetadata::Image`, produit par `image_fingerprint` et
+//!     porté (absent si `None`) par la version source du sidecar image ;
+//!   - V2-D : `MediaSpecificMetadata::Audio`, produit par `audio_fingerprint` et
+//!     porté de même par la version source du sidecar audio ;
+//!   - V2-M3C : `DocumentAdapter`, implémenté par `work_publish::PdfDocumentAdapter`,
+//!     `work_image::ImageDocumentAdapter` (V2-B) et
+//!     `work_audio::AudioDocumentAdapter` (V2-D), et `RenderedArtifactRef`,
+//!     référencé par `PackageManifest` (un artefact PDF reste sérialisé en
+//!     `labeled_pdf` V1).
 //! - Les `Option` respectent déjà la règle HO-JSON V2 : `skip_serializing_if = "Option::is_none"`
 //!   (voir docs/v2/V2_HO_JSON_V2_COMPATIBILITY_SPEC.md).
 //!
-//! Ce module est volontairement inerte : ses types sont du vocabulaire, pas du comportement.
+//! Ce module reste du vocabulaire : le comportement vit dans les adaptateurs.
 
-#![allow(dead_code)] // Types de vocabulaire V2 : aucun appelant runtime en V2-M2 (attendu).
+#![allow(dead_code)] // Vocabulaire V2 : une partie des types n'a pas encore d'appelant runtime.
 
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
-/// Numéro de schéma du futur HO-JSON V2 (distinct de `CertificateVersion::V2`, qui est un
-/// marqueur de chaînage). Ici purement documentaire ; non écrit dans aucun certificat.
+/// Numéro de schéma du payload HO-JSON V2 (distinct de `CertificateVersion::V2`, qui est
+/// un marqueur de chaînage). Écrit par `ho_json_v2` ; jamais dans un certificat.
 pub const HO_JSON_V2_SCHEMA_VERSION: u32 = 2;
 
 /// Claim canonique autorisé (rappel de vocabulaire, non affiché par ce module).
@@ -109,17 +119,104 @@ pub struct ObjectVersionRef {
     /// Type MIME, optionnel. Absent de la sérialisation si `None`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<String>,
+    /// Empreinte par blocs (gros médias), en plus de `fingerprint_sha256`.
+    /// Absente de la sérialisation si `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunked_fingerprint: Option<ChunkedFingerprint>,
+    /// Métadonnées propres au média (ex. empreinte perceptuelle d'une image).
+    /// Absentes de la sérialisation si `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_specific: Option<MediaSpecificMetadata>,
+}
+
+/// Empreinte par blocs d'une version : découpage défini par le contenu, racine
+/// Merkle des SHA256 de blocs (calcul : `file_chunks`).
+///
+/// Complète le SHA256 plat sans le remplacer : deux versions proches d'un gros
+/// média partagent la plupart de leurs blocs. Aucun chemin, aucun contenu.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkedFingerprint {
+    /// Schéma de découpage et d'arbre (ex. `HO-CDC-GEAR-SHA256-MERKLE-V1`).
+    pub scheme: String,
+    /// Racine Merkle SHA256 hex.
+    pub merkle_root: String,
+    /// Nombre de blocs.
+    pub chunk_count: u64,
 }
 
-/// Placeholder de métadonnées propres à un média (namespace `media_specific`).
+/// Métadonnées propres à un média (namespace `media_specific`).
 ///
-/// Volontairement inutilisé en V2-M2 : les métadonnées perceptuelles (image/audio),
-/// de séquence (vidéo), etc. seront modélisées ici lors d'un ticket ultérieur (V2-M5+).
+/// Image (V2-B) : empreinte perceptuelle calculée par `image_fingerprint`.
+/// Audio (V2-D) : empreinte du signal calculée par `audio_fingerprint`. Les
+/// métadonnées de séquence (vidéo) viendront avec leur tier.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum MediaSpecificMetadata {
     /// Aucune métadonnée spécifique (cas par défaut, y compris PDF actuel).
     Unspecified,
+    /// Image : empreinte perceptuelle + dimensions + profil couleur.
+    Image(ImageFingerprint),
+    /// Audio : format du flux + empreinte du signal (hors tags).
+    Audio(AudioFingerprint),
+}
+
+/// Empreinte d'une image décodée : survit à un ré-encodage (réseaux sociaux)
+/// là où le SHA256 du fichier ne correspond plus.
+///
+/// Aucun contenu : seulement des empreintes et des dimensions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageFingerprint {
+    pub width: u32,
+    pub height: u32,
+    /// Type de couleur décodé (ex. `rgb8`, `rgba16`, `l8`).
+    pub color_type: String,
+    /// SHA256 hex du profil ICC embarqué. Absent de la sérialisation si `None`
+    /// (image sans profil : sRGB implicite).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icc_profile_sha256: Option<String>,
+    /// SHA256 hex des pixels décodés (dimensions incluses) : identique si les
+    /// pixels le sont, quel que soit l'encodage du fichier.
+    pub pixel_sha256: String,
+    pub perceptual_hash: PerceptualHash,
+    /// dHash de la copie labellisée (marge + cartouche incluses) : une
+    /// recompression de CETTE copie reste reconnaissable. Absent de la
+    /// sérialisation si `None` (sidecars antérieurs inchangés).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labeled_perceptual_hash: Option<PerceptualHash>,
+}
+
+/// Hash perceptuel d'une image (ex. dHash 64 bits, hex).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PerceptualHash {
+    /// Algorithme + version (ex. `HO-DHASH-64-V2`).
+    pub scheme: String,
+    /// Valeur hex (16 caractères pour 64 bits).
+    pub value: String,
+}
+
+/// Empreinte d'un fichier audio : identique tant que le signal l'est, quels
+/// que soient les tags (BWF `bext`, ID3, Vorbis comments) ajoutés ou retirés.
+///
+/// Aucun contenu : seulement le format et une empreinte.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioFingerprint {
+    /// Conteneur (`wav`, `flac`, `mp3`).
+    pub container: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Profondeur d'échantillon. Absente pour un format à perte (MP3).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bits_per_sample: Option<u16>,
+    pub signal: AudioSignalDigest,
+}
+
+/// Empreinte du signal audio, qualifiée par son schéma (ex. `HO-PCM-SHA256-V1`
+/// pour le PCM décodé, `HO-AUDIO-FRAMES-SHA256-V1` pour les trames FLAC/MP3).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioSignalDigest {
+    pub scheme: String,
+    /// Valeur hex.
+    pub value: String,
 }
 
 impl Default for MediaSpecificMetadata {
@@ -129,31 +226,28 @@ impl Default for MediaSpecificMetadata {
 }
 
 // ---------------------------------------------------------------------------
-// V2-M3B : interface cible DocumentAdapter (READ-ONLY, NON câblée au runtime).
+// V2-M3B/C : interface DocumentAdapter (câblée en V2-M3C).
 //
-// Ces types formalisent la frontière déjà présente sous forme de closures dans
-// `work_publish.rs::create_labeled_package_inner` :
-//   - `cartouche_fn`      (FnOnce(&WorkCertificate, &Path))         → marquage
-//   - `generate_pdf_fn`   (FnOnce(&Path, &Path, &Path, …))          → rendu labellisé
-//   - `make_package_fn`   (FnOnce(&Path, &Path) -> PackageManifest) → reflété PARTIELLEMENT
-//     (le nom d'artefact `default_artifact_filename` correspond à `LABELED_PDF_FILENAME`).
+// Ces types formalisent la frontière de `work_publish.rs::labeled_package_flow` :
+//   - marquage (cartouche) + rendu labellisé → `render_labeled_artifact` ;
+//   - le package référence l'artefact produit (`RenderedArtifactRef`), et non
+//     plus un nom de fichier PDF codé en dur.
 //
 // IMPORTANT :
-//   - Ce trait n'est PAS câblé en V2-M3B : aucune closure de production n'est
-//     modifiée, `work_publish.rs` reste intact, le comportement PDF est inchangé.
-//   - Le trait exprime l'INTERFACE cible ; il n'implémente aucun rendu.
-//   - Il ne dépend NI de `publication_core.rs`, NI de PDFium, NI de `work_publish.rs`,
-//     NI d'aucun chemin runtime (la requête est media-agnostic, sans `Path`).
-//   - Le rebranchement réel (remplacer les closures par une impl de ce trait) sera
-//     V2-M3C, avec cold test publication PDF complet (multipage, paysage, contenu
-//     conservé, cartouche B4 inchangée, manifest inchangé).
+//   - Le trait ne dépend NI de `publication_core.rs`, NI de PDFium, NI de
+//     `work_publish.rs` : seuls la source à labelliser et le dossier de sortie
+//     (temporaire, fourni par le flux) sont des chemins ; la requête reste
+//     media-agnostic, sans `Path`.
+//   - L'implémentation PDF (`work_publish::PdfDocumentAdapter`) appelle le
+//     pipeline existant TEL QUEL : cartouche et manifest PDF inchangés.
+//   - Un nouveau média = un nouvel adaptateur, sans réécrire le flux de publication.
 // ---------------------------------------------------------------------------
 
 /// Type de l'artefact *rendu/labellisé* produit en SORTIE par un `DocumentAdapter`.
 ///
 /// À distinguer de [`MediaKind`], qui décrit le média de l'objet observé en ENTRÉE.
-/// Aujourd'hui, le seul rendu réel est `Pdf` (via `publish_pdf_core`) ; les autres
-/// variantes préparent la roadmap sans qu'aucune ne soit implémentée.
+/// Rendus réels : `Pdf` (via `publish_pdf_core`) et `Image` (via `work_image`) ;
+/// les autres variantes préparent la roadmap.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DocumentArtifactKind {
@@ -209,12 +303,11 @@ pub struct RenderedArtifactRef {
     pub mime_type: Option<String>,
 }
 
-/// Requête conceptuelle de rendu d'un artefact labellisé (placeholder V2-M3B).
+/// Requête de rendu d'un artefact labellisé.
 ///
-/// Reflète, de façon media-agnostic et SANS aucun `Path`, les paramètres que
-/// `generate_pdf_fn` reçoit aujourd'hui (source, id de certificat, URL de
-/// vérification, libellé de verdict). Volontairement dépourvue de tout type
-/// PDFium ou chemin runtime.
+/// Reflète, de façon media-agnostic et SANS aucun `Path`, ce que le marquage
+/// et le rendu reçoivent (source, certificat, URL de vérification, verdict).
+/// Volontairement dépourvue de tout type PDFium ou chemin runtime.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LabeledArtifactRequest {
     /// Objet observé source.
@@ -227,14 +320,35 @@ pub struct LabeledArtifactRequest {
     pub verify_url: String,
     /// Libellé de verdict (ex. `OBSERVED_WORK_CONSISTENT`).
     pub verdict_label: String,
+    /// Empreinte de la clé signataire du certificat (affichée par le marquage).
+    /// Absente si `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key_id: Option<String>,
+    /// Date de création du certificat (affichée par le marquage). Absente si `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub certificate_created_at: Option<String>,
+}
+
+/// Échec d'un adaptateur : marquage (cartouche…) ou rendu de l'artefact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactRenderError {
+    /// Le marquage a échoué.
+    MarkingFailed(String),
+    /// Le marquage n'a rien produit (absent ou vide).
+    MarkingNotProduced,
+    /// Le rendu de l'artefact a échoué.
+    RenderFailed(String),
+    /// Le rendu n'a produit aucun artefact.
+    NotProduced,
 }
 
-/// Interface cible d'un adaptateur de rendu document/média (NON câblée en V2-M3B).
+/// Interface d'un adaptateur de rendu document/média.
 ///
-/// Une future implémentation `PdfDocumentAdapter` regroupera les closures
-/// `cartouche_fn` + `generate_pdf_fn` de `work_publish.rs` ; le nom d'artefact
-/// (`default_artifact_filename`) correspondra à `LABELED_PDF_FILENAME`. Aucune
-/// implémentation de production n'existe ni n'est appelée à ce stade.
+/// Implémentations de production : `work_publish::PdfDocumentAdapter`
+/// (cartouche + `publish_pdf_core`) et `work_image::ImageDocumentAdapter`
+/// (marge + cartouche, XMP/EXIF, sidecar `.ho.json`). Le flux de publication ne connaît que ce
+/// trait : il fournit la source à labelliser et un dossier de sortie
+/// temporaire, puis embarque l'artefact décrit par le `RenderedArtifactRef`.
 pub trait DocumentAdapter {
     /// Nom court de l'adaptateur (diagnostic).
     fn adapter_name(&self) -> &'static str;
@@ -245,14 +359,20 @@ pub trait DocumentAdapter {
     /// Nom de fichier par défaut de l'artefact labellisé (ex. `labeled_document.pdf`).
     fn default_artifact_filename(&self) -> &'static str;
 
-    /// Produit l'artefact labellisé (reflète `cartouche_fn` + `generate_pdf_fn`).
-    ///
-    /// Signature placeholder : aucune implémentation de rendu n'est fournie ici.
-    /// Le rebranchement réel viendra en V2-M3C.
+    /// Produit l'artefact labellisé de `source` dans `output_dir` (marquage +
+    /// rendu). L'artefact renvoyé est nommé RELATIVEMENT à `output_dir`.
     fn render_labeled_artifact(
         &self,
         request: &LabeledArtifactRequest,
-    ) -> Result<RenderedArtifactRef, String>;
+        source: &Path,
+        output_dir: &Path,
+    ) -> Result<RenderedArtifactRef, ArtifactRenderError>;
+
+    /// Nom du sidecar `.ho.json` que `render_labeled_artifact` écrit à côté de
+    /// l'artefact dans `output_dir`, s'il en écrit un. Par défaut : aucun.
+    fn sidecar_filename(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -300,13 +420,42 @@ mod tests {
             fingerprint_sha256: "a".repeat(64),
             size_bytes: None,
             mime_type: None,
+            chunked_fingerprint: None,
+            media_specific: None,
         };
         let vj = serde_json::to_string(&v).unwrap();
         assert!(!vj.contains("size_bytes"));
         assert!(!vj.contains("mime_type"));
+        assert!(!vj.contains("chunked_fingerprint"));
         assert!(!vj.contains("null"));
     }
 
+    #[test]
+    fn object_version_carries_flat_and_chunked_fingerprints() {
+        let v = ObjectVersionRef {
+            fingerprint_sha256: "f".repeat(64),
+            size_bytes: Some(3 * 1024 * 1024 * 1024),
+            mime_type: Some("video/mp4".into()),
+            chunked_fingerprint: Some(ChunkedFingerprint {
+                scheme: "HO-CDC-GEAR-SHA256-MERKLE-V1".into(),
+                merkle_root: "0".repeat(64),
+                chunk_count: 3072,
+            }),
+            media_specific: None,
+        };
+        let json = serde_json::to_string(&v).unwrap();
+        assert!(json.contains("\"merkle_root\""));
+        let back: ObjectVersionRef = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, v);
+        // Une version V2-M2 (sans empreinte par blocs) reste lisible.
+        let old: ObjectVersionRef = serde_json::from_str(&format!(
+            "{{\"fingerprint_sha256\":\"{}\"}}",
+            "a".repeat(64)
+        ))
+        .unwrap();
+        assert!(old.chunked_fingerprint.is_none());
+    }
+
     #[test]
     fn represents_pdf_now_and_image_future_without_local_path() {
         // Cas PDF actuel.
@@ -315,6 +464,8 @@ mod tests {
             fingerprint_sha256: "b".repeat(64),
             size_bytes: Some(1024),
             mime_type: Some("application/pdf".into()),
+            chunked_fingerprint: None,
+            media_specific: None,
         };
         // Cas image future.
         let img = ObservedObjectRef::new("obj-img-9", MediaKind::Image);
@@ -322,6 +473,8 @@ mod tests {
             fingerprint_sha256: "c".repeat(64),
             size_bytes: Some(4096),
             mime_type: Some("image/png".into()),
+            chunked_fingerprint: None,
+            media_specific: None,
         };
 
         // Aucun chemin local ne doit apparaître dans une quelconque sérialisation.
@@ -344,7 +497,49 @@ mod tests {
         assert_eq!(MediaSpecificMetadata::default(), MediaSpecificMetadata::Unspecified);
     }
 
-    // --- V2-M3B : DocumentAdapter (trait non câblé, vérifié uniquement en test) ---
+    #[test]
+    fn image_metadata_serializes_under_image_namespace() {
+        let meta = MediaSpecificMetadata::Image(ImageFingerprint {
+            width: 640,
+            height: 480,
+            color_type: "rgb8".to_string(),
+            icc_profile_sha256: None,
+            pixel_sha256: "a".repeat(64),
+            perceptual_hash: PerceptualHash {
+                scheme: "HO-DHASH-64-V1".to_string(),
+                value: "0f0f0f0f0f0f0f0f".to_string(),
+            },
+            labeled_perceptual_hash: None,
+        });
+        let json = serde_json::to_string(&meta).unwrap();
+        assert!(json.starts_with("{\"image\":"));
+        assert!(!json.contains("icc_profile_sha256"));
+        assert!(!json.contains("labeled_perceptual_hash"));
+        assert!(!json.contains("null"));
+        let back: MediaSpecificMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, meta);
+    }
+
+    #[test]
+    fn audio_metadata_serializes_under_audio_namespace() {
+        let meta = MediaSpecificMetadata::Audio(AudioFingerprint {
+            container: "mp3".to_string(),
+            sample_rate: 44_100,
+            channels: 2,
+            bits_per_sample: None,
+            signal: AudioSignalDigest {
+                scheme: "HO-AUDIO-FRAMES-SHA256-V1".to_string(),
+                value: "b".repeat(64),
+            },
+        });
+        let json = serde_json::to_string(&meta).unwrap();
+        assert!(json.starts_with("{\"audio\":"));
+        assert!(!json.contains("bits_per_sample"));
+        let back: MediaSpecificMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, meta);
+    }
+
+    // --- V2-M3B/C : DocumentAdapter (fakes de test, sans rendu réel) ---
 
     /// Adaptateur factice PDF, présent UNIQUEMENT en test : prouve que le trait
     /// compile et se comporte, sans toucher au vrai package ni à PDFium.
@@ -362,7 +557,9 @@ mod tests {
         fn render_labeled_artifact(
             &self,
             _request: &LabeledArtifactRequest,
-        ) -> Result<RenderedArtifactRef, String> {
+            _source: &Path,
+            _output_dir: &Path,
+        ) -> Result<RenderedArtifactRef, ArtifactRenderError> {
             Ok(RenderedArtifactRef {
                 artifact_name: self.default_artifact_filename().to_string(),
                 media_type: self.artifact_kind(),
@@ -389,7 +586,9 @@ mod tests {
         fn render_labeled_artifact(
             &self,
             _request: &LabeledArtifactRequest,
-        ) -> Result<RenderedArtifactRef, String> {
+            _source: &Path,
+            _output_dir: &Path,
+        ) -> Result<RenderedArtifactRef, ArtifactRenderError> {
             Ok(RenderedArtifactRef {
                 artifact_name: self.default_artifact_filename().to_string(),
                 media_type: self.artifact_kind(),
@@ -407,10 +606,14 @@ mod tests {
                 fingerprint_sha256: "e".repeat(64),
                 size_bytes: Some(10),
                 mime_type: Some(mime.to_string()),
+                chunked_fingerprint: None,
+                media_specific: None,
             },
             certificate_id: "cert-1".to_string(),
             verify_url: "https://verify.humanorigin.app/r/x".to_string(),
             verdict_label: "OBSERVED_WORK_CONSISTENT".to_string(),
+            signing_key_id: None,
+            certificate_created_at: None,
         }
     }
 
@@ -449,7 +652,9 @@ mod tests {
         assert_eq!(a.default_artifact_filename(), "labeled_document.pdf");
 
         let req = sample_request("work-1", MediaKind::Pdf, "application/pdf");
-        let out = a.render_labeled_artifact(&req).unwrap();
+        let out = a
+            .render_labeled_artifact(&req, Path::new("source"), Path::new("out"))
+            .unwrap();
         // Le fake renvoie bien le nom d'artefact PDF, sans toucher au vrai package.
         assert_eq!(out.artifact_name, "labeled_document.pdf");
         assert_eq!(out.media_type, DocumentArtifactKind::Pdf);
@@ -461,7 +666,9 @@ mod tests {
         assert_eq!(a.artifact_kind().label(), "image");
 
         let req = sample_request("obj-img-9", MediaKind::Image, "image/png");
-        let out = a.render_labeled_artifact(&req).unwrap();
+        let out = a
+            .render_labeled_artifact(&req, Path::new("source"), Path::new("out"))
+            .unwrap();
 
         // Sérialisations : aucun chemin local, aucun null pour les Option None.
         let out_json = serde_json::to_string(&out).unwrap();
@@ -0,0 +1,490 @@
+//! file_chunks — Empreinte par blocs des gros médias (CDC + racine Merkle).
+//!
+//! Portée STRICTE : découper un fichier en blocs définis par son CONTENU
+//! (rolling hash « gear », bornes min/moyenne/max façon FastCDC), hacher
+//! chaque bloc (SHA256) et les réunir en une racine Merkle
+//! (`evidence_kernel::ChunkedFingerprint`, schéma `CHUNK_SCHEME`). Une
+//! insertion locale ne déplace que les blocs voisins : deux versions d'une
+//! vidéo partagent la plupart de leurs blocs (`shared_bytes`).
+//!
+//! Calcul en UNE passe (`file_hash::stream_file`, mémoire constante,
+//! progression des gros fichiers) qui produit aussi le SHA256 plat : la
+//! frontière d'une période garde `hash_start`/`hash_end` et y ajoute la
+//! racine. Résultat mis en cache par identité de fichier (chemin canonique,
+//! taille, mtime, inode) : deux captures d'un fichier inchangé ne le relisent
+//! pas.
+//!
+//! Garde-fous :
+//! - candidats uniquement (`is_chunk_candidate`) : vidéo/audio ou fichier
+//!   ≥ `CHUNK_CANDIDATE_MIN_BYTES` ; les autres documents restent au SHA256
+//!   plat seul ;
+//! - arbre Merkle à domaines séparés (feuille `0x00`, nœud `0x01`, nœud
+//!   impair promu tel quel) : aucune collision feuille/nœud ni par duplication ;
+//! - fichier modifié pendant la lecture -> résultat NON mis en cache ;
+//! - cache borné (`CACHE_MAX_ENTRIES`), en mémoire seulement.
+//!
+//! AUCUNE écriture, AUCUN contenu conservé (seulement des SHA256 de blocs),
+//! AUCUNE commande Tauri.
+
+// Fondation (V2-C) : `shared_bytes` servira au tier vidéo.
+#![allow(dead_code)]
+
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::UNIX_EPOCH;
+
+use crate::evidence_kernel::ChunkedFingerprint;
+use crate::file_hash;
+
+/// Identifiant du découpage + arbre. Tout changement de paramètre = nouveau schéma.
+pub(crate) const CHUNK_SCHEME: &str = "HO-CDC-GEAR-SHA256-MERKLE-V1";
+
+/// Au-delà, tout fichier reçoit une empreinte par blocs, quel que soit son type.
+pub(crate) const CHUNK_CANDIDATE_MIN_BYTES: u64 = 256 * 1024 * 1024;
+
+const MEDIA_EXTENSIONS: [&str; 13] = [
+    "mp4", "mov", "m4v", "mkv", "webm", "avi", "mp3", "wav", "m4a", "aac", "flac", "ogg", "opus",
+];
+
+const CACHE_MAX_ENTRIES: usize = 32;
+
+/// Bornes du découpage : blocs de `min` à `max` octets, ~2^`avg_bits` en moyenne.
+#[derive(Clone, Copy, Debug)]
+struct CdcParams {
+    min: usize,
+    avg_bits: u32,
+    max: usize,
+}
+
+/// Paramètres de `CHUNK_SCHEME` : 256 Kio / ~1 Mio / 8 Mio.
+const SCHEME_PARAMS: CdcParams = CdcParams {
+    min: 256 * 1024,
+    avg_bits: 20,
+    max: 8 * 1024 * 1024,
+};
+
+/// Table « gear » figée (splitmix64 déterministe) : fait partie du schéma.
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state: u64 = 0x484f_4344_4331_5631; // "HOCDC1V1"
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Masque sur les `bits` bits de POIDS FORT (ils dépendent des 64 derniers octets).
+fn high_mask(bits: u32) -> u64 {
+    !0u64 << (64 - bits)
+}
+
+// --- TYPES --------------------------------------------------------------------
+
+/// Un bloc du fichier (position, taille, SHA256 brut de ses octets).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ChunkRef {
+    pub offset: u64,
+    pub len: u64,
+    pub sha256: [u8; 32],
+}
+
+/// Résultat local d'une passe : SHA256 plat, blocs et empreinte Merkle.
+/// Seule `fingerprint` est destinée aux enregistrements signés.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ChunkedDigest {
+    pub sha256: String,
+    pub size_bytes: u64,
+    pub chunks: Vec<ChunkRef>,
+    pub fingerprint: ChunkedFingerprint,
+}
+
+// --- DÉCOUPAGE ----------------------------------------------------------------
+
+/// Découpeur incrémental : reçoit le flux bloc de lecture par bloc de lecture.
+struct Chunker {
+    params: CdcParams,
+    mask_small: u64,
+    mask_large: u64,
+    flat: Sha256,
+    current: Sha256,
+    rolling: u64,
+    current_len: usize,
+    offset: u64,
+    chunks: Vec<ChunkRef>,
+}
+
+impl Chunker {
+    fn new(params: CdcParams) -> Self {
+        Chunker {
+            params,
+            // Normalisation FastCDC : coupe plus rare avant la moyenne, plus
+            // fréquente après (distribution des tailles resserrée).
+            mask_small: high_mask(params.avg_bits + 1),
+            mask_large: high_mask(params.avg_bits - 1),
+            flat: Sha256::new(),
+            current: Sha256::new(),
+            rolling: 0,
+            current_len: 0,
+            offset: 0,
+            chunks: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.flat.update(data);
+        let avg = 1usize << self.params.avg_bits;
+        let mut start = 0;
+        for (i, &byte) in data.iter().enumerate() {
+            self.current_len += 1;
+            if self.current_len < self.params.min {
+                continue;
+            }
+            self.rolling = (self.rolling << 1).wrapping_add(GEAR[byte as usize]);
+            let mask = if self.current_len < avg {
+                self.mask_small
+            } else {
+                self.mask_large
+            };
+            if self.rolling & mask == 0 || self.current_len >= self.params.max {
+                self.current.update(&data[start..=i]);
+                start = i + 1;
+                self.cut();
+            }
+        }
+        self.current.update(&data[start..]);
+    }
+
+    fn cut(&mut self) {
+        let len = self.current_len as u64;
+        let hasher = std::mem::take(&mut self.current);
+        self.chunks.push(ChunkRef {
+            offset: self.offset,
+            len,
+            sha256: hasher.finalize().into(),
+        });
+        self.offset += len;
+        self.current_len = 0;
+        self.rolling = 0;
+    }
+
+    fn finish(mut self) -> ChunkedDigest {
+        if self.current_len > 0 {
+            self.cut();
+        }
+        let root = merkle_root(&self.chunks);
+        ChunkedDigest {
+            sha256: format!("{:x}", self.flat.finalize()),
+            size_bytes: self.offset,
+            fingerprint: ChunkedFingerprint {
+                scheme: CHUNK_SCHEME.to_string(),
+                merkle_root: root,
+                chunk_count: self.chunks.len() as u64,
+            },
+            chunks: self.chunks,
+        }
+    }
+}
+
+/// Racine Merkle hex : feuille = SHA256(0x00 ‖ sha256(bloc)), nœud =
+/// SHA256(0x01 ‖ gauche ‖ droite), nœud impair promu. Aucun bloc : SHA256("").
+fn merkle_root(chunks: &[ChunkRef]) -> String {
+    let mut level: Vec<[u8; 32]> = chunks
+        .iter()
+        .map(|c| {
+            Sha256::new()
+                .chain_update([0x00])
+                .chain_update(c.sha256)
+                .finalize()
+                .into()
+        })
+        .collect();
+    if level.is_empty() {
+        return format!("{:x}", Sha256::digest(b""));
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => Sha256::new()
+                    .chain_update([0x01])
+                    .chain_update(left)
+                    .chain_update(right)
+                    .finalize()
+                    .into(),
+                [odd] => *odd,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level[0].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn chunk_bytes(data: &[u8], params: CdcParams) -> ChunkedDigest {
+    let mut chunker = Chunker::new(params);
+    chunker.update(data);
+    chunker.finish()
+}
+
+// --- CACHE --------------------------------------------------------------------
+
+/// Identité d'un fichier pour le cache : tout changement de contenu normal
+/// change au moins la taille ou la mtime.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct FileIdentity {
+    path: PathBuf,
+    size: u64,
+    modified_ns: u128,
+    inode: Option<u64>,
+}
+
+#[cfg(unix)]
+fn inode_of(meta: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(meta.ino())
+}
+
+#[cfg(not(unix))]
+fn inode_of(_meta: &fs::Metadata) -> Option<u64> {
+    None
+}
+
+/// `None` si l'identité n'est pas fiable (mtime indisponible) : pas de cache.
+fn file_identity(path: &Path) -> Option<FileIdentity> {
+    let canonical = fs::canonicalize(path).ok()?;
+    let meta = fs::metadata(&canonical).ok()?;
+    let modified_ns = meta
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    Some(FileIdentity {
+        path: canonical,
+        size: meta.len(),
+        modified_ns,
+        inode: inode_of(&meta),
+    })
+}
+
+type DigestCache = Mutex<HashMap<FileIdentity, ChunkedDigest>>;
+
+fn cache() -> &'static DigestCache {
+    static CACHE: OnceLock<DigestCache> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn cache_get(identity: &FileIdentity) -> Option<ChunkedDigest> {
+    cache().lock().ok()?.get(identity).cloned()
+}
+
+fn cache_put(identity: FileIdentity, digest: &ChunkedDigest) {
+    if let Ok(mut map) = cache().lock() {
+        // Une entrée périmée du même chemin ne resservira jamais.
+        map.retain(|k, _| k.path != identity.path);
+        if map.len() >= CACHE_MAX_ENTRIES {
+            map.clear();
+        }
+        map.insert(identity, digest.clone());
+    }
+}
+
+// --- API ----------------------------------------------------------------------
+
+/// Vrai si le fichier reçoit une empreinte par blocs (média vidéo/audio, ou
+/// fichier ≥ `CHUNK_CANDIDATE_MIN_BYTES`).
+pub(crate) fn is_chunk_candidate(path: &Path, size_bytes: u64) -> bool {
+    if size_bytes >= CHUNK_CANDIDATE_MIN_BYTES {
+        return true;
+    }
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| MEDIA_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Empreinte par blocs + SHA256 plat d'un fichier, en une passe, via le cache
+/// si l'identité du fichier n'a pas changé depuis le dernier calcul.
+pub(crate) fn chunked_digest_file(path: &Path) -> Result<ChunkedDigest, String> {
+    let before = file_identity(path);
+    if let Some(hit) = before.as_ref().and_then(cache_get) {
+        return Ok(hit);
+    }
+    let mut chunker = Chunker::new(SCHEME_PARAMS);
+    file_hash::stream_file(path, &mut |block| chunker.update(block))?;
+    let digest = chunker.finish();
+    if let Some(identity) = before {
+        if file_identity(path).as_ref() == Some(&identity) && identity.size == digest.size_bytes {
+            cache_put(identity, &digest);
+        }
+    }
+    Ok(digest)
+}
+
+/// Octets de `current` dont le bloc existe déjà (même SHA256) dans `previous`.
+pub(crate) fn shared_bytes(previous: &ChunkedDigest, current: &ChunkedDigest) -> u64 {
+    let known: HashSet<[u8; 32]> = previous.chunks.iter().map(|c| c.sha256).collect();
+    current
+        .chunks
+        .iter()
+        .filter(|c| known.contains(&c.sha256))
+        .map(|c| c.len)
+        .sum()
+}
+
+// --- TESTS --------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    // Petits blocs (1-2-8 Kio) : même algorithme, tests rapides.
+    const TEST_PARAMS: CdcParams = CdcParams {
+        min: 1024,
+        avg_bits: 11,
+        max: 8 * 1024,
+    };
+
+    fn temp_base() -> PathBuf {
+        std::env::temp_dir().join(format!("ho_file_chunks_test_{}", Uuid::new_v4()))
+    }
+
+    fn cleanup(p: &Path) {
+        let _ = fs::remove_dir_all(p);
+    }
+
+    /// Octets pseudo-aléatoires déterministes (xorshift).
+    fn noise(len: usize, seed: u64) -> Vec<u8> {
+        let mut x = seed | 1;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                (x >> 24) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_1_blocs_bornes_et_contigus() {
+        let data = noise(200_000, 7);
+        let d = chunk_bytes(&data, TEST_PARAMS);
+        assert_eq!(d.sha256, format!("{:x}", Sha256::digest(&data)));
+        assert_eq!(d.size_bytes, data.len() as u64);
+        let mut offset = 0;
+        for (i, c) in d.chunks.iter().enumerate() {
+            assert_eq!(c.offset, offset);
+            assert!(c.len <= TEST_PARAMS.max as u64);
+            if i + 1 < d.chunks.len() {
+                assert!(c.len >= TEST_PARAMS.min as u64);
+            }
+            let bytes = &data[c.offset as usize..(c.offset + c.len) as usize];
+            assert_eq!(c.sha256, <[u8; 32]>::from(Sha256::digest(bytes)));
+            offset += c.len;
+        }
+        assert_eq!(offset, data.len() as u64);
+        assert_eq!(d.fingerprint.chunk_count, d.chunks.len() as u64);
+        assert_eq!(d.fingerprint.scheme, CHUNK_SCHEME);
+    }
+
+    #[test]
+    fn test_2_decoupage_independant_des_lectures() {
+        let data = noise(100_000, 11);
+        let whole = chunk_bytes(&data, TEST_PARAMS);
+        let mut chunker = Chunker::new(TEST_PARAMS);
+        for part in data.chunks(777) {
+            chunker.update(part);
+        }
+        assert_eq!(chunker.finish(), whole);
+    }
+
+    #[test]
+    fn test_3_insertion_locale_preserve_les_autres_blocs() {
+        let data = noise(300_000, 3);
+        let mut edited = data.clone();
+        edited.splice(150_000..150_000, b"insertion au milieu".iter().copied());
+        let (a, b) = (
+            chunk_bytes(&data, TEST_PARAMS),
+            chunk_bytes(&edited, TEST_PARAMS),
+        );
+        assert_ne!(a.fingerprint.merkle_root, b.fingerprint.merkle_root);
+        // Seuls quelques blocs autour de l'insertion diffèrent.
+        let shared = shared_bytes(&a, &b);
+        assert!(shared >= edited.len() as u64 - 4 * TEST_PARAMS.max as u64);
+        assert!(shared < edited.len() as u64);
+    }
+
+    #[test]
+    fn test_4_racine_merkle_domaines_separes() {
+        let c = |s: &str| ChunkRef {
+            offset: 0,
+            len: 1,
+            sha256: Sha256::digest(s.as_bytes()).into(),
+        };
+        assert_eq!(merkle_root(&[]), format!("{:x}", Sha256::digest(b"")));
+        // Une feuille seule n'est pas le SHA256 du bloc.
+        assert_ne!(
+            merkle_root(&[c("a")]),
+            format!("{:x}", Sha256::digest(b"a"))
+        );
+        // L'ordre compte ; un nœud impair n'est pas dupliqué.
+        assert_ne!(
+            merkle_root(&[c("a"), c("b")]),
+            merkle_root(&[c("b"), c("a")])
+        );
+        assert_ne!(
+            merkle_root(&[c("a"), c("b"), c("c")]),
+            merkle_root(&[c("a"), c("b"), c("c"), c("c")])
+        );
+        // Vide : aucun bloc, racine définie.
+        let empty = chunk_bytes(b"", TEST_PARAMS);
+        assert_eq!(empty.fingerprint.chunk_count, 0);
+        assert_eq!(empty.sha256, format!("{:x}", Sha256::digest(b"")));
+    }
+
+    #[test]
+    fn test_5_fichier_cache_par_identite() {
+        let base = temp_base();
+        fs::create_dir_all(&base).unwrap();
+        let path = base.join("clip.mp4");
+        fs::write(&path, noise(50_000, 5)).unwrap();
+
+        let first = chunked_digest_file(&path).unwrap();
+        assert_eq!(first.sha256, file_hash::sha256_file(&path).unwrap());
+        let identity = file_identity(&path).unwrap();
+        assert_eq!(cache_get(&identity), Some(first.clone()));
+        assert_eq!(chunked_digest_file(&path).unwrap(), first);
+
+        // Contenu modifié (taille différente) : nouvelle identité, recalcul.
+        fs::write(&path, noise(60_000, 6)).unwrap();
+        let second = chunked_digest_file(&path).unwrap();
+        assert_ne!(second.sha256, first.sha256);
+        assert_eq!(second.sha256, file_hash::sha256_file(&path).unwrap());
+        assert_eq!(cache_get(&identity), None);
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_6_candidats() {
+        assert!(is_chunk_candidate(Path::new("/x/film.MOV"), 10));
+        assert!(is_chunk_candidate(Path::new("/x/voix.wav"), 10));
+        assert!(!is_chunk_candidate(Path::new("/x/texte.docx"), 10));
+        assert!(is_chunk_candidate(
+            Path::new("/x/texte.pdf"),
+            CHUNK_CANDIDATE_MIN_BYTES
+        ));
+        assert!(!is_chunk_candidate(Path::new("/x/sans_extension"), 10));
+    }
+}
@@ -0,0 +1,247 @@
+//! file_hash — SHA256 de fichiers en flux, avec progression (utilitaire commun).
+//!
+//! Portée STRICTE : hacher un fichier par blocs (`CHUNK_BYTES`, mémoire
+//! constante quelle que soit la taille) et signaler la progression des gros
+//! fichiers (≥ `PROGRESS_THRESHOLD_BYTES`) au puits enregistré au démarrage
+//! (événement Tauri `PROGRESS_EVENT`, affiché par le front dans le toast).
+//! Source UNIQUE du SHA256 de fichier pour la capture, le package et la
+//! publication, et de la lecture en flux des empreintes par blocs
+//! (`file_chunks`). Les commandes qui hachent passent par `run_blocking` :
+//! jamais sur le thread principal.
+//!
+//! Garde-fous :
+//! - AUCUNE lecture intégrale en mémoire ;
+//! - au plus ~`PROGRESS_STEPS` notifications par fichier (jamais une par bloc) ;
+//! - la progression ne porte que le NOM du fichier, jamais son chemin ;
+//! - sans puits (tests, outils), même hash, simplement sans progression.
+//!
+//! AUCUNE écriture, AUCUNE commande Tauri.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, ErrorKind, Read};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Événement émis vers le front pour les gros fichiers.
+pub(crate) const PROGRESS_EVENT: &str = "file-hash-progress";
+
+/// En dessous, le hachage est assez rapide pour ne rien signaler.
+pub(crate) const PROGRESS_THRESHOLD_BYTES: u64 = 32 * 1024 * 1024;
+
+const CHUNK_BYTES: usize = 1024 * 1024;
+const PROGRESS_STEPS: u64 = 100;
+
+/// Avancement du hachage d'un fichier.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HashProgress {
+    pub file_name: String,
+    pub bytes_hashed: u64,
+    pub total_bytes: u64,
+}
+
+type ProgressSink = Box<dyn Fn(&HashProgress) + Send + Sync>;
+
+static PROGRESS_SINK: OnceLock<ProgressSink> = OnceLock::new();
+
+/// Enregistre le puits de progression (une seule fois, au démarrage de l'app).
+pub(crate) fn set_progress_sink(sink: impl Fn(&HashProgress) + Send + Sync + 'static) {
+    let _ = PROGRESS_SINK.set(Box::new(sink));
+}
+
+// --- HACHAGE ------------------------------------------------------------------
+
+/// Lit un flux par blocs ; `consume(bloc)` pour chaque bloc lu.
+pub(crate) fn stream_reader<R: Read>(mut reader: R, mut consume: impl FnMut(&[u8])) -> io::Result<()> {
+    let mut buf = vec![0u8; CHUNK_BYTES];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        consume(&buf[..n]);
+    }
+}
+
+/// Lit `reader` (taille annoncée `total`) en signalant la progression si
+/// `total >= threshold` : au départ, tous les ~1 %, puis à la fin.
+fn stream_with_progress<R: Read>(
+    reader: R,
+    file_name: &str,
+    total: u64,
+    threshold: u64,
+    on_progress: &mut dyn FnMut(&HashProgress),
+    consume: &mut dyn FnMut(&[u8]),
+) -> io::Result<()> {
+    if total < threshold {
+        return stream_reader(reader, consume);
+    }
+    let mut report = |bytes_hashed: u64| {
+        on_progress(&HashProgress {
+            file_name: file_name.to_string(),
+            bytes_hashed,
+            total_bytes: total,
+        })
+    };
+    let step = (total / PROGRESS_STEPS).max(1);
+    let (mut done, mut next, mut reported) = (0, step, 0);
+    report(0);
+    stream_reader(reader, |block| {
+        consume(block);
+        done += block.len() as u64;
+        if done >= next {
+            report(done);
+            reported = done;
+            next = done + step;
+        }
+    })?;
+    if done != reported {
+        report(done);
+    }
+    Ok(())
+}
+
+fn sha256_with_progress<R: Read>(
+    reader: R,
+    file_name: &str,
+    total: u64,
+    threshold: u64,
+    on_progress: &mut dyn FnMut(&HashProgress),
+) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    stream_with_progress(reader, file_name, total, threshold, on_progress, &mut |b| {
+        hasher.update(b)
+    })?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Lit un fichier en flux (`consume` par bloc), progression explicite (gros
+/// fichiers uniquement). Renvoie la taille lue.
+fn stream_file_with_progress(
+    path: &Path,
+    on_progress: &mut dyn FnMut(&HashProgress),
+    consume: &mut dyn FnMut(&[u8]),
+) -> Result<u64, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let total = file.metadata().map_err(|e| e.to_string())?.len();
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut read = 0u64;
+    stream_with_progress(
+        file,
+        &file_name,
+        total,
+        PROGRESS_THRESHOLD_BYTES,
+        on_progress,
+        &mut |b| {
+            read += b.len() as u64;
+            consume(b)
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(read)
+}
+
+/// Lit un fichier en flux ; progression vers le puits de l'app s'il est
+/// enregistré. Base commune des empreintes de fichier (`sha256_file`,
+/// `file_chunks`).
+pub(crate) fn stream_file(path: &Path, consume: &mut dyn FnMut(&[u8])) -> Result<u64, String> {
+    match PROGRESS_SINK.get() {
+        Some(sink) => stream_file_with_progress(path, &mut |p| sink(p), consume),
+        None => stream_file_with_progress(path, &mut |_| {}, consume),
+    }
+}
+
+/// SHA256 d'un fichier ; progression vers le puits de l'app s'il est enregistré.
+pub(crate) fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut hasher = Sha256::new();
+    stream_file(path, &mut |b| hasher.update(b))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// --- TESTS --------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn temp_base() -> PathBuf {
+        std::env::temp_dir().join(format!("ho_file_hash_test_{}", Uuid::new_v4()))
+    }
+
+    fn cleanup(p: &Path) {
+        let _ = fs::remove_dir_all(p);
+    }
+
+    #[test]
+    fn test_1_meme_hash_que_lecture_integrale() {
+        let base = temp_base();
+        fs::create_dir_all(&base).unwrap();
+        // Plusieurs blocs + un reste.
+        let bytes: Vec<u8> = (0..(CHUNK_BYTES * 2 + 17))
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let path = base.join("gros.bin");
+        fs::write(&path, &bytes).unwrap();
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            format!("{:x}", Sha256::digest(&bytes))
+        );
+        let empty = base.join("vide.bin");
+        fs::write(&empty, b"").unwrap();
+        assert_eq!(
+            sha256_file(&empty).unwrap(),
+            format!("{:x}", Sha256::digest(b""))
+        );
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_2_fichier_absent_erreur() {
+        assert!(sha256_file(&temp_base().join("absent.bin")).is_err());
+    }
+
+    #[test]
+    fn test_3_aucune_progression_sous_le_seuil() {
+        let mut events = Vec::new();
+        sha256_with_progress(Cursor::new(vec![1u8; 10]), "petit.pdf", 10, 11, &mut |p| {
+            events.push(p.clone())
+        })
+        .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_4_progression_bornee_et_complete() {
+        let total = (CHUNK_BYTES * 5) as u64;
+        let mut events = Vec::new();
+        let hash = sha256_with_progress(
+            Cursor::new(vec![7u8; total as usize]),
+            "video.mp4",
+            total,
+            1,
+            &mut |p| events.push(p.clone()),
+        )
+        .unwrap();
+        assert_eq!(
+            hash,
+            format!("{:x}", Sha256::digest(vec![7u8; total as usize]))
+        );
+        assert_eq!(events.first().unwrap().bytes_hashed, 0);
+        assert_eq!(events.last().unwrap().bytes_hashed, total);
+        assert!(events.len() <= PROGRESS_STEPS as usize + 2);
+        assert!(events
+            .windows(2)
+            .all(|w| w[0].bytes_hashed < w[1].bytes_hashed));
+        assert!(events.iter().all(|p| p.file_name == "video.mp4"));
+    }
+}
@@ -0,0 +1,679 @@
+//! ho_json_v1 — Enveloppe HO-JSON v1 native + pont avec les certificats Work.
+//!
+//! Portée STRICTE :
+//! - vérifier EN RUST une enveloppe `CERTIFICAT_FINAL.v1.ho.json` produite par le
+//!   frontend JS (docs/HO_JSON_v1_SPEC.md) : canonicaliser le payload, hacher,
+//!   comparer à `payload_sha256`, vérifier la signature Ed25519 `issuer` sur les
+//!   32 octets du hash (message de `sign_payload_hash`) ;
+//! - émettre une enveloppe v1 à partir d'un `WorkCertificate` (6B), certificat
+//!   embarqué tel quel, et l'en extraire vérifié.
+//!
+//! Chemin de vérification UNIQUE : `sign_envelope` / `verify_envelope` servent
+//! aussi HO-JSON V2 (`ho_json_v2`), dont l'enveloppe est identique.
+//!
+//! Le payload v1 JS est libre (`serde_json::Value`) : il est haché tel que reçu,
+//! jamais retypé. Les champs hors payload (`server_attestation`) ne sont pas
+//! signés par l'émetteur et ne sont pas vérifiés ici.
+//!
+//! AUCUNE UI, AUCUN réseau, AUCUN chemin local publié.
+//!
+//! Garde-fous :
+//! - `signatures[0]` DOIT être `issuer` / `ed25519` / `payload_sha256` ;
+//! - pont Work : clé de l'enveloppe == clé du certificat embarqué,
+//!   `document.sha256 == hash_current`, verdict identique.
+
+// Fondation (V2-M4) : pont v1 ; seul `verify_ho_json_document` est exposé.
+#![allow(dead_code)]
+
+use base64::{engine::general_purpose, Engine as _};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::work_certificate::{verify_certificate, ProofVerdict, WorkCertificate};
+use crate::work_period::canonical_bytes_excluding;
+
+pub(crate) const HO_JSON_FORMAT: &str = "humanorigin-hojson";
+pub(crate) const HO_JSON_V1_VERSION: &str = "1.0";
+
+pub(crate) const WORK_CERTIFICATE_TYPE: &str = "work_certificate";
+pub(crate) const PRODUCT: &str = "HumanOrigin";
+pub(crate) const VERIFICATION_METHOD: &str = "ed25519_payload_sha256";
+pub(crate) const LOCAL_DEVICE_IDENTITY: &str = "LOCAL_DEVICE";
+
+const SIGN_ALG: &str = "ed25519";
+const ISSUER_ROLE: &str = "issuer";
+const SIGNED_FIELD: &str = "payload_sha256";
+
+/// Schéma de sécurité annoncé par l'émetteur JS (`issuer.security_schema_version`),
+/// exigé par le contre-seing serveur.
+const SECURITY_SCHEMA_VERSION: &str = "2026-06-p0";
+
+// --- ENVELOPPE ----------------------------------------------------------------
+
+/// Signature d'enveloppe (`signatures[]`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HoJsonSignature {
+    pub role: String,
+    pub algorithm: String,
+    pub signed_field: String,
+    pub public_key: String,
+    pub signature: String,
+}
+
+/// Enveloppe HO-JSON v1, payload libre.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct HoJsonV1Envelope {
+    pub format: String,
+    pub version: String,
+    pub payload: Value,
+    pub payload_sha256: String,
+    pub signatures: Vec<HoJsonSignature>,
+    /// Contre-seing serveur (hors signature émetteur). Absent si `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_attestation: Option<Value>,
+}
+
+fn decode_b64_fixed<const N: usize>(s: &str) -> Result<[u8; N], String> {
+    let bytes = general_purpose::STANDARD
+        .decode(s)
+        .map_err(|e| e.to_string())?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| format!("longueur base64 inattendue : {}", b.len()))
+}
+
+pub(crate) fn decode_hex32(s: &str) -> Result<[u8; 32], String> {
+    if s.len() != 64 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err("payload_sha256 n'est pas un SHA256 hex".to_string());
+    }
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).map_err(|e| e.to_string())?;
+    }
+    Ok(out)
+}
+
+/// `payload_sha256` : SHA256 hex du payload canonique (HO-CANON-V1, identique à
+/// `JSON.stringify(canonicalize(payload))` côté JS).
+pub(crate) fn payload_sha256<T: Serialize>(payload: &T) -> Result<String, String> {
+    let bytes = canonical_bytes_excluding(payload, &[])?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+/// Hache et signe un payload : `(payload_sha256, signatures)`.
+pub(crate) fn sign_envelope<T: Serialize>(
+    payload: &T,
+    signing_key: &SigningKey,
+) -> Result<(String, Vec<HoJsonSignature>), String> {
+    let digest = payload_sha256(payload)?;
+    let signature = signing_key.sign(&decode_hex32(&digest)?);
+    let issuer = HoJsonSignature {
+        role: ISSUER_ROLE.to_string(),
+        algorithm: SIGN_ALG.to_string(),
+        signed_field: SIGNED_FIELD.to_string(),
+        public_key: general_purpose::STANDARD.encode(signing_key.verifying_key().to_bytes()),
+        signature: general_purpose::STANDARD.encode(signature.to_bytes()),
+    };
+    Ok((digest, vec![issuer]))
+}
+
+/// Vérifie une enveloppe (toutes versions) : format, `payload_sha256` recalculé,
+/// signature Ed25519 de l'émetteur. Renvoie la signature émetteur vérifiée.
+pub(crate) fn verify_envelope<'a, T: Serialize>(
+    format: &str,
+    payload: &T,
+    claimed_sha256: &str,
+    signatures: &'a [HoJsonSignature],
+) -> Result<&'a HoJsonSignature, String> {
+    if format != HO_JSON_FORMAT {
+        return Err(format!("format inattendu : {format}"));
+    }
+    if payload_sha256(payload)? != claimed_sha256 {
+        return Err("payload_sha256 ≠ payload canonique".to_string());
+    }
+    let issuer = signatures
+        .first()
+        .ok_or_else(|| "aucune signature".to_string())?;
+    if issuer.role != ISSUER_ROLE
+        || issuer.algorithm != SIGN_ALG
+        || issuer.signed_field != SIGNED_FIELD
+    {
+        return Err("signature émetteur mal formée".to_string());
+    }
+    let vk = VerifyingKey::from_bytes(&decode_b64_fixed::<32>(&issuer.public_key)?)
+        .map_err(|e| e.to_string())?;
+    let sig = Signature::from_bytes(&decode_b64_fixed::<64>(&issuer.signature)?);
+    vk.verify_strict(&decode_hex32(claimed_sha256)?, &sig)
+        .map_err(|_| "signature HO-JSON invalide".to_string())?;
+    Ok(issuer)
+}
+
+/// Vérifie une enveloppe v1 (payload JS ou Work).
+pub(crate) fn verify_ho_json_v1(env: &HoJsonV1Envelope) -> Result<&HoJsonSignature, String> {
+    if env.version != HO_JSON_V1_VERSION {
+        return Err(format!("version inattendue : {}", env.version));
+    }
+    if !env.payload.is_object() {
+        return Err("payload n'est pas un objet".to_string());
+    }
+    verify_envelope(
+        &env.format,
+        &env.payload,
+        &env.payload_sha256,
+        &env.signatures,
+    )
+}
+
+/// Relit et vérifie une enveloppe v1.
+pub(crate) fn parse_ho_json_v1(raw: &str) -> Result<HoJsonV1Envelope, String> {
+    let env: HoJsonV1Envelope =
+        serde_json::from_str(raw).map_err(|e| format!("HO-JSON v1 invalide : {e}"))?;
+    verify_ho_json_v1(&env)?;
+    Ok(env)
+}
+
+// --- PONT WORK ----------------------------------------------------------------
+
+/// Émetteur, champs v1 (`issuer_mode`, `app_version`… exigés par le contre-seing).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WorkV1Issuer {
+    pub product: String,
+    pub issuer_mode: String,
+    pub app_version: String,
+    pub security_schema_version: String,
+    pub proof_trust_level: String,
+    pub key_trust: String,
+    pub identity_status: String,
+    pub signing_key_id: String,
+}
+
+/// Liaison document v1 (`document.sha256`), sans nom de fichier.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WorkV1Document {
+    pub binding_mode: String,
+    pub sha256: String,
+    pub final_size_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WorkV1ProcessSummary {
+    pub verdict: ProofVerdict,
+    pub active_ms: u64,
+    pub valid_sessions_count: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WorkV1LabelEligibility {
+    pub visible_verdict: ProofVerdict,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WorkV1Verification {
+    pub verify_url: Option<String>,
+    pub verification_method: String,
+}
+
+/// Payload v1 d'un certificat Work.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WorkV1Payload {
+    pub certificate_type: String,
+    pub certificate_id: String,
+    pub issued_at: String,
+    pub issuer: WorkV1Issuer,
+    pub document: WorkV1Document,
+    pub process_summary: WorkV1ProcessSummary,
+    pub label_eligibility: WorkV1LabelEligibility,
+    pub verification: WorkV1Verification,
+    /// Certificat Work embarqué tel quel (signature 6B vérifiable seule).
+    pub work_certificate: WorkCertificate,
+}
+
+/// Émet l'enveloppe v1 de `cert`. La clé DOIT être celle du certificat.
+pub(crate) fn work_certificate_to_ho_json_v1(
+    cert: &WorkCertificate,
+    verify_url: Option<&str>,
+    signing_key: &SigningKey,
+) -> Result<HoJsonV1Envelope, String> {
+    verify_certificate(cert)?;
+    let public_key = general_purpose::STANDARD.encode(signing_key.verifying_key().to_bytes());
+    if public_key != cert.signature_metadata.public_key {
+        return Err("clé HO-JSON ≠ clé du certificat".to_string());
+    }
+    let ev = &cert.public_core_evidence;
+    let payload = WorkV1Payload {
+        certificate_type: WORK_CERTIFICATE_TYPE.to_string(),
+        certificate_id: cert.certificate_id.clone(),
+        issued_at: cert.created_at.clone(),
+        issuer: WorkV1Issuer {
+            product: PRODUCT.to_string(),
+            issuer_mode: "local".to_string(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            security_schema_version: SECURITY_SCHEMA_VERSION.to_string(),
+            proof_trust_level: "local_self_signed".to_string(),
+            key_trust: "local_unregistered_key".to_string(),
+            identity_status: LOCAL_DEVICE_IDENTITY.to_string(),
+            signing_key_id: cert.signature_metadata.signing_key_id.clone(),
+        },
+        document: WorkV1Document {
+            binding_mode: "work_certificate".to_string(),
+            sha256: ev.document.hash_current.clone(),
+            final_size_bytes: ev.document.size_current,
+        },
+        process_summary: WorkV1ProcessSummary {
+            verdict: ev.verdict.verdict,
+            active_ms: ev.verdict.total_active_seconds.saturating_mul(1000),
+            valid_sessions_count: ev.verdict.qualifying_periods,
+        },
+        label_eligibility: WorkV1LabelEligibility {
+            visible_verdict: ev.verdict.verdict,
+        },
+        verification: WorkV1Verification {
+            verify_url: verify_url.map(str::to_string),
+            verification_method: VERIFICATION_METHOD.to_string(),
+        },
+        work_certificate: cert.clone(),
+    };
+    let payload = serde_json::to_value(&payload).map_err(|e| e.to_string())?;
+    let (payload_sha256, signatures) = sign_envelope(&payload, signing_key)?;
+    Ok(HoJsonV1Envelope {
+        format: HO_JSON_FORMAT.to_string(),
+        version: HO_JSON_V1_VERSION.to_string(),
+        payload,
+        payload_sha256,
+        signatures,
+        server_attestation: None,
+    })
+}
+
+/// Point d'entrée production : signe avec la clé device (`ensure_signing_key`).
+pub(crate) fn work_certificate_to_ho_json_v1_core(
+    cert: &WorkCertificate,
+    verify_url: Option<&str>,
+) -> Result<HoJsonV1Envelope, String> {
+    let key = crate::ensure_signing_key()?;
+    work_certificate_to_ho_json_v1(cert, verify_url, &key)
+}
+
+/// Extrait le certificat Work d'une enveloppe v1, après vérification complète :
+/// enveloppe, certificat embarqué, clé et liaison document cohérentes.
+pub(crate) fn work_certificate_from_ho_json_v1(
+    env: &HoJsonV1Envelope,
+) -> Result<WorkCertificate, String> {
+    let issuer = verify_ho_json_v1(env)?;
+    let payload: WorkV1Payload = serde_json::from_value(env.payload.clone())
+        .map_err(|e| format!("payload Work v1 invalide : {e}"))?;
+    if payload.certificate_type != WORK_CERTIFICATE_TYPE {
+        return Err(format!(
+            "certificate_type inattendu : {}",
+            payload.certificate_type
+        ));
+    }
+    let cert = payload.work_certificate;
+    verify_certificate(&cert)?;
+    if issuer.public_key != cert.signature_metadata.public_key
+        || payload.issuer.signing_key_id != cert.signature_metadata.signing_key_id
+    {
+        return Err("clé émetteur ≠ clé du certificat".to_string());
+    }
+    if payload.certificate_id != cert.certificate_id {
+        return Err("payload ≠ certificat embarqué".to_string());
+    }
+    let ev = &cert.public_core_evidence;
+    if payload.document.sha256 != ev.document.hash_current {
+        return Err("document.sha256 ≠ document certifié".to_string());
+    }
+    if payload.process_summary.verdict != ev.verdict.verdict
+        || payload.label_eligibility.visible_verdict != ev.verdict.verdict
+    {
+        return Err("verdict ≠ certificat embarqué".to_string());
+    }
+    Ok(cert)
+}
+
+// --- VÉRIFICATION NATIVE (v1 et V2) -------------------------------------------
+
+/// Résumé de vérification renvoyé au frontend.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HoJsonVerification {
+    pub version: String,
+    pub payload_sha256: String,
+    pub public_key: String,
+    pub certificate_id: Option<String>,
+    /// `true` si un certificat Work embarqué a été vérifié (pont Work).
+    pub work_certificate_verified: bool,
+}
+
+/// Vérifie un `.ho.json` v1 (JS ou Work) ou V2, selon `version`.
+pub(crate) fn verify_ho_json_document_core(raw: &str) -> Result<HoJsonVerification, String> {
+    let value: Value = serde_json::from_str(raw).map_err(|e| format!("JSON invalide : {e}"))?;
+    match value.get("version").and_then(Value::as_str) {
+        Some(HO_JSON_V1_VERSION) => {
+            let env: HoJsonV1Envelope =
+                serde_json::from_value(value).map_err(|e| format!("HO-JSON v1 invalide : {e}"))?;
+            let public_key = verify_ho_json_v1(&env)?.public_key.clone();
+            let is_work = env.payload.get("certificate_type").and_then(Value::as_str)
+                == Some(WORK_CERTIFICATE_TYPE);
+            if is_work {
+                work_certificate_from_ho_json_v1(&env)?;
+            }
+            Ok(HoJsonVerification {
+                version: env.version,
+                certificate_id: env
+                    .payload
+                    .get("certificate_id")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                payload_sha256: env.payload_sha256,
+                public_key,
+                work_certificate_verified: is_work,
+            })
+        }
+        Some(crate::ho_json_v2::HO_JSON_V2_VERSION) => {
+            let doc = crate::ho_json_v2::parse_ho_json_v2(raw)?;
+            Ok(HoJsonVerification {
+                version: doc.version,
+                payload_sha256: doc.payload_sha256,
+                public_key: doc.signatures[0].public_key.clone(),
+                certificate_id: Some(doc.payload.certificate_id),
+                work_certificate_verified: true,
+            })
+        }
+        other => Err(format!("version HO-JSON non prise en charge : {other:?}")),
+    }
+}
+
+/// Commande : vérification native d'un `.ho.json` (contenu, pas de chemin).
+#[tauri::command]
+pub fn verify_ho_json_document(raw: String) -> Result<serde_json::Value, String> {
+    let summary = verify_ho_json_document_core(&raw)?;
+    serde_json::to_value(summary).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::work_certificate::{
+        compute_public_core_evidence_sha256, CertificateSignatureMetadata, CertificateVersion,
+        Continuity, ContinuityKind, PublicCoreEvidence, PublicDocumentRef, VerdictEngineSummary,
+        CERTIFICATE_SCHEMA_VERSION, CORE_EVIDENCE_SCHEMA_VERSION,
+    };
+    use crate::work_store::WorkId;
+    use rand_core::OsRng;
+
+    fn key() -> SigningKey {
+        SigningKey::generate(&mut OsRng)
+    }
+
+    fn cert(k: &SigningKey) -> WorkCertificate {
+        let public_key = general_purpose::STANDARD.encode(k.verifying_key().to_bytes());
+        let pce = PublicCoreEvidence {
+            schema_version: CORE_EVIDENCE_SCHEMA_VERSION,
+            work_id: WorkId("work-1".to_string()),
+            certificate_version: CertificateVersion::V1,
+            document: PublicDocumentRef {
+                hash_current: "a".repeat(64),
+                size_current: 42,
+                members: None,
+            },
+            included_period_summaries: vec![],
+            new_period_ids: vec![],
+            qualifying_new_period_count: 0,
+            continuity: Continuity {
+                kind: ContinuityKind::Full,
+                gaps: vec![],
+            },
+            verdict: VerdictEngineSummary {
+                total_periods: 2,
+                qualifying_periods: 2,
+                total_active_seconds: 90,
+                continuity: ContinuityKind::Full,
+                verdict: ProofVerdict::ObservedWorkConsistent,
+                flags: vec![],
+            },
+            previous_certificate_id: None,
+            previous_core_evidence_sha256: None,
+        };
+        let mut c = WorkCertificate {
+            schema_version: CERTIFICATE_SCHEMA_VERSION,
+            certificate_id: "cert-1".to_string(),
+            certificate_sequence: 1,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            work_id: WorkId("work-1".to_string()),
+            certificate_version: CertificateVersion::V1,
+            core_evidence_sha256: compute_public_core_evidence_sha256(&pce).unwrap(),
+            public_core_evidence: pce,
+            previous_certificate_id: None,
+            previous_core_evidence_sha256: None,
+            signature_metadata: CertificateSignatureMetadata {
+                signature_algorithm: SIGN_ALG.to_string(),
+                signing_key_id: format!("{:x}", Sha256::digest(public_key.as_bytes())),
+                public_key,
+                identity_status: LOCAL_DEVICE_IDENTITY.to_string(),
+                schema_version: CERTIFICATE_SCHEMA_VERSION,
+                canonicalization: None,
+            },
+            signature: String::new(),
+        };
+        let body = canonical_bytes_excluding(&c, &["signature"]).unwrap();
+        c.signature = general_purpose::STANDARD.encode(k.sign(&Sha256::digest(&body)).to_bytes());
+        c
+    }
+
+    /// Enveloppe au payload libre, de forme frontend mais signée en Rust (ne
+    /// prouve rien sur la canonicalisation JS : voir `JS_FRONTEND_FIXTURE`).
+    fn js_envelope(k: &SigningKey) -> HoJsonV1Envelope {
+        let payload = serde_json::json!({
+            "certificate_type": "final_project_certificate",
+            "certificate_id": "HO-1",
+            "document": { "sha256": "b".repeat(64), "filename": null, "delta_bytes_ratio": 0.25 },
+            "label_eligibility": { "visible_verdict": "OBSERVED" },
+        });
+        let (payload_sha256, signatures) = sign_envelope(&payload, k).unwrap();
+        HoJsonV1Envelope {
+            format: HO_JSON_FORMAT.to_string(),
+            version: HO_JSON_V1_VERSION.to_string(),
+            payload,
+            payload_sha256,
+            signatures,
+            server_attestation: None,
+        }
+    }
+
+    #[test]
+    fn test_1_work_vers_v1_et_retour() {
+        let k = key();
+        let c = cert(&k);
+        let env =
+            work_certificate_to_ho_json_v1(&c, Some("https://verify.example/r/1"), &k).unwrap();
+        assert_eq!(env.payload["document"]["sha256"], "a".repeat(64));
+        assert_eq!(env.payload["process_summary"]["active_ms"], 90_000);
+        let raw = serde_json::to_string_pretty(&env).unwrap();
+        assert!(!raw.contains("server_attestation"));
+        let relu = parse_ho_json_v1(&raw).unwrap();
+        assert_eq!(work_certificate_from_ho_json_v1(&relu).unwrap(), c);
+    }
+
+    #[test]
+    fn test_2_cle_differente_du_certificat_refusee() {
+        let k = key();
+        assert!(work_certificate_to_ho_json_v1(&cert(&k), None, &key()).is_err());
+
+        // Enveloppe valide re-signée par une autre clé : le pont refuse.
+        let env = work_certificate_to_ho_json_v1(&cert(&k), None, &k).unwrap();
+        let mut autre = env.clone();
+        (autre.payload_sha256, autre.signatures) = sign_envelope(&autre.payload, &key()).unwrap();
+        assert!(verify_ho_json_v1(&autre).is_ok());
+        assert!(work_certificate_from_ho_json_v1(&autre).is_err());
+    }
+
+    #[test]
+    fn test_3_liaison_document_incoherente_refusee() {
+        let k = key();
+        let mut env = work_certificate_to_ho_json_v1(&cert(&k), None, &k).unwrap();
+        env.payload["document"]["sha256"] = Value::String("c".repeat(64));
+        assert!(verify_ho_json_v1(&env).is_err());
+        (env.payload_sha256, env.signatures) = sign_envelope(&env.payload, &k).unwrap();
+        assert!(verify_ho_json_v1(&env).is_ok());
+        assert!(work_certificate_from_ho_json_v1(&env).is_err());
+    }
+
+    #[test]
+    fn test_4_enveloppe_js_verifiee_nativement() {
+        let k = key();
+        let env = js_envelope(&k);
+        assert!(verify_ho_json_v1(&env).is_ok());
+        assert!(work_certificate_from_ho_json_v1(&env).is_err());
+
+        // Le contre-seing serveur est hors signature émetteur.
+        let mut contre = env.clone();
+        contre.server_attestation = Some(serde_json::json!({ "signature": "x" }));
+        assert!(verify_ho_json_v1(&contre).is_ok());
+
+        let mut payload = env.clone();
+        payload.payload["document"]["delta_bytes_ratio"] = serde_json::json!(0.5);
+        assert!(verify_ho_json_v1(&payload).is_err());
+        let mut champ = env.clone();
+        champ.signatures[0].signed_field = "payload".to_string();
+        assert!(verify_ho_json_v1(&champ).is_err());
+        let mut version = env;
+        version.version = "2.0".to_string();
+        assert!(verify_ho_json_v1(&version).is_err());
+    }
+
+    #[test]
+    fn test_5_verification_native_selon_version() {
+        let k = key();
+        let js = serde_json::to_string(&js_envelope(&k)).unwrap();
+        let r = verify_ho_json_document_core(&js).unwrap();
+        assert_eq!(r.certificate_id.as_deref(), Some("HO-1"));
+        assert!(!r.work_certificate_verified);
+
+        let work = work_certificate_to_ho_json_v1(&cert(&k), None, &k).unwrap();
+        let r = verify_ho_json_document_core(&serde_json::to_string(&work).unwrap()).unwrap();
+        assert!(r.work_certificate_verified);
+        assert_eq!(r.public_key, work.signatures[0].public_key);
+
+        let mut inconnue = serde_json::to_value(&work).unwrap();
+        inconnue["version"] = Value::String("3.0".to_string());
+        assert!(verify_ho_json_document_core(&inconnue.to_string()).is_err());
+        assert!(verify_ho_json_document_core("{").is_err());
+    }
+
+    // Fixture FIGÉE produite par le vrai frontend : `canonicalize` extrait de
+    // src/main.js, `JSON.stringify(canonicalize(payload))`, SHA256 UTF-8, puis
+    // signature Ed25519 des 32 octets du hash (clé de test : graine 0x07 x 32),
+    // fichier écrit par `JSON.stringify(hoDocV1, null, 2)`. Flottants,
+    // non-ASCII et échappements dans le domaine commun JS / HO-CANON-V1
+    // (docs/HO_JSON_v1_SPEC.md). NE PAS régénérer depuis Rust.
+    const JS_FRONTEND_FIXTURE: &str = r#"{
+  "format": "humanorigin-hojson",
+  "version": "1.0",
+  "payload": {
+    "certificate_type": "final_project_certificate",
+    "certificate_id": "HO-FIXTURE-JS-1",
+    "issuer": {
+      "product": "HumanOrigin",
+      "security_schema_version": "2026-06-p0"
+    },
+    "document": {
+      "sha256": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+      "filename": "Mémoire — version finale (v2) ☕.docx",
+      "size_bytes": 48213,
+      "delta": {
+        "bytes": 1024,
+        "bytes_ratio": 0.25,
+        "significant": true
+      }
+    },
+    "process_summary": {
+      "active_ms": 120000,
+      "scp_score": 72.5,
+      "whole_ratio": 5,
+      "rhythm_cv": 0.30000000000000004,
+      "burst_ratio": 1e-7,
+      "tiny": -1.5e-9,
+      "huge": 1e+21,
+      "paste_share": 0.0346
+    },
+    "contribution": {
+      "flags": [
+        "PASTE:12ch",
+        "Élan"
+      ],
+      "cap_reason": null,
+      "coherence": true
+    },
+    "notes": "ligne 1\nligne 2\t\"citée\" \\ fin \u0001\u001f € 😀 </script>",
+    "éditeur": "Zoë",
+    "label_eligibility": {
+      "visible_verdict": "OBSERVED"
+    }
+  },
+  "payload_sha256": "107da79b1071b73c3646c968b38468b7eb4d89f079f390a5f34e1aa2672e12c5",
+  "signatures": [
+    {
+      "role": "issuer",
+      "algorithm": "ed25519",
+      "signed_field": "payload_sha256",
+      "public_key": "6kpsY+KcUgq+9VB7Ey7F+ZVHdq6+vnuSQh7qaRRG0iw=",
+      "signature": "ZlbUsOQ+iOm/SXaZj1TCCxXrptk/Mj1INF4qhpM++YC7qJKPUSBweoRP519qbgra3R9S+ivykHaP36baj79UCQ=="
+    }
+  ]
+}"#;
+
+    /// Écarts connus JS / HO-CANON-V1 : (cas, forme canonique JS, forme Rust).
+    /// Un payload JS qui en contient un n'est PAS vérifiable nativement.
+    const JS_CANON_DIVERGENCES: &[(&str, &str, &str)] = &[
+        (
+            "entier_flottant_1e20",
+            r#"{"v":100000000000000000000}"#,
+            r#"{"v":1e+20}"#,
+        ),
+        ("petit_decimal", r#"{"v":0.000001}"#, r#"{"v":1e-6}"#),
+        (
+            "derive_parseur",
+            r#"{"v":241.33333333333334}"#,
+            r#"{"v":241.33333333333337}"#,
+        ),
+        (
+            "cles_entieres",
+            r#"{"9":2,"10":1,"a":3}"#,
+            r#"{"10":1,"9":2,"a":3}"#,
+        ),
+        (
+            "cles_hors_bmp",
+            "{\"\u{1F600}\":1,\"\u{FB33}\":2}",
+            "{\"\u{FB33}\":2,\"\u{1F600}\":1}",
+        ),
+    ];
+
+    #[test]
+    fn test_6_fixture_frontend_reelle_verifiee() {
+        let env = parse_ho_json_v1(JS_FRONTEND_FIXTURE).unwrap();
+        verify_ho_json_v1(&env).unwrap();
+        assert_eq!(env.payload["process_summary"]["huge"], 1e21);
+        assert!(env.payload["notes"]
+            .as_str()
+            .unwrap()
+            .contains("\u{1}\u{1f} € 😀"));
+        let r = verify_ho_json_document_core(JS_FRONTEND_FIXTURE).unwrap();
+        assert_eq!(r.certificate_id.as_deref(), Some("HO-FIXTURE-JS-1"));
+        assert!(!r.work_certificate_verified);
+
+        // Un seul caractère du payload modifié : refus.
+        let altere = JS_FRONTEND_FIXTURE.replace("Zoë", "Zoe");
+        assert!(verify_ho_json_document_core(&altere).is_err());
+    }
+
+    #[test]
+    fn test_7_ecarts_js_documentes() {
+        for (cas, js, rust) in JS_CANON_DIVERGENCES {
+            let v: Value = serde_json::from_str(js).unwrap();
+            let canon = String::from_utf8(canonical_bytes_excluding(&v, &[]).unwrap()).unwrap();
+            assert_eq!(&canon, rust, "{cas}");
+            assert_ne!(&canon, js, "{cas} : écart résorbé, mettre à jour la spec");
+        }
+    }
+}
@@ -0,0 +1,489 @@
+//! ho_json_v2 — Document HO-JSON V2 portable (writer + parser, V2-M4).
+//!
+//! Portée STRICTE : à partir d'un `WorkCertificate` (6B), d'un `ObservedObjectRef`
+//! et d'un `ObjectVersionRef`, produire UN document `.ho.json` signé couvrant
+//! tout média (PDF, image, audio, code…), et le relire/vérifier.
+//!
+//! Enveloppe IDENTIQUE à HO-JSON v1 (docs/HO_JSON_v1_SPEC.md) — superset
+//! non-rupture (docs/v2/V2_HO_JSON_V2_COMPATIBILITY_SPEC.md) :
+//!   { format: "humanorigin-hojson", version: "2.0", payload,
+//!     payload_sha256 = SHA256(HO-CANON-V1(payload)),
+//!     signatures: [{ role: "issuer", algorithm: "ed25519",
+//!                    signed_field: "payload_sha256", public_key, signature }] }
+//! La signature porte sur les 32 octets de `payload_sha256` (comme v1) : un
+//! verifier v1 vérifie l'enveloppe V2 sans changement. Signature et
+//! vérification d'enveloppe : `ho_json_v1` (chemin unique).
+//!
+//! Le payload (`schema_version = 2`) embarque le certificat Work TEL QUEL
+//! (vérifiable seul), `document.sha256` (liaison v1), l'objet observé et sa
+//! version finale (`media_type`, `media_specific`…).
+//!
+//! AUCUNE commande Tauri, AUCUNE UI, AUCUN réseau, AUCUN chemin local, AUCUN
+//! contenu média.
+//!
+//! Garde-fous :
+//! - règle skip-none : tout champ V2 optionnel est ABSENT si `None` (jamais
+//!   `null`) ; le parser refuse un payload qui ne se resérialise pas à
+//!   l'identique (champ inconnu, `null` à la place d'un champ absent) ;
+//! - `local_display_name` n'est jamais publié ;
+//! - la version finale DOIT être le document certifié (`hash_current`) et
+//!   l'objet observé DOIT être le Work du certificat ;
+//! - la clé de l'enveloppe DOIT être celle du certificat embarqué.
+
+// Fondation (V2-M4) : writer/parser ; exposition (export, verifier JS) à suivre.
+#![allow(dead_code)]
+
+use base64::{engine::general_purpose, Engine as _};
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+use crate::evidence_kernel::{ObjectVersionRef, ObservedObjectRef, HO_JSON_V2_SCHEMA_VERSION};
+use crate::ho_json_v1::{
+    sign_envelope, verify_envelope, HoJsonSignature, HO_JSON_FORMAT, LOCAL_DEVICE_IDENTITY,
+    PRODUCT, VERIFICATION_METHOD, WORK_CERTIFICATE_TYPE as CERTIFICATE_TYPE,
+};
+use crate::work_certificate::{verify_certificate, ProofVerdict, WorkCertificate};
+use crate::work_period::canonical_bytes_excluding;
+
+pub(crate) const HO_JSON_V2_VERSION: &str = "2.0";
+
+// --- TYPES --------------------------------------------------------------------
+
+/// Émetteur : produit + identité device (jamais une identité civile).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HoJsonV2Issuer {
+    pub product: String,
+    pub identity_status: String,
+    pub signing_key_id: String,
+}
+
+/// Liaison document, au format v1 (`payload.document.sha256`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HoJsonV2DocumentBinding {
+    pub sha256: String,
+    /// Type MIME, optionnel. Absent de la sérialisation si `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HoJsonV2Verification {
+    /// URL publique de vérification. Absente de la sérialisation si `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify_url: Option<String>,
+    pub verification_method: String,
+}
+
+/// Payload signé (via `payload_sha256`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HoJsonV2Payload {
+    pub schema_version: u32,
+    pub certificate_type: String,
+    pub certificate_id: String,
+    pub issued_at: String,
+    pub issuer: HoJsonV2Issuer,
+    pub verdict: ProofVerdict,
+    pub document: HoJsonV2DocumentBinding,
+    pub observed_object: ObservedObjectRef,
+    pub final_version: ObjectVersionRef,
+    pub verification: HoJsonV2Verification,
+    /// Certificat Work embarqué tel quel (signature 6B vérifiable seule).
+    pub work_certificate: WorkCertificate,
+}
+
+/// Document HO-JSON V2 complet.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HoJsonV2Document {
+    pub format: String,
+    pub version: String,
+    pub payload: HoJsonV2Payload,
+    pub payload_sha256: String,
+    pub signatures: Vec<HoJsonSignature>,
+}
+
+// --- HELPERS ------------------------------------------------------------------
+
+fn sha256_hex_str(s: &str) -> String {
+    format!("{:x}", Sha256::digest(s.as_bytes()))
+}
+
+// --- ÉCRITURE -----------------------------------------------------------------
+
+/// Construit et signe le document V2 de `cert` pour l'objet/la version donnés.
+/// La clé DOIT être celle du certificat.
+pub(crate) fn build_ho_json_v2(
+    cert: &WorkCertificate,
+    object: &ObservedObjectRef,
+    version: &ObjectVersionRef,
+    verify_url: Option<&str>,
+    signing_key: &SigningKey,
+) -> Result<HoJsonV2Document, String> {
+    let public_key = general_purpose::STANDARD.encode(signing_key.verifying_key().to_bytes());
+    if public_key != cert.signature_metadata.public_key {
+        return Err("clé HO-JSON ≠ clé du certificat".to_string());
+    }
+    let mut object = object.clone();
+    object.local_display_name = None;
+
+    let payload = HoJsonV2Payload {
+        schema_version: HO_JSON_V2_SCHEMA_VERSION,
+        certificate_type: CERTIFICATE_TYPE.to_string(),
+        certificate_id: cert.certificate_id.clone(),
+        issued_at: cert.created_at.clone(),
+        issuer: HoJsonV2Issuer {
+            product: PRODUCT.to_string(),
+            identity_status: LOCAL_DEVICE_IDENTITY.to_string(),
+            signing_key_id: cert.signature_metadata.signing_key_id.clone(),
+        },
+        verdict: cert.public_core_evidence.verdict.verdict,
+        document: HoJsonV2DocumentBinding {
+            sha256: version.fingerprint_sha256.clone(),
+            mime: version.mime_type.clone(),
+        },
+        observed_object: object,
+        final_version: version.clone(),
+        verification: HoJsonV2Verification {
+            verify_url: verify_url.map(str::to_string),
+            verification_method: VERIFICATION_METHOD.to_string(),
+        },
+        work_certificate: cert.clone(),
+    };
+    check_payload(&payload)?;
+
+    let (payload_sha256, signatures) = sign_envelope(&payload, signing_key)?;
+    Ok(HoJsonV2Document {
+        format: HO_JSON_FORMAT.to_string(),
+        version: HO_JSON_V2_VERSION.to_string(),
+        payload,
+        payload_sha256,
+        signatures,
+    })
+}
+
+/// Point d'entrée production : signe avec la clé device (`ensure_signing_key`).
+pub(crate) fn build_ho_json_v2_core(
+    cert: &WorkCertificate,
+    object: &ObservedObjectRef,
+    version: &ObjectVersionRef,
+    verify_url: Option<&str>,
+) -> Result<HoJsonV2Document, String> {
+    let key = crate::ensure_signing_key()?;
+    build_ho_json_v2(cert, object, version, verify_url, &key)
+}
+
+/// Sérialisation `.ho.json` (indentée, comme les exports v1).
+pub(crate) fn to_json_string(doc: &HoJsonV2Document) -> Result<String, String> {
+    serde_json::to_string_pretty(doc).map_err(|e| e.to_string())
+}
+
+/// Écrit le document (à côté du fichier livré, ou dans un package).
+pub(crate) fn write_ho_json_v2(doc: &HoJsonV2Document, path: &Path) -> Result<(), String> {
+    fs::write(path, to_json_string(doc)?).map_err(|e| e.to_string())
+}
+
+// --- LECTURE / VÉRIFICATION ---------------------------------------------------
+
+/// Cohérence interne du payload (indépendante de la signature d'enveloppe).
+fn check_payload(payload: &HoJsonV2Payload) -> Result<(), String> {
+    let cert = &payload.work_certificate;
+    if payload.schema_version != HO_JSON_V2_SCHEMA_VERSION {
+        return Err(format!(
+            "schema_version inattendu : {}",
+            payload.schema_version
+        ));
+    }
+    if payload.certificate_type != CERTIFICATE_TYPE {
+        return Err(format!(
+            "certificate_type inattendu : {}",
+            payload.certificate_type
+        ));
+    }
+    if payload.certificate_id != cert.certificate_id || payload.issued_at != cert.created_at {
+        return Err("payload ≠ certificat embarqué".to_string());
+    }
+    if payload.issuer.identity_status != LOCAL_DEVICE_IDENTITY
+        || payload.issuer.signing_key_id != cert.signature_metadata.signing_key_id
+    {
+        return Err("émetteur ≠ certificat embarqué".to_string());
+    }
+    if payload.verdict != cert.public_core_evidence.verdict.verdict {
+        return Err("verdict ≠ certificat embarqué".to_string());
+    }
+    if payload.observed_object.object_id != cert.work_id.as_str() {
+        return Err("objet observé ≠ Work du certificat".to_string());
+    }
+    if payload.observed_object.local_display_name.is_some() {
+        return Err("local_display_name publié".to_string());
+    }
+    let hash = &cert.public_core_evidence.document.hash_current;
+    if payload.final_version.fingerprint_sha256 != *hash || payload.document.sha256 != *hash {
+        return Err("version finale ≠ document certifié".to_string());
+    }
+    if payload.document.mime != payload.final_version.mime_type {
+        return Err("document.mime ≠ final_version.mime_type".to_string());
+    }
+    if payload.verification.verification_method != VERIFICATION_METHOD {
+        return Err("méthode de vérification inattendue".to_string());
+    }
+    Ok(())
+}
+
+/// Vérifie un document : enveloppe, `payload_sha256`, signature Ed25519 de
+/// l'émetteur (clé du certificat), certificat embarqué, cohérence du payload.
+pub(crate) fn verify_ho_json_v2(doc: &HoJsonV2Document) -> Result<(), String> {
+    if doc.version != HO_JSON_V2_VERSION {
+        return Err(format!("version inattendue : {}", doc.version));
+    }
+    let issuer = verify_envelope(
+        &doc.format,
+        &doc.payload,
+        &doc.payload_sha256,
+        &doc.signatures,
+    )?;
+    let cert = &doc.payload.work_certificate;
+    if issuer.public_key != cert.signature_metadata.public_key
+        || sha256_hex_str(&issuer.public_key) != doc.payload.issuer.signing_key_id
+    {
+        return Err("clé émetteur ≠ clé du certificat".to_string());
+    }
+
+    verify_certificate(cert)?;
+    check_payload(&doc.payload)
+}
+
+/// Relit un document : le payload typé DOIT se resérialiser à l'identique du
+/// payload reçu (octets canoniques), sinon refus — ni champ inconnu, ni `null`
+/// à la place d'un champ V2 absent. Puis `verify_ho_json_v2`.
+pub(crate) fn parse_ho_json_v2(raw: &str) -> Result<HoJsonV2Document, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(raw).map_err(|e| format!("JSON invalide : {e}"))?;
+    let raw_payload = value
+        .get("payload")
+        .ok_or_else(|| "payload absent".to_string())?;
+    let doc: HoJsonV2Document =
+        serde_json::from_value(value.clone()).map_err(|e| format!("HO-JSON V2 invalide : {e}"))?;
+    if canonical_bytes_excluding(raw_payload, &[])? != canonical_bytes_excluding(&doc.payload, &[])?
+    {
+        return Err("payload non canonique (champ inconnu ou null)".to_string());
+    }
+    verify_ho_json_v2(&doc)?;
+    Ok(doc)
+}
+
+/// Lit et vérifie un `.ho.json` V2.
+pub(crate) fn read_ho_json_v2(path: &Path) -> Result<HoJsonV2Document, String> {
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    parse_ho_json_v2(&raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evidence_kernel::{
+        AudioFingerprint, AudioSignalDigest, MediaKind, MediaSpecificMetadata,
+    };
+    use crate::ho_json_v1::{decode_hex32, payload_sha256};
+    use crate::work_certificate::{
+        compute_public_core_evidence_sha256, CertificateSignatureMetadata, CertificateVersion,
+        Continuity, ContinuityKind, PublicCoreEvidence, PublicDocumentRef, VerdictEngineSummary,
+        CERTIFICATE_SCHEMA_VERSION, CORE_EVIDENCE_SCHEMA_VERSION,
+    };
+    use crate::work_store::WorkId;
+    use ed25519_dalek::Signer;
+    use rand_core::OsRng;
+    use uuid::Uuid;
+
+    const WORK: &str = "00000000-0000-4000-8000-000000000001";
+
+    fn key() -> SigningKey {
+        SigningKey::generate(&mut OsRng)
+    }
+
+    fn cert(k: &SigningKey) -> WorkCertificate {
+        let public_key = general_purpose::STANDARD.encode(k.verifying_key().to_bytes());
+        let pce = PublicCoreEvidence {
+            schema_version: CORE_EVIDENCE_SCHEMA_VERSION,
+            work_id: WorkId(WORK.to_string()),
+            certificate_version: CertificateVersion::V1,
+            document: PublicDocumentRef {
+                hash_current: "a".repeat(64),
+                size_current: 42,
+                members: None,
+            },
+            included_period_summaries: vec![],
+            new_period_ids: vec![],
+            qualifying_new_period_count: 0,
+            continuity: Continuity {
+                kind: ContinuityKind::Full,
+                gaps: vec![],
+            },
+            verdict: VerdictEngineSummary {
+                total_periods: 0,
+                qualifying_periods: 0,
+                total_active_seconds: 0,
+                continuity: ContinuityKind::Full,
+                verdict: ProofVerdict::ObservedWorkConsistent,
+                flags: vec![],
+            },
+            previous_certificate_id: None,
+            previous_core_evidence_sha256: None,
+        };
+        let mut c = WorkCertificate {
+            schema_version: CERTIFICATE_SCHEMA_VERSION,
+            certificate_id: "cert-1".to_string(),
+            certificate_sequence: 1,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            work_id: WorkId(WORK.to_string()),
+            certificate_version: CertificateVersion::V1,
+            core_evidence_sha256: compute_public_core_evidence_sha256(&pce).unwrap(),
+            public_core_evidence: pce,
+            previous_certificate_id: None,
+            previous_core_evidence_sha256: None,
+            signature_metadata: CertificateSignatureMetadata {
+                signature_algorithm: "ed25519".to_string(),
+                signing_key_id: sha256_hex_str(&public_key),
+                public_key,
+                identity_status: LOCAL_DEVICE_IDENTITY.to_string(),
+                schema_version: CERTIFICATE_SCHEMA_VERSION,
+                canonicalization: None,
+            },
+            signature: String::new(),
+        };
+        let body = canonical_bytes_excluding(&c, &["signature"]).unwrap();
+        c.signature = general_purpose::STANDARD.encode(k.sign(&Sha256::digest(&body)).to_bytes());
+        verify_certificate(&c).unwrap();
+        c
+    }
+
+    fn version(media_specific: Option<MediaSpecificMetadata>) -> ObjectVersionRef {
+        ObjectVersionRef {
+            fingerprint_sha256: "a".repeat(64),
+            size_bytes: Some(42),
+            mime_type: None,
+            chunked_fingerprint: None,
+            media_specific,
+        }
+    }
+
+    fn audio() -> MediaSpecificMetadata {
+        MediaSpecificMetadata::Audio(AudioFingerprint {
+            container: "wav".to_string(),
+            sample_rate: 44_100,
+            channels: 2,
+            bits_per_sample: Some(16),
+            signal: AudioSignalDigest {
+                scheme: "HO-PCM-SHA256-V1".to_string(),
+                value: "b".repeat(64),
+            },
+        })
+    }
+
+    fn build(k: &SigningKey) -> HoJsonV2Document {
+        let object = ObservedObjectRef::new(WORK, MediaKind::Audio);
+        build_ho_json_v2(&cert(k), &object, &version(Some(audio())), None, k).unwrap()
+    }
+
+    fn reparse_with(
+        doc: &HoJsonV2Document,
+        edit: impl FnOnce(&mut serde_json::Value),
+    ) -> Result<HoJsonV2Document, String> {
+        let mut v = serde_json::to_value(doc).unwrap();
+        edit(&mut v);
+        parse_ho_json_v2(&v.to_string())
+    }
+
+    #[test]
+    fn test_1_document_signe_relu_a_l_identique() {
+        let k = key();
+        let doc = build(&k);
+        assert_eq!(doc.format, HO_JSON_FORMAT);
+        assert_eq!(doc.version, HO_JSON_V2_VERSION);
+        assert_eq!(doc.payload.schema_version, HO_JSON_V2_SCHEMA_VERSION);
+        assert_eq!(doc.payload.document.sha256, "a".repeat(64));
+        verify_ho_json_v2(&doc).unwrap();
+        assert_eq!(
+            parse_ho_json_v2(&to_json_string(&doc).unwrap()).unwrap(),
+            doc
+        );
+    }
+
+    #[test]
+    fn test_2_champs_v2_absents_jamais_null() {
+        let k = key();
+        let mut object = ObservedObjectRef::new(WORK, MediaKind::Pdf);
+        object.local_display_name = Some("brouillon-final.pdf".to_string());
+        let doc = build_ho_json_v2(&cert(&k), &object, &version(None), None, &k).unwrap();
+        let mut payload = serde_json::to_value(&doc.payload).unwrap();
+        payload.as_object_mut().unwrap().remove("work_certificate");
+        let json = payload.to_string();
+        assert!(!json.contains("null"), "{json}");
+        for absent in ["mime", "verify_url", "media_specific", "local_display_name"] {
+            assert!(!json.contains(absent), "{absent} : {json}");
+        }
+    }
+
+    #[test]
+    fn test_3_cle_ou_version_incoherentes_refusees() {
+        let k = key();
+        let c = cert(&k);
+        let object = ObservedObjectRef::new(WORK, MediaKind::Audio);
+        assert!(build_ho_json_v2(&c, &object, &version(None), None, &key()).is_err());
+        let mut autre = version(None);
+        autre.fingerprint_sha256 = "c".repeat(64);
+        assert!(build_ho_json_v2(&c, &object, &autre, None, &k).is_err());
+        let ailleurs = ObservedObjectRef::new("autre-work", MediaKind::Audio);
+        assert!(build_ho_json_v2(&c, &ailleurs, &version(None), None, &k).is_err());
+    }
+
+    #[test]
+    fn test_4_payload_ou_signature_falsifies_refuses() {
+        let k = key();
+        let doc = build(&k);
+        let mut url = doc.clone();
+        url.payload.verification.verify_url = Some("https://ailleurs.example/r/x".to_string());
+        assert!(verify_ho_json_v2(&url).is_err());
+
+        // Payload re-haché mais signé par une autre clé : refusé (≠ clé du certificat).
+        let autre = key();
+        let mut resigne = url.clone();
+        resigne.payload_sha256 = payload_sha256(&resigne.payload).unwrap();
+        let sig = autre.sign(&decode_hex32(&resigne.payload_sha256).unwrap());
+        resigne.signatures[0].signature = general_purpose::STANDARD.encode(sig.to_bytes());
+        assert!(verify_ho_json_v2(&resigne).is_err());
+        resigne.signatures[0].public_key =
+            general_purpose::STANDARD.encode(autre.verifying_key().to_bytes());
+        assert!(verify_ho_json_v2(&resigne).is_err());
+    }
+
+    #[test]
+    fn test_5_null_ou_champ_inconnu_refuses_au_parse() {
+        let doc = build(&key());
+        assert!(reparse_with(&doc, |v| {
+            v["payload"]["document"]["mime"] = serde_json::Value::Null;
+        })
+        .is_err());
+        assert!(reparse_with(&doc, |v| {
+            v["payload"]["final_version"]["mime_type"] = serde_json::Value::Null;
+        })
+        .is_err());
+        assert!(reparse_with(&doc, |v| {
+            v["payload"]["inconnu"] = serde_json::json!(1);
+        })
+        .is_err());
+        assert!(reparse_with(&doc, |_| {}).is_ok());
+    }
+
+    #[test]
+    fn test_6_ecriture_lecture_fichier() {
+        let dir = std::env::temp_dir().join(format!("ho_json_v2_test_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let doc = build(&key());
+        let path = dir.join("labeled_audio.ho.json");
+        write_ho_json_v2(&doc, &path).unwrap();
+        assert_eq!(read_ho_json_v2(&path).unwrap(), doc);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
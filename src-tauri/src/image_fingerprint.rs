@@ -0,0 +1,461 @@
+//! image_fingerprint — Empreinte perceptuelle d'une image + vérification (V2-B).
+//!
+//! Portée STRICTE : décoder une image (crate `image`), la redresser selon son
+//! orientation EXIF, et en tirer une `evidence_kernel::ImageFingerprint` :
+//! dimensions, type de couleur, SHA256 du profil ICC, SHA256 des pixels décodés
+//! et dHash 64 bits (`DHASH_SCHEME`). Puis comparer une image candidate à cette
+//! référence (dHash de la source OU de la copie labellisée si présent) :
+//! - `PixelIdentical` : mêmes pixels décodés (fichier ré-encodé sans perte,
+//!   métadonnées retirées…) ;
+//! - `PerceptuallyEquivalent` : distance de Hamming du dHash ≤
+//!   `PERCEPTUAL_MAX_DISTANCE` (recompression JPEG, redimensionnement) ;
+//! - `Different` : au-delà.
+//!
+//! Le SHA256 du fichier reste la preuve ; cette empreinte ne sert qu'à
+//! retrouver une copie recompressée en aval. AUCUNE écriture, AUCUN contenu
+//! conservé (seulement des empreintes), AUCUNE commande Tauri.
+//!
+//! Garde-fous :
+//! - pixels hachés en 8 bits SAUF source 16 bits (hachée en 16 bits, big-endian) ;
+//!   dimensions et profondeur incluses dans le hash (pas de collision triviale) ;
+//! - orientation EXIF appliquée avant hachage : une copie dont l'orientation a
+//!   été « cuite » dans les pixels (réseaux sociaux) reste reconnaissable ;
+//! - une référence `DHASH_SCHEME_V1` (pixels non redressés) est vérifiée sans
+//!   redresser la candidate : les sidecars existants restent vérifiables ;
+//! - une référence d'un autre schéma perceptuel -> `Err` (jamais de faux
+//!   « équivalent »).
+
+// Fondation (V2-B) : consommé par `work_image` (sidecar) ; verifier sans UI.
+#![allow(dead_code)]
+
+use image::imageops::{self, FilterType};
+use image::metadata::Orientation;
+use image::{DynamicImage, GenericImageView, ImageDecoder, ImageReader};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::evidence_kernel::{ImageFingerprint, PerceptualHash};
+
+/// dHash 64 bits : luminance réduite à 9x8 (filtre triangle), 1 bit par paire
+/// de pixels horizontalement voisins (gauche < droite), sur l'image redressée
+/// selon son orientation EXIF.
+pub(crate) const DHASH_SCHEME: &str = "HO-DHASH-64-V2";
+
+/// Schéma antérieur : même dHash, pixels tels que stockés (orientation EXIF
+/// ignorée). Accepté en vérification seulement.
+pub(crate) const DHASH_SCHEME_V1: &str = "HO-DHASH-64-V1";
+
+/// Distance de Hamming maximale (sur 64 bits) pour « perceptuellement équivalent ».
+pub(crate) const PERCEPTUAL_MAX_DISTANCE: u32 = 10;
+
+const PIXEL_DOMAIN_8: &[u8] = b"HO-PIXELS-RGBA8-V1\0";
+const PIXEL_DOMAIN_16: &[u8] = b"HO-PIXELS-RGBA16BE-V1\0";
+
+/// Résultat de la comparaison d'une image candidate à une référence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "result")]
+pub(crate) enum ImageMatch {
+    PixelIdentical,
+    PerceptuallyEquivalent { distance: u32 },
+    Different { distance: u32 },
+}
+
+impl ImageMatch {
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            ImageMatch::PixelIdentical => "pixel-identical",
+            ImageMatch::PerceptuallyEquivalent { .. } => "perceptually equivalent",
+            ImageMatch::Different { .. } => "different",
+        }
+    }
+}
+
+/// Schéma -> faut-il redresser l'image (orientation EXIF) avant hachage.
+fn scheme_is_oriented(scheme: &str) -> Result<bool, String> {
+    match scheme {
+        DHASH_SCHEME => Ok(true),
+        DHASH_SCHEME_V1 => Ok(false),
+        other => Err(format!("schéma perceptuel non supporté : {other}")),
+    }
+}
+
+fn decode(bytes: &[u8], oriented: bool) -> Result<(DynamicImage, Option<Vec<u8>>), String> {
+    let mut decoder = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?
+        .into_decoder()
+        .map_err(|e| format!("image illisible : {e}"))?;
+    let icc = decoder.icc_profile().map_err(|e| e.to_string())?;
+    let orientation = if oriented {
+        decoder.orientation().map_err(|e| e.to_string())?
+    } else {
+        Orientation::NoTransforms
+    };
+    let mut img =
+        DynamicImage::from_decoder(decoder).map_err(|e| format!("image illisible : {e}"))?;
+    img.apply_orientation(orientation);
+    Ok((img, icc))
+}
+
+fn color_type_label(img: &DynamicImage) -> String {
+    format!("{:?}", img.color()).to_lowercase()
+}
+
+fn is_16_bit(img: &DynamicImage) -> bool {
+    img.color().bytes_per_pixel() / img.color().channel_count() >= 2
+}
+
+fn pixel_sha256(img: &DynamicImage) -> String {
+    let (w, h) = img.dimensions();
+    let mut hasher = Sha256::new();
+    if is_16_bit(img) {
+        hasher.update(PIXEL_DOMAIN_16);
+        hasher.update(w.to_be_bytes());
+        hasher.update(h.to_be_bytes());
+        let samples = img.to_rgba16().into_raw();
+        let mut buf = Vec::with_capacity(samples.len() * 2);
+        for c in samples {
+            buf.extend_from_slice(&c.to_be_bytes());
+        }
+        hasher.update(&buf);
+    } else {
+        hasher.update(PIXEL_DOMAIN_8);
+        hasher.update(w.to_be_bytes());
+        hasher.update(h.to_be_bytes());
+        hasher.update(img.to_rgba8().as_raw());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn dhash(img: &DynamicImage) -> u64 {
+    let small = imageops::resize(&img.to_luma8(), 9, 8, FilterType::Triangle);
+    let mut bits = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            bits <<= 1;
+            if small.get_pixel(x, y)[0] < small.get_pixel(x + 1, y)[0] {
+                bits |= 1;
+            }
+        }
+    }
+    bits
+}
+
+fn perceptual_hash(img: &DynamicImage, scheme: &str) -> PerceptualHash {
+    PerceptualHash {
+        scheme: scheme.to_string(),
+        value: format!("{:016x}", dhash(img)),
+    }
+}
+
+fn fingerprint_decoded(img: &DynamicImage, icc: Option<&[u8]>, scheme: &str) -> ImageFingerprint {
+    let (width, height) = img.dimensions();
+    ImageFingerprint {
+        width,
+        height,
+        color_type: color_type_label(img),
+        icc_profile_sha256: icc.map(|p| format!("{:x}", Sha256::digest(p))),
+        pixel_sha256: pixel_sha256(img),
+        perceptual_hash: perceptual_hash(img, scheme),
+        labeled_perceptual_hash: None,
+    }
+}
+
+fn fingerprint_with_scheme(bytes: &[u8], scheme: &str) -> Result<ImageFingerprint, String> {
+    let (img, icc) = decode(bytes, scheme_is_oriented(scheme)?)?;
+    Ok(fingerprint_decoded(&img, icc.as_deref(), scheme))
+}
+
+/// Empreinte d'une image encodée (PNG, JPEG, … : formats du crate `image`).
+pub(crate) fn fingerprint_image_bytes(bytes: &[u8]) -> Result<ImageFingerprint, String> {
+    fingerprint_with_scheme(bytes, DHASH_SCHEME)
+}
+
+/// dHash seul d'une image encodée (ex. copie labellisée, marge incluse).
+pub(crate) fn perceptual_hash_bytes(bytes: &[u8]) -> Result<PerceptualHash, String> {
+    let (img, _) = decode(bytes, true)?;
+    Ok(perceptual_hash(&img, DHASH_SCHEME))
+}
+
+/// Empreinte d'un fichier image.
+pub(crate) fn fingerprint_image_file(path: &Path) -> Result<ImageFingerprint, String> {
+    let bytes = fs::read(path).map_err(|e| format!("image illisible : {e}"))?;
+    fingerprint_image_bytes(&bytes)
+}
+
+fn parse_dhash(hash: &PerceptualHash) -> Result<u64, String> {
+    scheme_is_oriented(&hash.scheme)?;
+    if hash.value.len() != 16 {
+        return Err("dHash invalide (16 caractères hex attendus)".to_string());
+    }
+    u64::from_str_radix(&hash.value, 16).map_err(|_| "dHash invalide (hex)".to_string())
+}
+
+/// Compare deux empreintes (référence, candidate), de même schéma. La distance
+/// retenue est la plus faible entre le dHash de la source et celui de la copie
+/// labellisée (si la référence le porte) : une recompression de l'une ou de
+/// l'autre est reconnue.
+pub(crate) fn compare_fingerprints(
+    reference: &ImageFingerprint,
+    candidate: &ImageFingerprint,
+) -> Result<ImageMatch, String> {
+    if reference.perceptual_hash.scheme != candidate.perceptual_hash.scheme {
+        return Err(format!(
+            "schémas perceptuels différents : {} / {}",
+            reference.perceptual_hash.scheme, candidate.perceptual_hash.scheme
+        ));
+    }
+    let candidate_hash = parse_dhash(&candidate.perceptual_hash)?;
+    let mut distance = (parse_dhash(&reference.perceptual_hash)? ^ candidate_hash).count_ones();
+    if let Some(labeled) = &reference.labeled_perceptual_hash {
+        if labeled.scheme != reference.perceptual_hash.scheme {
+            return Err(format!("schéma perceptuel incohérent : {}", labeled.scheme));
+        }
+        distance = distance.min((parse_dhash(labeled)? ^ candidate_hash).count_ones());
+    }
+    if reference.pixel_sha256 == candidate.pixel_sha256 {
+        return Ok(ImageMatch::PixelIdentical);
+    }
+    if distance <= PERCEPTUAL_MAX_DISTANCE {
+        Ok(ImageMatch::PerceptuallyEquivalent { distance })
+    } else {
+        Ok(ImageMatch::Different { distance })
+    }
+}
+
+/// Verifier : compare une image candidate (octets) à l'empreinte de référence.
+pub(crate) fn verify_image_bytes(
+    reference: &ImageFingerprint,
+    candidate: &[u8],
+) -> Result<ImageMatch, String> {
+    let scheme = &reference.perceptual_hash.scheme;
+    compare_fingerprints(reference, &fingerprint_with_scheme(candidate, scheme)?)
+}
+
+/// Verifier : compare un fichier image candidat à l'empreinte de référence.
+pub(crate) fn verify_image_file(
+    reference: &ImageFingerprint,
+    candidate: &Path,
+) -> Result<ImageMatch, String> {
+    let bytes = fs::read(candidate).map_err(|e| format!("image illisible : {e}"))?;
+    verify_image_bytes(reference, &bytes)
+}
+
+// --- TESTS UNITAIRES ----------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::jpeg::JpegEncoder;
+    use image::{ExtendedColorType, ImageBuffer, ImageEncoder, ImageFormat, Rgb, RgbImage};
+
+    /// Scène déterministe : dégradé + disque + bande (structure pour le dHash).
+    fn scene(w: u32, h: u32) -> RgbImage {
+        ImageBuffer::from_fn(w, h, |x, y| {
+            let (fx, fy) = (x as f32 / w as f32, y as f32 / h as f32);
+            let in_disc = (fx - 0.35).powi(2) + (fy - 0.5).powi(2) < 0.04;
+            let in_band = fx > 0.7 && fx < 0.8;
+            if in_disc {
+                Rgb([230, 40, 40])
+            } else if in_band {
+                Rgb([20, 20, 20])
+            } else {
+                Rgb([(fx * 200.0) as u8, (fy * 180.0) as u8, 120])
+            }
+        })
+    }
+
+    fn encode(img: &DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut out = Cursor::new(Vec::new());
+        img.write_to(&mut out, format).unwrap();
+        out.into_inner()
+    }
+
+    fn jpeg(img: &DynamicImage, quality: u8) -> Vec<u8> {
+        let mut out = Vec::new();
+        JpegEncoder::new_with_quality(&mut out, quality)
+            .encode_image(img)
+            .unwrap();
+        out
+    }
+
+    /// EXIF minimal (TIFF big-endian) : IFD0 avec la seule entrée `Orientation`.
+    fn exif_orientation(orientation: u16) -> Vec<u8> {
+        let mut t = b"MM\0*".to_vec();
+        t.extend(8u32.to_be_bytes());
+        t.extend(1u16.to_be_bytes());
+        t.extend(0x0112u16.to_be_bytes());
+        t.extend(3u16.to_be_bytes());
+        t.extend(1u32.to_be_bytes());
+        t.extend(orientation.to_be_bytes());
+        t.extend([0, 0]);
+        t.extend(0u32.to_be_bytes());
+        t
+    }
+
+    #[test]
+    fn test_1_empreinte_dimensions_et_schema() {
+        let img = DynamicImage::ImageRgb8(scene(120, 80));
+        let fp = fingerprint_image_bytes(&encode(&img, ImageFormat::Png)).unwrap();
+        assert_eq!((fp.width, fp.height), (120, 80));
+        assert_eq!(fp.color_type, "rgb8");
+        assert_eq!(fp.icc_profile_sha256, None);
+        assert_eq!(fp.perceptual_hash.scheme, DHASH_SCHEME);
+        assert_eq!(fp.perceptual_hash.value.len(), 16);
+        assert_eq!(fp.pixel_sha256.len(), 64);
+    }
+
+    #[test]
+    fn test_2_reencodage_sans_perte_pixel_identique() {
+        let img = DynamicImage::ImageRgb8(scene(120, 80));
+        let png = encode(&img, ImageFormat::Png);
+        let reference = fingerprint_image_bytes(&png).unwrap();
+        // Autre conteneur sans perte : octets différents, pixels identiques.
+        let tiff = encode(&img, ImageFormat::Tiff);
+        assert_ne!(png, tiff);
+        assert_eq!(
+            verify_image_bytes(&reference, &tiff).unwrap(),
+            ImageMatch::PixelIdentical
+        );
+    }
+
+    #[test]
+    fn test_3_recompression_et_redimensionnement_equivalents() {
+        let img = DynamicImage::ImageRgb8(scene(320, 240));
+        let reference = fingerprint_image_bytes(&encode(&img, ImageFormat::Png)).unwrap();
+
+        let recompressed = jpeg(&img, 60);
+        let m = verify_image_bytes(&reference, &recompressed).unwrap();
+        assert!(
+            matches!(m, ImageMatch::PerceptuallyEquivalent { .. }),
+            "recompression : {m:?}"
+        );
+
+        let resized = img.resize_exact(160, 120, FilterType::Lanczos3);
+        let m = verify_image_bytes(&reference, &jpeg(&resized, 75)).unwrap();
+        assert!(
+            matches!(m, ImageMatch::PerceptuallyEquivalent { .. }),
+            "redimensionnement : {m:?}"
+        );
+        assert_eq!(m.label(), "perceptually equivalent");
+    }
+
+    #[test]
+    fn test_4_autre_image_differente() {
+        let img = DynamicImage::ImageRgb8(scene(120, 80));
+        let reference = fingerprint_image_bytes(&encode(&img, ImageFormat::Png)).unwrap();
+        let other = DynamicImage::ImageRgb8(scene(120, 80)).fliph();
+        let m = verify_image_bytes(&reference, &encode(&other, ImageFormat::Png)).unwrap();
+        assert!(matches!(m, ImageMatch::Different { .. }), "{m:?}");
+        assert_eq!(m.label(), "different");
+    }
+
+    #[test]
+    fn test_5_profondeur_16_bits_distinguee() {
+        let deep: ImageBuffer<Rgb<u16>, Vec<u16>> = ImageBuffer::from_fn(16, 16, |x, y| {
+            Rgb([x as u16 * 4096 + 1, y as u16 * 4096, 7])
+        });
+        let deep = DynamicImage::ImageRgb16(deep);
+        let fp16 = fingerprint_image_bytes(&encode(&deep, ImageFormat::Png)).unwrap();
+        let fp8 = fingerprint_image_bytes(&encode(
+            &DynamicImage::ImageRgb8(deep.to_rgb8()),
+            ImageFormat::Png,
+        ))
+        .unwrap();
+        assert_eq!(fp16.color_type, "rgb16");
+        // Échantillons mis en tampon : même hash qu'un `update` par canal.
+        let mut hasher = Sha256::new();
+        hasher.update(PIXEL_DOMAIN_16);
+        hasher.update(16u32.to_be_bytes());
+        hasher.update(16u32.to_be_bytes());
+        for c in deep.to_rgba16().into_raw() {
+            hasher.update(c.to_be_bytes());
+        }
+        assert_eq!(fp16.pixel_sha256, format!("{:x}", hasher.finalize()));
+        // Réduire à 8 bits change les pixels : jamais « pixel-identique ».
+        assert_ne!(fp16.pixel_sha256, fp8.pixel_sha256);
+        assert!(matches!(
+            compare_fingerprints(&fp16, &fp8).unwrap(),
+            ImageMatch::PerceptuallyEquivalent { .. }
+        ));
+    }
+
+    #[test]
+    fn test_6_schema_inconnu_refuse() {
+        let img = DynamicImage::ImageRgb8(scene(40, 30));
+        let mut reference = fingerprint_image_bytes(&encode(&img, ImageFormat::Png)).unwrap();
+        reference.perceptual_hash.scheme = "PHASH-X".to_string();
+        assert!(verify_image_bytes(&reference, &encode(&img, ImageFormat::Png)).is_err());
+        assert!(verify_image_bytes(&reference, b"pas une image").is_err());
+    }
+
+    #[test]
+    fn test_7_orientation_exif_appliquee() {
+        let upright = DynamicImage::ImageRgb8(scene(120, 80));
+        // Stockée pivotée, orientation 6 (« tourner de 90° horaire » à l'affichage).
+        let stored = upright.rotate270().to_rgb8();
+        let mut oriented = Vec::new();
+        let mut enc = JpegEncoder::new_with_quality(&mut oriented, 95);
+        enc.set_exif_metadata(exif_orientation(6)).unwrap();
+        enc.write_image(stored.as_raw(), 80, 120, ExtendedColorType::Rgb8)
+            .unwrap();
+
+        let reference = fingerprint_image_bytes(&oriented).unwrap();
+        assert_eq!((reference.width, reference.height), (120, 80));
+        // Copie dont l'orientation a été « cuite » dans les pixels, sans EXIF.
+        let m = verify_image_bytes(&reference, &jpeg(&upright, 80)).unwrap();
+        assert!(
+            matches!(m, ImageMatch::PerceptuallyEquivalent { .. }),
+            "{m:?}"
+        );
+
+        // Référence V1 (pixels non redressés) : toujours vérifiable à l'identique.
+        let v1 = fingerprint_with_scheme(&oriented, DHASH_SCHEME_V1).unwrap();
+        assert_eq!((v1.width, v1.height), (80, 120));
+        assert_eq!(
+            verify_image_bytes(&v1, &oriented).unwrap(),
+            ImageMatch::PixelIdentical
+        );
+        // Schémas mélangés : refus, jamais de distance calculée.
+        assert!(compare_fingerprints(&v1, &reference).is_err());
+    }
+
+    #[test]
+    fn test_8_copie_labellisee_reconnue() {
+        let source = DynamicImage::ImageRgb8(scene(120, 80));
+        // Copie labellisée : source en (0,0) + marge à droite (cartouche rayée).
+        let labeled = DynamicImage::ImageRgb8(ImageBuffer::from_fn(200, 80, |x, y| {
+            if x < 120 {
+                *source.as_rgb8().unwrap().get_pixel(x, y)
+            } else if (y / 10) % 2 == 0 {
+                Rgb([250, 250, 250])
+            } else {
+                Rgb([10, 10, 10])
+            }
+        }));
+        let recompressed = jpeg(&labeled, 70);
+
+        let mut reference = fingerprint_image_bytes(&encode(&source, ImageFormat::Png)).unwrap();
+        let m = verify_image_bytes(&reference, &recompressed).unwrap();
+        assert!(matches!(m, ImageMatch::Different { .. }), "{m:?}");
+
+        reference.labeled_perceptual_hash =
+            Some(perceptual_hash_bytes(&encode(&labeled, ImageFormat::Png)).unwrap());
+        let m = verify_image_bytes(&reference, &recompressed).unwrap();
+        assert!(
+            matches!(m, ImageMatch::PerceptuallyEquivalent { .. }),
+            "{m:?}"
+        );
+        // La source elle-même reste pixel-identique.
+        assert_eq!(
+            verify_image_bytes(&reference, &encode(&source, ImageFormat::Png)).unwrap(),
+            ImageMatch::PixelIdentical
+        );
+
+        reference.labeled_perceptual_hash.as_mut().unwrap().scheme = DHASH_SCHEME_V1.to_string();
+        assert!(verify_image_bytes(&reference, &recompressed).is_err());
+    }
+}
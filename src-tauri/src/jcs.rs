@@ -0,0 +1,288 @@
+//! jcs — Canonicalisation JSON RFC 8785 (JCS), schéma `HO-JCS-V1`.
+//!
+//! Portée STRICTE : sérialiser une `serde_json::Value` en octets JCS :
+//! - clés d'objets triées par unités de code UTF-16 (et non par octets UTF-8) ;
+//! - nombres au format ECMAScript `Number.prototype.toString` (plus court
+//!   aller-retour, `1e+21`, `5` pour `5.0`, `0.000001`, `1e-7`) ;
+//! - chaînes : seuls `"`, `\` et les contrôles < U+0020 sont échappés
+//!   (`\b \t \n \f \r`, sinon `\u00xx` minuscule) ; tout le reste est littéral ;
+//! - aucune espace.
+//!
+//! Proche de `JSON.stringify(canonicalize(v))` côté JS (frontend, contre-seing
+//! serveur), SAUF pour les clés de forme entière (`"1"`) que les objets JS
+//! énumèrent en premier : seul ce module suit l'ordre RFC 8785 strict.
+//!
+//! AUCUN choix de schéma ici (`work_period::CanonScheme`), AUCUNE I/O.
+//!
+//! Garde-fous :
+//! - entier hors de ±(2^53 − 1) refusé (I-JSON) plutôt qu'arrondi en silence ;
+//! - la valeur est canonicalisée telle que parsée par `serde_json` : aucune
+//!   reconversion texte -> nombre ici. Sans `float_roundtrip`, un décimal
+//!   étranger peut être lu à 1 ULP près ; une valeur signée par HumanOrigin est
+//!   déjà un point-fixe du parseur (`work_period::normalize_engine`).
+
+use serde_json::{Number, Value};
+
+/// Plus grand entier exactement représentable en IEEE-754 double (I-JSON).
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// Octets canoniques RFC 8785 d'une valeur JSON.
+pub(crate) fn jcs_bytes(value: &Value) -> Result<Vec<u8>, String> {
+    let mut out = String::new();
+    write_value(value, &mut out)?;
+    Ok(out.into_bytes())
+}
+
+fn write_value(value: &Value, out: &mut String) -> Result<(), String> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(n, out)?,
+        Value::String(s) => write_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(item, out)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(Vec<u16>, &String, &Value)> = map
+                .iter()
+                .map(|(k, v)| (k.encode_utf16().collect(), k, v))
+                .collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            out.push('{');
+            for (i, (_, key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(key, out);
+                out.push(':');
+                write_value(item, out)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+fn write_number(n: &Number, out: &mut String) -> Result<(), String> {
+    if let Some(u) = n.as_u64() {
+        if u > MAX_SAFE_INTEGER {
+            return Err(format!("entier hors plage I-JSON : {u}"));
+        }
+        out.push_str(&u.to_string());
+    } else if let Some(i) = n.as_i64() {
+        if i.unsigned_abs() > MAX_SAFE_INTEGER {
+            return Err(format!("entier hors plage I-JSON : {i}"));
+        }
+        out.push_str(&i.to_string());
+    } else {
+        let f = n
+            .as_f64()
+            .ok_or_else(|| format!("nombre non représentable : {n}"))?;
+        out.push_str(&es_number(f));
+    }
+    Ok(())
+}
+
+/// `Number.prototype.toString()` (ECMA-262 §6.1.6.1.20) d'un double fini.
+fn es_number(x: f64) -> String {
+    if x == 0.0 {
+        return "0".to_string();
+    }
+    if x < 0.0 {
+        return format!("-{}", es_number(-x));
+    }
+    // `{:e}` donne les chiffres les plus courts qui font l'aller-retour.
+    let sci = format!("{x:e}");
+    let (mantissa, exp) = sci.split_once('e').unwrap_or((&sci, "0"));
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    let n = exp.parse::<i32>().unwrap_or(0) + 1;
+
+    if k <= n && n <= 21 {
+        format!("{digits}{}", "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        let (int, frac) = digits.split_at(n as usize);
+        format!("{int}.{frac}")
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{digits}", "0".repeat((-n) as usize))
+    } else {
+        let e = n - 1;
+        let sign = if e < 0 { '-' } else { '+' };
+        let (first, rest) = digits.split_at(1);
+        if rest.is_empty() {
+            format!("{first}e{sign}{}", e.abs())
+        } else {
+            format!("{first}.{rest}e{sign}{}", e.abs())
+        }
+    }
+}
+
+fn write_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    struct JcsVector {
+        name: &'static str,
+        input: &'static str,
+        canonical: &'static str,
+        sha256: &'static str,
+    }
+
+    // Vecteurs croisés (docs/v2/V2_HO_JCS_V1_SPEC.md) : `canonical` et `sha256`
+    // frappés HORS Rust, par `JSON.stringify(canonicalize(JSON.parse(input)))`
+    // (Node, `canonicalize` de src/main.js), SAUF `rfc8785_tri_utf16` : un objet
+    // JS énumère d'abord les clés entières (`"1"` avant `"\r"`), la valeur est
+    // donc celle de RFC 8785 §3.2.3. Entrée de `rfc8785_nombres_objet` en forme
+    // la plus courte (`333333333.3333333`, cf. garde-fou parseur en tête de
+    // module). NE PAS régénérer depuis Rust.
+    const VECTORS: &[JcsVector] = &[
+    JcsVector {
+        name: "entiers",
+        input: "[0, -0, 1, -1, 9007199254740991, -9007199254740991]",
+        canonical: "[0,0,1,-1,9007199254740991,-9007199254740991]",
+        sha256: "988269163715a2c527532fd0b9694d6a4256b8dca6c3cf3c31862f8c765d13c6",
+    },
+    JcsVector {
+        name: "flottants_es",
+        input: "[4.50, 2e-3, 0.000001, 1e-7, 1e20, 1e21, 1E+30, -1.5e-9, 5.0, 100.0]",
+        canonical: "[4.5,0.002,0.000001,1e-7,100000000000000000000,1e+21,1e+30,-1.5e-9,5,100]",
+        sha256: "b59b9dc83dd1540b3f690efe67d8ed5f06bc636194b0f48cdc738d4c28d6346c",
+    },
+    JcsVector {
+        name: "flottants_17_chiffres",
+        input: "[0.30000000000000004, 0.3333333333333333, 333333333.3333333, 1.5e20]",
+        canonical: "[0.30000000000000004,0.3333333333333333,333333333.3333333,150000000000000000000]",
+        sha256: "96aa044aaeca06ddb21cf67cb3988f7fcd3bf018318b078524ddd154f1fa4520",
+    },
+    JcsVector {
+        name: "flottants_extremes",
+        input: "[1.7976931348623157e308, 5e-324, 2.2250738585072014e-308, -1.7976931348623157e308]",
+        canonical: "[1.7976931348623157e+308,5e-324,2.2250738585072014e-308,-1.7976931348623157e+308]",
+        sha256: "b6a5edb1de5e46c6be166c2d9e23312075e49703998d4ac745a80820aa952abc",
+    },
+    JcsVector {
+        name: "rfc8785_chaines",
+        input: "[\"\\u20ac$\\u000F\\u000aA'\\u0042\\u0022\\u005c\\\\\\\"\\/\"]",
+        canonical: "[\"€$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"]",
+        sha256: "23247fdde115ba9e94cc104ce7d0c5df2f563bb1c8dc765f145d1b75daac98ed",
+    },
+    JcsVector {
+        name: "echappements_controle",
+        input: "[\"\\u0000\\u0001\\u0008\\u0009\\u000a\\u000b\\u000c\\u000d\\u001f\\u007f\\u2028\\u2029\"]",
+        canonical: "[\"\\u0000\\u0001\\b\\t\\n\\u000b\\f\\r\\u001f\u{7f}\u{2028}\u{2029}\"]",
+        sha256: "8dcf4e0ed847006c60ea48cc5225dd95fb1da3478628dd51068830e4360ed1e5",
+    },
+    JcsVector {
+        name: "rfc8785_tri_utf16",
+        input: "{\"\\u20ac\":\"Euro Sign\",\"\\r\":\"Carriage Return\",\"\\ufb33\":\"Hebrew Letter Dalet With Dagesh\",\"1\":\"One\",\"\\ud83d\\ude00\":\"Emoji: Grinning Face\",\"\\u0080\":\"Control\",\"\\u00f6\":\"Latin Small Letter O With Diaeresis\"}",
+        canonical: "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",\"ö\":\"Latin Small Letter O With Diaeresis\",\"€\":\"Euro Sign\",\"😀\":\"Emoji: Grinning Face\",\"דּ\":\"Hebrew Letter Dalet With Dagesh\"}",
+        sha256: "5e321556d22018a9656991a9e94f77ec175fa193e52a2429d312f8419ec8b08c",
+    },
+    JcsVector {
+        name: "rfc8785_nombres_objet",
+        input: "{\"numbers\":[333333333.3333333,1E30,4.50,2e-3,0.000000000000000000000000001],\"string\":\"\\u20ac$\\u000F\\u000aA'\\u0042\\u0022\\u005c\\\\\\\"\\/\",\"literals\":[null,true,false]}",
+        canonical: "{\"literals\":[null,true,false],\"numbers\":[333333333.3333333,1e+30,4.5,0.002,1e-27],\"string\":\"€$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"}",
+        sha256: "2d5e01a318d0f0879ab568c4be289c8b1f64ef8921a53c6277d5e069978baacb",
+    },
+    JcsVector {
+        name: "bloc_engine",
+        input: "{\"engine\":{\"scp_score\":72.5,\"rhythm_variance\":0.1,\"burst_ratio\":1e-7,\"whole\":5.0,\"active_ms\":120000,\"nested\":{\"b\":[1.0,2.25],\"a\":null}}}",
+        canonical: "{\"engine\":{\"active_ms\":120000,\"burst_ratio\":1e-7,\"nested\":{\"a\":null,\"b\":[1,2.25]},\"rhythm_variance\":0.1,\"scp_score\":72.5,\"whole\":5}}",
+        sha256: "0a733f1f67e1299a220968556b8b302e65a886e5f69308c824fcfe01bcd2c7e7",
+    },
+    ];
+
+    fn canon(input: &str) -> Result<String, String> {
+        let value: Value = serde_json::from_str(input).unwrap();
+        Ok(String::from_utf8(jcs_bytes(&value)?).unwrap())
+    }
+
+    #[test]
+    fn test_1_vecteurs_croises() {
+        for v in VECTORS {
+            let got = canon(v.input).unwrap();
+            assert_eq!(got, v.canonical, "vecteur {}", v.name);
+            assert_eq!(
+                format!("{:x}", Sha256::digest(got.as_bytes())),
+                v.sha256,
+                "vecteur {}",
+                v.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_2_idempotent() {
+        for v in VECTORS {
+            assert_eq!(
+                canon(v.canonical).unwrap(),
+                v.canonical,
+                "vecteur {}",
+                v.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_3_nombres_format_ecmascript() {
+        for (x, attendu) in [
+            (-0.0, "0"),
+            (1.0, "1"),
+            (0.5, "0.5"),
+            (123e-20, "1.23e-18"),
+            (1e21, "1e+21"),
+            (999999999999999900000.0, "999999999999999900000"),
+            (0.000001234, "0.000001234"),
+            (0.0000001234, "1.234e-7"),
+            (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+        ] {
+            assert_eq!(es_number(x), attendu);
+        }
+    }
+
+    #[test]
+    fn test_4_entier_hors_plage_refuse() {
+        assert!(canon("9007199254740992").is_err());
+        assert!(canon("-9007199254740992").is_err());
+        assert!(canon("[1, {\"a\": 18446744073709551615}]").is_err());
+        assert_eq!(canon("4503599627370496.0").unwrap(), "4503599627370496");
+    }
+
+    #[test]
+    fn test_5_differe_de_ho_canon_v1_sur_les_flottants() {
+        // Même valeur, octets différents : d'où un schéma distinct.
+        let value: Value = serde_json::from_str(r#"{"b":5.0,"a":1e-7}"#).unwrap();
+        let ho = crate::work_period::canonical_bytes_excluding(&value, &[]).unwrap();
+        assert_eq!(String::from_utf8(ho).unwrap(), r#"{"a":1e-7,"b":5.0}"#);
+        assert_eq!(
+            String::from_utf8(jcs_bytes(&value).unwrap()).unwrap(),
+            r#"{"a":1e-7,"b":5}"#
+        );
+    }
+}
@@ -3,14 +3,14 @@
     windows_subsystem = "windows"
 )]
 
+mod pdf_signatures;
+mod pdfa;
 mod publication_core;
 
 use chrono::Utc;
 use device_query::{DeviceQuery, DeviceState, Keycode};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::fs::File;
-use std::io::{BufReader, Read};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -20,8 +20,6 @@ use std::time::Duration;
 use tauri::{Manager, State};
 use uuid::Uuid;
 
-use sha2::{Digest, Sha256};
-
 use base64::{engine::general_purpose, Engine as _};
 use ed25519_dalek::{Signer, SigningKey};
 use rand_core::OsRng;
@@ -33,18 +31,34 @@ use resvg::usvg::{Options, Tree};
 #[cfg(target_os = "macos")]
 use macos_accessibility_client::accessibility;
 
+mod audio_fingerprint;
 mod drafts;
-mod evidence_kernel; // V2-M2 : vocabulaire media-agnostic read-only, non câblé au runtime.
+mod docx_metrics;
+mod document_set;
+mod file_chunks;
+mod file_hash;
+mod image_fingerprint;
+mod jcs;
+mod repo_tree;
+mod evidence_kernel; // V2-M2 : vocabulaire media-agnostic ; seul ChunkedFingerprint est câblé (V2-C).
+mod ho_json_v1; // V2-M4 : enveloppe HO-JSON v1 native + pont certificats Work.
+mod ho_json_v2; // V2-M4 : document HO-JSON V2 (writer/parser), signé clé device.
 #[cfg(test)]
 mod compat_v1; // V2-M4B : golden fixtures V1 (test-only), compat signature HO-JSON.
+mod work_audio;
 mod work_cartouche;
 mod work_certificate;
+mod work_change;
 mod work_commands;
 mod work_engine;
+mod work_image;
 mod work_package;
 mod work_pending;
 mod work_period;
 mod work_publish;
+mod work_repo;
+mod work_snapshot;
+mod work_sidecar;
 mod work_store;
 
 const EXTRA_CARRE_DRAIN: bool = true;
@@ -1285,8 +1299,6 @@ fn stop_work_period(
 fn pick_document_to_bind_windows() -> Result<serde_json::Value, String> {
     #[cfg(target_os = "windows")]
     {
-        use sha2::{Digest, Sha256};
-        
         let output = Command::new("powershell")
             .args([
                 "-NoProfile",
@@ -1297,7 +1309,7 @@ Add-Type -AssemblyName System.Windows.Forms
 $dialog = New-Object System.Windows.Forms.OpenFileDialog
 $dialog.Title = "Choisir le document à certifier avec HumanOrigin"
 $dialog.Multiselect = $false
-$dialog.Filter = "Documents|*.pdf;*.docx;*.doc;*.txt;*.md;*.html;*.htm;*.png;*.jpg;*.jpeg;*.webp;*.gif|Tous les fichiers|*.*"
+$dialog.Filter = "Documents|*.pdf;*.docx;*.doc;*.odt;*.rtf;*.txt;*.md;*.markdown;*.html;*.htm;*.png;*.jpg;*.jpeg;*.webp;*.gif|Tous les fichiers|*.*"
 $result = $dialog.ShowDialog()
 if ($result -eq [System.Windows.Forms.DialogResult]::OK) {
   [Console]::OutputEncoding = [System.Text.Encoding]::UTF8
@@ -1345,19 +1357,7 @@ if ($result -eq [System.Windows.Forms.DialogResult]::OK) {
             _ => "application/octet-stream",
         };
 
-        let mut file = File::open(&path).map_err(|e| format!("open failed: {e}"))?;
-        let mut hasher = Sha256::new();
-        let mut buffer = [0u8; 1024 * 64];
-
-        loop {
-            let n = file.read(&mut buffer).map_err(|e| format!("read failed: {e}"))?;
-            if n == 0 {
-                break;
-            }
-            hasher.update(&buffer[..n]);
-        }
-
-        let sha256 = format!("{:x}", hasher.finalize());
+        let sha256 = file_hash::sha256_file(&path)?;
 
         Ok(serde_json::json!({
             "cancelled": false,
@@ -1377,25 +1377,21 @@ if ($result -eq [System.Windows.Forms.DialogResult]::OK) {
     }
 }
 
-#[tauri::command]
-fn sha256_file(path: String) -> Result<String, String> {
-    let file = File::open(&path).map_err(|e| format!("open failed: {e}"))?;
-    let mut reader = BufReader::new(file);
-
-    let mut hasher = Sha256::new();
-    let mut buf = [0u8; 1024 * 1024];
-
-    loop {
-        let n = reader
-            .read(&mut buf)
-            .map_err(|e| format!("read failed: {e}"))?;
-        if n == 0 {
-            break;
-        }
-        hasher.update(&buf[..n]);
-    }
+/// Exécute un travail bloquant (hachage, rendu, package) hors du thread principal :
+/// en Tauri v1, une commande synchrone y tourne et gèle l'interface.
+pub(crate) async fn run_blocking<T, F>(work: F) -> Result<T, String>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(work)
+        .await
+        .map_err(|e| e.to_string())?
+}
 
-    Ok(format!("{:x}", hasher.finalize()))
+#[tauri::command]
+async fn sha256_file(path: String) -> Result<String, String> {
+    run_blocking(move || file_hash::sha256_file(Path::new(&path))).await
 }
 #[tauri::command]
 fn file_size_bytes(path: String) -> Result<u64, String> {
@@ -1429,38 +1425,8 @@ fn pdf_page_count(path: String) -> Result<u32, String> {
 }
 #[tauri::command]
 fn extract_docx_text_metrics(path: String) -> Result<serde_json::Value, String> {
-    let file = File::open(&path).map_err(|e| e.to_string())?;
-    let reader = BufReader::new(file);
-    let mut archive = zip::ZipArchive::new(reader).map_err(|e| e.to_string())?;
-    let mut xml_content = String::new();
-    {
-        let mut xml_file = archive.by_name("word/document.xml")
-            .map_err(|_| "no_document_xml".to_string())?;
-        xml_file.read_to_string(&mut xml_content).map_err(|e| e.to_string())?;
-    }
-    let mut text = String::with_capacity(xml_content.len() / 4);
-    let mut in_tag = false;
-    for ch in xml_content.chars() {
-        match ch {
-            '<' => { in_tag = true; text.push(' '); }
-            '>' => { in_tag = false; }
-            _ if !in_tag => text.push(ch),
-            _ => {}
-        }
-    }
-    let words: Vec<&str> = text.split_whitespace().collect();
-    let word_count = words.len();
-    let normalized = words.join(" ");
-    let text_length = normalized.chars().count();
-    let mut hasher = Sha256::new();
-    hasher.update(normalized.as_bytes());
-    let text_extract_hash = format!("{:x}", hasher.finalize());
-    Ok(serde_json::json!({
-        "text_length": text_length,
-        "word_count": word_count,
-        "text_extract_hash": text_extract_hash,
-        "extraction_status": "ok"
-    }))
+    let metrics = docx_metrics::extract_docx_metrics(Path::new(&path))?;
+    serde_json::to_value(metrics).map_err(|e| e.to_string())
 }
 #[tauri::command]
 fn copy_file(src_path: String, dest_path: String) -> Result<(), String> {
@@ -2027,6 +1993,11 @@ thread::spawn(move || {
             let handle = app.handle();
             let pending_for_deep_link = pending_deep_link.clone();
 
+            let progress_handle = handle.clone();
+            file_hash::set_progress_sink(move |progress| {
+                let _ = progress_handle.emit_all(file_hash::PROGRESS_EVENT, progress);
+            });
+
             let _ = tauri_plugin_deep_link::register("humanorigin", move |request| {
                 let payload = serde_json::to_value(&request).unwrap_or(serde_json::Value::Null);
 
@@ -2070,15 +2041,23 @@ thread::spawn(move || {
             publish_pdf_native,
             work_commands::resolve_work_for_document,
             work_commands::create_work,
+            work_commands::create_document_set_work,
             work_commands::list_works,
             work_commands::load_work,
             work_commands::archive_work,
+            work_commands::set_work_canonicalization,
             work_commands::get_work_summary,
             work_commands::close_interrupted_observation,
             start_work_period,
             stop_work_period,
             work_publish::create_labeled_work_package,
             work_publish::create_native_labeled_work_package,
+            work_publish::create_native_docx_work_package,
+            work_publish::create_native_image_work_package,
+            work_publish::create_native_audio_work_package,
+            work_publish::create_native_repo_work_package,
+            work_publish::create_sidecar_work_package,
+            ho_json_v1::verify_ho_json_document,
         ])
         .run(tauri::generate_context!())
         .expect("error");
@@ -0,0 +1,531 @@
+//! pdf_signatures — Signatures PDF existantes : détection + estampille incrémentale.
+//!
+//! Estampiller via PDFium réécrit TOUT le fichier : toute signature PDF
+//! existante (contrat e-signé, etc.) est invalidée. Ce module :
+//! (1) détecte les champs de signature SIGNÉS de la source (AcroForm `/FT /Sig`
+//!     avec `/V`) et la permission DocMDP éventuelle ;
+//! (2) ajoute la cartouche en MISE À JOUR INCRÉMENTALE : les octets source sont
+//!     recopiés TELS QUELS, puis une révision est ajoutée (annotation Stamp avec
+//!     apparence + lien de vérification). Les `/ByteRange` signés restent
+//!     couverts à l'identique : chaque signature reste vérifiable pour sa révision.
+//!
+//! Annotations (et non contenu de page) : c'est ce que DocMDP P=3 autorise.
+//! P=1 / P=2 interdisent toute annotation -> refus explicite par l'appelant
+//! (jamais de signature certifiante cassée en silence).
+//!
+//! Partagé tel quel avec le publisher (`#[path]` depuis `src/publisher`) :
+//! aucune dépendance hors `lopdf` et `pdfa`, tests compris.
+
+use crate::pdfa::{decode_pdf_text, deref, deref_dict, dict_of};
+use lopdf::{dictionary, Document, IncrementalDocument, Object, ObjectId, Stream};
+use std::path::Path;
+
+/// Résultat du scan des signatures de la source.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SignatureScan {
+    /// Noms complets des champs de signature portant une valeur (`/V`).
+    pub(crate) signed_fields: Vec<String>,
+    /// Permission DocMDP (`/Perms /DocMDP`), 1..=3, si signature certifiante.
+    pub(crate) docmdp_permission: Option<i64>,
+}
+
+impl SignatureScan {
+    pub(crate) fn is_signed(&self) -> bool {
+        !self.signed_fields.is_empty()
+    }
+
+    /// DocMDP P=1 (aucune modification) et P=2 (formulaires/signatures seulement)
+    /// interdisent l'ajout d'annotations.
+    pub(crate) fn allows_annotations(&self) -> bool {
+        !matches!(self.docmdp_permission, Some(1) | Some(2))
+    }
+}
+
+// --- DÉTECTION ------------------------------------------------------------------
+
+fn walk_field(
+    doc: &Document,
+    obj: &Object,
+    parent_name: Option<&str>,
+    inherited_ft: Option<&[u8]>,
+    out: &mut Vec<String>,
+    depth: u8,
+) {
+    if depth > 16 {
+        return;
+    }
+    let Some(field) = deref_dict(doc, obj) else {
+        return;
+    };
+    let partial = field
+        .get(b"T")
+        .ok()
+        .and_then(|o| o.as_str().ok())
+        .map(decode_pdf_text);
+    let name = match (parent_name, partial) {
+        (Some(p), Some(t)) => format!("{p}.{t}"),
+        (None, Some(t)) => t,
+        (p, None) => p.unwrap_or_default().to_string(),
+    };
+    let ft = field
+        .get(b"FT")
+        .ok()
+        .and_then(|o| o.as_name().ok())
+        .or(inherited_ft);
+    let has_value = field
+        .get(b"V")
+        .ok()
+        .and_then(|o| deref(doc, o))
+        .map(|v| !v.is_null())
+        .unwrap_or(false);
+    if ft == Some(b"Sig".as_slice()) && has_value {
+        out.push(if name.is_empty() {
+            "<unnamed>".to_string()
+        } else {
+            name.clone()
+        });
+    }
+    if let Some(kids) = field
+        .get(b"Kids")
+        .ok()
+        .and_then(|o| deref(doc, o))
+        .and_then(|o| o.as_array().ok())
+    {
+        for kid in kids {
+            walk_field(
+                doc,
+                kid,
+                Some(&name).filter(|n| !n.is_empty()).map(|n| n.as_str()),
+                ft,
+                out,
+                depth + 1,
+            );
+        }
+    }
+}
+
+fn docmdp_permission(doc: &Document) -> Option<i64> {
+    let catalog = doc.catalog().ok()?;
+    let perms = catalog
+        .get(b"Perms")
+        .ok()
+        .and_then(|o| deref_dict(doc, o))?;
+    let sig = perms.get(b"DocMDP").ok().and_then(|o| deref_dict(doc, o))?;
+    let refs = sig
+        .get(b"Reference")
+        .ok()
+        .and_then(|o| deref(doc, o))
+        .and_then(|o| o.as_array().ok())?;
+    refs.iter()
+        .filter_map(|r| deref_dict(doc, r))
+        .find(|r| r.get(b"TransformMethod").and_then(|o| o.as_name()).ok() == Some(b"DocMDP"))
+        .map(|r| {
+            r.get(b"TransformParams")
+                .ok()
+                .and_then(|o| deref_dict(doc, o))
+                .and_then(|p| p.get(b"P").and_then(|o| o.as_i64()).ok())
+                // ISO 32000-1 §12.8.2.2 : P absent = 2.
+                .unwrap_or(2)
+        })
+}
+
+pub(crate) fn scan_document(doc: &Document) -> SignatureScan {
+    let mut signed_fields = Vec::new();
+    let fields = doc
+        .catalog()
+        .ok()
+        .and_then(|c| c.get(b"AcroForm").ok())
+        .and_then(|o| deref_dict(doc, o))
+        .and_then(|f| f.get(b"Fields").ok())
+        .and_then(|o| deref(doc, o))
+        .and_then(|o| o.as_array().ok());
+    if let Some(fields) = fields {
+        for field in fields {
+            walk_field(doc, field, None, None, &mut signed_fields, 0);
+        }
+    }
+
+    // Repli : dictionnaires /Sig signés hors AcroForm (fichiers mal formés).
+    if signed_fields.is_empty() {
+        let orphans = doc
+            .objects
+            .values()
+            .filter_map(dict_of)
+            .filter(|d| d.type_is(b"Sig") && d.has(b"ByteRange"))
+            .count();
+        signed_fields.extend((1..=orphans).map(|i| format!("<signature {i}>")));
+    }
+
+    SignatureScan {
+        signed_fields,
+        docmdp_permission: docmdp_permission(doc),
+    }
+}
+
+/// Scan de la source. Échec de parsing = `Err` : l'appelant refuse plutôt que
+/// de supposer « aucune signature ».
+pub(crate) fn scan_signatures(path: &Path) -> Result<SignatureScan, String> {
+    let doc =
+        Document::load(path).map_err(|e| format!("Unable to parse PDF for signature scan: {e}"))?;
+    Ok(scan_document(&doc))
+}
+
+// --- ESTAMPILLE INCRÉMENTALE ----------------------------------------------------
+
+/// MediaBox héritée : (x0, y0, largeur, hauteur). Défaut US Letter.
+fn page_box(doc: &Document, page_id: ObjectId) -> (f32, f32, f32, f32) {
+    let mut current = doc.get_dictionary(page_id).ok();
+    let mut depth = 0;
+    while let Some(node) = current {
+        if let Some(arr) = node
+            .get(b"MediaBox")
+            .ok()
+            .and_then(|o| deref(doc, o))
+            .and_then(|o| o.as_array().ok())
+        {
+            let v: Vec<f32> = arr.iter().filter_map(|o| o.as_float().ok()).collect();
+            if v.len() == 4 {
+                return (
+                    v[0].min(v[2]),
+                    v[1].min(v[3]),
+                    (v[2] - v[0]).abs(),
+                    (v[3] - v[1]).abs(),
+                );
+            }
+        }
+        depth += 1;
+        if depth > 32 {
+            break;
+        }
+        current = node.get(b"Parent").ok().and_then(|o| deref_dict(doc, o));
+    }
+    (0.0, 0.0, 612.0, 792.0)
+}
+
+fn add_image_xobject(doc: &mut Document, img: &image::DynamicImage) -> Result<ObjectId, String> {
+    let rgba = img.to_rgba8();
+    let (w, h) = rgba.dimensions();
+    let mut rgb = Vec::with_capacity((w * h * 3) as usize);
+    let mut alpha = Vec::with_capacity((w * h) as usize);
+    for p in rgba.pixels() {
+        rgb.extend_from_slice(&p.0[..3]);
+        alpha.push(p.0[3]);
+    }
+
+    let mut dict = dictionary! {
+        "Type" => "XObject",
+        "Subtype" => "Image",
+        "Width" => w as i64,
+        "Height" => h as i64,
+        "ColorSpace" => "DeviceRGB",
+        "BitsPerComponent" => 8,
+    };
+    if alpha.iter().any(|&a| a != 255) {
+        let mut smask = Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Image",
+                "Width" => w as i64,
+                "Height" => h as i64,
+                "ColorSpace" => "DeviceGray",
+                "BitsPerComponent" => 8,
+            },
+            alpha,
+        );
+        smask.compress().map_err(|e| e.to_string())?;
+        dict.set("SMask", doc.add_object(smask));
+    }
+    let mut stream = Stream::new(dict, rgb);
+    stream.compress().map_err(|e| e.to_string())?;
+    Ok(doc.add_object(stream))
+}
+
+fn append_annotations(
+    inc: &mut IncrementalDocument,
+    page_id: ObjectId,
+    annots: &[ObjectId],
+) -> Result<(), String> {
+    let refs = annots.iter().map(|id| Object::Reference(*id));
+    let existing = inc
+        .get_prev_documents()
+        .get_dictionary(page_id)
+        .map_err(|e| e.to_string())?
+        .get(b"Annots")
+        .ok()
+        .cloned();
+
+    match existing {
+        Some(Object::Reference(array_id)) => {
+            inc.opt_clone_object_to_new_document(array_id)
+                .map_err(|e| e.to_string())?;
+            inc.new_document
+                .get_object_mut(array_id)
+                .and_then(Object::as_array_mut)
+                .map_err(|e| e.to_string())?
+                .extend(refs);
+        }
+        other => {
+            let mut array = match other {
+                Some(Object::Array(a)) => a,
+                _ => Vec::new(),
+            };
+            array.extend(refs);
+            inc.opt_clone_object_to_new_document(page_id)
+                .map_err(|e| e.to_string())?;
+            inc.new_document
+                .get_dictionary_mut(page_id)
+                .map_err(|e| e.to_string())?
+                .set("Annots", array);
+        }
+    }
+    Ok(())
+}
+
+/// Écrit `output` = octets de `source` inchangés + une révision incrémentale
+/// ajoutant, sur chaque page retenue par `placement`, une annotation Stamp
+/// (cartouche) et une annotation Link (`verify_url`).
+///
+/// `placement(index, largeur, hauteur)` renvoie `[x, y, w, h]` relatif à la
+/// MediaBox, ou `None` pour ne pas marquer la page. Retour : pages marquées.
+pub(crate) fn stamp_incremental<F>(
+    source: &Path,
+    output: &Path,
+    cartouche: &image::DynamicImage,
+    verify_url: &str,
+    mut placement: F,
+) -> Result<u32, String>
+where
+    F: FnMut(usize, f32, f32) -> Option<[f32; 4]>,
+{
+    let mut inc = IncrementalDocument::load(source)
+        .map_err(|e| format!("Unable to load PDF for incremental update: {e}"))?;
+    inc.new_document.version = inc.get_prev_documents().version.clone();
+    inc.new_document.trailer.remove(b"XRefStm");
+
+    let pages: Vec<ObjectId> = inc.get_prev_documents().page_iter().collect();
+    let image_id = add_image_xobject(&mut inc.new_document, cartouche)?;
+    let mut pages_marked = 0u32;
+
+    for (index, page_id) in pages.into_iter().enumerate() {
+        let (x0, y0, page_w, page_h) = page_box(inc.get_prev_documents(), page_id);
+        let Some([x, y, w, h]) = placement(index, page_w, page_h) else {
+            continue;
+        };
+        let rect = vec![
+            Object::Real(x0 + x),
+            Object::Real(y0 + y),
+            Object::Real(x0 + x + w),
+            Object::Real(y0 + y + h),
+        ];
+
+        let appearance = Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Form",
+                "BBox" => vec![0.into(), 0.into(), Object::Real(w), Object::Real(h)],
+                "Resources" => dictionary! {
+                    "XObject" => dictionary! { "HOCartouche" => image_id },
+                },
+            },
+            format!("q {w:.3} 0 0 {h:.3} 0 0 cm /HOCartouche Do Q").into_bytes(),
+        );
+        let appearance_id = inc.new_document.add_object(appearance);
+
+        let stamp_id = inc.new_document.add_object(dictionary! {
+            "Type" => "Annot",
+            "Subtype" => "Stamp",
+            "Rect" => rect.clone(),
+            "F" => 4,
+            "P" => page_id,
+            "Contents" => Object::string_literal("HumanOrigin"),
+            "AP" => dictionary! { "N" => appearance_id },
+        });
+        let link_id = inc.new_document.add_object(dictionary! {
+            "Type" => "Annot",
+            "Subtype" => "Link",
+            "Rect" => rect,
+            "F" => 4,
+            "P" => page_id,
+            "Border" => vec![0.into(), 0.into(), 0.into()],
+            "A" => dictionary! {
+                "S" => "URI",
+                "URI" => Object::string_literal(verify_url),
+            },
+        });
+
+        append_annotations(&mut inc, page_id, &[stamp_id, link_id])?;
+        pages_marked += 1;
+    }
+
+    inc.save(output)
+        .map_err(|e| format!("Unable to save incremental update: {e}"))?;
+    Ok(pages_marked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn temp_pdf(tag: &str) -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        std::env::temp_dir().join(format!(
+            "ho_sig_test_{tag}_{}_{}.pdf",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    fn cleanup(paths: &[&Path]) {
+        for p in paths {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    /// PDF d'une page ; `signed` ajoute un champ /Sig signé, `docmdp` une
+    /// signature certifiante avec la permission donnée.
+    fn write_sample_pdf(path: &Path, signed: bool, docmdp: Option<i64>) {
+        let mut doc = Document::with_version("1.7");
+        let pages_id = doc.new_object_id();
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+        });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![Object::Reference(page_id)],
+                "Count" => 1,
+            }),
+        );
+        let mut catalog = dictionary! { "Type" => "Catalog", "Pages" => pages_id };
+        if signed {
+            let mut sig = dictionary! {
+                "Type" => "Sig",
+                "Filter" => "Adobe.PPKLite",
+                "ByteRange" => vec![0.into(), 10.into(), 20.into(), 30.into()],
+                "Contents" => Object::String(vec![0u8; 16], lopdf::StringFormat::Hexadecimal),
+            };
+            if let Some(p) = docmdp {
+                sig.set(
+                    "Reference",
+                    vec![Object::Dictionary(dictionary! {
+                        "Type" => "SigRef",
+                        "TransformMethod" => "DocMDP",
+                        "TransformParams" => dictionary! { "P" => p },
+                    })],
+                );
+            }
+            let sig_id = doc.add_object(sig);
+            let field_id = doc.add_object(dictionary! {
+                "FT" => "Sig",
+                "T" => Object::string_literal("Signature1"),
+                "V" => sig_id,
+            });
+            catalog.set(
+                "AcroForm",
+                dictionary! { "Fields" => vec![Object::Reference(field_id)] },
+            );
+            if docmdp.is_some() {
+                catalog.set("Perms", dictionary! { "DocMDP" => sig_id });
+            }
+        }
+        let catalog_id = doc.add_object(catalog);
+        doc.trailer.set("Root", catalog_id);
+        doc.save(path).unwrap();
+    }
+
+    fn cartouche() -> image::DynamicImage {
+        let mut img = image::RgbaImage::new(8, 12);
+        for (x, _, p) in img.enumerate_pixels_mut() {
+            *p = image::Rgba([20, 40, 80, if x < 4 { 255 } else { 128 }]);
+        }
+        image::DynamicImage::ImageRgba8(img)
+    }
+
+    #[test]
+    fn test_1_pdf_non_signe() {
+        let p = temp_pdf("plain");
+        write_sample_pdf(&p, false, None);
+        let scan = scan_signatures(&p).unwrap();
+        assert!(!scan.is_signed());
+        assert!(scan.allows_annotations());
+        cleanup(&[&p]);
+    }
+
+    #[test]
+    fn test_2_champ_signe_detecte() {
+        let p = temp_pdf("signed");
+        write_sample_pdf(&p, true, None);
+        let scan = scan_signatures(&p).unwrap();
+        assert_eq!(scan.signed_fields, vec!["Signature1".to_string()]);
+        assert_eq!(scan.docmdp_permission, None);
+        assert!(scan.allows_annotations());
+        cleanup(&[&p]);
+    }
+
+    #[test]
+    fn test_3_docmdp_permissions() {
+        for (p_value, allowed) in [(1, false), (2, false), (3, true)] {
+            let p = temp_pdf("mdp");
+            write_sample_pdf(&p, true, Some(p_value));
+            let scan = scan_signatures(&p).unwrap();
+            assert_eq!(scan.docmdp_permission, Some(p_value));
+            assert_eq!(scan.allows_annotations(), allowed);
+            cleanup(&[&p]);
+        }
+    }
+
+    #[test]
+    fn test_4_estampille_incrementale_preserve_octets_source() {
+        let src = temp_pdf("src");
+        let out = temp_pdf("out");
+        write_sample_pdf(&src, true, None);
+        let original = fs::read(&src).unwrap();
+
+        let marked = stamp_incremental(
+            &src,
+            &out,
+            &cartouche(),
+            "https://verify.humanorigin.app/r/abc",
+            |i, w, _h| (i == 0).then_some([w - 100.0, 20.0, 80.0, 120.0]),
+        )
+        .unwrap();
+        assert_eq!(marked, 1);
+
+        // Révision précédente recopiée octet pour octet (ByteRange intacts).
+        let stamped = fs::read(&out).unwrap();
+        assert!(stamped.len() > original.len());
+        assert_eq!(&stamped[..original.len()], original.as_slice());
+
+        // La révision ajoutée est lisible : 2 annotations + signature toujours là.
+        let doc = Document::load(&out).unwrap();
+        let page_id = doc.page_iter().next().unwrap();
+        let annots = doc.get_page_annotations(page_id).unwrap();
+        let subtypes: Vec<&[u8]> = annots
+            .iter()
+            .map(|a| a.get(b"Subtype").unwrap().as_name().unwrap())
+            .collect();
+        assert_eq!(subtypes, vec![b"Stamp".as_slice(), b"Link".as_slice()]);
+        assert_eq!(
+            scan_document(&doc).signed_fields,
+            vec!["Signature1".to_string()]
+        );
+        cleanup(&[&src, &out]);
+    }
+
+    #[test]
+    fn test_5_placement_none_aucune_page_marquee() {
+        let src = temp_pdf("src");
+        let out = temp_pdf("out");
+        write_sample_pdf(&src, false, None);
+        let marked =
+            stamp_incremental(&src, &out, &cartouche(), "https://x.test", |_, _, _| None).unwrap();
+        assert_eq!(marked, 0);
+        cleanup(&[&src, &out]);
+    }
+}
@@ -0,0 +1,1183 @@
+//! pdfa — Sortie PDF/A-2b du PDF labellisé + contrôle de conformité.
+//!
+//! Post-traitement (lopdf) du PDF déjà sauvegardé par PDFium :
+//! en-tête 1.7 + commentaire binaire, OutputIntent sRGB (profil ICC embarqué),
+//! métadonnées XMP `pdfaid:part=2 / conformance=B` alignées sur /Info,
+//! /ID de trailer, annotations imprimables, /Interpolate retiré des images.
+//! /Info et XMP existants sont FUSIONNÉS : `CreationDate` d'origine conservée,
+//! propriétés XMP étrangères gardées ; seules les propriétés PDF/A, dates,
+//! Producer et champs /Info reportés sont réécrits.
+//!
+//! `validate_pdfa2b` est un contrôle STRUCTUREL (sous-ensemble ISO 19005-2
+//! niveau B) : il ne remplace pas un validateur complet, mais refuse tout ce
+//! qu'il sait détecter (polices non embarquées, chiffrement, JavaScript/Launch,
+//! XMP ou OutputIntent absents, DeviceCMYK sans intent CMYK, annotations
+//! masquées ou sans apparence).
+//!
+//! AUCUNE police n'est embarquée a posteriori : ce chemin NE PEUT PAS produire
+//! de PDF/A depuis une source dont une police n'est pas embarquée (ex. les
+//! polices standard 14 du rendu natif `text_pdf` du convertisseur).
+//! `check_pdfa2b_source` le constate sur la SOURCE, avant tout rendu (la
+//! publication échoue en `PDFA_SOURCE_UNSUPPORTED`) ; `convert_to_pdfa2b`
+//! refuse de même, avant toute écriture.
+//!
+//! Partagé tel quel avec le publisher (`#[path]` dans
+//! `src/publisher/src/main.rs`) : une seule implémentation, testée ici.
+
+use lopdf::xref::XrefType;
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, Stream, StringFormat};
+use std::fs;
+use std::path::Path;
+
+const PRODUCER: &str = "HumanOrigin";
+const OUTPUT_CONDITION: &str = "sRGB IEC61966-2.1";
+
+/// Ligne de commentaire binaire exigée juste après l'en-tête (4 octets > 127).
+const BINARY_COMMENT: &[u8] = b"%\xe2\xe3\xcf\xd3\n";
+
+/// Propriétés XMP toujours réécrites (en plus des champs /Info reportés).
+const MANAGED_XMP_PROPS: &[&str] = &[
+    "pdfaid:part",
+    "pdfaid:conformance",
+    "pdfaid:amd",
+    "pdfaid:corr",
+    "pdf:Producer",
+    "xmp:CreateDate",
+    "xmp:ModifyDate",
+];
+
+/// Champs /Info reportés dans le XMP (clé /Info, propriété XMP).
+const INFO_TEXT_FIELDS: &[(&[u8], &str)] = &[
+    (b"Title", "dc:title"),
+    (b"Author", "dc:creator"),
+    (b"Subject", "dc:description"),
+    (b"Keywords", "pdf:Keywords"),
+    (b"Creator", "xmp:CreatorTool"),
+];
+
+// --- PROFIL ICC sRGB ------------------------------------------------------------
+
+/// Profil ICC v2 sRGB minimal (moniteur, PCS XYZ D50, TRC tabulée 1024 points).
+/// Généré en code : aucun fichier binaire embarqué dans le dépôt.
+pub(crate) fn srgb_icc_profile() -> Vec<u8> {
+    fn s15f16(v: f64) -> [u8; 4] {
+        ((v * 65536.0).round() as i32).to_be_bytes()
+    }
+    fn xyz(x: f64, y: f64, z: f64) -> Vec<u8> {
+        let mut t = b"XYZ \0\0\0\0".to_vec();
+        t.extend(s15f16(x));
+        t.extend(s15f16(y));
+        t.extend(s15f16(z));
+        t
+    }
+    fn desc(text: &str) -> Vec<u8> {
+        let mut t = b"desc\0\0\0\0".to_vec();
+        t.extend(((text.len() + 1) as u32).to_be_bytes());
+        t.extend(text.as_bytes());
+        t.push(0);
+        t.extend(0u32.to_be_bytes()); // unicode language code
+        t.extend(0u32.to_be_bytes()); // unicode count
+        t.extend(0u16.to_be_bytes()); // scriptcode code
+        t.push(0); // scriptcode count
+        t.extend([0u8; 67]);
+        t
+    }
+    fn text(s: &str) -> Vec<u8> {
+        let mut t = b"text\0\0\0\0".to_vec();
+        t.extend(s.as_bytes());
+        t.push(0);
+        t
+    }
+    fn curve() -> Vec<u8> {
+        let mut t = b"curv\0\0\0\0".to_vec();
+        t.extend(1024u32.to_be_bytes());
+        for i in 0..1024 {
+            let v = i as f64 / 1023.0;
+            let lin = if v <= 0.04045 {
+                v / 12.92
+            } else {
+                ((v + 0.055) / 1.055).powf(2.4)
+            };
+            t.extend(((lin * 65535.0).round() as u16).to_be_bytes());
+        }
+        t
+    }
+
+    let tags: Vec<(&[u8; 4], Vec<u8>)> = vec![
+        (b"desc", desc(OUTPUT_CONDITION)),
+        (b"cprt", text("No copyright, use freely")),
+        (b"wtpt", xyz(0.9642, 1.0, 0.8249)),
+        (b"rXYZ", xyz(0.4361, 0.2225, 0.0139)),
+        (b"gXYZ", xyz(0.3851, 0.7169, 0.0971)),
+        (b"bXYZ", xyz(0.1431, 0.0606, 0.7141)),
+        (b"rTRC", curve()),
+        (b"gTRC", curve()),
+        (b"bTRC", curve()),
+    ];
+
+    let mut table = Vec::new();
+    let mut data = Vec::new();
+    let data_start = 128 + 4 + 12 * tags.len();
+    for (sig, body) in &tags {
+        while !(data_start + data.len()).is_multiple_of(4) {
+            data.push(0);
+        }
+        table.extend(*sig);
+        table.extend(((data_start + data.len()) as u32).to_be_bytes());
+        table.extend((body.len() as u32).to_be_bytes());
+        data.extend(body);
+    }
+    let total = data_start + data.len();
+
+    let mut p = Vec::with_capacity(total);
+    p.extend((total as u32).to_be_bytes());
+    p.extend([0u8; 4]); // CMM
+    p.extend(0x0210_0000u32.to_be_bytes()); // version 2.1
+    p.extend(b"mntr");
+    p.extend(b"RGB ");
+    p.extend(b"XYZ ");
+    for v in [2024u16, 1, 1, 0, 0, 0] {
+        p.extend(v.to_be_bytes());
+    }
+    p.extend(b"acsp");
+    p.extend([0u8; 4]); // platform
+    p.extend([0u8; 4]); // flags
+    p.extend([0u8; 4]); // manufacturer
+    p.extend([0u8; 4]); // model
+    p.extend([0u8; 8]); // attributes
+    p.extend([0u8; 4]); // rendering intent : perceptual
+    p.extend(s15f16(0.9642));
+    p.extend(s15f16(1.0));
+    p.extend(s15f16(0.8249));
+    p.extend([0u8; 4]); // creator
+    p.extend([0u8; 44]); // ID + réservé
+    p.extend((tags.len() as u32).to_be_bytes());
+    p.extend(table);
+    p.extend(data);
+    p
+}
+
+// --- HELPERS --------------------------------------------------------------------
+
+pub(crate) fn deref<'a>(doc: &'a Document, obj: &'a Object) -> Option<&'a Object> {
+    doc.dereference(obj).ok().map(|(_, o)| o)
+}
+
+pub(crate) fn dict_of(obj: &Object) -> Option<&Dictionary> {
+    match obj {
+        Object::Dictionary(d) => Some(d),
+        Object::Stream(s) => Some(&s.dict),
+        _ => None,
+    }
+}
+
+pub(crate) fn deref_dict<'a>(doc: &'a Document, obj: &'a Object) -> Option<&'a Dictionary> {
+    deref(doc, obj).and_then(dict_of)
+}
+
+/// Chaîne texte PDF -> String (UTF-16BE avec BOM, UTF-8 avec BOM, sinon Latin-1).
+pub(crate) fn decode_pdf_text(bytes: &[u8]) -> String {
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = rest
+            .chunks_exact(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        String::from_utf8_lossy(rest).to_string()
+    } else {
+        bytes.iter().map(|&b| b as char).collect()
+    }
+}
+
+fn encode_pdf_text(s: &str) -> Object {
+    if s.is_ascii() {
+        Object::String(s.as_bytes().to_vec(), StringFormat::Literal)
+    } else {
+        let mut bytes = vec![0xFE, 0xFF];
+        for u in s.encode_utf16() {
+            bytes.extend(u.to_be_bytes());
+        }
+        Object::String(bytes, StringFormat::Hexadecimal)
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn info_text_fields(doc: &Document) -> Vec<(&'static [u8], &'static str, String)> {
+    let Some(info) = doc.trailer.get(b"Info").ok().and_then(|o| deref_dict(doc, o)) else {
+        return vec![];
+    };
+    INFO_TEXT_FIELDS
+        .iter()
+        .filter_map(|(key, prop)| {
+            let raw = info.get(key).ok().and_then(|o| deref(doc, o))?;
+            let text = decode_pdf_text(raw.as_str().ok()?);
+            if text.is_empty() {
+                None
+            } else {
+                Some((*key, *prop, text))
+            }
+        })
+        .collect()
+}
+
+/// Chaîne texte d'une entrée /Info (`None` si absente ou non textuelle).
+fn info_string(doc: &Document, key: &[u8]) -> Option<String> {
+    let info = doc
+        .trailer
+        .get(b"Info")
+        .ok()
+        .and_then(|o| deref_dict(doc, o))?;
+    let raw = info.get(key).ok().and_then(|o| deref(doc, o))?;
+    Some(decode_pdf_text(raw.as_str().ok()?))
+}
+
+/// Date PDF (`D:YYYYMMDDHHmmSSOHH'mm'`, champs de fin facultatifs) -> date XMP
+/// équivalente. `None` si la date n'est pas interprétable.
+fn pdf_date_to_xmp(raw: &str) -> Option<String> {
+    let s = raw.trim().strip_prefix("D:").unwrap_or(raw.trim());
+    let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+    if !(4..=14).contains(&digits) || !digits.is_multiple_of(2) {
+        return None;
+    }
+    let field = |i: usize, default: u32| -> u32 {
+        s.get(i..i + 2)
+            .filter(|_| i + 2 <= digits)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+    let year: i32 = s[..4].parse().ok()?;
+    let (month, day, hour, min, sec) = (
+        field(4, 1),
+        field(6, 1),
+        field(8, 0),
+        field(10, 0),
+        field(12, 0),
+    );
+    chrono::NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(hour, min, sec)?;
+
+    let tz = &s[digits..];
+    let zone = match tz.chars().next() {
+        None => String::new(),
+        Some('Z') => "Z".to_string(),
+        Some(sign @ ('+' | '-')) => {
+            let parts: Vec<&str> = tz[1..].split('\'').filter(|p| !p.is_empty()).collect();
+            let hh: u32 = parts.first()?.parse().ok()?;
+            let mm: u32 = parts.get(1).map_or(Some(0), |m| m.parse().ok())?;
+            if hh > 23 || mm > 59 {
+                return None;
+            }
+            if hh == 0 && mm == 0 {
+                "Z".to_string()
+            } else {
+                format!("{sign}{hh:02}:{mm:02}")
+            }
+        }
+        Some(_) => return None,
+    };
+    Some(format!(
+        "{year:04}-{month:02}-{day:02}T{hour:02}:{min:02}:{sec:02}{zone}"
+    ))
+}
+
+/// Retire la propriété `prop` d'un fragment RDF, en élément (`<prop>…</prop>`,
+/// `<prop/>`) comme en attribut (`prop="…"`).
+fn strip_xmp_property(rdf: &str, prop: &str) -> String {
+    let open = format!("<{prop}");
+    let close = format!("</{prop}>");
+    let mut out = String::with_capacity(rdf.len());
+    let mut rest = rdf;
+    while let Some(i) = rest.find(&open) {
+        let after = &rest[i + open.len()..];
+        if !after.starts_with(|c: char| c == '>' || c == '/' || c.is_whitespace()) {
+            out.push_str(&rest[..i + open.len()]);
+            rest = after;
+            continue;
+        }
+        out.push_str(rest[..i].trim_end_matches([' ', '\t']));
+        let Some(tag_end) = after.find('>') else {
+            rest = "";
+            break;
+        };
+        rest = if after[..tag_end].ends_with('/') {
+            &after[tag_end + 1..]
+        } else {
+            after.find(&close).map_or("", |j| &after[j + close.len()..])
+        };
+    }
+    out.push_str(rest);
+
+    let attr = format!("{prop}=");
+    let mut result = String::with_capacity(out.len());
+    let mut rest = out.as_str();
+    while let Some(i) = rest.find(&attr) {
+        let value = &rest[i + attr.len()..];
+        let quote = value.chars().next().filter(|q| *q == '"' || *q == '\'');
+        let close = quote.and_then(|q| value[1..].find(q));
+        match close {
+            Some(end) if rest[..i].ends_with(char::is_whitespace) => {
+                result.push_str(rest[..i].trim_end());
+                rest = &value[end + 2..];
+            }
+            _ => {
+                result.push_str(&rest[..i + attr.len()]);
+                rest = value;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// XMP existant du catalogue : balise ouvrante `<rdf:RDF …>` et contenu, les
+/// propriétés `managed` retirées. `None` si absent ou illisible.
+fn existing_rdf(doc: &Document, managed: &[&str]) -> Option<(String, String)> {
+    let stream = doc
+        .catalog()
+        .ok()?
+        .get(b"Metadata")
+        .ok()
+        .and_then(|o| deref(doc, o))?
+        .as_stream()
+        .ok()?;
+    let content = stream
+        .decompressed_content()
+        .unwrap_or_else(|_| stream.content.clone());
+    let xmp = String::from_utf8(content).ok()?;
+    let start = xmp.find("<rdf:RDF")?;
+    let open_end = start + xmp[start..].find('>')? + 1;
+    let end = open_end + xmp[open_end..].find("</rdf:RDF>")?;
+    let body = managed
+        .iter()
+        .fold(xmp[open_end..end].to_string(), |body, prop| {
+            strip_xmp_property(&body, prop)
+        });
+    Some((xmp[start..open_end].to_string(), body))
+}
+
+/// Paquet XMP PDF/A-2b. `existing` : RDF d'origine conservé (voir
+/// `existing_rdf`), notre `rdf:Description` ajoutée à la suite.
+fn xmp_packet(
+    fields: &[(&[u8], &str, String)],
+    producer: &str,
+    create_date: &str,
+    modify_date: &str,
+    existing: Option<(String, String)>,
+) -> String {
+    let mut props = String::new();
+    for (_, prop, value) in fields {
+        let v = xml_escape(value);
+        let body = match *prop {
+            "dc:title" | "dc:description" => {
+                format!("<rdf:Alt><rdf:li xml:lang=\"x-default\">{v}</rdf:li></rdf:Alt>")
+            }
+            "dc:creator" => format!("<rdf:Seq><rdf:li>{v}</rdf:li></rdf:Seq>"),
+            _ => v,
+        };
+        props.push_str(&format!("   <{prop}>{body}</{prop}>\n"));
+    }
+    let (rdf_open, preserved) = existing.unwrap_or_else(|| {
+        (
+            "<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">".to_string(),
+            "\n".to_string(),
+        )
+    });
+    format!(
+        "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n\
+<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n\
+ {rdf_open}{preserved}\
+  <rdf:Description rdf:about=\"\"\n\
+    xmlns:pdfaid=\"http://www.aiim.org/pdfa/ns/id/\"\n\
+    xmlns:dc=\"http://purl.org/dc/elements/1.1/\"\n\
+    xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\"\n\
+    xmlns:pdf=\"http://ns.adobe.com/pdf/1.3/\">\n\
+   <pdfaid:part>2</pdfaid:part>\n\
+   <pdfaid:conformance>B</pdfaid:conformance>\n\
+{props}\
+   <pdf:Producer>{producer}</pdf:Producer>\n\
+   <xmp:CreateDate>{create_date}</xmp:CreateDate>\n\
+   <xmp:ModifyDate>{modify_date}</xmp:ModifyDate>\n\
+  </rdf:Description>\n\
+ </rdf:RDF>\n\
+</x:xmpmeta>\n\
+<?xpacket end=\"w\"?>",
+        producer = xml_escape(producer),
+    )
+}
+
+/// Références des annotations de toutes les pages (`/Annots`, directes ou non).
+fn page_annotation_ids(doc: &Document) -> Vec<ObjectId> {
+    let mut ids = Vec::new();
+    for page_id in doc.page_iter() {
+        let Ok(page) = doc.get_dictionary(page_id) else {
+            continue;
+        };
+        let Some(annots) = page.get(b"Annots").ok().and_then(|o| deref(doc, o)) else {
+            continue;
+        };
+        if let Ok(arr) = annots.as_array() {
+            ids.extend(arr.iter().filter_map(|o| o.as_reference().ok()));
+        }
+    }
+    ids
+}
+
+/// Visite récursive des dictionnaires (y compris imbriqués / dict de stream),
+/// sans suivre les références (chaque objet indirect est visité à son tour).
+fn visit_dicts<'a>(obj: &'a Object, f: &mut dyn FnMut(&'a Dictionary)) {
+    match obj {
+        Object::Dictionary(d) => {
+            f(d);
+            d.iter().for_each(|(_, v)| visit_dicts(v, f));
+        }
+        Object::Stream(s) => {
+            f(&s.dict);
+            s.dict.iter().for_each(|(_, v)| visit_dicts(v, f));
+        }
+        Object::Array(a) => a.iter().for_each(|v| visit_dicts(v, f)),
+        _ => {}
+    }
+}
+
+fn font_is_embedded(doc: &Document, font: &Dictionary, depth: u8) -> bool {
+    if depth > 4 {
+        return false;
+    }
+    match font.get(b"Subtype").and_then(|o| o.as_name()) {
+        Ok(b"Type3") => true,
+        Ok(b"Type0") => font
+            .get(b"DescendantFonts")
+            .ok()
+            .and_then(|o| deref(doc, o))
+            .and_then(|o| o.as_array().ok())
+            .and_then(|arr| arr.first())
+            .and_then(|o| deref_dict(doc, o))
+            .map(|d| font_is_embedded(doc, d, depth + 1))
+            .unwrap_or(false),
+        _ => font
+            .get(b"FontDescriptor")
+            .ok()
+            .and_then(|o| deref_dict(doc, o))
+            .map(|fd| fd.has(b"FontFile") || fd.has(b"FontFile2") || fd.has(b"FontFile3"))
+            .unwrap_or(false),
+    }
+}
+
+fn font_name(font: &Dictionary) -> String {
+    font.get(b"BaseFont")
+        .and_then(|o| o.as_name())
+        .map(|n| String::from_utf8_lossy(n).to_string())
+        .unwrap_or_else(|_| "<unnamed>".to_string())
+}
+
+/// Polices non embarquées (noms `/BaseFont`, triés, sans doublon).
+pub(crate) fn unembedded_fonts(doc: &Document) -> Vec<String> {
+    let mut missing = std::collections::BTreeSet::new();
+    for obj in doc.objects.values() {
+        visit_dicts(obj, &mut |d| {
+            if d.type_is(b"Font") && !font_is_embedded(doc, d, 0) {
+                missing.insert(font_name(d));
+            }
+        });
+    }
+    missing.into_iter().collect()
+}
+
+/// Sérialise `doc` en PDF 1.7 avec la ligne `BINARY_COMMENT` après l'en-tête.
+/// lopdf n'écrit que `%PDF-x.y` : la ligne est insérée après coup, et la table
+/// xref (classique, imposée ici) comme `startxref` sont décalées d'autant.
+fn save_with_binary_comment(doc: &mut Document) -> Result<Vec<u8>, String> {
+    doc.version = "1.7".to_string();
+    doc.reference_table.cross_reference_type = XrefType::CrossReferenceTable;
+    doc.trailer.remove(b"Prev");
+    doc.trailer.remove(b"XRefStm");
+    let mut raw = Vec::new();
+    doc.save_to(&mut raw)
+        .map_err(|e| format!("Unable to serialize PDF/A output: {e}"))?;
+    insert_binary_comment(&raw)
+}
+
+fn insert_binary_comment(raw: &[u8]) -> Result<Vec<u8>, String> {
+    let malformed = || "Unexpected PDF layout while writing the PDF/A header".to_string();
+    let header_end = raw.iter().position(|&b| b == b'\n').ok_or_else(malformed)? + 1;
+    let marker = b"startxref\n";
+    let num_start = raw
+        .windows(marker.len())
+        .rposition(|w| w == marker)
+        .ok_or_else(malformed)?
+        + marker.len();
+    let num_end = num_start
+        + raw[num_start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .ok_or_else(malformed)?;
+    let xref_start: usize = std::str::from_utf8(&raw[num_start..num_end])
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .filter(|&n: &usize| n > header_end && raw[n..].starts_with(b"xref\n"))
+        .ok_or_else(malformed)?;
+    let trailer = xref_start
+        + raw[xref_start..]
+            .windows(8)
+            .position(|w| w == b"trailer\n")
+            .ok_or_else(malformed)?;
+    let shift = BINARY_COMMENT.len();
+
+    let mut out = Vec::with_capacity(raw.len() + shift);
+    out.extend_from_slice(&raw[..header_end]);
+    out.extend_from_slice(BINARY_COMMENT);
+    out.extend_from_slice(&raw[header_end..xref_start]);
+    for line in raw[xref_start..trailer].split_inclusive(|&b| b == b'\n') {
+        // Entrée `oooooooooo ggggg n \n` : position décalée, largeur fixe.
+        let offset = (line.len() == 20 && line.ends_with(b" n \n"))
+            .then(|| std::str::from_utf8(&line[..10]).ok()?.parse::<usize>().ok())
+            .flatten();
+        match offset {
+            Some(offset) => {
+                out.extend_from_slice(format!("{:010}", offset + shift).as_bytes());
+                out.extend_from_slice(&line[10..]);
+            }
+            None => out.extend_from_slice(line),
+        }
+    }
+    out.extend_from_slice(&raw[trailer..num_start]);
+    out.extend_from_slice((xref_start + shift).to_string().as_bytes());
+    out.extend_from_slice(&raw[num_end..]);
+    Ok(out)
+}
+
+// --- CONVERSION -----------------------------------------------------------------
+
+/// Ce que la conversion ne sait pas corriger : chiffrement, police non embarquée.
+fn pdfa2b_blockers(doc: &Document) -> Result<(), String> {
+    if doc.is_encrypted() {
+        return Err("Encrypted PDF cannot be converted to PDF/A".to_string());
+    }
+    let missing_fonts = unembedded_fonts(doc);
+    if !missing_fonts.is_empty() {
+        return Err(format!(
+            "PDF/A-2b requires embedded fonts and this conversion never embeds them; \
+             not embedded: {}",
+            missing_fonts.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Contrôle préalable de la SOURCE, avant tout rendu : la cartouche n'ajoute
+/// ni police ni chiffrement, donc une source refusée ici le serait par
+/// `convert_to_pdfa2b` une fois le travail fait. Source illisible par lopdf :
+/// pas de refus ici, la sortie PDFium est de toute façon contrôlée ensuite.
+pub(crate) fn check_pdfa2b_source(path: &Path) -> Result<(), String> {
+    match Document::load(path) {
+        Ok(doc) => pdfa2b_blockers(&doc),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Convertit EN PLACE un PDF en PDF/A-2b (écriture temp + rename).
+/// Ne valide pas : appeler `validate_pdfa2b` ensuite.
+pub(crate) fn convert_to_pdfa2b(path: &Path) -> Result<(), String> {
+    let mut doc = Document::load(path).map_err(|e| format!("Unable to load PDF: {e}"))?;
+    pdfa2b_blockers(&doc)?;
+
+    let now = chrono::Utc::now();
+    let pdf_date = now.format("D:%Y%m%d%H%M%S+00'00'").to_string();
+    let xmp_date = now.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    // Date de création d'origine conservée si interprétable (sinon : maintenant).
+    let (creation_pdf, creation_xmp) = info_string(&doc, b"CreationDate")
+        .and_then(|raw| pdf_date_to_xmp(&raw).map(|xmp| (raw, xmp)))
+        .unwrap_or_else(|| (pdf_date.clone(), xmp_date.clone()));
+
+    // /Info fusionné : entrées existantes gardées, champs texte ré-encodés,
+    // dates + Producer alignés sur le XMP. /Trapped retiré (non reporté).
+    let fields = info_text_fields(&doc);
+    let mut info = doc
+        .trailer
+        .get(b"Info")
+        .ok()
+        .and_then(|o| deref_dict(&doc, o))
+        .cloned()
+        .unwrap_or_default();
+    for (key, _, value) in &fields {
+        info.set(key.to_vec(), encode_pdf_text(value));
+    }
+    info.remove(b"Trapped");
+    info.set("Producer", encode_pdf_text(PRODUCER));
+    info.set("CreationDate", Object::string_literal(creation_pdf));
+    info.set("ModDate", Object::string_literal(pdf_date));
+    let info_id = doc.add_object(info);
+    doc.trailer.set("Info", info_id);
+
+    let mut managed: Vec<&str> = MANAGED_XMP_PROPS.to_vec();
+    managed.extend(fields.iter().map(|(_, prop, _)| *prop));
+    let existing = existing_rdf(&doc, &managed);
+
+    // XMP non compressé (Filter interdit sur le flux de métadonnées).
+    let xmp = xmp_packet(&fields, PRODUCER, &creation_xmp, &xmp_date, existing);
+    let metadata = Stream::new(
+        dictionary! { "Type" => "Metadata", "Subtype" => "XML" },
+        xmp.into_bytes(),
+    )
+    .with_compression(false);
+    let metadata_id = doc.add_object(metadata);
+
+    let icc = Stream::new(dictionary! { "N" => 3 }, srgb_icc_profile());
+    let icc_id = doc.add_object(icc);
+    let intent_id = doc.add_object(dictionary! {
+        "Type" => "OutputIntent",
+        "S" => "GTS_PDFA1",
+        "OutputConditionIdentifier" => Object::string_literal(OUTPUT_CONDITION),
+        "Info" => Object::string_literal(OUTPUT_CONDITION),
+        "DestOutputProfile" => icc_id,
+    });
+
+    {
+        let catalog = doc
+            .catalog_mut()
+            .map_err(|e| format!("PDF catalog not found: {e}"))?;
+        catalog.set("Metadata", metadata_id);
+        catalog.set("OutputIntents", vec![Object::Reference(intent_id)]);
+    }
+
+    if !doc.trailer.has(b"ID") {
+        use std::hash::{Hash, Hasher};
+        let mut h = std::collections::hash_map::DefaultHasher::new();
+        now.timestamp_nanos_opt().hash(&mut h);
+        path.hash(&mut h);
+        let a = h.finish().to_be_bytes();
+        PRODUCER.hash(&mut h);
+        let b = h.finish().to_be_bytes();
+        let id: Vec<u8> = a.iter().chain(b.iter()).copied().collect();
+        doc.trailer.set(
+            "ID",
+            vec![
+                Object::String(id.clone(), StringFormat::Hexadecimal),
+                Object::String(id, StringFormat::Hexadecimal),
+            ],
+        );
+    }
+
+    // Annotations : Print obligatoire, Hidden/Invisible/NoView interdits.
+    for id in page_annotation_ids(&doc) {
+        if let Ok(annot) = doc.get_dictionary_mut(id) {
+            let flags = annot.get(b"F").and_then(|o| o.as_i64()).unwrap_or(0);
+            annot.set("F", (flags | 4) & !(1 | 2 | 32));
+        }
+    }
+
+    // /Interpolate true interdit sur les images.
+    for obj in doc.objects.values_mut() {
+        if let Object::Stream(s) = obj {
+            if let Ok(b"Image") = s.dict.get(b"Subtype").and_then(|o| o.as_name()) {
+                s.dict.remove(b"Interpolate");
+            }
+        }
+    }
+
+    // Anciens /Info et XMP remplacés : plus référencés, retirés du fichier.
+    doc.prune_objects();
+    let bytes = save_with_binary_comment(&mut doc)?;
+    let tmp = path.with_extension("pdfa.tmp");
+    fs::write(&tmp, bytes).map_err(|e| format!("Unable to save PDF/A output: {e}"))?;
+    fs::rename(&tmp, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Unable to replace PDF with PDF/A output: {e}")
+    })
+}
+
+// --- VALIDATION -----------------------------------------------------------------
+
+fn check_header(bytes: &[u8], issues: &mut Vec<String>) {
+    let ok_version = bytes.len() > 8
+        && bytes.starts_with(b"%PDF-1.")
+        && (b'0'..=b'7').contains(&bytes[7]);
+    if !ok_version {
+        issues.push("header must declare PDF 1.0 to 1.7".to_string());
+        return;
+    }
+    let second = bytes
+        .iter()
+        .position(|&b| b == b'\n' || b == b'\r')
+        .map(|i| {
+            let rest = &bytes[i..];
+            let start = rest.iter().position(|&b| b != b'\n' && b != b'\r').unwrap_or(0);
+            let rest = &rest[start..];
+            let end = rest
+                .iter()
+                .position(|&b| b == b'\n' || b == b'\r')
+                .unwrap_or(rest.len());
+            &rest[..end]
+        })
+        .unwrap_or(&[]);
+    let high = second.iter().filter(|&&b| b > 127).count();
+    if !second.starts_with(b"%") || high < 4 {
+        issues.push("binary comment line missing after header".to_string());
+    }
+}
+
+/// Espace couleur de l'OutputIntent PDF/A (`RGB `, `CMYK`, `GRAY`) si présent et valide.
+fn check_output_intent(doc: &Document, catalog: &Dictionary, issues: &mut Vec<String>) -> Option<[u8; 4]> {
+    let intents = catalog
+        .get(b"OutputIntents")
+        .ok()
+        .and_then(|o| deref(doc, o))
+        .and_then(|o| o.as_array().ok());
+    let Some(intents) = intents else {
+        issues.push("OutputIntents missing".to_string());
+        return None;
+    };
+    for intent in intents.iter().filter_map(|o| deref_dict(doc, o)) {
+        if intent.get(b"S").and_then(|o| o.as_name()).ok() != Some(b"GTS_PDFA1") {
+            continue;
+        }
+        let profile = intent
+            .get(b"DestOutputProfile")
+            .ok()
+            .and_then(|o| deref(doc, o))
+            .and_then(|o| o.as_stream().ok());
+        let Some(profile) = profile else {
+            issues.push("GTS_PDFA1 OutputIntent without DestOutputProfile".to_string());
+            return None;
+        };
+        let content = profile
+            .decompressed_content()
+            .unwrap_or_else(|_| profile.content.clone());
+        if content.len() < 132 || &content[36..40] != b"acsp" {
+            issues.push("DestOutputProfile is not an ICC profile".to_string());
+            return None;
+        }
+        let cs = [content[16], content[17], content[18], content[19]];
+        let expected_n = match &cs {
+            b"RGB " => 3,
+            b"CMYK" => 4,
+            b"GRAY" => 1,
+            _ => {
+                issues.push("DestOutputProfile color space unsupported".to_string());
+                return None;
+            }
+        };
+        let n = profile.dict.get(b"N").and_then(|o| o.as_i64()).unwrap_or(0);
+        if n != expected_n {
+            issues.push("DestOutputProfile /N does not match ICC color space".to_string());
+        }
+        return Some(cs);
+    }
+    issues.push("no GTS_PDFA1 OutputIntent".to_string());
+    None
+}
+
+fn check_metadata(doc: &Document, catalog: &Dictionary, issues: &mut Vec<String>) {
+    let stream = catalog
+        .get(b"Metadata")
+        .ok()
+        .and_then(|o| deref(doc, o))
+        .and_then(|o| o.as_stream().ok());
+    let Some(stream) = stream else {
+        issues.push("XMP metadata stream missing".to_string());
+        return;
+    };
+    if stream.dict.has(b"Filter") {
+        issues.push("XMP metadata stream must not be filtered".to_string());
+    }
+    let xmp = String::from_utf8_lossy(&stream.content);
+    let part = xmp.contains("<pdfaid:part>2</pdfaid:part>") || xmp.contains("pdfaid:part=\"2\"");
+    let conf = xmp.contains("<pdfaid:conformance>B</pdfaid:conformance>")
+        || xmp.contains("pdfaid:conformance=\"B\"");
+    if !part || !conf {
+        issues.push("XMP does not identify PDF/A-2b (pdfaid:part=2, conformance=B)".to_string());
+    }
+    for (key, prop, value) in info_text_fields(doc) {
+        if !xmp.contains(&xml_escape(&value)) {
+            issues.push(format!(
+                "Info /{} not mirrored in XMP {prop}",
+                String::from_utf8_lossy(key)
+            ));
+        }
+    }
+}
+
+/// Contrôle PDF/A-2b (structurel). `Err` liste TOUS les problèmes détectés.
+pub(crate) fn validate_pdfa2b(path: &Path) -> Result<(), Vec<String>> {
+    let bytes = fs::read(path).map_err(|e| vec![format!("Unable to read PDF: {e}")])?;
+    let mut issues = Vec::new();
+
+    check_header(&bytes, &mut issues);
+
+    let doc = Document::load_mem(&bytes).map_err(|e| vec![format!("Unable to parse PDF: {e}")])?;
+
+    if doc.trailer.has(b"Encrypt") {
+        issues.push("encryption is forbidden".to_string());
+    }
+    if !doc.trailer.has(b"ID") {
+        issues.push("trailer /ID missing".to_string());
+    }
+
+    let intent_cs = match doc.catalog() {
+        Ok(catalog) => {
+            check_metadata(&doc, catalog, &mut issues);
+            let cs = check_output_intent(&doc, catalog, &mut issues);
+            if let Some(names) = catalog.get(b"Names").ok().and_then(|o| deref_dict(&doc, o)) {
+                if names.has(b"JavaScript") {
+                    issues.push("document-level JavaScript is forbidden".to_string());
+                }
+                if names.has(b"EmbeddedFiles") {
+                    issues.push("embedded files are not allowed".to_string());
+                }
+            }
+            cs
+        }
+        Err(e) => {
+            issues.push(format!("PDF catalog not found: {e}"));
+            None
+        }
+    };
+
+    let mut forbidden_actions = std::collections::BTreeSet::new();
+    let mut interpolated = false;
+    let mut cmyk_without_intent = false;
+    for obj in doc.objects.values() {
+        visit_dicts(obj, &mut |d| {
+            if let Ok(s) = d.get(b"S").and_then(|o| o.as_name()) {
+                if matches!(s, b"JavaScript" | b"Launch" | b"ImportData" | b"ResetForm") {
+                    forbidden_actions.insert(String::from_utf8_lossy(s).to_string());
+                }
+            }
+            if let Ok(b"Image") = d.get(b"Subtype").and_then(|o| o.as_name()) {
+                if d.get(b"Interpolate").and_then(|o| o.as_bool()).unwrap_or(false) {
+                    interpolated = true;
+                }
+                if let Ok(b"DeviceCMYK") = d.get(b"ColorSpace").and_then(|o| o.as_name()) {
+                    if intent_cs.as_ref() != Some(b"CMYK") {
+                        cmyk_without_intent = true;
+                    }
+                }
+            }
+        });
+    }
+    for name in unembedded_fonts(&doc) {
+        issues.push(format!("font not embedded: {name}"));
+    }
+    for action in forbidden_actions {
+        issues.push(format!("forbidden action: {action}"));
+    }
+    if interpolated {
+        issues.push("image /Interpolate true is forbidden".to_string());
+    }
+    if cmyk_without_intent {
+        issues.push("DeviceCMYK image without CMYK OutputIntent".to_string());
+    }
+
+    for id in page_annotation_ids(&doc) {
+        let Ok(annot) = doc.get_dictionary(id) else {
+            continue;
+        };
+        let subtype = annot
+            .get(b"Subtype")
+            .and_then(|o| o.as_name())
+            .map(|n| String::from_utf8_lossy(n).to_string())
+            .unwrap_or_default();
+        let flags = annot.get(b"F").and_then(|o| o.as_i64()).unwrap_or(0);
+        if flags & 4 == 0 || flags & (1 | 2 | 32) != 0 {
+            issues.push(format!("annotation {subtype} ({} {}) must be printable and visible", id.0, id.1));
+        }
+        if subtype != "Link" && subtype != "Popup" && !annot.has(b"AP") {
+            issues.push(format!("annotation {subtype} ({} {}) has no appearance stream", id.0, id.1));
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn temp_pdf() -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        std::env::temp_dir().join(format!(
+            "ho_pdfa_test_{}_{}.pdf",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    fn cleanup(p: &Path) {
+        let _ = fs::remove_file(p);
+    }
+
+    /// PDF d'une page ; `with_font` ajoute une Helvetica NON embarquée.
+    fn write_sample_pdf(path: &Path, with_font: bool) {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let content = Stream::new(dictionary! {}, b"0 0 1 rg 10 10 50 50 re f".to_vec());
+        let content_id = doc.add_object(content);
+        let mut resources = Dictionary::new();
+        if with_font {
+            let font_id = doc.add_object(dictionary! {
+                "Type" => "Font",
+                "Subtype" => "Type1",
+                "BaseFont" => "Helvetica",
+            });
+            resources.set("Font", dictionary! { "F1" => font_id });
+        }
+        let link_id = doc.add_object(dictionary! {
+            "Type" => "Annot",
+            "Subtype" => "Link",
+            "Rect" => vec![10.into(), 10.into(), 60.into(), 60.into()],
+        });
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => vec![0.into(), 0.into(), 200.into(), 200.into()],
+            "Contents" => content_id,
+            "Resources" => resources,
+            "Annots" => vec![Object::Reference(link_id)],
+        });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![Object::Reference(page_id)],
+                "Count" => 1,
+            }),
+        );
+        let info_id = doc.add_object(dictionary! {
+            "Title" => Object::string_literal("Chapitre <1> & annexes"),
+        });
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+        doc.trailer.set("Info", info_id);
+        doc.save(path).unwrap();
+    }
+
+    #[test]
+    fn test_1_profil_icc_coherent() {
+        let icc = srgb_icc_profile();
+        assert_eq!(u32::from_be_bytes([icc[0], icc[1], icc[2], icc[3]]) as usize, icc.len());
+        assert_eq!(&icc[36..40], b"acsp");
+        assert_eq!(&icc[16..20], b"RGB ");
+    }
+
+    #[test]
+    fn test_2_pdf_brut_non_conforme() {
+        let p = temp_pdf();
+        write_sample_pdf(&p, false);
+        let issues = validate_pdfa2b(&p).unwrap_err();
+        assert!(issues.iter().any(|i| i.contains("XMP")));
+        assert!(issues.iter().any(|i| i.contains("OutputIntents")));
+        cleanup(&p);
+    }
+
+    #[test]
+    fn test_3_conversion_puis_validation_ok() {
+        let p = temp_pdf();
+        write_sample_pdf(&p, false);
+        convert_to_pdfa2b(&p).unwrap();
+        assert_eq!(validate_pdfa2b(&p), Ok(()));
+        // Le titre /Info est reporté (échappé) dans le XMP.
+        let bytes = fs::read(&p).unwrap();
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(text.contains("Chapitre &lt;1&gt; &amp; annexes"));
+        cleanup(&p);
+    }
+
+    #[test]
+    fn test_4_police_non_embarquee_refusee() {
+        let p = temp_pdf();
+        write_sample_pdf(&p, true);
+        let before = fs::read(&p).unwrap();
+        let err = convert_to_pdfa2b(&p).unwrap_err();
+        assert!(err.contains("never embeds"), "{err}");
+        assert!(err.ends_with("not embedded: Helvetica"), "{err}");
+        // Refus avant toute écriture : la source est intacte.
+        assert_eq!(fs::read(&p).unwrap(), before);
+        let issues = validate_pdfa2b(&p).unwrap_err();
+        assert!(issues.contains(&"font not embedded: Helvetica".to_string()));
+        cleanup(&p);
+    }
+
+    #[test]
+    fn test_4b_source_a_polices_non_embarquees_refusee_avant_rendu() {
+        let p = temp_pdf();
+        write_sample_pdf(&p, true);
+        let err = check_pdfa2b_source(&p).unwrap_err();
+        assert!(err.ends_with("not embedded: Helvetica"), "{err}");
+
+        write_sample_pdf(&p, false);
+        assert!(check_pdfa2b_source(&p).is_ok());
+        cleanup(&p);
+    }
+
+    #[test]
+    fn test_5_javascript_refuse() {
+        let p = temp_pdf();
+        write_sample_pdf(&p, false);
+        convert_to_pdfa2b(&p).unwrap();
+        let mut doc = Document::load(&p).unwrap();
+        let js = doc.add_object(dictionary! {
+            "S" => "JavaScript",
+            "JS" => Object::string_literal("app.alert(1)"),
+        });
+        doc.catalog_mut().unwrap().set("OpenAction", js);
+        doc.save(&p).unwrap();
+        let issues = validate_pdfa2b(&p).unwrap_err();
+        assert!(issues.iter().any(|i| i == "forbidden action: JavaScript"));
+        cleanup(&p);
+    }
+
+    /// Chaque entrée xref pointe exactement sur `id gen obj`.
+    fn assert_xref_exact(bytes: &[u8]) {
+        let doc = Document::load_mem(bytes).unwrap();
+        let mut checked = 0;
+        for (id, entry) in &doc.reference_table.entries {
+            if let lopdf::xref::XrefEntry::Normal { offset, generation } = entry {
+                let expected = format!("{id} {generation} obj");
+                assert!(
+                    bytes[*offset as usize..].starts_with(expected.as_bytes()),
+                    "xref {id} -> {offset}"
+                );
+                checked += 1;
+            }
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn test_6_entete_binaire_et_xref_exacts() {
+        let p = temp_pdf();
+        write_sample_pdf(&p, false);
+        // Source en flux xref : la sortie repasse en table classique.
+        let mut doc = Document::load(&p).unwrap();
+        doc.reference_table.cross_reference_type = XrefType::CrossReferenceStream;
+        doc.save(&p).unwrap();
+
+        convert_to_pdfa2b(&p).unwrap();
+        let bytes = fs::read(&p).unwrap();
+        assert!(bytes.starts_with(b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n"));
+        assert_xref_exact(&bytes);
+        assert_eq!(validate_pdfa2b(&p), Ok(()));
+
+        // Reconversion : toujours une seule ligne binaire, offsets exacts.
+        convert_to_pdfa2b(&p).unwrap();
+        let again = fs::read(&p).unwrap();
+        assert!(again.starts_with(b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n1 0 obj"));
+        assert_xref_exact(&again);
+        cleanup(&p);
+    }
+
+    #[test]
+    fn test_7_creation_date_et_xmp_existants_fusionnes() {
+        let p = temp_pdf();
+        write_sample_pdf(&p, false);
+        let mut doc = Document::load(&p).unwrap();
+        let info_id = doc.trailer.get(b"Info").unwrap().as_reference().unwrap();
+        let info = doc.get_dictionary_mut(info_id).unwrap();
+        info.set(
+            "CreationDate",
+            Object::string_literal("D:20190304050607+01'00'"),
+        );
+        info.set("Trapped", Object::Name(b"False".to_vec()));
+        info.set("Department", Object::string_literal("Archives"));
+        let old_xmp = "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n\
+<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n\
+ <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n\
+  <rdf:Description rdf:about=\"\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\"\n\
+    xmlns:xmpRights=\"http://ns.adobe.com/xap/1.0/rights/\"\n\
+    xmlns:pdf=\"http://ns.adobe.com/pdf/1.3/\"\n\
+    xmp:CreateDate=\"2001-01-01T00:00:00Z\" xmpRights:Marked=\"True\">\n\
+   <pdf:Producer>Old Producer</pdf:Producer>\n\
+   <pdf:ProducerNote>kept</pdf:ProducerNote>\n\
+   <xmpRights:Owner><rdf:Bag><rdf:li>Studio</rdf:li></rdf:Bag></xmpRights:Owner>\n\
+  </rdf:Description>\n\
+ </rdf:RDF>\n\
+</x:xmpmeta>\n\
+<?xpacket end=\"w\"?>";
+        let xmp_id = doc.add_object(Stream::new(
+            dictionary! { "Type" => "Metadata", "Subtype" => "XML" },
+            old_xmp.as_bytes().to_vec(),
+        ));
+        doc.catalog_mut().unwrap().set("Metadata", xmp_id);
+        doc.save(&p).unwrap();
+
+        convert_to_pdfa2b(&p).unwrap();
+        assert_eq!(validate_pdfa2b(&p), Ok(()));
+
+        let doc = Document::load(&p).unwrap();
+        assert_eq!(
+            info_string(&doc, b"CreationDate").as_deref(),
+            Some("D:20190304050607+01'00'")
+        );
+        assert_eq!(
+            info_string(&doc, b"Department").as_deref(),
+            Some("Archives")
+        );
+        assert_eq!(info_string(&doc, b"Producer").as_deref(), Some(PRODUCER));
+        let info = doc
+            .trailer
+            .get(b"Info")
+            .and_then(|o| doc.dereference(o))
+            .unwrap()
+            .1;
+        assert!(!info.as_dict().unwrap().has(b"Trapped"));
+
+        let catalog = doc.catalog().unwrap();
+        let meta = deref(&doc, catalog.get(b"Metadata").unwrap()).unwrap();
+        let xmp = String::from_utf8(meta.as_stream().unwrap().content.clone()).unwrap();
+        assert!(xmp.contains("<xmp:CreateDate>2019-03-04T05:06:07+01:00</xmp:CreateDate>"));
+        assert!(!xmp.contains("2001-01-01"));
+        assert!(!xmp.contains("Old Producer"));
+        assert_eq!(xmp.matches("<pdf:Producer>").count(), 1);
+        assert!(xmp.contains("xmpRights:Marked=\"True\""));
+        assert!(xmp.contains("<pdf:ProducerNote>kept</pdf:ProducerNote>"));
+        assert!(xmp.contains("<rdf:li>Studio</rdf:li>"));
+        // Un seul flux XMP dans le fichier : l'ancien n'est plus écrit.
+        let bytes = fs::read(&p).unwrap();
+        assert_eq!(bytes.windows(10).filter(|w| w == b"<x:xmpmeta").count(), 1);
+        cleanup(&p);
+    }
+
+    #[test]
+    fn test_8_dates_pdf_vers_xmp() {
+        let cases = [
+            ("D:20190304050607+01'00'", Some("2019-03-04T05:06:07+01:00")),
+            ("D:20190304050607-05'30", Some("2019-03-04T05:06:07-05:30")),
+            ("D:20190304050607Z00'00'", Some("2019-03-04T05:06:07Z")),
+            ("D:20190304050607+00'00'", Some("2019-03-04T05:06:07Z")),
+            ("D:2019", Some("2019-01-01T00:00:00")),
+            ("20190304", Some("2019-03-04T00:00:00")),
+            ("D:20191304", None),
+            ("D:201903", Some("2019-03-01T00:00:00")),
+            ("D:2019030", None),
+            ("D:20190304050607+25'00'", None),
+            ("hier", None),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(pdf_date_to_xmp(raw).as_deref(), expected, "{raw}");
+        }
+    }
+
+    #[test]
+    fn test_9_retrait_propriete_xmp() {
+        let rdf = "<d a:x=\"1\" pdf:Producer='P' b:y=\"2\">\
+<pdf:Producer>P</pdf:Producer><pdf:Producers/><pdf:Producer/>\
+<pdf:Producer lang=\"fr\">Q</pdf:Producer></d>";
+        assert_eq!(
+            strip_xmp_property(rdf, "pdf:Producer"),
+            "<d a:x=\"1\" b:y=\"2\"><pdf:Producers/></d>"
+        );
+    }
+}
@@ -3,19 +3,91 @@ use image::ImageReader;
 use pdfium_render::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::pdf_signatures::SignatureScan;
+
+/// Versions de job acceptées (miroir de `src/publisher/src/job.rs`).
+/// Schéma publié : `src/publisher/schema/publication_job.schema.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobVersion {
+    #[serde(rename = "1.0")]
+    V1_0,
+}
+
+impl JobVersion {
+    pub const SUPPORTED: &'static [&'static str] = &["1.0"];
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "1.0" => Some(JobVersion::V1_0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderMode {
+    CompactCartouche,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PageSelection {
+    First,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Anchor {
+    BottomRight,
+}
+
+/// Conformité du PDF produit. `pdfa_2b` : post-traitement PDF/A-2b puis
+/// contrôle bloquant (`PDFA_VALIDATION_FAILED` si non conforme). Aucune police
+/// n'est embarquée : une source à polices non embarquées (ex. rendu natif du
+/// convertisseur, polices standard 14) ou chiffrée est refusée AVANT tout
+/// rendu, en `PDFA_SOURCE_UNSUPPORTED`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PdfConformance {
+    #[default]
+    Standard,
+    #[serde(rename = "pdfa_2b")]
+    Pdfa2b,
+}
+
+/// Politique si la source porte des signatures PDF (contrat e-signé, etc.).
+/// `refuse` (défaut) : `SOURCE_PDF_SIGNED`. `warn` : réécriture complète,
+/// signatures invalidées + avertissement. `preserve` : mise à jour incrémentale.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignaturePolicy {
+    #[default]
+    Refuse,
+    Warn,
+    Preserve,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct RenderOptions {
-    pub mode: String,
-    pub pages: String,
+    pub mode: RenderMode,
+    pub pages: PageSelection,
     pub first_page_scale: f32,
     pub other_pages_scale: f32,
-    pub anchor: String,
+    pub anchor: Anchor,
     pub margin_pt: f32,
+    #[serde(default)]
+    pub conformance: PdfConformance,
+    #[serde(default)]
+    pub signature_policy: SignaturePolicy,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct PublicationJob {
-    pub job_version: String,
+    pub job_version: JobVersion,
     pub job_type: String,
 
     pub source_pdf_path: String,
@@ -32,6 +104,31 @@ pub struct PublicationJob {
     pub render: RenderOptions,
 }
 
+/// Parse strict d'un job : `job_version` vérifié en premier
+/// (`JOB_VERSION_UNSUPPORTED`), puis désérialisation complète avec enums
+/// fermés et champs inconnus refusés (`JOB_PARSE_FAILED`).
+/// Le code est préfixé au message d'erreur (`CODE: détail`).
+pub fn parse_publication_job(input: serde_json::Value) -> Result<PublicationJob, String> {
+    match input.get("job_version").and_then(|v| v.as_str()) {
+        Some(v) if JobVersion::parse(v).is_some() => {}
+        Some(v) => {
+            return Err(format!(
+                "JOB_VERSION_UNSUPPORTED: Unsupported job version: {v} (supported: {})",
+                JobVersion::SUPPORTED.join(", ")
+            ));
+        }
+        None => {
+            return Err(format!(
+                "JOB_VERSION_UNSUPPORTED: Missing or non-string job_version (supported: {})",
+                JobVersion::SUPPORTED.join(", ")
+            ));
+        }
+    }
+
+    serde_json::from_value(input)
+        .map_err(|e| format!("JOB_PARSE_FAILED: Invalid publication job: {e}"))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublicationResult {
     pub ok: bool,
@@ -124,17 +221,16 @@ fn add_clickable_link_on_page(
     Ok(())
 }
 
-fn render_cartouche_on_page(
-    page: &mut PdfPage,
-    cartouche: &image::DynamicImage,
+/// Géométrie de la cartouche (coin bas-droit), partagée entre le rendu PDFium
+/// et l'estampille incrémentale (`pdf_signatures`).
+fn cartouche_placement(
+    page_w: f32,
+    page_h: f32,
+    (img_w, img_h): (u32, u32),
     scale: f32,
     margin_pt: f32,
     is_first_page: bool,
-) -> Result<CartouchePlacement, PdfiumError> {
-    let page_w = page.width().value;
-    let page_h = page.height().value;
-
-    let (img_w, img_h) = cartouche.dimensions();
+) -> CartouchePlacement {
     let image_ratio = img_h as f32 / img_w as f32;
 
     // Cartouche micro-estampille B4 (0.1.27) : ~44×60 mm, 1re page uniquement.
@@ -181,22 +277,152 @@ fn render_cartouche_on_page(
     let x = (page_w - margin - target_w).max(0.0);
     let y = margin.max(0.0);
 
+    CartouchePlacement {
+        x,
+        y,
+        w: target_w,
+        h: target_h,
+    }
+}
+
+fn render_cartouche_on_page(
+    page: &mut PdfPage,
+    cartouche: &image::DynamicImage,
+    scale: f32,
+    margin_pt: f32,
+    is_first_page: bool,
+) -> Result<CartouchePlacement, PdfiumError> {
+    let placement = cartouche_placement(
+        page.width().value,
+        page.height().value,
+        cartouche.dimensions(),
+        scale,
+        margin_pt,
+        is_first_page,
+    );
+
     page.objects_mut().create_image_object(
-        PdfPoints::new(x),
-        PdfPoints::new(y),
+        PdfPoints::new(placement.x),
+        PdfPoints::new(placement.y),
         cartouche,
-        Some(PdfPoints::new(target_w)),
-        Some(PdfPoints::new(target_h)),
+        Some(PdfPoints::new(placement.w)),
+        Some(PdfPoints::new(placement.h)),
     )?;
 
     page.regenerate_content()?;
 
-    Ok(CartouchePlacement {
-        x,
-        y,
-        w: target_w,
-        h: target_h,
-    })
+    Ok(placement)
+}
+
+fn decode_cartouche(path: &str) -> Result<image::DynamicImage, (&'static str, String)> {
+    match ImageReader::open(path) {
+        Ok(r) => r.decode().map_err(|e| {
+            (
+                "CARTOUCHE_DECODE_FAILED",
+                format!("Unable to decode cartouche PNG: {e}"),
+            )
+        }),
+        Err(e) => Err((
+            "CARTOUCHE_OPEN_FAILED",
+            format!("Unable to open cartouche PNG: {e}"),
+        )),
+    }
+}
+
+/// Politique `preserve` : cartouche ajoutée en mise à jour incrémentale,
+/// sans PDFium, pour que les signatures existantes restent vérifiables.
+fn run_incremental_publication(job: &PublicationJob, scan: &SignatureScan) -> PublicationResult {
+    if job.render.conformance == PdfConformance::Pdfa2b {
+        return PublicationResult::err(
+            "JOB_OPTIONS_CONFLICT",
+            "signature_policy=preserve cannot be combined with conformance=pdfa_2b \
+             (PDF/A conversion rewrites the whole file)",
+        );
+    }
+    if !scan.allows_annotations() {
+        return PublicationResult::err(
+            "SIGNATURE_FORBIDS_STAMP",
+            &format!(
+                "Certifying signature (DocMDP P={}) forbids adding annotations",
+                scan.docmdp_permission.unwrap_or_default()
+            ),
+        );
+    }
+
+    let cartouche = match decode_cartouche(&job.cartouche_png_path) {
+        Ok(img) => img,
+        Err((code, msg)) => return PublicationResult::err(code, &msg),
+    };
+    let margin_pt = if job.render.margin_pt > 0.0 {
+        job.render.margin_pt
+    } else {
+        mm_to_pt(12.0)
+    };
+    let scale = clamp_f32(job.render.first_page_scale, 0.72, 1.55);
+    let dims = cartouche.dimensions();
+
+    // Cartouche premium (0.1.27) : PREMIÈRE PAGE UNIQUEMENT, comme le rendu PDFium.
+    let output = std::path::Path::new(&job.output_pdf_path);
+    let stamped = crate::pdf_signatures::stamp_incremental(
+        std::path::Path::new(&job.source_pdf_path),
+        output,
+        &cartouche,
+        &job.verify_url,
+        |index, page_w, page_h| {
+            if index != 0 {
+                return None;
+            }
+            let p = cartouche_placement(page_w, page_h, dims, scale, margin_pt, true);
+            Some([p.x, p.y, p.w, p.h])
+        },
+    );
+
+    match stamped {
+        Ok(pages_marked) => PublicationResult::ok(
+            job.output_pdf_path.clone(),
+            pages_marked,
+            "lopdf-incremental-core",
+            vec![format!(
+                "incremental update: {} existing signature(s) preserved",
+                scan.signed_fields.len()
+            )],
+        ),
+        Err(e) => {
+            let _ = std::fs::remove_file(output);
+            PublicationResult::err("INCREMENTAL_UPDATE_FAILED", &e)
+        }
+    }
+}
+
+/// Signatures existantes : `Ok(warnings)` pour poursuivre le rendu PDFium,
+/// `Err(result)` pour s'arrêter (refus, ou publication incrémentale déjà
+/// effectuée en politique `preserve`). Seules des signatures effectivement
+/// trouvées bloquent : un source que lopdf ne sait pas lire (mais que PDFium
+/// ouvre) est publié avec un avertissement, comme avant le scan.
+fn apply_signature_policy(job: &PublicationJob) -> Result<Vec<String>, Box<PublicationResult>> {
+    let scan = crate::pdf_signatures::scan_signatures(std::path::Path::new(&job.source_pdf_path));
+    match (scan, job.render.signature_policy) {
+        (Ok(scan), policy) if scan.is_signed() => {
+            let fields = scan.signed_fields.join(", ");
+            match policy {
+                SignaturePolicy::Refuse => Err(Box::new(PublicationResult::err(
+                    "SOURCE_PDF_SIGNED",
+                    &format!(
+                        "Source PDF carries {} signature(s) ({fields}); set \
+                         render.signature_policy to \"warn\" or \"preserve\"",
+                        scan.signed_fields.len()
+                    ),
+                ))),
+                SignaturePolicy::Warn => Ok(vec![format!(
+                    "{} existing signature(s) invalidated by full rewrite: {fields}",
+                    scan.signed_fields.len()
+                )]),
+                SignaturePolicy::Preserve => Err(Box::new(run_incremental_publication(job, &scan))),
+            }
+        }
+        (Ok(_), _) => Ok(vec![]),
+        (Err(e), _) => Ok(vec![format!("signature scan unavailable: {e}")]),
+    }
 }
 
 pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
@@ -207,6 +433,19 @@ pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
         );
     }
 
+    if job.render.conformance == PdfConformance::Pdfa2b {
+        // Refus avant tout rendu : le post-traitement PDF/A n'embarque aucune police.
+        let source = std::path::Path::new(&job.source_pdf_path);
+        if let Err(e) = crate::pdfa::check_pdfa2b_source(source) {
+            return PublicationResult::err("PDFA_SOURCE_UNSUPPORTED", &e);
+        }
+    }
+
+    let mut warnings = match apply_signature_policy(job) {
+        Ok(w) => w,
+        Err(result) => return *result,
+    };
+
     let pdfium = {
         let exe_path = match std::env::current_exe() {
             Ok(p) => p,
@@ -244,22 +483,9 @@ pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
         return PublicationResult::err("EMPTY_PDF", "Source PDF contains no pages");
     }
 
-    let cartouche = match ImageReader::open(&job.cartouche_png_path) {
-        Ok(r) => match r.decode() {
-            Ok(img) => img,
-            Err(e) => {
-                return PublicationResult::err(
-                    "CARTOUCHE_DECODE_FAILED",
-                    &format!("Unable to decode cartouche PNG: {e}"),
-                );
-            }
-        },
-        Err(e) => {
-            return PublicationResult::err(
-                "CARTOUCHE_OPEN_FAILED",
-                &format!("Unable to open cartouche PNG: {e}"),
-            );
-        }
+    let cartouche = match decode_cartouche(&job.cartouche_png_path) {
+        Ok(img) => img,
+        Err((code, msg)) => return PublicationResult::err(code, &msg),
     };
 
     let margin_pt = if job.render.margin_pt > 0.0 {
@@ -268,9 +494,12 @@ pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
         mm_to_pt(12.0)
     };
 
-    let mut warnings = vec![];
     let mut pages_marked = 0u32;
 
+    if job.render.pages == PageSelection::All && page_count > 1 {
+        warnings.push("pages=all: core engine marks the first page only".to_string());
+    }
+
     for index in 0..page_count {
         // Cartouche premium (0.1.27) : PREMIÈRE PAGE UNIQUEMENT.
         if index != 0 {
@@ -325,6 +554,22 @@ pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
         );
     }
 
+    if job.render.conformance == PdfConformance::Pdfa2b {
+        // PDF/A-2b : un fichier non conforme n'est jamais laissé en sortie.
+        let output = std::path::Path::new(&job.output_pdf_path);
+        if let Err(e) = crate::pdfa::convert_to_pdfa2b(output) {
+            let _ = std::fs::remove_file(output);
+            return PublicationResult::err("PDFA_CONVERSION_FAILED", &e);
+        }
+        if let Err(issues) = crate::pdfa::validate_pdfa2b(output) {
+            let _ = std::fs::remove_file(output);
+            return PublicationResult::err(
+                "PDFA_VALIDATION_FAILED",
+                &format!("PDF/A-2b validation failed: {}", issues.join("; ")),
+            );
+        }
+    }
+
     PublicationResult::ok(
         job.output_pdf_path.clone(),
         pages_marked,
@@ -335,10 +580,186 @@ pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
 
 #[tauri::command]
 pub fn publish_pdf_core(input: serde_json::Value) -> Result<serde_json::Value, String> {
-    let job: PublicationJob = serde_json::from_value(input)
-        .map_err(|e| format!("Invalid publication job: {e}"))?;
+    let job = parse_publication_job(input)?;
 
     let result = run_pdf_publication(&job);
 
     serde_json::to_value(result).map_err(|e| format!("Unable to serialize publication result: {e}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_job() -> serde_json::Value {
+        serde_json::json!({
+            "job_version": "1.0",
+            "job_type": "pdf_publication",
+            "source_pdf_path": "/tmp/in.pdf",
+            "output_pdf_path": "/tmp/out.pdf",
+            "cartouche_png_path": "/tmp/c.png",
+            "certificate_json_path": null,
+            "verify_txt_path": null,
+            "certificate_id": "CERT",
+            "verify_url": "https://humanorigin.app/v/CERT",
+            "verdict": "OBSERVED_WORK_CONSISTENT",
+            "render": {
+                "mode": "compact_cartouche",
+                "pages": "first",
+                "first_page_scale": 1.0,
+                "other_pages_scale": 0.85,
+                "anchor": "bottom_right",
+                "margin_pt": 34.0
+            }
+        })
+    }
+
+    #[test]
+    fn test_1_job_valide_parse() {
+        let job = parse_publication_job(valid_job()).unwrap();
+        assert_eq!(job.job_version, JobVersion::V1_0);
+        assert_eq!(job.render.mode, RenderMode::CompactCartouche);
+        assert_eq!(job.render.pages, PageSelection::First);
+        assert_eq!(job.render.anchor, Anchor::BottomRight);
+    }
+
+    #[test]
+    fn test_2_version_inconnue_rejetee() {
+        let mut v = valid_job();
+        v["job_version"] = serde_json::json!("2.0");
+        let err = parse_publication_job(v).unwrap_err();
+        assert!(err.starts_with("JOB_VERSION_UNSUPPORTED:"), "{err}");
+    }
+
+    #[test]
+    fn test_3_version_absente_rejetee() {
+        let mut v = valid_job();
+        v.as_object_mut().unwrap().remove("job_version");
+        let err = parse_publication_job(v).unwrap_err();
+        assert!(err.starts_with("JOB_VERSION_UNSUPPORTED:"), "{err}");
+    }
+
+    #[test]
+    fn test_4_enum_inconnu_rejete() {
+        for (field, bad) in [("mode", "compact_cartouch"), ("pages", "firts"), ("anchor", "top_left")] {
+            let mut v = valid_job();
+            v["render"][field] = serde_json::json!(bad);
+            let err = parse_publication_job(v).unwrap_err();
+            assert!(err.starts_with("JOB_PARSE_FAILED:"), "{field}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_5_champ_inconnu_rejete() {
+        let mut v = valid_job();
+        v["render"]["margn_pt"] = serde_json::json!(10.0);
+        assert!(parse_publication_job(v).is_err());
+
+        let mut v = valid_job();
+        v["verfy_url"] = serde_json::json!("x");
+        assert!(parse_publication_job(v).is_err());
+    }
+
+    #[test]
+    fn test_6_conformance_par_defaut_et_pdfa() {
+        let job = parse_publication_job(valid_job()).unwrap();
+        assert_eq!(job.render.conformance, PdfConformance::Standard);
+
+        let mut v = valid_job();
+        v["render"]["conformance"] = serde_json::json!("pdfa_2b");
+        let job = parse_publication_job(v).unwrap();
+        assert_eq!(job.render.conformance, PdfConformance::Pdfa2b);
+
+        let mut v = valid_job();
+        v["render"]["conformance"] = serde_json::json!("pdfa");
+        assert!(parse_publication_job(v).is_err());
+    }
+
+    #[test]
+    fn test_7_politique_signature() {
+        let job = parse_publication_job(valid_job()).unwrap();
+        assert_eq!(job.render.signature_policy, SignaturePolicy::Refuse);
+
+        for (raw, expected) in [
+            ("refuse", SignaturePolicy::Refuse),
+            ("warn", SignaturePolicy::Warn),
+            ("preserve", SignaturePolicy::Preserve),
+        ] {
+            let mut v = valid_job();
+            v["render"]["signature_policy"] = serde_json::json!(raw);
+            let job = parse_publication_job(v).unwrap();
+            assert_eq!(job.render.signature_policy, expected);
+        }
+
+        let mut v = valid_job();
+        v["render"]["signature_policy"] = serde_json::json!("ignore");
+        assert!(parse_publication_job(v).is_err());
+    }
+
+    #[test]
+    fn test_8_scan_illisible_poursuit_avec_avertissement() {
+        // Source que lopdf ne sait pas parser : aucune signature trouvée, donc
+        // la politique par défaut (`refuse`) ne doit PAS bloquer la publication.
+        let src = std::env::temp_dir().join(format!("ho_pub_scan_{}.pdf", uuid::Uuid::new_v4()));
+        std::fs::write(&src, b"%PDF-1.7\n%garbage sans xref ni trailer\n").unwrap();
+        for policy in ["refuse", "warn", "preserve"] {
+            let mut v = valid_job();
+            v["source_pdf_path"] = serde_json::json!(src.to_string_lossy());
+            v["render"]["signature_policy"] = serde_json::json!(policy);
+            let job = parse_publication_job(v).unwrap();
+            let warnings = apply_signature_policy(&job)
+                .unwrap_or_else(|r| panic!("{policy} : publication bloquée ({r:?})"));
+            assert_eq!(warnings.len(), 1, "{policy}");
+            assert!(
+                warnings[0].starts_with("signature scan unavailable:"),
+                "{warnings:?}"
+            );
+        }
+        let _ = std::fs::remove_file(&src);
+    }
+
+    #[test]
+    fn test_9_pdfa_source_a_polices_non_embarquees_refusee_avant_rendu() {
+        use lopdf::{dictionary, Document, Object};
+        let src = std::env::temp_dir().join(format!("ho_pub_pdfa_{}.pdf", uuid::Uuid::new_v4()));
+        let out = src.with_extension("out.pdf");
+        let mut doc = Document::with_version("1.7");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            "Resources" => dictionary! { "Font" => dictionary! { "F1" => font_id } },
+        });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![Object::Reference(page_id)],
+                "Count" => 1,
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+        doc.save(&src).unwrap();
+
+        let mut v = valid_job();
+        v["source_pdf_path"] = serde_json::json!(src.to_string_lossy());
+        v["output_pdf_path"] = serde_json::json!(out.to_string_lossy());
+        v["render"]["conformance"] = serde_json::json!("pdfa_2b");
+        // Refus avant PDFium : aucune liaison nécessaire, aucune sortie écrite.
+        let result = run_pdf_publication(&parse_publication_job(v).unwrap());
+        assert_eq!(
+            result.error_code.as_deref(),
+            Some("PDFA_SOURCE_UNSUPPORTED")
+        );
+        assert!(result.message.unwrap().ends_with("not embedded: Helvetica"));
+        assert!(!out.exists());
+        let _ = std::fs::remove_file(&src);
+    }
+}
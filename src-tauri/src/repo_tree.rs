@@ -0,0 +1,558 @@
+//! repo_tree — Empreinte déterministe de l'arbre d'un dépôt (V2 code).
+//!
+//! Portée STRICTE : pour un document de type DÉPÔT (dossier contenant `.git`),
+//! parcourir l'arbre de travail en respectant `.gitignore` (à chaque niveau) et
+//! `.git/info/exclude`, hacher chaque fichier retenu (SHA256) et réunir le tout
+//! en une empreinte d'arbre (`REPO_TREE_SCHEME`). Cette empreinte joue le rôle
+//! du « SHA256 du document » : périodes et certificats se lient à un ÉTAT du
+//! dépôt, fichiers non suivis compris, indépendamment de l'historique git.
+//!
+//! Empreinte = SHA256 de `REPO_TREE_SCHEME\n` puis, par chemin (octets, ordre
+//! croissant), `"{type} {sha256} {taille} {chemin}\n"` ; type `f` (fichier),
+//! `x` (exécutable, Unix), `l` (lien symbolique : SHA256 de la cible, jamais
+//! suivi). Chemins relatifs à la racine, séparateur `/`. Dossiers vides ignorés.
+//!
+//! Garde-fous :
+//! - `.git` (dossier ou fichier de worktree) n'est JAMAIS parcouru ;
+//! - un dossier ignoré n'est pas parcouru (règle git : aucune ré-inclusion) ;
+//! - chemin non UTF-8 ou contenant un saut de ligne -> `Err` (jamais d'ambiguïté) ;
+//! - au plus `MAX_TREE_FILES` fichiers (un `node_modules` non ignoré échoue
+//!   franchement au lieu de bloquer le démarrage d'une période).
+//!
+//! Sous-ensemble `.gitignore` pris en charge : `#`, `!`, `/` de tête (ancrage),
+//! `/` final (dossier), `*`, `?`, `**`, classes `[...]`, échappement `\`.
+//! AUCUNE écriture, AUCUN appel à `git`, AUCUNE commande Tauri.
+
+// Fondation (V2 code) : consommé par `work_commands` et `work_repo`.
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+use crate::file_hash;
+
+/// Identifiant du parcours + format des lignes. Tout changement = nouveau schéma.
+pub(crate) const REPO_TREE_SCHEME: &str = "HO-REPO-TREE-SHA256-V1";
+
+/// MIME d'un document de type dépôt (arbre de travail).
+pub(crate) const REPO_MIME: &str = "application/vnd.humanorigin.repo-tree";
+
+/// Nombre maximal de fichiers retenus dans un arbre.
+pub(crate) const MAX_TREE_FILES: usize = 100_000;
+
+const GIT_DIR: &str = ".git";
+const GITIGNORE: &str = ".gitignore";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RepoEntryKind {
+    File,
+    Executable,
+    Symlink,
+}
+
+impl RepoEntryKind {
+    fn code(self) -> char {
+        match self {
+            RepoEntryKind::File => 'f',
+            RepoEntryKind::Executable => 'x',
+            RepoEntryKind::Symlink => 'l',
+        }
+    }
+}
+
+/// Entrée de l'arbre : chemin RELATIF à la racine du dépôt (jamais absolu).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RepoTreeEntry {
+    pub path: String,
+    pub kind: RepoEntryKind,
+    pub sha256: String,
+    pub size_bytes: u64,
+}
+
+/// Liste complète de l'arbre + empreinte : permet à un tiers de recalculer
+/// l'empreinte et de la confronter à un dépôt livré.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RepoTreeListing {
+    pub scheme: String,
+    pub tree_sha256: String,
+    pub file_count: u64,
+    pub total_bytes: u64,
+    pub entries: Vec<RepoTreeEntry>,
+}
+
+/// Vrai si `path` est un dossier de travail git (contient `.git`).
+pub(crate) fn is_repository(path: &Path) -> bool {
+    path.is_dir() && path.join(GIT_DIR).exists()
+}
+
+/// Empreinte d'arbre d'entrées déjà triées par chemin.
+pub(crate) fn tree_sha256(entries: &[RepoTreeEntry]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(REPO_TREE_SCHEME.as_bytes());
+    hasher.update(b"\n");
+    for e in entries {
+        hasher.update(
+            format!(
+                "{} {} {} {}\n",
+                e.kind.code(),
+                e.sha256,
+                e.size_bytes,
+                e.path
+            )
+            .as_bytes(),
+        );
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Parcourt le dépôt `root` et construit sa liste + empreinte.
+pub(crate) fn repo_tree_listing(root: &Path) -> Result<RepoTreeListing, String> {
+    if !is_repository(root) {
+        return Err(format!("Pas un dépôt git : {}", root.display()));
+    }
+    let mut rules = Vec::new();
+    if let Ok(text) = fs::read_to_string(root.join(GIT_DIR).join("info").join("exclude")) {
+        rules.extend(parse_rules(&text, ""));
+    }
+    let mut entries = Vec::new();
+    walk(root, "", &mut rules, &mut entries)?;
+    entries.sort_by(|a, b| a.path.as_bytes().cmp(b.path.as_bytes()));
+    Ok(RepoTreeListing {
+        scheme: REPO_TREE_SCHEME.to_string(),
+        tree_sha256: tree_sha256(&entries),
+        file_count: entries.len() as u64,
+        total_bytes: entries.iter().map(|e| e.size_bytes).sum(),
+        entries,
+    })
+}
+
+/// Empreinte d'arbre + taille totale du dépôt (frontière d'une période).
+pub(crate) fn repo_tree_digest(root: &Path) -> Result<(String, u64), String> {
+    let listing = repo_tree_listing(root)?;
+    Ok((listing.tree_sha256, listing.total_bytes))
+}
+
+/// Vérifie la cohérence interne d'une liste (schéma, ordre, empreinte, totaux).
+pub(crate) fn verify_listing(listing: &RepoTreeListing) -> Result<(), String> {
+    if listing.scheme != REPO_TREE_SCHEME {
+        return Err(format!("schéma d'arbre inattendu : {}", listing.scheme));
+    }
+    if listing
+        .entries
+        .windows(2)
+        .any(|w| w[0].path.as_bytes() >= w[1].path.as_bytes())
+    {
+        return Err("entrées non triées ou dupliquées".to_string());
+    }
+    if listing.file_count != listing.entries.len() as u64
+        || listing.total_bytes != listing.entries.iter().map(|e| e.size_bytes).sum::<u64>()
+    {
+        return Err("totaux incohérents avec les entrées".to_string());
+    }
+    if tree_sha256(&listing.entries) != listing.tree_sha256 {
+        return Err("empreinte d'arbre incohérente avec les entrées".to_string());
+    }
+    Ok(())
+}
+
+// --- PARCOURS -----------------------------------------------------------------
+
+fn walk(
+    dir: &Path,
+    rel_dir: &str,
+    rules: &mut Vec<IgnoreRule>,
+    out: &mut Vec<RepoTreeEntry>,
+) -> Result<(), String> {
+    let rules_before = rules.len();
+    if let Ok(text) = fs::read_to_string(dir.join(GITIGNORE)) {
+        rules.extend(parse_rules(&text, rel_dir));
+    }
+
+    let mut children: Vec<_> = fs::read_dir(dir)
+        .map_err(|e| format!("Dossier illisible : {} ({e})", dir.display()))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+    children.sort_by_key(|e| e.file_name());
+
+    for child in children {
+        let name = child.file_name();
+        let name = name
+            .to_str()
+            .ok_or_else(|| format!("Nom non UTF-8 dans {}", dir.display()))?;
+        if name == GIT_DIR {
+            continue;
+        }
+        if name.contains('\n') {
+            return Err(format!("Nom avec saut de ligne dans {}", dir.display()));
+        }
+        let rel = if rel_dir.is_empty() {
+            name.to_string()
+        } else {
+            format!("{rel_dir}/{name}")
+        };
+        let meta = fs::symlink_metadata(child.path()).map_err(|e| e.to_string())?;
+        let file_type = meta.file_type();
+        if is_ignored(rules, &rel, file_type.is_dir()) {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            walk(&child.path(), &rel, rules, out)?;
+            continue;
+        }
+        let (kind, sha256, size_bytes) = if file_type.is_symlink() {
+            let target = fs::read_link(child.path()).map_err(|e| e.to_string())?;
+            let target = target.to_string_lossy().replace('\\', "/");
+            (
+                RepoEntryKind::Symlink,
+                format!("{:x}", Sha256::digest(target.as_bytes())),
+                target.len() as u64,
+            )
+        } else if file_type.is_file() {
+            let kind = if is_executable(&meta) {
+                RepoEntryKind::Executable
+            } else {
+                RepoEntryKind::File
+            };
+            (kind, file_hash::sha256_file(&child.path())?, meta.len())
+        } else {
+            // Socket, FIFO… : hors contenu du dépôt.
+            continue;
+        };
+        if out.len() >= MAX_TREE_FILES {
+            return Err(format!(
+                "Dépôt trop volumineux (> {MAX_TREE_FILES} fichiers non ignorés)"
+            ));
+        }
+        out.push(RepoTreeEntry {
+            path: rel,
+            kind,
+            sha256,
+            size_bytes,
+        });
+    }
+
+    rules.truncate(rules_before);
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_meta: &fs::Metadata) -> bool {
+    false
+}
+
+// --- RÈGLES .gitignore --------------------------------------------------------
+
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    /// Dossier (relatif à la racine) du `.gitignore` qui porte la règle.
+    base: String,
+    pattern: String,
+    negated: bool,
+    dir_only: bool,
+    /// Motif contenant un `/` : comparé au chemin relatif à `base`, sinon au nom.
+    anchored: bool,
+}
+
+fn parse_rules(text: &str, base: &str) -> Vec<IgnoreRule> {
+    text.lines()
+        .filter_map(|line| parse_rule(line, base))
+        .collect()
+}
+
+fn parse_rule(line: &str, base: &str) -> Option<IgnoreRule> {
+    let mut line = line.trim_end_matches(['\r', ' ', '\t']);
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let negated = line.starts_with('!');
+    // `!` nie la règle ; `\!` / `\#` sont des littéraux échappés.
+    if negated || line.starts_with("\\!") || line.starts_with("\\#") {
+        line = &line[1..];
+    }
+    let dir_only = line.ends_with('/');
+    let line = line.trim_end_matches('/');
+    let anchored = line.contains('/');
+    let pattern = line.trim_start_matches('/');
+    if pattern.is_empty() {
+        return None;
+    }
+    Some(IgnoreRule {
+        base: base.to_string(),
+        pattern: pattern.to_string(),
+        negated,
+        dir_only,
+        anchored,
+    })
+}
+
+/// Dernière règle applicable gagnante (sémantique git).
+fn is_ignored(rules: &[IgnoreRule], rel_path: &str, is_dir: bool) -> bool {
+    let name = rel_path.rsplit('/').next().unwrap_or(rel_path);
+    let mut ignored = false;
+    for rule in rules {
+        if rule.dir_only && !is_dir {
+            continue;
+        }
+        let under_base = if rule.base.is_empty() {
+            Some(rel_path)
+        } else {
+            rel_path
+                .strip_prefix(rule.base.as_str())
+                .and_then(|r| r.strip_prefix('/'))
+        };
+        let Some(rel) = under_base else {
+            continue;
+        };
+        let target = if rule.anchored { rel } else { name };
+        if glob_match(rule.pattern.as_bytes(), target.as_bytes()) {
+            ignored = !rule.negated;
+        }
+    }
+    ignored
+}
+
+/// Glob gitignore : `*`/`?` ne traversent pas `/`, `**` traverse tout et `**/`
+/// peut ne rien consommer.
+fn glob_match(p: &[u8], t: &[u8]) -> bool {
+    match p.first() {
+        None => t.is_empty(),
+        Some(b'*') if p.get(1) == Some(&b'*') => {
+            let rest = &p[2..];
+            if let Some(after) = rest.strip_prefix(b"/") {
+                if glob_match(after, t) {
+                    return true;
+                }
+            }
+            (0..=t.len()).any(|i| glob_match(rest, &t[i..]))
+        }
+        Some(b'*') => {
+            for i in 0..=t.len() {
+                if glob_match(&p[1..], &t[i..]) {
+                    return true;
+                }
+                if i < t.len() && t[i] == b'/' {
+                    break;
+                }
+            }
+            false
+        }
+        Some(b'?') => !t.is_empty() && t[0] != b'/' && glob_match(&p[1..], &t[1..]),
+        Some(b'[') => match match_class(&p[1..], t.first().copied()) {
+            Some((true, consumed)) => glob_match(&p[1 + consumed..], &t[1..]),
+            Some((false, _)) => false,
+            // Classe non fermée : `[` littéral.
+            None => t.first() == Some(&b'[') && glob_match(&p[1..], &t[1..]),
+        },
+        Some(b'\\') if p.len() > 1 => t.first() == Some(&p[1]) && glob_match(&p[2..], &t[1..]),
+        Some(&c) => t.first() == Some(&c) && glob_match(&p[1..], &t[1..]),
+    }
+}
+
+/// Classe `[...]` (après le `[`) : `(correspond, octets consommés jusqu'au `]`
+/// inclus)`, `None` si la classe n'est pas fermée.
+fn match_class(p: &[u8], c: Option<u8>) -> Option<(bool, usize)> {
+    let mut i = 0;
+    let negated = matches!(p.first(), Some(b'!') | Some(b'^'));
+    if negated {
+        i += 1;
+    }
+    let mut matched = false;
+    let mut first = true;
+    while i < p.len() {
+        if p[i] == b']' && !first {
+            let ok = c.is_some_and(|c| c != b'/') && (matched != negated);
+            return Some((ok, i + 1));
+        }
+        first = false;
+        let lo = p[i];
+        if i + 2 < p.len() && p[i + 1] == b'-' && p[i + 2] != b']' {
+            if c.is_some_and(|c| (lo..=p[i + 2]).contains(&c)) {
+                matched = true;
+            }
+            i += 3;
+        } else {
+            if c == Some(lo) {
+                matched = true;
+            }
+            i += 1;
+        }
+    }
+    None
+}
+
+// --- TESTS UNITAIRES ----------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn tmp_repo() -> PathBuf {
+        let d = std::env::temp_dir().join(format!("ho_repo_tree_test_{}", Uuid::new_v4()));
+        fs::create_dir_all(d.join(".git").join("info")).unwrap();
+        fs::write(d.join(".git").join("HEAD"), b"ref: refs/heads/main\n").unwrap();
+        d
+    }
+
+    fn cleanup(p: &Path) {
+        let _ = fs::remove_dir_all(p);
+    }
+
+    fn write(root: &Path, rel: &str, content: &[u8]) {
+        let p = root.join(rel);
+        fs::create_dir_all(p.parent().unwrap()).unwrap();
+        fs::write(p, content).unwrap();
+    }
+
+    fn paths(l: &RepoTreeListing) -> Vec<&str> {
+        l.entries.iter().map(|e| e.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_1_empreinte_deterministe_et_git_exclu() {
+        let root = tmp_repo();
+        write(&root, "src/main.rs", b"fn main() {}\n");
+        write(&root, "README.md", b"# devoir\n");
+        let a = repo_tree_listing(&root).unwrap();
+        let b = repo_tree_listing(&root).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(paths(&a), vec!["README.md", "src/main.rs"]);
+        assert_eq!(a.total_bytes, 22);
+        assert!(verify_listing(&a).is_ok());
+        // Modifier .git ne change pas l'état du dépôt.
+        fs::write(root.join(".git").join("HEAD"), b"ref: refs/heads/other\n").unwrap();
+        assert_eq!(repo_tree_listing(&root).unwrap().tree_sha256, a.tree_sha256);
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_2_modification_renommage_ajout_detectes() {
+        let root = tmp_repo();
+        write(&root, "a.txt", b"un");
+        let h0 = repo_tree_digest(&root).unwrap().0;
+        write(&root, "a.txt", b"deux");
+        let h1 = repo_tree_digest(&root).unwrap().0;
+        fs::rename(root.join("a.txt"), root.join("b.txt")).unwrap();
+        let h2 = repo_tree_digest(&root).unwrap().0;
+        write(&root, "notes/todo.md", b"");
+        let h3 = repo_tree_digest(&root).unwrap().0;
+        let all = [&h0, &h1, &h2, &h3];
+        for (i, x) in all.iter().enumerate() {
+            for y in &all[i + 1..] {
+                assert_ne!(x, y);
+            }
+        }
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_3_gitignore_respecte_a_chaque_niveau() {
+        let root = tmp_repo();
+        write(
+            &root,
+            ".gitignore",
+            b"# build\ntarget/\n*.log\n!keep.log\n/secret.env\n",
+        );
+        write(&root, "target/debug/app", b"bin");
+        write(&root, "run.log", b"x");
+        write(&root, "keep.log", b"y");
+        write(&root, "secret.env", b"TOKEN=1");
+        write(&root, "sub/secret.env", b"ok");
+        write(&root, "sub/.gitignore", b"gen/**\n");
+        write(&root, "sub/gen/deep/x.rs", b"gen");
+        write(&root, "sub/lib.rs", b"lib");
+        write(&root, "other/gen/y.rs", b"hors de sub");
+        fs::write(root.join(".git").join("info").join("exclude"), b"*.tmp\n").unwrap();
+        write(&root, "scratch.tmp", b"z");
+
+        let l = repo_tree_listing(&root).unwrap();
+        assert_eq!(
+            paths(&l),
+            vec![
+                ".gitignore",
+                "keep.log",
+                "other/gen/y.rs",
+                "sub/.gitignore",
+                "sub/lib.rs",
+                "sub/secret.env",
+            ]
+        );
+        // Un fichier ignoré modifié ne change pas l'empreinte.
+        let h = l.tree_sha256.clone();
+        write(&root, "target/debug/app", b"autre binaire");
+        assert_eq!(repo_tree_digest(&root).unwrap().0, h);
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_4_glob_motifs() {
+        assert!(glob_match(b"*.rs", b"main.rs"));
+        assert!(!glob_match(b"*.rs", b"src/main.rs"));
+        assert!(glob_match(b"src/**/*.rs", b"src/main.rs"));
+        assert!(glob_match(b"src/**/*.rs", b"src/a/b/main.rs"));
+        assert!(glob_match(b"**/build", b"build"));
+        assert!(glob_match(b"**/build", b"x/y/build"));
+        assert!(glob_match(b"doc/**", b"doc/a/b"));
+        assert!(glob_match(b"file?.txt", b"file1.txt"));
+        assert!(!glob_match(b"file?.txt", b"file/.txt"));
+        assert!(glob_match(b"[a-c]x", b"bx"));
+        assert!(!glob_match(b"[!a-c]x", b"bx"));
+        assert!(glob_match(b"\\*lit", b"*lit"));
+        assert!(glob_match(b"[x", b"[x"));
+    }
+
+    #[test]
+    fn test_5_liste_alteree_refusee() {
+        let root = tmp_repo();
+        write(&root, "a.txt", b"a");
+        write(&root, "b.txt", b"b");
+        let l = repo_tree_listing(&root).unwrap();
+        let mut sha = l.clone();
+        sha.entries[0].sha256 = "0".repeat(64);
+        assert!(verify_listing(&sha).is_err());
+        let mut order = l.clone();
+        order.entries.swap(0, 1);
+        assert!(verify_listing(&order).is_err());
+        let mut total = l.clone();
+        total.total_bytes += 1;
+        assert!(verify_listing(&total).is_err());
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_6_dossier_sans_git_refuse() {
+        let d = std::env::temp_dir().join(format!("ho_repo_tree_test_{}", Uuid::new_v4()));
+        fs::create_dir_all(&d).unwrap();
+        assert!(!is_repository(&d));
+        assert!(repo_tree_listing(&d).is_err());
+        cleanup(&d);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_7_lien_symbolique_non_suivi_et_executable() {
+        use std::os::unix::fs::PermissionsExt;
+        let root = tmp_repo();
+        write(&root, "run.sh", b"#!/bin/sh\n");
+        fs::set_permissions(root.join("run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink("/etc/passwd", root.join("lien")).unwrap();
+        let l = repo_tree_listing(&root).unwrap();
+        let lien = l.entries.iter().find(|e| e.path == "lien").unwrap();
+        assert_eq!(lien.kind, RepoEntryKind::Symlink);
+        assert_eq!(lien.size_bytes, "/etc/passwd".len() as u64);
+        let run = l.entries.iter().find(|e| e.path == "run.sh").unwrap();
+        assert_eq!(run.kind, RepoEntryKind::Executable);
+        cleanup(&root);
+    }
+}
@@ -0,0 +1,927 @@
+//! work_audio — Adaptateur audio (V2-D) : copie WAV/FLAC/MP3 étiquetée + sidecar.
+//!
+//! Portée STRICTE : à partir d'un fichier audio source certifié, produire dans
+//! le dossier de sortie :
+//! - `labeled_audio.wav|flac|mp3` : copie du fichier dont SEULES les
+//!   métadonnées changent — identifiant de certificat, URL de vérification,
+//!   verdict et SHA256 source écrits dans :
+//!   - WAV : chunk BWF `bext` (Description, Originator, OriginatorReference,
+//!     date/heure du certificat ; champs `ho:` dans CodingHistory) ;
+//!   - FLAC : bloc VORBIS_COMMENT (`HO_CERTIFICATE_ID`, `HO_VERIFY_URL`, …) ;
+//!   - MP3 : tag ID3v2 (trames `TXXX` `HO:CertificateId`, `HO:VerifyUrl`, …) ;
+//! - `labeled_audio.ho.json` : sidecar signé (`work_sidecar`) décrivant la
+//!   copie et l'empreinte du signal de la source (`audio_fingerprint`).
+//!
+//! Le signal n'est JAMAIS touché : chunk `data`, trames FLAC et trames MPEG
+//! sont recopiés octet pour octet, en flux depuis la source (seules les
+//! métadonnées sont chargées en mémoire), et l'empreinte du signal de la copie
+//! doit égaler celle de la source (sinon échec du rendu, copie supprimée). Le
+//! fichier source n'est JAMAIS modifié.
+//!
+//! AUCUNE commande Tauri (voir `work_publish`), AUCUNE UI, AUCUN réseau,
+//! AUCUN chemin local dans la copie ou le sidecar.
+//!
+//! Garde-fous :
+//! - format détecté par signature (magic), pas par extension ;
+//! - WAV : un `bext` existant est remplacé ; valeurs non ASCII refusées (BWF) ;
+//! - FLAC : commentaires `HO_*` existants remplacés, les autres conservés ;
+//! - MP3 : tag ID3v2.3/2.4 existant conservé (trames `TXXX` `HO:*` remplacées) ;
+//!   un tag ID3v2.2, désynchronisé ou à en-tête étendu est remplacé par un tag
+//!   ID3v2.4 neuf (ses trames ne sont pas reprises) ;
+//! - le sidecar est signé par la clé du certificat (sinon échec du rendu).
+
+// Fondation (V2-D) : branché par `work_publish::create_native_audio_package_core`.
+#![allow(dead_code)]
+
+use ed25519_dalek::SigningKey;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::Path;
+
+use crate::audio_fingerprint::{self, read_range, AudioFormat};
+use crate::evidence_kernel::{
+    ArtifactRenderError, DocumentAdapter, DocumentArtifactKind, LabeledArtifactRequest,
+    MediaSpecificMetadata, RenderedArtifactRef,
+};
+use crate::work_sidecar;
+
+const LABELED_WAV_FILENAME: &str = "labeled_audio.wav";
+const LABELED_FLAC_FILENAME: &str = "labeled_audio.flac";
+const LABELED_MP3_FILENAME: &str = "labeled_audio.mp3";
+const SIDECAR_FILENAME: &str = "labeled_audio.ho.json";
+
+const ORIGINATOR: &str = "HumanOrigin";
+const BEXT_FIXED_LEN: usize = 602;
+const FLAC_VORBIS_COMMENT: u8 = 4;
+const FLAC_MAX_BLOCK_LEN: usize = (1 << 24) - 1;
+const ID3_MAX_TAG_LEN: usize = (1 << 28) - 1;
+
+fn artifact_filename(format: AudioFormat) -> &'static str {
+    match format {
+        AudioFormat::Wav => LABELED_WAV_FILENAME,
+        AudioFormat::Flac => LABELED_FLAC_FILENAME,
+        AudioFormat::Mp3 => LABELED_MP3_FILENAME,
+    }
+}
+
+/// Champs écrits dans la copie (clé stable, valeur), dans l'ordre d'écriture.
+fn label_fields(request: &LabeledArtifactRequest) -> [(&'static str, &str); 4] {
+    [
+        ("CertificateId", request.certificate_id.as_str()),
+        ("VerifyUrl", request.verify_url.as_str()),
+        ("Verdict", request.verdict_label.as_str()),
+        (
+            "SourceSha256",
+            request.source_version.fingerprint_sha256.as_str(),
+        ),
+    ]
+}
+
+// --- ADAPTATEUR ---------------------------------------------------------------
+
+/// Adaptateur audio : signe le sidecar avec la clé du certificat.
+pub(crate) struct AudioDocumentAdapter<'a> {
+    signing_key: &'a SigningKey,
+}
+
+impl<'a> AudioDocumentAdapter<'a> {
+    pub(crate) fn new(signing_key: &'a SigningKey) -> Self {
+        Self { signing_key }
+    }
+}
+
+impl DocumentAdapter for AudioDocumentAdapter<'_> {
+    fn adapter_name(&self) -> &'static str {
+        "audio"
+    }
+
+    fn artifact_kind(&self) -> DocumentArtifactKind {
+        DocumentArtifactKind::Audio
+    }
+
+    fn default_artifact_filename(&self) -> &'static str {
+        LABELED_WAV_FILENAME
+    }
+
+    fn render_labeled_artifact(
+        &self,
+        request: &LabeledArtifactRequest,
+        source: &Path,
+        output_dir: &Path,
+    ) -> Result<RenderedArtifactRef, ArtifactRenderError> {
+        let render_err = |e: String| ArtifactRenderError::RenderFailed(e);
+        let mut src = File::open(source).map_err(|e| render_err(e.to_string()))?;
+        let format = audio_fingerprint::detect_audio_stream(&mut src)
+            .map_err(render_err)?
+            .ok_or_else(|| render_err("format audio non supporté (WAV/FLAC/MP3)".to_string()))?;
+        let source_fp = audio_fingerprint::fingerprint_audio(&mut src).map_err(render_err)?;
+
+        let pieces = match format {
+            AudioFormat::Wav => label_wav(&mut src, request),
+            AudioFormat::Flac => label_flac(&mut src, request),
+            AudioFormat::Mp3 => label_mp3(&mut src, request),
+        }
+        .map_err(ArtifactRenderError::MarkingFailed)?;
+
+        let artifact_name = artifact_filename(format);
+        let artifact_path = output_dir.join(artifact_name);
+        let written = File::create(&artifact_path)
+            .map_err(|e| e.to_string())
+            .and_then(|f| {
+                let mut out = BufWriter::new(f);
+                write_pieces(&mut src, &pieces, &mut out)?;
+                let f = out.into_inner().map_err(|e| e.to_string())?;
+                f.sync_all().map_err(|e| e.to_string())
+            })
+            // Le signal de la copie doit être celui de la source, au bit près.
+            .and_then(|_| audio_fingerprint::fingerprint_audio_file(&artifact_path))
+            .and_then(|labeled_fp| {
+                if labeled_fp == source_fp {
+                    Ok(())
+                } else {
+                    Err("signal audio altéré par l'étiquetage".to_string())
+                }
+            });
+        if let Err(e) = written {
+            let _ = fs::remove_file(&artifact_path);
+            return Err(render_err(e));
+        }
+
+        let artifact = RenderedArtifactRef {
+            artifact_name: artifact_name.to_string(),
+            media_type: self.artifact_kind(),
+            fingerprint_sha256: Some(
+                crate::file_hash::sha256_file(&artifact_path).map_err(render_err)?,
+            ),
+            size_bytes: Some(pieces_len(&pieces)),
+            mime_type: Some(format.mime_type().to_string()),
+        };
+
+        // Le sidecar porte l'empreinte du signal de la SOURCE : une copie
+        // ré-étiquetée en aval reste reconnaissable (`audio_fingerprint`).
+        let mut sidecar_request = request.clone();
+        sidecar_request.source_version.media_specific =
+            Some(MediaSpecificMetadata::Audio(source_fp));
+        let sidecar =
+            work_sidecar::sign_sidecar(&sidecar_request, Some(artifact.clone()), self.signing_key)
+                .map_err(ArtifactRenderError::RenderFailed)?;
+        work_sidecar::write_sidecar(&sidecar, &output_dir.join(SIDECAR_FILENAME))
+            .map_err(ArtifactRenderError::RenderFailed)?;
+        Ok(artifact)
+    }
+
+    fn sidecar_filename(&self) -> Option<&'static str> {
+        Some(SIDECAR_FILENAME)
+    }
+}
+
+// --- COPIE EN FLUX ------------------------------------------------------------
+
+/// Morceau de la copie étiquetée : métadonnées neuves, ou étendue recopiée
+/// telle quelle depuis la source (signal, chunks et blocs conservés).
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    New(Vec<u8>),
+    Source(Range<u64>),
+}
+
+fn pieces_len(pieces: &[Piece]) -> u64 {
+    pieces
+        .iter()
+        .map(|p| match p {
+            Piece::New(b) => b.len() as u64,
+            Piece::Source(r) => r.end - r.start,
+        })
+        .sum()
+}
+
+/// Écrit la copie : morceaux neufs + étendues de la source recopiées en flux.
+fn write_pieces<R: Read + Seek, W: Write>(
+    src: &mut R,
+    pieces: &[Piece],
+    out: &mut W,
+) -> Result<(), String> {
+    for piece in pieces {
+        match piece {
+            Piece::New(bytes) => out.write_all(bytes).map_err(|e| e.to_string())?,
+            Piece::Source(range) => {
+                src.seek(SeekFrom::Start(range.start))
+                    .map_err(|e| e.to_string())?;
+                let len = range.end - range.start;
+                let copied =
+                    io::copy(&mut src.by_ref().take(len), out).map_err(|e| e.to_string())?;
+                if copied != len {
+                    return Err("source audio tronquée pendant la copie".to_string());
+                }
+            }
+        }
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+// --- WAV : chunk BWF `bext` ---------------------------------------------------
+
+/// Champ ASCII de longueur fixe, complété par des NUL (tronqué si plus long).
+fn fixed_ascii(out: &mut Vec<u8>, value: &str, len: usize) {
+    let bytes = value.as_bytes();
+    let n = bytes.len().min(len);
+    out.extend_from_slice(&bytes[..n]);
+    out.resize(out.len() + len - n, 0);
+}
+
+/// Corps du chunk `bext` (BWF v2) : 602 octets fixes + CodingHistory.
+fn bext_chunk(request: &LabeledArtifactRequest) -> Result<Vec<u8>, String> {
+    let fields = label_fields(request);
+    if fields.iter().any(|(_, v)| !v.is_ascii()) {
+        return Err("BWF : valeurs non ASCII refusées".to_string());
+    }
+    let (date, time) = request
+        .certificate_created_at
+        .as_deref()
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|d| {
+            let d = d.with_timezone(&chrono::Utc);
+            (
+                d.format("%Y-%m-%d").to_string(),
+                d.format("%H:%M:%S").to_string(),
+            )
+        })
+        .unwrap_or_default();
+
+    let mut b = Vec::with_capacity(BEXT_FIXED_LEN + 256);
+    fixed_ascii(
+        &mut b,
+        &format!("{ORIGINATOR} certificate {}", request.certificate_id),
+        256,
+    );
+    fixed_ascii(&mut b, ORIGINATOR, 32);
+    fixed_ascii(&mut b, &request.certificate_id, 32);
+    fixed_ascii(&mut b, &date, 10);
+    fixed_ascii(&mut b, &time, 8);
+    b.extend_from_slice(&0u64.to_le_bytes()); // TimeReference
+    b.extend_from_slice(&2u16.to_le_bytes()); // Version
+    b.resize(b.len() + 64, 0); // UMID
+    b.resize(b.len() + 10, 0); // Loudness (5 x u16, non renseignés)
+    b.resize(b.len() + 180, 0); // Reserved
+    debug_assert_eq!(b.len(), BEXT_FIXED_LEN);
+    for (key, value) in fields {
+        b.extend_from_slice(format!("ho:{key}={value}\r\n").as_bytes());
+    }
+    Ok(b)
+}
+
+/// Copie WAV : chunk `bext` neuf en tête, autres chunks recopiés tels quels.
+fn label_wav<R: Read + Seek>(
+    src: &mut R,
+    request: &LabeledArtifactRequest,
+) -> Result<Vec<Piece>, String> {
+    let chunks = audio_fingerprint::riff_chunks(src)?;
+    let bext = bext_chunk(request)?;
+    let mut head = Vec::with_capacity(bext.len() + 21);
+    head.extend_from_slice(b"RIFF\0\0\0\0WAVE");
+    head.extend_from_slice(b"bext");
+    head.extend_from_slice(&(bext.len() as u32).to_le_bytes());
+    head.extend_from_slice(&bext);
+    if bext.len() % 2 == 1 {
+        head.push(0);
+    }
+    let mut pieces = vec![Piece::New(head)];
+    for c in chunks.iter().filter(|c| &c.id != b"bext") {
+        pieces.push(Piece::Source(c.span.clone()));
+        if (c.data.end - c.data.start) % 2 == 1 && c.span.end == c.data.end {
+            pieces.push(Piece::New(vec![0])); // bourrage manquant en fin de fichier source
+        }
+    }
+    let riff = u32::try_from(pieces_len(&pieces) - 8).map_err(|_| "WAV > 4 Go".to_string())?;
+    if let Piece::New(head) = &mut pieces[0] {
+        head[4..8].copy_from_slice(&riff.to_le_bytes());
+    }
+    Ok(pieces)
+}
+
+// --- FLAC : bloc VORBIS_COMMENT -----------------------------------------------
+
+fn vorbis_key(key: &str) -> String {
+    let mut out = String::from("HO_");
+    for (i, c) in key.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.push(c.to_ascii_uppercase());
+    }
+    out
+}
+
+/// Commentaires d'un bloc VORBIS_COMMENT : (vendor, commentaires).
+fn parse_vorbis_comment(data: &[u8]) -> Result<(Vec<u8>, Vec<Vec<u8>>), String> {
+    let err = || "bloc VORBIS_COMMENT invalide".to_string();
+    let read_u32 = |at: usize| -> Result<usize, String> {
+        let b = data.get(at..at + 4).ok_or_else(err)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+    };
+    let vendor_len = read_u32(0)?;
+    let vendor = data.get(4..4 + vendor_len).ok_or_else(err)?.to_vec();
+    let mut off = 4 + vendor_len;
+    let count = read_u32(off)?;
+    off += 4;
+    let mut comments = Vec::new();
+    for _ in 0..count {
+        let len = read_u32(off)?;
+        comments.push(data.get(off + 4..off + 4 + len).ok_or_else(err)?.to_vec());
+        off += 4 + len;
+    }
+    Ok((vendor, comments))
+}
+
+fn is_ho_comment(comment: &[u8]) -> bool {
+    comment.len() >= 3 && comment[..3].eq_ignore_ascii_case(b"HO_")
+}
+
+/// Copie FLAC : VORBIS_COMMENT (commentaires `HO_*` remplacés) juste après
+/// STREAMINFO ; autres blocs et trames recopiés tels quels.
+fn label_flac<R: Read + Seek>(
+    src: &mut R,
+    request: &LabeledArtifactRequest,
+) -> Result<Vec<Piece>, String> {
+    let (blocks, audio_start) = audio_fingerprint::flac_metadata_blocks(src)?;
+    let (vendor, mut comments) = match blocks.iter().find(|b| b.kind == FLAC_VORBIS_COMMENT) {
+        Some(b) => parse_vorbis_comment(&read_range(src, b.data.clone())?)?,
+        None => (ORIGINATOR.as_bytes().to_vec(), Vec::new()),
+    };
+    comments.retain(|c| !is_ho_comment(c));
+    for (key, value) in label_fields(request) {
+        comments.push(format!("{}={value}", vorbis_key(key)).into_bytes());
+    }
+    let mut vc = Vec::new();
+    vc.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    vc.extend_from_slice(&vendor);
+    vc.extend_from_slice(&(comments.len() as u32).to_le_bytes());
+    for c in &comments {
+        vc.extend_from_slice(&(c.len() as u32).to_le_bytes());
+        vc.extend_from_slice(c);
+    }
+    if vc.len() > FLAC_MAX_BLOCK_LEN {
+        return Err("bloc VORBIS_COMMENT trop grand".to_string());
+    }
+
+    // (type, longueur, données) dans l'ordre de sortie ; drapeau « dernier »
+    // recalculé. Les blocs repris (image, table de recherche…) restent en flux.
+    let block_len = |b: &audio_fingerprint::FlacBlock| (b.data.end - b.data.start) as usize;
+    let mut out_blocks = vec![(
+        blocks[0].kind,
+        block_len(&blocks[0]),
+        Piece::Source(blocks[0].data.clone()),
+    )];
+    out_blocks.push((FLAC_VORBIS_COMMENT, vc.len(), Piece::New(vc)));
+    for b in blocks[1..].iter().filter(|b| b.kind != FLAC_VORBIS_COMMENT) {
+        out_blocks.push((b.kind, block_len(b), Piece::Source(b.data.clone())));
+    }
+    let mut pieces = vec![Piece::New(b"fLaC".to_vec())];
+    let last = out_blocks.len() - 1;
+    for (i, (kind, len, data)) in out_blocks.into_iter().enumerate() {
+        let mut header = vec![if i == last { kind | 0x80 } else { kind }];
+        header.extend_from_slice(&(len as u32).to_be_bytes()[1..]);
+        pieces.push(Piece::New(header));
+        pieces.push(data);
+    }
+    pieces.push(Piece::Source(
+        audio_start..audio_fingerprint::stream_len(src)?,
+    ));
+    Ok(pieces)
+}
+
+// --- MP3 : tag ID3v2 ----------------------------------------------------------
+
+fn syncsafe(n: usize) -> [u8; 4] {
+    [
+        (n >> 21) as u8 & 0x7F,
+        (n >> 14) as u8 & 0x7F,
+        (n >> 7) as u8 & 0x7F,
+        n as u8 & 0x7F,
+    ]
+}
+
+fn from_syncsafe(b: &[u8]) -> usize {
+    b.iter()
+        .fold(0usize, |acc, &x| (acc << 7) | usize::from(x & 0x7F))
+}
+
+/// Trame `TXXX` : UTF-8 (v2.4) ou UTF-16 avec BOM (v2.3, sans UTF-8).
+fn txxx_frame(major: u8, description: &str, value: &str) -> Vec<u8> {
+    let mut body = Vec::new();
+    if major == 4 {
+        body.push(3);
+        body.extend_from_slice(description.as_bytes());
+        body.push(0);
+        body.extend_from_slice(value.as_bytes());
+    } else {
+        body.push(1);
+        for text in [description, value] {
+            body.extend_from_slice(&[0xFF, 0xFE]);
+            body.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+            if text == description {
+                body.extend_from_slice(&[0, 0]);
+            }
+        }
+    }
+    let mut frame = b"TXXX".to_vec();
+    if major == 4 {
+        frame.extend_from_slice(&syncsafe(body.len()));
+    } else {
+        frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    }
+    frame.extend_from_slice(&[0, 0]);
+    frame.extend_from_slice(&body);
+    frame
+}
+
+/// Description d'une trame `TXXX` (encodages ISO-8859-1, UTF-16, UTF-8).
+fn txxx_description(body: &[u8]) -> Option<String> {
+    let (&enc, rest) = body.split_first()?;
+    match enc {
+        0 | 3 => {
+            let end = rest.iter().position(|&b| b == 0)?;
+            Some(String::from_utf8_lossy(&rest[..end]).to_string())
+        }
+        1 | 2 => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|p| {
+                    if enc == 2 || rest.starts_with(&[0xFE, 0xFF]) {
+                        u16::from_be_bytes([p[0], p[1]])
+                    } else {
+                        u16::from_le_bytes([p[0], p[1]])
+                    }
+                })
+                .take_while(|&u| u != 0)
+                .collect();
+            Some(
+                String::from_utf16_lossy(&units)
+                    .trim_start_matches('\u{feff}')
+                    .to_string(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Valeur d'une trame `TXXX` (après la description).
+fn txxx_value(body: &[u8]) -> Option<String> {
+    let (&enc, rest) = body.split_first()?;
+    match enc {
+        0 | 3 => {
+            let end = rest.iter().position(|&b| b == 0)?;
+            Some(String::from_utf8_lossy(&rest[end + 1..]).to_string())
+        }
+        1 | 2 => {
+            let mut units = rest.chunks_exact(2).map(|p| [p[0], p[1]]);
+            let be = enc == 2 || rest.starts_with(&[0xFE, 0xFF]);
+            units.by_ref().take_while(|&u| u != [0, 0]).for_each(drop);
+            let value: Vec<u16> = units
+                .map(|u| {
+                    if be {
+                        u16::from_be_bytes(u)
+                    } else {
+                        u16::from_le_bytes(u)
+                    }
+                })
+                .take_while(|&u| u != 0)
+                .collect();
+            Some(
+                String::from_utf16_lossy(&value)
+                    .trim_start_matches('\u{feff}')
+                    .to_string(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Trame ID3v2 : (identifiant, trame complète, corps).
+type Id3Frame<'a> = ([u8; 4], &'a [u8], &'a [u8]);
+
+/// Trames d'un tag ID3v2.3/2.4 sans désynchronisation ni en-tête étendu.
+/// `None` si le tag n'est pas repris.
+fn id3_frames(tag: &[u8]) -> Option<Vec<Id3Frame<'_>>> {
+    let major = tag[3];
+    if !(major == 3 || major == 4) || tag[5] & 0xC0 != 0 {
+        return None;
+    }
+    let end = 10 + from_syncsafe(&tag[6..10]);
+    let mut frames = Vec::new();
+    let mut off = 10;
+    while off + 10 <= end && tag[off] != 0 {
+        let id = [tag[off], tag[off + 1], tag[off + 2], tag[off + 3]];
+        let size = if major == 4 {
+            from_syncsafe(&tag[off + 4..off + 8])
+        } else {
+            u32::from_be_bytes([tag[off + 4], tag[off + 5], tag[off + 6], tag[off + 7]]) as usize
+        };
+        let frame_end = off + 10 + size;
+        if frame_end > end {
+            return None;
+        }
+        frames.push((id, &tag[off..frame_end], &tag[off + 10..frame_end]));
+        off = frame_end;
+    }
+    Some(frames)
+}
+
+fn is_ho_txxx(id: &[u8; 4], body: &[u8]) -> bool {
+    id == b"TXXX" && txxx_description(body).is_some_and(|d| d.starts_with("HO:"))
+}
+
+/// Copie MP3 : tag ID3v2 (existant repris si possible) + trames MPEG et tags
+/// de queue recopiés tels quels.
+fn label_mp3<R: Read + Seek>(
+    src: &mut R,
+    request: &LabeledArtifactRequest,
+) -> Result<Vec<Piece>, String> {
+    let tag_len = audio_fingerprint::id3v2_len(src)?;
+    let tag = read_range(src, 0..tag_len)?;
+    let kept = if tag_len > 0 { id3_frames(&tag) } else { None };
+    let major = if kept.is_some() { tag[3] } else { 4 };
+
+    let mut frames = Vec::new();
+    for (id, frame, body) in kept.unwrap_or_default() {
+        if !is_ho_txxx(&id, body) {
+            frames.extend_from_slice(frame);
+        }
+    }
+    for (key, value) in label_fields(request) {
+        frames.extend_from_slice(&txxx_frame(major, &format!("HO:{key}"), value));
+    }
+    if frames.len() > ID3_MAX_TAG_LEN {
+        return Err("tag ID3v2 trop grand".to_string());
+    }
+
+    let mut head = Vec::with_capacity(10 + frames.len());
+    head.extend_from_slice(b"ID3");
+    head.extend_from_slice(&[major, 0, 0]);
+    head.extend_from_slice(&syncsafe(frames.len()));
+    head.extend_from_slice(&frames);
+    Ok(vec![
+        Piece::New(head),
+        Piece::Source(tag_len..audio_fingerprint::stream_len(src)?),
+    ])
+}
+
+// --- LECTURE ------------------------------------------------------------------
+
+/// Champs `HO` écrits dans une copie (`CertificateId`, `VerifyUrl`, `Verdict`,
+/// `SourceSha256`), lus selon le format (métadonnées seules). Vide si aucun.
+pub(crate) fn embedded_labels<R: Read + Seek>(
+    src: &mut R,
+) -> Result<Vec<(String, String)>, String> {
+    let mut out = Vec::new();
+    match audio_fingerprint::detect_audio_stream(src)? {
+        Some(AudioFormat::Wav) => {
+            let chunks = audio_fingerprint::riff_chunks(src)?;
+            if let Some(c) = chunks.iter().find(|c| &c.id == b"bext") {
+                let data = read_range(src, c.data.clone())?;
+                let history = String::from_utf8_lossy(data.get(BEXT_FIXED_LEN..).unwrap_or(&[]));
+                for line in history.split("\r\n") {
+                    if let Some((k, v)) = line.strip_prefix("ho:").and_then(|l| l.split_once('=')) {
+                        out.push((k.to_string(), v.to_string()));
+                    }
+                }
+            }
+        }
+        Some(AudioFormat::Flac) => {
+            let (blocks, _) = audio_fingerprint::flac_metadata_blocks(src)?;
+            if let Some(b) = blocks.iter().find(|b| b.kind == FLAC_VORBIS_COMMENT) {
+                let (_, comments) = parse_vorbis_comment(&read_range(src, b.data.clone())?)?;
+                for c in comments.iter().filter(|c| is_ho_comment(c)) {
+                    let c = String::from_utf8_lossy(c);
+                    if let Some((k, v)) = c.split_once('=') {
+                        let key = k[3..]
+                            .split('_')
+                            .map(|w| {
+                                let w = w.to_ascii_lowercase();
+                                let mut cs = w.chars();
+                                cs.next()
+                                    .map(|f| f.to_ascii_uppercase().to_string() + cs.as_str())
+                                    .unwrap_or_default()
+                            })
+                            .collect::<String>();
+                        out.push((key, v.to_string()));
+                    }
+                }
+            }
+        }
+        Some(AudioFormat::Mp3) => {
+            let tag_len = audio_fingerprint::id3v2_len(src)?;
+            if tag_len > 0 {
+                let tag = read_range(src, 0..tag_len)?;
+                for (id, _, body) in id3_frames(&tag).unwrap_or_default() {
+                    if is_ho_txxx(&id, body) {
+                        let desc = txxx_description(body).unwrap_or_default();
+                        out.push((desc[3..].to_string(), txxx_value(body).unwrap_or_default()));
+                    }
+                }
+            }
+        }
+        None => return Err("format audio non supporté (WAV/FLAC/MP3)".to_string()),
+    }
+    Ok(out)
+}
+
+// --- TESTS UNITAIRES ----------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evidence_kernel::{MediaKind, ObjectVersionRef, ObservedObjectRef};
+    use base64::{engine::general_purpose, Engine as _};
+    use rand_core::OsRng;
+    use sha2::{Digest, Sha256};
+    use std::io::Cursor;
+    use uuid::Uuid;
+
+    fn tmp() -> std::path::PathBuf {
+        let d = std::env::temp_dir().join(format!("ho_work_audio_test_{}", Uuid::new_v4()));
+        fs::create_dir_all(&d).unwrap();
+        d
+    }
+
+    fn cleanup(p: &Path) {
+        let _ = fs::remove_dir_all(p);
+    }
+
+    fn request(k: &SigningKey, source: &Path) -> LabeledArtifactRequest {
+        let pk = general_purpose::STANDARD.encode(k.verifying_key().to_bytes());
+        let bytes = fs::read(source).unwrap();
+        LabeledArtifactRequest {
+            source_object: ObservedObjectRef::new("work-audio", MediaKind::Audio),
+            source_version: ObjectVersionRef {
+                fingerprint_sha256: format!("{:x}", Sha256::digest(&bytes)),
+                size_bytes: Some(bytes.len() as u64),
+                mime_type: None,
+                chunked_fingerprint: None,
+                media_specific: None,
+            },
+            certificate_id: "cert-audio-1".to_string(),
+            verify_url: "https://verify.humanorigin.app/r/audio".to_string(),
+            verdict_label: "OBSERVED_WORK_CONSISTENT".to_string(),
+            signing_key_id: Some(format!("{:x}", Sha256::digest(pk.as_bytes()))),
+            certificate_created_at: Some("2026-01-01T10:30:00Z".to_string()),
+        }
+    }
+
+    /// WAV PCM 16 bits mono 8 kHz avec un chunk LIST (tags) de taille impaire.
+    fn sample_wav(dir: &Path) -> std::path::PathBuf {
+        let data: Vec<u8> = (0..200i16).flat_map(|i| (i * 37).to_le_bytes()).collect();
+        let mut fmt = Vec::new();
+        for v in [1u16, 1] {
+            fmt.extend_from_slice(&v.to_le_bytes());
+        }
+        fmt.extend_from_slice(&8_000u32.to_le_bytes());
+        fmt.extend_from_slice(&16_000u32.to_le_bytes());
+        fmt.extend_from_slice(&2u16.to_le_bytes());
+        fmt.extend_from_slice(&16u16.to_le_bytes());
+        let mut out = b"RIFF\0\0\0\0WAVE".to_vec();
+        for (id, body) in [
+            (b"fmt ", fmt),
+            (b"LIST", b"INFOx".to_vec()),
+            (b"data", data),
+        ] {
+            out.extend_from_slice(id);
+            out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+            out.extend_from_slice(&body);
+            if body.len() % 2 == 1 {
+                out.push(0);
+            }
+        }
+        let riff = (out.len() - 8) as u32;
+        out[4..8].copy_from_slice(&riff.to_le_bytes());
+        let p = dir.join("source.wav");
+        fs::write(&p, &out).unwrap();
+        p
+    }
+
+    /// FLAC : STREAMINFO + VORBIS_COMMENT (ARTIST, ancien HO_VERDICT) + PADDING
+    /// + trames factices.
+    fn sample_flac(dir: &Path) -> std::path::PathBuf {
+        let mut si = vec![0u8; 34];
+        si[10] = 0x0A;
+        si[11] = 0xC4;
+        si[12] = 0x42;
+        si[13] = 0xF0;
+        si[18..34].copy_from_slice(&[0x5A; 16]);
+        let mut vc = Vec::new();
+        vc.extend_from_slice(&3u32.to_le_bytes());
+        vc.extend_from_slice(b"enc");
+        vc.extend_from_slice(&2u32.to_le_bytes());
+        for c in [&b"ARTIST=Moi"[..], &b"HO_VERDICT=ancien"[..]] {
+            vc.extend_from_slice(&(c.len() as u32).to_le_bytes());
+            vc.extend_from_slice(c);
+        }
+        let mut out = b"fLaC".to_vec();
+        for (i, (kind, data)) in [(0u8, si), (4, vc), (1, vec![0u8; 16])]
+            .into_iter()
+            .enumerate()
+        {
+            out.push(if i == 2 { kind | 0x80 } else { kind });
+            out.extend_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+            out.extend_from_slice(&data);
+        }
+        out.extend_from_slice(&[0xFF, 0xF8, 0x69, 0x08, 9, 8, 7, 6]);
+        let p = dir.join("source.flac");
+        fs::write(&p, &out).unwrap();
+        p
+    }
+
+    /// MP3 : ID3v2.3 (TIT2) + trames MPEG-1 Layer III + ID3v1.
+    fn sample_mp3(dir: &Path) -> std::path::PathBuf {
+        let mut frame = b"TIT2".to_vec();
+        frame.extend_from_slice(&6u32.to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 0]);
+        frame.extend_from_slice(b"Titre");
+        let mut out = b"ID3\x03\x00\x00".to_vec();
+        out.extend_from_slice(&syncsafe(frame.len()));
+        out.extend_from_slice(&frame);
+        for i in 0..3u8 {
+            out.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x64]);
+            out.resize(out.len() + 413, i + 7);
+        }
+        let mut v1 = b"TAG".to_vec();
+        v1.resize(128, b' ');
+        out.extend_from_slice(&v1);
+        let p = dir.join("source.mp3");
+        fs::write(&p, &out).unwrap();
+        p
+    }
+
+    fn label(fields: &[(String, String)], key: &str) -> Option<String> {
+        fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+    }
+
+    /// Rendu + contrôles communs : signal intact, champs écrits, sidecar signé.
+    fn render_and_check(src: &Path, dir: &Path, expected_name: &str) -> Vec<u8> {
+        let out = dir.join("out");
+        fs::create_dir_all(&out).unwrap();
+        let source_before = fs::read(src).unwrap();
+        let k = SigningKey::generate(&mut OsRng);
+        let req = request(&k, src);
+        let art = AudioDocumentAdapter::new(&k)
+            .render_labeled_artifact(&req, src, &out)
+            .unwrap();
+        assert_eq!(art.artifact_name, expected_name);
+        assert_eq!(art.media_type, DocumentArtifactKind::Audio);
+
+        let labeled = fs::read(out.join(expected_name)).unwrap();
+        assert_eq!(fs::read(src).unwrap(), source_before, "source modifiée");
+        assert_ne!(labeled, source_before);
+        let src_fp = audio_fingerprint::fingerprint_audio_bytes(&source_before).unwrap();
+        assert_eq!(
+            audio_fingerprint::verify_audio_bytes(&src_fp, &labeled).unwrap(),
+            audio_fingerprint::AudioMatch::SignalIdentical
+        );
+        let fields = embedded_labels(&mut Cursor::new(&labeled)).unwrap();
+        assert_eq!(
+            label(&fields, "CertificateId").as_deref(),
+            Some("cert-audio-1")
+        );
+        assert_eq!(
+            label(&fields, "VerifyUrl").as_deref(),
+            Some(req.verify_url.as_str())
+        );
+        assert_eq!(
+            label(&fields, "SourceSha256"),
+            Some(req.source_version.fingerprint_sha256.clone())
+        );
+
+        let sidecar = work_sidecar::read_sidecar(&out.join(SIDECAR_FILENAME)).unwrap();
+        assert!(work_sidecar::verify_sidecar(&sidecar).is_ok());
+        assert_eq!(
+            sidecar.source_version.media_specific,
+            Some(MediaSpecificMetadata::Audio(src_fp))
+        );
+        assert_eq!(
+            sidecar
+                .labeled_artifact
+                .as_ref()
+                .unwrap()
+                .fingerprint_sha256,
+            Some(format!("{:x}", Sha256::digest(&labeled)))
+        );
+        labeled
+    }
+
+    #[test]
+    fn test_1_wav_bext_signal_intact() {
+        let dir = tmp();
+        let src = sample_wav(&dir);
+        let labeled = render_and_check(&src, &dir, LABELED_WAV_FILENAME);
+        let chunks = audio_fingerprint::riff_chunks(&mut Cursor::new(&labeled)).unwrap();
+        let ids: Vec<&[u8; 4]> = chunks.iter().map(|c| &c.id).collect();
+        assert_eq!(ids, vec![b"bext", b"fmt ", b"LIST", b"data"]);
+        let bext = &labeled[chunks[0].data.start as usize..chunks[0].data.end as usize];
+        assert!(bext.starts_with(b"HumanOrigin certificate cert-audio-1\0"));
+        assert_eq!(&bext[256 + 64..256 + 64 + 18], b"2026-01-0110:30:00");
+        // RIFF cohérent.
+        assert_eq!(
+            u32::from_le_bytes(labeled[4..8].try_into().unwrap()) as usize,
+            labeled.len() - 8
+        );
+        // Ré-étiqueter une copie remplace le bext (jamais deux).
+        let k = SigningKey::generate(&mut OsRng);
+        let mut copy = Cursor::new(&labeled);
+        let pieces = label_wav(&mut copy, &request(&k, &src)).unwrap();
+        let mut again = Vec::new();
+        write_pieces(&mut copy, &pieces, &mut again).unwrap();
+        let n = audio_fingerprint::riff_chunks(&mut Cursor::new(&again))
+            .unwrap()
+            .iter()
+            .filter(|c| &c.id == b"bext")
+            .count();
+        assert_eq!(n, 1);
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_2_flac_vorbis_comment_autres_tags_conserves() {
+        let dir = tmp();
+        let src = sample_flac(&dir);
+        let labeled = render_and_check(&src, &dir, LABELED_FLAC_FILENAME);
+        let (blocks, _) =
+            audio_fingerprint::flac_metadata_blocks(&mut Cursor::new(&labeled)).unwrap();
+        let kinds: Vec<u8> = blocks.iter().map(|b| b.kind).collect();
+        assert_eq!(kinds, vec![0, FLAC_VORBIS_COMMENT, 1]);
+        assert!(blocks.last().unwrap().is_last);
+        let vc = &labeled[blocks[1].data.start as usize..blocks[1].data.end as usize];
+        let (vendor, comments) = parse_vorbis_comment(vc).unwrap();
+        assert_eq!(vendor, b"enc");
+        assert!(comments.iter().any(|c| c == b"ARTIST=Moi"));
+        assert!(!comments.iter().any(|c| c == b"HO_VERDICT=ancien"));
+        assert!(comments
+            .iter()
+            .any(|c| c == b"HO_CERTIFICATE_ID=cert-audio-1"));
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_3_mp3_id3_txxx_tag_existant_conserve() {
+        let dir = tmp();
+        let src = sample_mp3(&dir);
+        let labeled = render_and_check(&src, &dir, LABELED_MP3_FILENAME);
+        assert_eq!(labeled[3], 3, "version ID3v2 source conservée");
+        let tag_len = audio_fingerprint::id3v2_len(&mut Cursor::new(&labeled)).unwrap() as usize;
+        let frames = id3_frames(&labeled[..tag_len]).unwrap();
+        assert_eq!(&frames[0].0, b"TIT2");
+        assert_eq!(frames.iter().filter(|f| &f.0 == b"TXXX").count(), 4);
+        // ID3v1 de queue conservé.
+        assert!(labeled[labeled.len() - 128..].starts_with(b"TAG"));
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_4_mp3_sans_tag_recoit_id3v24() {
+        let dir = tmp();
+        let src = sample_mp3(&dir);
+        let bytes = fs::read(&src).unwrap();
+        let start = audio_fingerprint::id3v2_len(&mut Cursor::new(&bytes)).unwrap() as usize;
+        fs::write(&src, &bytes[start..]).unwrap();
+        let labeled = render_and_check(&src, &dir, LABELED_MP3_FILENAME);
+        assert!(labeled.starts_with(b"ID3\x04"));
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_5_format_non_supporte_ou_cle_differente_refuses() {
+        let dir = tmp();
+        let pdf = dir.join("doc.pdf");
+        fs::write(&pdf, b"%PDF-1.7 nope").unwrap();
+        let k = SigningKey::generate(&mut OsRng);
+        let a = AudioDocumentAdapter::new(&k);
+        let res = a.render_labeled_artifact(&request(&k, &pdf), &pdf, &dir);
+        assert!(matches!(res, Err(ArtifactRenderError::RenderFailed(_))));
+
+        let src = sample_wav(&dir);
+        let other = SigningKey::generate(&mut OsRng);
+        let res = AudioDocumentAdapter::new(&other).render_labeled_artifact(
+            &request(&k, &src),
+            &src,
+            &dir,
+        );
+        assert!(matches!(res, Err(ArtifactRenderError::RenderFailed(_))));
+        assert!(!dir.join(SIDECAR_FILENAME).exists());
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_6_wav_valeur_non_ascii_refusee() {
+        let dir = tmp();
+        let src = sample_wav(&dir);
+        let k = SigningKey::generate(&mut OsRng);
+        let mut req = request(&k, &src);
+        req.verify_url = "https://verify.humanorigin.app/r/é".to_string();
+        let res = AudioDocumentAdapter::new(&k).render_labeled_artifact(&req, &src, &dir);
+        assert!(matches!(res, Err(ArtifactRenderError::MarkingFailed(_))));
+        cleanup(&dir);
+    }
+}
@@ -10,7 +10,10 @@
 //!   sélection opportuniste) ;
 //! - certifiable ssi : chaîne valide non vide, ≥ 1 NOUVELLE période qualifiante,
 //!   et hash actuel du document == hash_end de la dernière période ;
-//! - période qualifiante = `gate_passed && net_document_change` ;
+//! - période qualifiante = `gate_passed && net_document_change`, sauf si
+//!   `change_magnitude` (mesuré) montre un changement de texte trivial ;
+//! - `verdict.flags` signale toute période dont la croissance de texte dépasse
+//!   la saisie observée (`GROWTH_EXCEEDS_INPUT`, cf. `work_period`) ;
 //! - continuité DOCUMENTAIRE FULL/GAPPED (hash_start[i] == hash_end[i-1]) — ce
 //!   n'est PAS l'intégrité cryptographique de chaîne (elle, garantie par
 //!   `load_verified_chain`, échoue durement).
@@ -28,7 +31,9 @@ use std::fs::{File, OpenOptions};
 use std::io::{ErrorKind, Write};
 use std::path::{Path, PathBuf};
 
-use crate::work_period::{self, ObservationPeriod};
+use crate::document_set::{DocumentSetMember, MemberChange};
+use crate::work_change::ChangeMagnitude;
+use crate::work_period::{self, CanonScheme, ObservationPeriod};
 use crate::work_store::{self, WorkId};
 
 /// Version de schéma du HO-JSON Work Certificate.
@@ -95,6 +100,11 @@ pub(crate) struct IncludedPeriod {
     pub net_document_change: bool,
     pub gate_passed: bool,
     pub qualifying: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_magnitude: Option<ChangeMagnitude>,
+    /// Changements par fichier (Work multi-fichiers, chemins relatifs).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member_changes: Option<Vec<MemberChange>>,
     pub period_record_sha256: String,
 }
 
@@ -114,6 +124,9 @@ pub(crate) struct VerdictEngineSummary {
     pub total_active_seconds: u64,
     pub continuity: ContinuityKind,
     pub verdict: ProofVerdict,
+    /// Signaux de plausibilité sur la chaîne ; absent si aucun (compat V1).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<String>,
 }
 
 /// Référence documentaire à l'instant de la construction.
@@ -122,6 +135,9 @@ pub(crate) struct DocumentRef {
     pub document_path: String,
     pub hash_current: String,
     pub size_current: u64,
+    /// Hash final de chaque fichier (Work multi-fichiers) ; absent sinon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<DocumentSetMember>>,
 }
 
 /// Évidence cumulative (NON signée en 6A).
@@ -176,11 +192,32 @@ fn period_active_sec(p: &ObservationPeriod) -> u64 {
         .unwrap_or(0)
 }
 
-/// Période qualifiante = gate franchi ET changement documentaire net.
+/// Croissance du texte très au-delà de la saisie (`engine.document_growth`,
+/// absent pour les périodes sans mesure).
+fn period_growth_exceeds_input(p: &ObservationPeriod) -> bool {
+    p.engine
+        .get("document_growth")
+        .and_then(|g| g.get("exceeds_input"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Changement documentaire réel : hash différent ET, quand l'ampleur a été
+/// mesurée, pas un simple ré-enregistrement / une retouche triviale. Sans
+/// mesure (format non texte, période antérieure) : `net_document_change` seul.
+fn period_document_changed(p: &ObservationPeriod) -> bool {
+    p.net_document_change
+        && !p
+            .change_magnitude
+            .as_ref()
+            .is_some_and(ChangeMagnitude::is_trivial)
+}
+
+/// Période qualifiante = gate franchi ET changement documentaire réel.
 /// `pub(crate)` : réutilisée en lecture seule par `get_work_summary` (aucune
 /// duplication de la définition de qualification).
 pub(crate) fn period_is_qualifying(p: &ObservationPeriod) -> bool {
-    period_gate_passed(p) && p.net_document_change
+    period_gate_passed(p) && period_document_changed(p)
 }
 
 fn to_included(p: &ObservationPeriod) -> IncludedPeriod {
@@ -194,7 +231,9 @@ fn to_included(p: &ObservationPeriod) -> IncludedPeriod {
         size_end: p.size_end,
         net_document_change: p.net_document_change,
         gate_passed,
-        qualifying: gate_passed && p.net_document_change,
+        qualifying: period_is_qualifying(p),
+        change_magnitude: p.change_magnitude.clone(),
+        member_changes: p.member_changes.clone(),
         period_record_sha256: p.period_record_sha256.clone(),
     }
 }
@@ -229,12 +268,17 @@ fn summarize_verdict(
         ContinuityKind::Full => ProofVerdict::ObservedWorkConsistent,
         ContinuityKind::Gapped => ProofVerdict::ObservedWorkWithGaps,
     };
+    let mut flags = Vec::new();
+    if chain.iter().any(period_growth_exceeds_input) {
+        flags.push(work_period::GROWTH_EXCEEDS_INPUT_FLAG.to_string());
+    }
     VerdictEngineSummary {
         total_periods: included.len() as u32,
         qualifying_periods,
         total_active_seconds,
         continuity: continuity.kind,
         verdict,
+        flags,
     }
 }
 
@@ -270,6 +314,7 @@ pub(crate) fn build_certificate_draft(
     let record = work_store::read_work_metadata(works_root, work_id)
         .map_err(CertificateError::DocumentUnavailable)?;
     let document_path = record.document.document_path.clone();
+    let document_set = record.document.document_set.clone();
 
     // Chaîne vérifiée 0->N (fork/trou/altération/linkage -> erreur dure).
     let chain = work_period::load_verified_chain(works_root, work_id)
@@ -300,9 +345,18 @@ pub(crate) fn build_certificate_draft(
 
     // Document inchangé depuis le dernier stop : hash actuel == hash_end final.
     let last = chain.last().expect("chaîne non vide");
-    let (_p, hash_current, size_current) =
-        crate::work_commands::capture_start_boundary(&document_path)
-            .map_err(CertificateError::DocumentUnavailable)?;
+    let (hash_current, size_current, members) = match &document_set {
+        Some(spec) => {
+            let (_p, hash, size, members) = crate::work_commands::capture_set_boundary(spec)
+                .map_err(CertificateError::DocumentUnavailable)?;
+            (hash, size, Some(members))
+        }
+        None => {
+            let (_p, hash, size) = crate::work_commands::capture_start_boundary(&document_path)
+                .map_err(CertificateError::DocumentUnavailable)?;
+            (hash, size, None)
+        }
+    };
     if hash_current != last.hash_end {
         return Err(CertificateError::DocumentModifiedAfterStop);
     }
@@ -323,6 +377,7 @@ pub(crate) fn build_certificate_draft(
             document_path,
             hash_current,
             size_current,
+            members,
         },
         included_periods,
         new_period_ids,
@@ -353,6 +408,10 @@ pub(crate) fn compute_core_evidence_sha256(ev: &CoreEvidence) -> Result<String,
 pub(crate) struct PublicDocumentRef {
     pub hash_current: String,
     pub size_current: u64,
+    /// Hash final de chaque fichier (Work multi-fichiers) : chemins RELATIFS à
+    /// la racine de l'ensemble, jamais absolus. Absent sinon (compat V1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<DocumentSetMember>>,
 }
 
 /// Projection PARTAGEABLE de `CoreEvidence` : path-free (garde-fou #1).
@@ -381,6 +440,7 @@ fn project_public(ev: &CoreEvidence) -> PublicCoreEvidence {
         document: PublicDocumentRef {
             hash_current: ev.document.hash_current.clone(),
             size_current: ev.document.size_current,
+            members: ev.document.members.clone(),
         },
         included_period_summaries: ev.included_periods.clone(),
         new_period_ids: ev.new_period_ids.clone(),
@@ -400,6 +460,14 @@ pub(crate) fn compute_public_core_evidence_sha256(
     work_period::canonical_sha256(p)
 }
 
+/// Comme `compute_public_core_evidence_sha256`, dans le schéma `scheme`.
+pub(crate) fn compute_public_core_evidence_sha256_with(
+    p: &PublicCoreEvidence,
+    scheme: CanonScheme,
+) -> Result<String, String> {
+    work_period::canonical_sha256_with(p, scheme)
+}
+
 /// Métadonnées de signature du certificat (incluses dans le corps signé).
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub(crate) struct CertificateSignatureMetadata {
@@ -408,6 +476,20 @@ pub(crate) struct CertificateSignatureMetadata {
     pub signing_key_id: String,
     pub identity_status: String,
     pub schema_version: u32,
+    /// Schéma de canonicalisation du corps signé et de `core_evidence_sha256`.
+    /// Absent = `HO-CANON-V1` (certificats antérieurs inchangés, octet pour octet).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonicalization: Option<String>,
+}
+
+impl CertificateSignatureMetadata {
+    /// Schéma effectif du certificat (`HO-CANON-V1` si absent).
+    pub(crate) fn canon_scheme(&self) -> Result<CanonScheme, String> {
+        match &self.canonicalization {
+            None => Ok(CanonScheme::HoCanonV1),
+            Some(label) => CanonScheme::from_label(label),
+        }
+    }
 }
 
 /// HO-JSON Work Certificate : résumé path-free signé par la clé device.
@@ -447,7 +529,8 @@ fn decode_b64_fixed<const N: usize>(s: &str) -> Result<[u8; N], String> {
 
 /// Vérifie un certificat : algo, identité, `signing_key_id`, cohérence du
 /// `core_evidence_sha256` public, et signature Ed25519 sur le corps canonique
-/// excluant UNIQUEMENT `signature`. `Err(raison)` si invalide.
+/// (schéma déclaré par `signature_metadata`) excluant UNIQUEMENT `signature`.
+/// `Err(raison)` si invalide.
 pub(crate) fn verify_certificate(cert: &WorkCertificate) -> Result<(), String> {
     let meta = &cert.signature_metadata;
     if meta.signature_algorithm != SIGN_ALG {
@@ -463,12 +546,14 @@ pub(crate) fn verify_certificate(cert: &WorkCertificate) -> Result<(), String> {
         return Err("signing_key_id incohérent avec la clé publique".to_string());
     }
 
-    let expected = compute_public_core_evidence_sha256(&cert.public_core_evidence)?;
+    let scheme = meta.canon_scheme()?;
+
+    let expected = compute_public_core_evidence_sha256_with(&cert.public_core_evidence, scheme)?;
     if cert.core_evidence_sha256 != expected {
         return Err("core_evidence_sha256 ne correspond pas à public_core_evidence".to_string());
     }
 
-    let body = work_period::canonical_bytes_excluding(cert, &["signature"])?;
+    let body = work_period::canonical_bytes_with(cert, &["signature"], scheme)?;
     let digest = Sha256::digest(&body);
     let pk = decode_b64_fixed::<32>(&meta.public_key)?;
     let verifying_key = VerifyingKey::from_bytes(&pk).map_err(|e| e.to_string())?;
@@ -653,12 +738,27 @@ pub(crate) fn find_last_valid_certificate(
 
 /// Construit + signe + écrit le certificat HO-JSON path-free d'un Work.
 /// `created_at` fourni par l'appelant (déterminisme). Clé injectée (`sign_fn`
-/// interne) pour l'isolation des tests. Écrit `certificate_{seq}.json`.
-fn create_work_certificate_with(
+/// interne) pour l'isolation des tests. Schéma : réglage du Work
+/// (`work_period::work_canon_scheme`). Écrit `certificate_{seq}.json`.
+pub(crate) fn create_work_certificate_with(
+    works_root: &Path,
+    work_id: &WorkId,
+    created_at: &str,
+    signing_key: &SigningKey,
+) -> Result<WorkCertificate, CertificateError> {
+    let scheme =
+        work_period::work_canon_scheme(works_root, work_id).map_err(CertificateError::Io)?;
+    create_work_certificate_with_scheme(works_root, work_id, created_at, signing_key, scheme)
+}
+
+/// Comme `create_work_certificate_with`, canonicalisé selon `scheme`.
+/// `HO-CANON-V1` n'écrit PAS de champ `canonicalization` (format antérieur).
+fn create_work_certificate_with_scheme(
     works_root: &Path,
     work_id: &WorkId,
     created_at: &str,
     signing_key: &SigningKey,
+    scheme: CanonScheme,
 ) -> Result<WorkCertificate, CertificateError> {
     // Chaîne de certificats existante (erreur dure si un certificat est invalide).
     let existing = load_certificates(works_root, work_id)?;
@@ -679,7 +779,7 @@ fn create_work_certificate_with(
     let draft = build_certificate_draft(works_root, work_id, previous_ref.as_ref())?;
     let public = project_public(&draft.core_evidence);
     let core_evidence_sha256 =
-        compute_public_core_evidence_sha256(&public).map_err(CertificateError::Io)?;
+        compute_public_core_evidence_sha256_with(&public, scheme).map_err(CertificateError::Io)?;
 
     let certificate_sequence = last.map(|c| c.certificate_sequence + 1).unwrap_or(1);
     let previous_certificate_id = last.map(|c| c.certificate_id.clone());
@@ -705,12 +805,16 @@ fn create_work_certificate_with(
             signing_key_id,
             identity_status: LOCAL_DEVICE_IDENTITY.to_string(),
             schema_version: CERTIFICATE_SCHEMA_VERSION,
+            canonicalization: match scheme {
+                CanonScheme::HoCanonV1 => None,
+                other => Some(other.label().to_string()),
+            },
         },
         signature: String::new(),
     };
 
     // Signature sur corps canonique excluant UNIQUEMENT `signature`.
-    let body = work_period::canonical_bytes_excluding(&cert, &["signature"])
+    let body = work_period::canonical_bytes_with(&cert, &["signature"], scheme)
         .map_err(CertificateError::Io)?;
     let digest = Sha256::digest(&body);
     cert.signature =
@@ -797,9 +901,13 @@ mod tests {
             document_path: "/tmp/sujet.txt".to_string(),
             hash_start: hash_start.to_string(),
             size_start: 10,
+            chunked_start: None,
             hash_end: hash_end.to_string(),
             size_end: 20,
+            chunked_end: None,
+            member_changes: None,
             change_observed_during_period: false,
+            change_magnitude: None,
             engine: json!({ "analysis": { "gate_passed": gate_passed, "active_est_sec": 60 } }),
         };
         let p = work_period::sign_period_record(inputs, &key).unwrap();
@@ -1060,6 +1168,110 @@ mod tests {
         cleanup(&base);
     }
 
+    /// Période genèse qualifiante côté gate/hash, avec une ampleur mesurée.
+    fn seed_measured(
+        works: &Path,
+        wid: &WorkId,
+        hash_end: &str,
+        magnitude: ChangeMagnitude,
+    ) -> ObservationPeriod {
+        let inputs = work_period::PeriodInputs {
+            period_id: Uuid::new_v4().to_string(),
+            work_id: wid.clone(),
+            sequence_number: 0,
+            previous_period_id: None,
+            previous_period_record_sha256: None,
+            document_path: "/tmp/sujet.txt".to_string(),
+            hash_start: "a".repeat(64),
+            size_start: 10,
+            chunked_start: None,
+            hash_end: hash_end.to_string(),
+            size_end: 20,
+            chunked_end: None,
+            member_changes: None,
+            change_observed_during_period: false,
+            change_magnitude: Some(magnitude),
+            engine: json!({ "analysis": { "gate_passed": true, "active_est_sec": 60 } }),
+        };
+        let p = work_period::sign_period_record(inputs, &key()).unwrap();
+        work_period::write_period_once(works, &p).unwrap();
+        p
+    }
+
+    fn magnitude(chars_added: u64, chars_removed: u64) -> ChangeMagnitude {
+        ChangeMagnitude {
+            format: "txt".to_string(),
+            chars_start: 100,
+            chars_end: 100 + chars_added - chars_removed,
+            chars_added,
+            chars_removed,
+            edit_distance_ratio_bps: 0,
+            paragraphs_start: 1,
+            paragraphs_end: 1,
+            paragraphs_touched: 1,
+            exact: true,
+        }
+    }
+
+    #[test]
+    fn test_12_changement_trivial_non_qualifiant() {
+        let base = temp_base();
+        let (works, wid, _doc, h) = make_work(&base, b"contenu");
+        // Gate franchi, hash différent, mais 3 caractères touchés : ré-enregistrement.
+        let p = seed_measured(&works, &wid, &h, magnitude(2, 1));
+        assert!(p.net_document_change);
+        assert!(!period_is_qualifying(&p));
+        let res = build_certificate_draft(&works, &wid, None);
+        assert_eq!(res.err(), Some(CertificateError::NoQualifyingNewPeriod));
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_13_changement_mesure_qualifiant_et_resume_inclus() {
+        let base = temp_base();
+        let (works, wid, _doc, h) = make_work(&base, b"contenu");
+        seed_measured(&works, &wid, &h, magnitude(240, 15));
+        let draft = build_certificate_draft(&works, &wid, None).unwrap();
+        let included = &draft.core_evidence.included_periods[0];
+        assert!(included.qualifying);
+        assert_eq!(included.change_magnitude, Some(magnitude(240, 15)));
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_14_sans_mesure_regle_v1_inchangee() {
+        let base = temp_base();
+        let (works, wid, _doc, _h) = make_certifiable(&base, b"contenu");
+        let draft = build_certificate_draft(&works, &wid, None).unwrap();
+        let included = &draft.core_evidence.included_periods[0];
+        assert!(included.qualifying);
+        assert!(included.change_magnitude.is_none());
+        // Absent du JSON : empreinte canonique V1 inchangée.
+        let v = serde_json::to_value(included).unwrap();
+        assert!(v.get("change_magnitude").is_none());
+        let verdict = serde_json::to_value(&draft.core_evidence.verdict).unwrap();
+        assert!(verdict.get("flags").is_none());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_15_croissance_sans_saisie_signalee_et_non_qualifiante() {
+        let base = temp_base();
+        let (works, wid, _doc, h) = make_work(&base, b"contenu");
+        // 40 000 caractères ajoutés, aucune frappe ni collage observés.
+        let p0 = seed_measured(&works, &wid, &"b".repeat(64), magnitude(40_000, 0));
+        assert!(!period_is_qualifying(&p0));
+        seed(&works, &wid, 1, Some(&p0), &"b".repeat(64), &h, true);
+
+        let draft = build_certificate_draft(&works, &wid, None).unwrap();
+        let ev = &draft.core_evidence;
+        assert!(!ev.included_periods[0].qualifying);
+        assert!(!ev.included_periods[0].gate_passed);
+        assert_eq!(ev.verdict.qualifying_periods, 1);
+        assert_eq!(ev.verdict.flags, vec!["GROWTH_EXCEEDS_INPUT".to_string()]);
+        cleanup(&base);
+    }
+
     // --- 6B : HO-JSON Work Certificate signé -------------------------------
 
     fn key() -> SigningKey {
@@ -1276,6 +1488,118 @@ mod tests {
         cleanup(&base);
     }
 
+    #[test]
+    fn test_6b_11_work_multi_fichiers_hash_final_par_fichier() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let livre = base.join("livre");
+        fs::create_dir_all(&livre).unwrap();
+        fs::write(livre.join("ch1.md"), b"# Un\n").unwrap();
+        fs::write(livre.join("ch2.md"), b"# Deux\n").unwrap();
+        let outcome = crate::work_commands::create_document_set_work_core(
+            &works,
+            &[livre.to_string_lossy().to_string()],
+            None,
+        )
+        .unwrap();
+        let crate::work_commands::CreateWorkOutcome::Created { work_id: wid } = outcome else {
+            panic!("attendu Created, reçu {outcome:?}");
+        };
+        let spec = work_store::read_work_metadata(&works, &wid)
+            .unwrap()
+            .document
+            .document_set
+            .unwrap();
+        let start = crate::document_set::capture_set(&spec).unwrap();
+        fs::write(livre.join("ch2.md"), b"# Deux, revu\n").unwrap();
+        let end = crate::document_set::capture_set(&spec).unwrap();
+
+        let inputs = work_period::PeriodInputs {
+            period_id: Uuid::new_v4().to_string(),
+            work_id: wid.clone(),
+            sequence_number: 0,
+            previous_period_id: None,
+            previous_period_record_sha256: None,
+            document_path: spec.root.clone(),
+            hash_start: start.manifest_sha256.clone(),
+            size_start: start.total_bytes,
+            chunked_start: None,
+            hash_end: end.manifest_sha256.clone(),
+            size_end: end.total_bytes,
+            chunked_end: None,
+            member_changes: Some(crate::document_set::member_changes(
+                &start.members,
+                &end.members,
+            )),
+            change_observed_during_period: false,
+            change_magnitude: None,
+            engine: json!({ "analysis": { "gate_passed": true, "active_est_sec": 60 } }),
+        };
+        let p = work_period::sign_period_record(inputs, &key()).unwrap();
+        work_period::write_period_once(&works, &p).unwrap();
+
+        let cert =
+            create_work_certificate_with(&works, &wid, "2026-08-01T10:00:00Z", &key()).unwrap();
+        assert!(verify_certificate(&cert).is_ok());
+        let doc = &cert.public_core_evidence.document;
+        assert_eq!(doc.hash_current, end.manifest_sha256);
+        assert_eq!(doc.members.as_ref(), Some(&end.members));
+        let changes = cert.public_core_evidence.included_period_summaries[0]
+            .member_changes
+            .as_ref()
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "ch2.md");
+        let json = fs::read_to_string(cert_dir(&works, &wid).join("certificate_1.json")).unwrap();
+        assert!(!json.contains(&spec.root), "racine de l'ensemble exposée !");
+
+        // Un chapitre modifié après le stop bloque la certification.
+        fs::write(livre.join("ch1.md"), b"# Un, retouche\n").unwrap();
+        assert_eq!(
+            build_certificate_draft(&works, &wid, None).err(),
+            Some(CertificateError::DocumentModifiedAfterStop)
+        );
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_6b_12_certificat_jcs_signe_verifiable() {
+        let base = temp_base();
+        let (works, wid, _doc, _h) = make_certifiable(&base, b"contenu");
+        let cert = create_work_certificate_with_scheme(
+            &works,
+            &wid,
+            "2026-08-01T10:00:00Z",
+            &key(),
+            CanonScheme::HoJcsV1,
+        )
+        .unwrap();
+        assert_eq!(
+            cert.signature_metadata.canonicalization.as_deref(),
+            Some("HO-JCS-V1")
+        );
+        verify_certificate(&cert).unwrap();
+        // Relecture disque (chaîne vérifiée) : le schéma voyage avec le certificat.
+        let chain = load_certificates(&works, &wid).unwrap();
+        assert_eq!(chain, vec![cert.clone()]);
+
+        // Le libellé est signé : le retirer (=> HO-CANON-V1) casse la signature.
+        let mut relabel = cert;
+        relabel.signature_metadata.canonicalization = None;
+        assert!(verify_certificate(&relabel).is_err());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_6b_13_certificat_par_defaut_sans_champ_canonicalization() {
+        let base = temp_base();
+        let (works, wid, _doc, _h) = make_certifiable(&base, b"contenu");
+        create_work_certificate_with(&works, &wid, "2026-08-01T10:00:00Z", &key()).unwrap();
+        let json = fs::read_to_string(cert_dir(&works, &wid).join("certificate_1.json")).unwrap();
+        assert!(!json.contains("canonicalization"), "format V1 modifié");
+        cleanup(&base);
+    }
+
     /// Parcours récursif simple (tests uniquement).
     fn walk(dir: &Path) -> Vec<PathBuf> {
         let mut out = Vec::new();
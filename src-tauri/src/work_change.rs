@@ -0,0 +1,444 @@
+//! work_change — ampleur du changement documentaire d'une période (sans contenu).
+//!
+//! Portée STRICTE : comparer le texte du document au DÉBUT et à la FIN d'une
+//! période et n'en garder que des COMPTES :
+//!   - caractères ajoutés / retirés ;
+//!   - ratio de distance d'édition (points de base, 10000 = réécriture totale) ;
+//!   - paragraphes au début / à la fin / touchés.
+//!
+//! Formats pris en charge : DOCX (paragraphes du corps via `docx_metrics`),
+//! TXT et MD (blocs séparés par une ligne vide). Autres formats : aucune mesure,
+//! la période garde seulement `net_document_change`.
+//!
+//! Garde-fous :
+//! - AUCUN texte n'est renvoyé ni écrit : `DocumentText` reste en mémoire,
+//!   seul `ChangeMagnitude` (des entiers) entre dans la période signée ;
+//! - entiers uniquement (pas de flottant) : payload signé stable au round-trip ;
+//! - coût borné (`MAX_DP_CELLS`) : au-delà, diff grossier et `exact = false`,
+//!   qui SOUS-estime le changement (jamais l'inverse).
+//!
+//! AUCUNE I/O hors lecture du document, AUCUNE commande Tauri.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+/// En dessous (ajouts + retraits), un changement est un simple ré-enregistrement
+/// ou une retouche : il ne qualifie pas une période.
+pub(crate) const MIN_CHANGED_CHARS: u64 = 20;
+
+const MAX_DP_CELLS: usize = 4_000_000;
+const RATIO_SCALE: u64 = 10_000;
+
+// --- TYPES --------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TextFormat {
+    Docx,
+    Txt,
+    Md,
+}
+
+impl TextFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "docx" => Some(TextFormat::Docx),
+            "txt" | "text" => Some(TextFormat::Txt),
+            "md" | "markdown" => Some(TextFormat::Md),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            TextFormat::Docx => "docx",
+            TextFormat::Txt => "txt",
+            TextFormat::Md => "md",
+        }
+    }
+}
+
+/// Texte d'une version du document, en mémoire uniquement (jamais sérialisé).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DocumentText {
+    pub format: TextFormat,
+    pub paragraphs: Vec<String>,
+}
+
+/// Ampleur du changement début -> fin. Comptes en caractères Unicode, hors
+/// séparateurs de paragraphes et espaces redondants.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ChangeMagnitude {
+    pub format: String,
+    pub chars_start: u64,
+    pub chars_end: u64,
+    pub chars_added: u64,
+    pub chars_removed: u64,
+    /// (ajouts + retraits) / (chars_start + chars_end), en points de base.
+    pub edit_distance_ratio_bps: u64,
+    pub paragraphs_start: u64,
+    pub paragraphs_end: u64,
+    pub paragraphs_touched: u64,
+    /// `false` si un diff a été borné (comptes alors sous-estimés).
+    pub exact: bool,
+}
+
+impl ChangeMagnitude {
+    pub(crate) fn changed_chars(&self) -> u64 {
+        self.chars_added + self.chars_removed
+    }
+
+    /// Ré-enregistrement sans changement de texte, ou retouche minime.
+    pub(crate) fn is_trivial(&self) -> bool {
+        self.changed_chars() < MIN_CHANGED_CHARS
+    }
+}
+
+// --- EXTRACTION ---------------------------------------------------------------
+
+/// Blocs TXT/MD : séparés par une ligne vide, espaces normalisés.
+fn plain_paragraphs(raw: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for line in raw.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join(" "));
+                current.clear();
+            }
+        } else {
+            current.extend(line.split_whitespace());
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join(" "));
+    }
+    paragraphs
+}
+
+/// Vrai si l'ampleur du changement est mesurable pour ce document.
+pub(crate) fn is_measurable(path: &Path) -> bool {
+    TextFormat::from_path(path).is_some()
+}
+
+/// Texte comparable d'une version du document déjà lue (`path` ne sert qu'au
+/// format). `None` si le format n'est pas pris en charge.
+pub(crate) fn document_text_from_bytes(
+    path: &Path,
+    bytes: &[u8],
+) -> Result<Option<DocumentText>, String> {
+    let Some(format) = TextFormat::from_path(path) else {
+        return Ok(None);
+    };
+    let paragraphs = match format {
+        TextFormat::Docx => crate::docx_metrics::docx_body_paragraphs(bytes)?,
+        TextFormat::Txt | TextFormat::Md => plain_paragraphs(&String::from_utf8_lossy(bytes)),
+    };
+    Ok(Some(DocumentText { format, paragraphs }))
+}
+
+/// Texte comparable du document, `None` si le format n'est pas pris en charge.
+pub(crate) fn extract_document_text(path: &Path) -> Result<Option<DocumentText>, String> {
+    if !is_measurable(path) {
+        return Ok(None);
+    }
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    document_text_from_bytes(path, &bytes)
+}
+
+// --- DIFF ---------------------------------------------------------------------
+
+fn common_prefix_suffix<T: PartialEq>(a: &[T], b: &[T]) -> (usize, usize) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    (prefix, suffix)
+}
+
+/// Zones modifiées du diff par paragraphes : (retirés dans `a`, ajoutés dans `b`).
+/// Au-delà de `MAX_DP_CELLS`, tout le milieu non commun forme une seule zone.
+fn paragraph_hunks(a: &[String], b: &[String]) -> Vec<(Range<usize>, Range<usize>)> {
+    let (prefix, suffix) = common_prefix_suffix(a, b);
+    let (a_end, b_end) = (a.len() - suffix, b.len() - suffix);
+    let (a_mid, b_mid) = (&a[prefix..a_end], &b[prefix..b_end]);
+    if a_mid.is_empty() && b_mid.is_empty() {
+        return vec![];
+    }
+    let (n, m) = (a_mid.len(), b_mid.len());
+    if n.saturating_mul(m) > MAX_DP_CELLS {
+        return vec![(prefix..a_end, prefix..b_end)];
+    }
+
+    // lcs[i][j] = LCS de a_mid[i..] et b_mid[j..].
+    let width = m + 1;
+    let mut lcs = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i * width + j] = if a_mid[i] == b_mid[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut hunks = Vec::new();
+    let mut open: Option<(usize, usize)> = None;
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && a_mid[i] == b_mid[j] {
+            if let Some((hi, hj)) = open.take() {
+                hunks.push((prefix + hi..prefix + i, prefix + hj..prefix + j));
+            }
+            i += 1;
+            j += 1;
+            continue;
+        }
+        open.get_or_insert((i, j));
+        if j < m && (i == n || lcs[i * width + j + 1] >= lcs[(i + 1) * width + j]) {
+            j += 1;
+        } else {
+            i += 1;
+        }
+    }
+    if let Some((hi, hj)) = open {
+        hunks.push((prefix + hi..prefix + n, prefix + hj..prefix + m));
+    }
+    hunks
+}
+
+/// (retirés, ajoutés, exact) entre deux suites de caractères : LCS exacte si
+/// bornée, sinon intersection des multiensembles (>= LCS, donc sous-estime).
+fn char_changes(removed: &[char], added: &[char]) -> (u64, u64, bool) {
+    let (prefix, suffix) = common_prefix_suffix(removed, added);
+    let a = &removed[prefix..removed.len() - suffix];
+    let b = &added[prefix..added.len() - suffix];
+    if a.is_empty() || b.is_empty() {
+        return (a.len() as u64, b.len() as u64, true);
+    }
+
+    let (common, exact) = if a.len().saturating_mul(b.len()) <= MAX_DP_CELLS {
+        let mut prev = vec![0u32; b.len() + 1];
+        let mut row = vec![0u32; b.len() + 1];
+        for x in a {
+            for (j, y) in b.iter().enumerate() {
+                row[j + 1] = if x == y {
+                    prev[j] + 1
+                } else {
+                    prev[j + 1].max(row[j])
+                };
+            }
+            std::mem::swap(&mut prev, &mut row);
+        }
+        (prev[b.len()] as usize, true)
+    } else {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for c in a {
+            *counts.entry(*c).or_default() += 1;
+        }
+        let mut shared = 0;
+        for c in b {
+            if let Some(n) = counts.get_mut(c).filter(|n| **n > 0) {
+                *n -= 1;
+                shared += 1;
+            }
+        }
+        (shared, false)
+    };
+    ((a.len() - common) as u64, (b.len() - common) as u64, exact)
+}
+
+fn chars_of(paragraphs: &[String]) -> Vec<char> {
+    paragraphs.iter().flat_map(|p| p.chars()).collect()
+}
+
+/// Ampleur du changement entre deux versions d'un même document.
+pub(crate) fn compute_change_magnitude(
+    start: &DocumentText,
+    end: &DocumentText,
+) -> ChangeMagnitude {
+    let mut chars_added = 0;
+    let mut chars_removed = 0;
+    let mut paragraphs_touched = 0;
+    let mut exact = true;
+    for (removed, added) in paragraph_hunks(&start.paragraphs, &end.paragraphs) {
+        // Un paragraphe réécrit compte une fois, pas deux.
+        paragraphs_touched += removed.len().max(added.len()) as u64;
+        let (r, a, e) = char_changes(
+            &chars_of(&start.paragraphs[removed]),
+            &chars_of(&end.paragraphs[added]),
+        );
+        chars_removed += r;
+        chars_added += a;
+        exact &= e;
+    }
+
+    let chars_start = chars_of(&start.paragraphs).len() as u64;
+    let chars_end = chars_of(&end.paragraphs).len() as u64;
+    let total = chars_start + chars_end;
+    let edit_distance_ratio_bps = ((chars_added + chars_removed) * RATIO_SCALE)
+        .checked_div(total)
+        .map_or(0, |bps| bps.min(RATIO_SCALE));
+
+    ChangeMagnitude {
+        format: end.format.as_str().to_string(),
+        chars_start,
+        chars_end,
+        chars_added,
+        chars_removed,
+        edit_distance_ratio_bps,
+        paragraphs_start: start.paragraphs.len() as u64,
+        paragraphs_end: end.paragraphs.len() as u64,
+        paragraphs_touched,
+        exact,
+    }
+}
+
+/// Mesure à la fin de période : `None` si pas de texte de départ, format
+/// différent ou fin illisible (la période reste valide, sans mesure).
+pub(crate) fn measure_period_change(
+    start: Option<&DocumentText>,
+    end_path: &Path,
+) -> Option<ChangeMagnitude> {
+    let start = start?;
+    let end = extract_document_text(end_path).ok().flatten()?;
+    if end.format != start.format {
+        return None;
+    }
+    Some(compute_change_magnitude(start, &end))
+}
+
+// --- TESTS --------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn text(paragraphs: &[&str]) -> DocumentText {
+        DocumentText {
+            format: TextFormat::Txt,
+            paragraphs: paragraphs.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    fn temp_base() -> PathBuf {
+        std::env::temp_dir().join(format!("ho_change_test_{}", Uuid::new_v4()))
+    }
+
+    fn cleanup(p: &Path) {
+        let _ = fs::remove_dir_all(p);
+    }
+
+    #[test]
+    fn test_1_texte_identique_aucun_changement() {
+        let t = text(&["Un paragraphe.", "Un autre."]);
+        let m = compute_change_magnitude(&t, &t.clone());
+        assert_eq!(
+            (m.chars_added, m.chars_removed, m.paragraphs_touched),
+            (0, 0, 0)
+        );
+        assert_eq!(m.edit_distance_ratio_bps, 0);
+        assert!(m.is_trivial());
+        assert!(m.exact);
+    }
+
+    #[test]
+    fn test_2_un_caractere_est_trivial() {
+        let start = text(&["Le chat dort.", "Fin."]);
+        let end = text(&["Le chat dort!", "Fin."]);
+        let m = compute_change_magnitude(&start, &end);
+        assert_eq!((m.chars_added, m.chars_removed), (1, 1));
+        assert_eq!(m.paragraphs_touched, 1);
+        assert!(m.is_trivial());
+    }
+
+    #[test]
+    fn test_3_reecriture_totale_ratio_maximal() {
+        let start = text(&["aaaa aaaa aaaa", "bbbb bbbb"]);
+        let end = text(&["xxxxyyyyzzzzqqqq"]);
+        let m = compute_change_magnitude(&start, &end);
+        assert_eq!(m.chars_removed, m.chars_start);
+        assert_eq!(m.chars_added, m.chars_end);
+        assert_eq!(m.edit_distance_ratio_bps, 10_000);
+        assert_eq!(m.paragraphs_touched, 2);
+        assert!(!m.is_trivial());
+    }
+
+    #[test]
+    fn test_4_ajout_de_paragraphes_au_milieu() {
+        let start = text(&["Introduction.", "Conclusion."]);
+        let end = text(&[
+            "Introduction.",
+            "Premier développement assez long.",
+            "Second développement.",
+            "Conclusion.",
+        ]);
+        let m = compute_change_magnitude(&start, &end);
+        assert_eq!(m.chars_removed, 0);
+        assert_eq!(
+            m.chars_added,
+            ("Premier développement assez long.".chars().count()
+                + "Second développement.".chars().count()) as u64
+        );
+        assert_eq!(m.paragraphs_touched, 2);
+        assert_eq!((m.paragraphs_start, m.paragraphs_end), (2, 4));
+    }
+
+    #[test]
+    fn test_5_diff_borne_sous_estime() {
+        // 3001 x 3001 > MAX_DP_CELLS : intersection des multiensembles.
+        let a: Vec<char> = format!("x{}", "ab".repeat(1500)).chars().collect();
+        let b: Vec<char> = format!("y{}", "ba".repeat(1500)).chars().collect();
+        assert!(a.len() * b.len() > MAX_DP_CELLS);
+        assert_eq!(char_changes(&a, &b), (1, 1, false));
+    }
+
+    #[test]
+    fn test_6_extraction_txt_md_et_format_non_pris_en_charge() {
+        let base = temp_base();
+        fs::create_dir_all(&base).unwrap();
+        let md = base.join("notes.MD");
+        fs::write(&md, "# Titre\n\nligne une\nligne   deux\n\n\n- item\n").unwrap();
+        let t = extract_document_text(&md).unwrap().unwrap();
+        assert_eq!(t.format, TextFormat::Md);
+        assert_eq!(
+            t.paragraphs,
+            vec!["# Titre", "ligne une ligne deux", "- item"]
+        );
+
+        let pdf = base.join("doc.pdf");
+        fs::write(&pdf, b"%PDF").unwrap();
+        assert_eq!(extract_document_text(&pdf).unwrap(), None);
+        assert_eq!(measure_period_change(Some(&t), &pdf), None);
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_7_mesure_de_periode_sur_fichier_reel() {
+        let base = temp_base();
+        fs::create_dir_all(&base).unwrap();
+        let doc = base.join("chapitre.txt");
+        fs::write(&doc, "Il était une fois.").unwrap();
+        let start = extract_document_text(&doc).unwrap();
+        fs::write(
+            &doc,
+            "Il était une fois.\n\nUn long chapitre écrit pendant la période.",
+        )
+        .unwrap();
+        let m = measure_period_change(start.as_ref(), &doc).unwrap();
+        assert_eq!(m.format, "txt");
+        assert_eq!(m.chars_removed, 0);
+        assert!(!m.is_trivial());
+        assert_eq!(measure_period_change(None, &doc), None);
+        cleanup(&base);
+    }
+}
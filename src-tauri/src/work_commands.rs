@@ -9,12 +9,14 @@
 //! sur des dossiers temporaires isolés (jamais le vrai `~/.../Works`).
 
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use std::fs;
-use std::fs::File;
-use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::document_set::{self, DocumentSetMember, DocumentSetSpec};
+use crate::evidence_kernel::ChunkedFingerprint;
+use crate::file_chunks;
+use crate::file_hash;
+use crate::repo_tree;
 use crate::work_store::{
     self, WorkDocumentMetadata, WorkId, WorkIndexCache, WorkLifecycle, WorkRecord,
 };
@@ -60,6 +62,8 @@ pub struct LoadedWork {
     pub last_activity_utc: String,
     pub period_count: u32,
     pub certificate_count: u32,
+    /// Schéma de canonicalisation effectif des nouvelles périodes/certificats.
+    pub canonicalization: String,
 }
 
 // --- HELPERS DOCUMENTAIRES ----------------------------------------------------
@@ -68,21 +72,6 @@ fn now_utc() -> String {
     chrono::Utc::now().to_rfc3339()
 }
 
-/// SHA256 hex d'un fichier, lu en flux (buffers de 64 Kio).
-fn sha256_file(path: &Path) -> Result<String, String> {
-    let mut f = File::open(path).map_err(|e| e.to_string())?;
-    let mut hasher = Sha256::new();
-    let mut buf = [0u8; 65536];
-    loop {
-        let n = f.read(&mut buf).map_err(|e| e.to_string())?;
-        if n == 0 {
-            break;
-        }
-        hasher.update(&buf[..n]);
-    }
-    Ok(format!("{:x}", hasher.finalize()))
-}
-
 /// MIME déduit de l'extension (best-effort ; `octet-stream` par défaut).
 fn mime_from_extension(path: &Path) -> Option<String> {
     let ext = path.extension()?.to_str()?.to_ascii_lowercase();
@@ -111,11 +100,28 @@ fn file_identity(_meta: &fs::Metadata) -> (Option<u64>, Option<u64>) {
     (None, None)
 }
 
-/// Capture l'empreinte documentaire d'un fichier réel.
-/// Refuse un dossier ou un chemin non-fichier ou inexistant.
+/// Capture l'empreinte documentaire d'un fichier réel, ou d'un dépôt git
+/// (dossier contenant `.git`) : empreinte d'arbre `repo_tree`, taille = somme
+/// des fichiers retenus. Refuse tout autre dossier, un chemin non-fichier ou
+/// inexistant.
 fn capture_document(path: &Path) -> Result<WorkDocumentMetadata, String> {
     let meta = fs::metadata(path)
         .map_err(|e| format!("Document introuvable : {} ({})", path.display(), e))?;
+    if meta.is_dir() && repo_tree::is_repository(path) {
+        let (inode, device_id) = file_identity(&meta);
+        let normalized = normalize_path(path);
+        let (hash, size) = repo_tree::repo_tree_digest(path)?;
+        return Ok(WorkDocumentMetadata {
+            document_path: normalized.clone(),
+            known_paths: vec![normalized],
+            hash_initial: Some(hash),
+            size_initial: Some(size),
+            mime: Some(repo_tree::REPO_MIME.to_string()),
+            inode,
+            device_id,
+            document_set: None,
+        });
+    }
     if meta.is_dir() {
         return Err(format!(
             "Un dossier ne peut pas être certifié : {}",
@@ -131,20 +137,48 @@ fn capture_document(path: &Path) -> Result<WorkDocumentMetadata, String> {
 
     let (inode, device_id) = file_identity(&meta);
     let normalized = normalize_path(path);
+    // Gros média : une seule passe donne le SHA256 et l'empreinte par blocs
+    // (mise en cache pour `capture_chunked_fingerprint`).
+    let hash = if file_chunks::is_chunk_candidate(path, meta.len()) {
+        file_chunks::chunked_digest_file(path)?.sha256
+    } else {
+        file_hash::sha256_file(path)?
+    };
     Ok(WorkDocumentMetadata {
         document_path: normalized.clone(),
         known_paths: vec![normalized],
-        hash_initial: Some(sha256_file(path)?),
+        hash_initial: Some(hash),
         size_initial: Some(meta.len()),
         mime: mime_from_extension(path),
         inode,
         device_id,
+        document_set: None,
+    })
+}
+
+/// Capture l'empreinte d'un ensemble de fichiers (`document_set`) : empreinte
+/// de manifeste, taille = somme des membres, identité du dossier racine.
+fn capture_document_set(spec: DocumentSetSpec) -> Result<WorkDocumentMetadata, String> {
+    let digest = document_set::capture_set(&spec)?;
+    let meta = fs::metadata(&spec.root)
+        .map_err(|e| format!("Dossier introuvable : {} ({})", spec.root, e))?;
+    let (inode, device_id) = file_identity(&meta);
+    Ok(WorkDocumentMetadata {
+        document_path: spec.root.clone(),
+        known_paths: vec![spec.root.clone()],
+        hash_initial: Some(digest.manifest_sha256),
+        size_initial: Some(digest.total_bytes),
+        mime: Some(document_set::DOCUMENT_SET_MIME.to_string()),
+        inode,
+        device_id,
+        document_set: Some(spec),
     })
 }
 
 /// Frontière de départ d'une période : (chemin normalisé, SHA256, taille) du
 /// document réel. Réutilise `capture_document` (même normalisation/hash que
-/// `create_work`). Refuse dossier / inexistant. Utilisé par `start_work_period`.
+/// `create_work`). Refuse dossier (hors dépôt git) / inexistant. Utilisé par
+/// `start_work_period`.
 pub(crate) fn capture_start_boundary(document_path: &str) -> Result<(String, String, u64), String> {
     let doc = capture_document(Path::new(document_path))?;
     let hash = doc
@@ -156,6 +190,38 @@ pub(crate) fn capture_start_boundary(document_path: &str) -> Result<(String, Str
     Ok((doc.document_path, hash, size))
 }
 
+/// Frontière d'une période d'un Work multi-fichiers : (racine, empreinte de
+/// manifeste, taille totale, membres). Un membre manquant fait échouer la
+/// capture comme un document unique introuvable.
+pub(crate) fn capture_set_boundary(
+    spec: &DocumentSetSpec,
+) -> Result<(String, String, u64, Vec<DocumentSetMember>), String> {
+    let digest = document_set::capture_set(spec)?;
+    Ok((
+        spec.root.clone(),
+        digest.manifest_sha256,
+        digest.total_bytes,
+        digest.members,
+    ))
+}
+
+/// Empreinte par blocs d'une frontière (gros médias uniquement) : servie par
+/// le cache de `file_chunks` si le document n'a pas changé depuis la capture.
+/// `None` si non candidat, illisible ou si le contenu ne correspond plus à
+/// `hash` (jamais bloquant : le SHA256 plat reste la référence).
+pub(crate) fn capture_chunked_fingerprint(
+    document_path: &str,
+    hash: &str,
+) -> Option<ChunkedFingerprint> {
+    let path = Path::new(document_path);
+    let size = fs::metadata(path).ok()?.len();
+    if !file_chunks::is_chunk_candidate(path, size) {
+        return None;
+    }
+    let digest = file_chunks::chunked_digest_file(path).ok()?;
+    (digest.sha256 == hash).then_some(digest.fingerprint)
+}
+
 /// Normalise un chemin : canonique si le fichier existe (résout `.`, `..`,
 /// liens symboliques), sinon rendu absolu au mieux. Évite les doublons dus à
 /// deux représentations du même chemin.
@@ -318,17 +384,55 @@ pub fn create_work_core(
         _ => {}
     }
 
+    let work_id = write_new_work(works_root, cur, display_name)?;
+    Ok(CreateWorkOutcome::Created { work_id })
+}
+
+/// Crée un Work multi-fichiers (`document_set`) : un seul chemin de dossier
+/// donne un ensemble `Folder`, sinon les chemins forment un ensemble `Files`.
+/// Un Work déjà lié au même ensemble (nature, racine, membres) est renvoyé en
+/// `ExactExists`, jamais dupliqué. Un dépôt git passe par `create_work_core`.
+pub fn create_document_set_work_core(
+    works_root: &Path,
+    paths: &[String],
+    display_name: Option<String>,
+) -> Result<CreateWorkOutcome, String> {
+    let spec = match paths {
+        [one] if Path::new(one).is_dir() => document_set::folder_spec(Path::new(one))?,
+        _ => document_set::files_spec(&paths.iter().map(PathBuf::from).collect::<Vec<_>>())?,
+    };
+    let cur = capture_document_set(spec)?;
+    let records = work_store::read_all_work_records(works_root)?;
+    if let Some(existing) = records
+        .iter()
+        .find(|r| r.document.document_set == cur.document_set)
+    {
+        return Ok(CreateWorkOutcome::ExactExists {
+            work_id: existing.work_id.clone(),
+        });
+    }
+
+    let work_id = write_new_work(works_root, cur, display_name)?;
+    Ok(CreateWorkOutcome::Created { work_id })
+}
+
+/// Écrit un nouveau Work (dossiers + métadonnées) et met l'index à jour.
+fn write_new_work(
+    works_root: &Path,
+    document: WorkDocumentMetadata,
+    display_name: Option<String>,
+) -> Result<WorkId, String> {
     let id = WorkId::new();
     work_store::create_work_directories(works_root, &id)?;
 
-    let mut record = WorkRecord::new(id.clone(), now_utc(), cur);
+    let mut record = WorkRecord::new(id.clone(), now_utc(), document);
     record.local_metadata.display_name = display_name;
     work_store::write_work_metadata_atomic(works_root, &record)?;
 
     // Mise à jour atomique du cache (index.json reste un cache reconstructible).
     work_store::rebuild_index_from_disk(works_root)?;
 
-    Ok(CreateWorkOutcome::Created { work_id: id })
+    Ok(id)
 }
 
 // --- LISTE / CHARGEMENT / ARCHIVAGE ------------------------------------------
@@ -363,6 +467,7 @@ fn count_period_files(works_root: &Path, work_id: &WorkId) -> u32 {
 /// résumé des périodes/certificats (listes vides possibles).
 pub fn load_work_core(works_root: &Path, work_id: &WorkId) -> Result<LoadedWork, String> {
     let record = work_store::read_work_metadata(works_root, work_id)?;
+    let canonicalization = crate::work_period::work_canon_scheme(works_root, work_id)?;
     Ok(LoadedWork {
         work_id: record.work_id.clone(),
         lifecycle: record.lifecycle,
@@ -372,9 +477,24 @@ pub fn load_work_core(works_root: &Path, work_id: &WorkId) -> Result<LoadedWork,
         last_activity_utc: record.last_activity_utc.clone(),
         period_count: count_period_files(works_root, work_id),
         certificate_count: 0,
+        canonicalization: canonicalization.label().to_string(),
     })
 }
 
+/// Règle le schéma de canonicalisation des NOUVELLES périodes et certificats
+/// du Work (`HO-CANON-V1` | `HO-JCS-V1`). Les records déjà signés gardent le
+/// leur (déclaré et signé dans chacun) : rien n'est re-signé.
+pub fn set_work_canonicalization_core(
+    works_root: &Path,
+    work_id: &WorkId,
+    scheme: &str,
+) -> Result<(), String> {
+    let scheme = crate::work_period::CanonScheme::from_label(scheme)?;
+    let mut record = work_store::read_work_metadata(works_root, work_id)?;
+    record.canonicalization = Some(scheme.label().to_string());
+    work_store::write_work_metadata_atomic(works_root, &record)
+}
+
 /// Archive un Work : ACTIVE → ARCHIVED, sans aucune suppression de fichier.
 pub fn archive_work_core(works_root: &Path, work_id: &WorkId) -> Result<(), String> {
     let mut record = work_store::read_work_metadata(works_root, work_id)?;
@@ -523,19 +643,37 @@ pub fn close_interrupted_observation_core(
 // --- COMMANDES TAURI (fines enveloppes) --------------------------------------
 
 #[tauri::command]
-pub fn resolve_work_for_document(document_path: String) -> Result<ResolveResult, String> {
-    let root = work_store::works_root()?;
-    resolve_work_for_document_core(&root, &document_path)
+pub async fn resolve_work_for_document(document_path: String) -> Result<ResolveResult, String> {
+    crate::run_blocking(move || {
+        let root = work_store::works_root()?;
+        resolve_work_for_document_core(&root, &document_path)
+    })
+    .await
 }
 
 #[tauri::command]
-pub fn create_work(
+pub async fn create_work(
     document_path: String,
     display_name: Option<String>,
     force_new: Option<bool>,
 ) -> Result<CreateWorkOutcome, String> {
-    let root = work_store::works_root()?;
-    create_work_core(&root, &document_path, display_name, force_new)
+    crate::run_blocking(move || {
+        let root = work_store::works_root()?;
+        create_work_core(&root, &document_path, display_name, force_new)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn create_document_set_work(
+    paths: Vec<String>,
+    display_name: Option<String>,
+) -> Result<CreateWorkOutcome, String> {
+    crate::run_blocking(move || {
+        let root = work_store::works_root()?;
+        create_document_set_work_core(&root, &paths, display_name)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -556,6 +694,12 @@ pub fn archive_work(work_id: String) -> Result<(), String> {
     archive_work_core(&root, &WorkId(work_id))
 }
 
+#[tauri::command]
+pub fn set_work_canonicalization(work_id: String, scheme: String) -> Result<(), String> {
+    let root = work_store::works_root()?;
+    set_work_canonicalization_core(&root, &WorkId(work_id), &scheme)
+}
+
 /// Read-only : résumé de l'état d'un Work (comptes d'observations, pending,
 /// dernières séquences cert/package). Ne signe rien, ne crée rien.
 #[tauri::command]
@@ -577,6 +721,7 @@ pub fn close_interrupted_observation(work_id: String) -> Result<WorkSummary, Str
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sha2::{Digest, Sha256};
     use uuid::Uuid;
 
     fn temp_root() -> std::path::PathBuf {
@@ -737,6 +882,95 @@ mod tests {
         cleanup(&root);
     }
 
+    #[test]
+    fn test_7c_depot_git_lie_a_l_etat_de_l_arbre() {
+        let root = temp_root();
+        let works = root.join("Works");
+        let repo = root.join("devoir");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        write_file(&repo.join("src"), "main.rs", b"fn main() {}\n");
+        write_file(&repo, ".gitignore", b"target/\n");
+        write_file(&repo.join("target"), "app", b"binaire");
+
+        let out = create_work_core(&works, repo.to_str().unwrap(), None, None).unwrap();
+        let CreateWorkOutcome::Created { work_id } = out else {
+            panic!("création attendue : {out:?}");
+        };
+        let loaded = load_work_core(&works, &work_id).unwrap();
+        let (hash, size) = repo_tree::repo_tree_digest(&repo).unwrap();
+        assert_eq!(loaded.document.hash_initial.as_deref(), Some(hash.as_str()));
+        assert_eq!(loaded.document.size_initial, Some(size));
+        assert_eq!(loaded.document.mime.as_deref(), Some(repo_tree::REPO_MIME));
+
+        // La frontière d'une période suit l'état du dépôt (pas `target/`).
+        write_file(&repo.join("target"), "app", b"autre binaire");
+        let (_p, h0, _s) = capture_start_boundary(repo.to_str().unwrap()).unwrap();
+        assert_eq!(h0, hash);
+        write_file(&repo.join("src"), "lib.rs", b"pub fn f() {}\n");
+        let (_p, h1, _s) = capture_start_boundary(repo.to_str().unwrap()).unwrap();
+        assert_ne!(h1, hash);
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_7d_ensemble_de_fichiers_dossier_et_liste() {
+        let root = temp_root();
+        let works = root.join("Works");
+        let livre = root.join("livre");
+        let ch1 = write_file(&livre, "ch1.md", b"# Un\n");
+        let ch2 = write_file(&livre.join("partie2"), "ch2.md", b"# Deux\n");
+        write_file(&livre, "notes.txt", b"hors liste");
+
+        // Dossier : tous les fichiers, empreinte de manifeste.
+        let dir = vec![livre.to_string_lossy().to_string()];
+        let out = create_document_set_work_core(&works, &dir, None).unwrap();
+        let CreateWorkOutcome::Created { work_id } = out else {
+            panic!("création attendue : {out:?}");
+        };
+        let loaded = load_work_core(&works, &work_id).unwrap();
+        let spec = loaded.document.document_set.clone().unwrap();
+        let (_p, h0, s0, members) = capture_set_boundary(&spec).unwrap();
+        assert_eq!(loaded.document.hash_initial.as_deref(), Some(h0.as_str()));
+        assert_eq!(loaded.document.size_initial, Some(s0));
+        assert_eq!(
+            loaded.document.mime.as_deref(),
+            Some(document_set::DOCUMENT_SET_MIME)
+        );
+        assert_eq!(members.len(), 3);
+        // Même ensemble : jamais dupliqué.
+        assert_eq!(
+            create_document_set_work_core(&works, &dir, None).unwrap(),
+            CreateWorkOutcome::ExactExists {
+                work_id: work_id.clone()
+            }
+        );
+
+        // Liste : seuls les fichiers choisis comptent.
+        let files = vec![
+            ch1.to_string_lossy().to_string(),
+            ch2.to_string_lossy().to_string(),
+        ];
+        let out = create_document_set_work_core(&works, &files, None).unwrap();
+        let CreateWorkOutcome::Created { work_id: w2 } = out else {
+            panic!("création attendue : {out:?}");
+        };
+        let spec2 = load_work_core(&works, &w2)
+            .unwrap()
+            .document
+            .document_set
+            .unwrap();
+        assert_eq!(spec2.members, vec!["ch1.md", "partie2/ch2.md"]);
+        let (_p, a, _s, _m) = capture_set_boundary(&spec2).unwrap();
+        write_file(&livre, "notes.txt", b"autre");
+        assert_eq!(capture_set_boundary(&spec2).unwrap().1, a);
+        write_file(&livre.join("partie2"), "ch2.md", b"# Deux, revu\n");
+        assert_ne!(capture_set_boundary(&spec2).unwrap().1, a);
+
+        // `create_work` refuse toujours un dossier (test_7b inchangé).
+        assert!(create_work_core(&works, &dir[0], None, None).is_err());
+        cleanup(&root);
+    }
+
     #[test]
     fn test_8_index_absent_reconstruction() {
         let root = temp_root();
@@ -788,6 +1022,40 @@ mod tests {
         cleanup(&root);
     }
 
+    #[test]
+    fn test_10b_reglage_canonicalisation() {
+        let root = temp_root();
+        let works = root.join("Works");
+        let doc = write_file(&root.join("docs"), "j.txt", b"schema");
+        let id = match create_work_core(&works, doc.to_str().unwrap(), None, None).unwrap() {
+            CreateWorkOutcome::Created { work_id } => work_id,
+            other => panic!("attendu Created, reçu {:?}", other),
+        };
+        let work_json = works.join(id.as_str()).join("work.json");
+        // Défaut : aucun champ écrit (work.json antérieurs inchangés).
+        assert!(!fs::read_to_string(&work_json)
+            .unwrap()
+            .contains("canonicalization"));
+        assert_eq!(
+            load_work_core(&works, &id).unwrap().canonicalization,
+            "HO-CANON-V1"
+        );
+
+        set_work_canonicalization_core(&works, &id, "HO-JCS-V1").unwrap();
+        assert_eq!(
+            load_work_core(&works, &id).unwrap().canonicalization,
+            "HO-JCS-V1"
+        );
+        assert!(set_work_canonicalization_core(&works, &id, "RFC8785")
+            .unwrap_err()
+            .contains("schéma de canonicalisation inattendu"));
+        assert_eq!(
+            load_work_core(&works, &id).unwrap().canonicalization,
+            "HO-JCS-V1"
+        );
+        cleanup(&root);
+    }
+
     #[test]
     fn test_11_work_id_invalide_rejete() {
         let root = temp_root();
@@ -1002,9 +1270,13 @@ mod tests {
                 document_path: "/tmp/x".to_string(),
                 hash_start: start,
                 size_start: 10,
+                chunked_start: None,
                 hash_end: end,
                 size_end: 20,
+                chunked_end: None,
+                member_changes: None,
                 change_observed_during_period: true,
+                change_magnitude: None,
                 engine: json!({ "analysis": { "gate_passed": gate } }),
             };
             let p = crate::work_period::sign_period_record(inputs, k).unwrap();
@@ -1100,6 +1372,8 @@ mod tests {
             document_path: "/tmp/x".to_string(),
             hash_start: "a".repeat(64),
             size_start: 10,
+            chunked_start: None,
+            members_start: None,
             started_at: "2026-08-05T10:00:00Z".to_string(),
             state: PendingState::Pending,
         };
@@ -1122,6 +1396,25 @@ mod tests {
         cleanup(&root);
     }
 
+    #[test]
+    fn test_empreinte_par_blocs_gros_media_seulement() {
+        let root = temp_root();
+        let clip = write_file(&root, "clip.mp4", &[7u8; 4096]);
+        let note = write_file(&root, "note.txt", b"texte");
+
+        let (path, hash, _) = capture_start_boundary(&clip.to_string_lossy()).unwrap();
+        assert_eq!(hash, file_hash::sha256_file(&clip).unwrap());
+        let fp = capture_chunked_fingerprint(&path, &hash).unwrap();
+        assert_eq!(fp.scheme, file_chunks::CHUNK_SCHEME);
+        assert_eq!(fp.chunk_count, 1);
+        // Contenu différent du hash de la frontière : aucune empreinte.
+        assert!(capture_chunked_fingerprint(&path, &"0".repeat(64)).is_none());
+
+        let (path, hash, _) = capture_start_boundary(&note.to_string_lossy()).unwrap();
+        assert!(capture_chunked_fingerprint(&path, &hash).is_none());
+        cleanup(&root);
+    }
+
     #[test]
     fn test_summary_work_inconnu_erreur() {
         let root = temp_root();
@@ -1145,6 +1438,8 @@ mod tests {
             document_path: "/tmp/x".to_string(),
             hash_start: "a".repeat(64),
             size_start: 10,
+            chunked_start: None,
+            members_start: None,
             started_at: "2026-08-05T10:00:00Z".to_string(),
             state: PendingState::Pending,
         };
@@ -1198,6 +1493,8 @@ mod tests {
             document_path: "/tmp/x".to_string(),
             hash_start: p0.hash_start.clone(),
             size_start: p0.size_start,
+            chunked_start: p0.chunked_start.clone(),
+            members_start: None,
             started_at: "2026-08-05T10:00:00Z".to_string(),
             state: PendingState::Pending,
         };
@@ -22,11 +22,13 @@
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 
-use crate::work_period::{self, ObservationPeriod};
-use crate::work_store::{WorkId, WorkLifecycle};
+use crate::document_set::{self, MemberChange};
+use crate::work_period::{self, CanonScheme, ObservationPeriod};
+use crate::work_store::{self, WorkId, WorkLifecycle};
 use crate::{
     begin_capture, capture_owner, end_capture, ensure_signing_key, finalize_capture,
-    is_capture_active, work_commands, work_pending, ActiveCaptureOwner, AppState, PasteStats,
+    is_capture_active, work_change, work_commands, work_pending, work_snapshot, ActiveCaptureOwner,
+    AppState, PasteStats,
 };
 
 fn now_utc() -> String {
@@ -95,8 +97,25 @@ where
     };
 
     // 5) Recalcul de la frontière de départ depuis le document réel.
-    let (document_path, hash_start, size_start) =
-        work_commands::capture_start_boundary(&loaded.document.document_path)?;
+    //    Work multi-fichiers : empreinte de manifeste + membres (changements
+    //    par fichier au stop), ni snapshot de texte ni empreinte par blocs.
+    let (document_path, hash_start, size_start, start_version, chunked_start, members_start) =
+        match &loaded.document.document_set {
+            Some(spec) => {
+                let (path, hash, size, members) = work_commands::capture_set_boundary(spec)?;
+                (path, hash, size, None, None, Some(members))
+            }
+            None => {
+                let (path, hash, size) =
+                    work_commands::capture_start_boundary(&loaded.document.document_path)?;
+                // Version de départ (mesure d'ampleur au stop). Non fatal : format
+                // non mesurable, trop gros ou déjà modifié -> aucune mesure.
+                let start_version = work_snapshot::read_start_version(Path::new(&path), &hash);
+                // Gros média : empreinte par blocs (cache de la passe de hash).
+                let chunked = work_commands::capture_chunked_fingerprint(&path, &hash);
+                (path, hash, size, start_version, chunked, None)
+            }
+        };
 
     // 6) Identité stable de la période.
     let period_id = uuid::Uuid::new_v4().to_string();
@@ -112,6 +131,8 @@ where
         document_path,
         hash_start,
         size_start,
+        chunked_start,
+        members_start,
         started_at: now_utc(),
         state: work_pending::PendingState::Pending,
     };
@@ -137,6 +158,11 @@ where
         let _ = work_pending::abort_pending_before_start(works_root, &work_id, &period_id);
         return Err(e);
     }
+    // Snapshot non bloquant : sans lui, pas de mesure texte, et le contrôle
+    // saisie/croissance de la période se rabat sur la taille du fichier.
+    if let Some(bytes) = start_version {
+        let _ = work_snapshot::write_start_snapshot(works_root, &work_id, &period_id, &bytes);
+    }
 
     Ok(StartOutcome {
         work_id,
@@ -174,6 +200,29 @@ pub(crate) struct StopOutcome {
     pub pending_cleanup_deferred: bool,
 }
 
+/// Frontière de fin : (hash_end, size_end, changements par fichier). Un Work
+/// multi-fichiers (pending avec `members_start`) est recapturé depuis la
+/// définition de son ensemble ; sinon le document unique du pending.
+fn capture_end_boundary(
+    works_root: &Path,
+    work_id: &WorkId,
+    pending: &work_pending::PendingPeriod,
+) -> Result<(String, u64, Option<Vec<MemberChange>>), String> {
+    let Some(members_start) = &pending.members_start else {
+        let (_p, hash_end, size_end) =
+            work_commands::capture_start_boundary(&pending.document_path)?;
+        return Ok((hash_end, size_end, None));
+    };
+    let record = work_store::read_work_metadata(works_root, work_id)?;
+    let spec = record
+        .document
+        .document_set
+        .ok_or_else(|| "ensemble de fichiers du Work introuvable".to_string())?;
+    let (_p, hash_end, size_end, members_end) = work_commands::capture_set_boundary(&spec)?;
+    let changes = document_set::member_changes(members_start, &members_end);
+    Ok((hash_end, size_end, Some(changes)))
+}
+
 /// Cœur de `stop_work_period`, testable via seams `sign_fn`/`write_fn`.
 ///
 /// Règles d'échec figées :
@@ -191,7 +240,7 @@ fn stop_work_period_inner<SG, WR>(
     write_fn: WR,
 ) -> Result<StopOutcome, String>
 where
-    SG: FnOnce(work_period::PeriodInputs) -> Result<ObservationPeriod, String>,
+    SG: FnOnce(work_period::PeriodInputs, CanonScheme) -> Result<ObservationPeriod, String>,
     WR: FnOnce(&Path, &ObservationPeriod) -> Result<PathBuf, String>,
 {
     // 1) Capture active + owner WORK.
@@ -218,6 +267,10 @@ where
         return Err("period_id du pending ne correspond pas à la capture active".to_string());
     }
 
+    // 5b) Schéma de canonicalisation réglé sur le Work (lu avant l'arrêt :
+    //     un réglage illisible refuse l'arrêt, moteur intact).
+    let canon_scheme = work_period::work_canon_scheme(works_root, &requested_work_id)?;
+
     // 6) Arrêter réellement le moteur (owner -> Idle).
     let snap = end_capture(state);
 
@@ -234,14 +287,29 @@ where
     let score = out.score;
 
     // 8) hash_end/size_end. Échec après arrêt -> INTERRUPTED (Décision 5B #2).
-    let (_p, hash_end, size_end) =
-        match work_commands::capture_start_boundary(&pending.document_path) {
+    let (hash_end, size_end, member_changes) =
+        match capture_end_boundary(works_root, &requested_work_id, &pending) {
             Ok(t) => t,
             Err(e) => {
                 let _ = work_pending::mark_pending_interrupted(works_root, &requested_work_id);
                 return Err(e);
             }
         };
+    let chunked_end = if member_changes.is_some() {
+        None
+    } else {
+        work_commands::capture_chunked_fingerprint(&pending.document_path, &hash_end)
+    };
+    // Ampleur du changement (seuls des compteurs entrent dans la période ; le
+    // snapshot est purgé avec le pending).
+    let document_path = Path::new(&pending.document_path);
+    let start_text =
+        work_snapshot::read_start_snapshot(works_root, &requested_work_id, &pending.period_id)
+            .ok()
+            .flatten()
+            .and_then(|bytes| work_change::document_text_from_bytes(document_path, &bytes).ok())
+            .flatten();
+    let change_magnitude = work_change::measure_period_change(start_text.as_ref(), document_path);
 
     // 9) Construire l'ObservationPeriod (frontière depuis le pending).
     let inputs = work_period::PeriodInputs {
@@ -253,15 +321,19 @@ where
         document_path: pending.document_path.clone(),
         hash_start: pending.hash_start.clone(),
         size_start: pending.size_start,
+        chunked_start: pending.chunked_start.clone(),
         hash_end,
         size_end,
+        chunked_end,
+        member_changes,
         // 5B : aucun watcher documentaire backend -> jamais dérivé du clavier/souris.
         change_observed_during_period: false,
+        change_magnitude,
         engine: out.engine,
     };
 
     // 10) Signer.
-    let period = match sign_fn(inputs) {
+    let period = match sign_fn(inputs, canon_scheme) {
         Ok(p) => p,
         Err(e) => {
             let _ = work_pending::mark_pending_interrupted(works_root, &requested_work_id);
@@ -306,9 +378,9 @@ pub(crate) fn stop_work_period_core(
         state,
         work_id,
         paste,
-        |inputs| {
+        |inputs, scheme| {
             let key = ensure_signing_key()?;
-            work_period::sign_period_record(inputs, &key)
+            work_period::sign_period_record_with(inputs, &key, scheme)
         },
         |root, period| work_period::write_period_once(root, period),
     )
@@ -369,9 +441,13 @@ mod tests {
             document_path: "/tmp/sujet.txt".to_string(),
             hash_start: "a".repeat(64),
             size_start: 10,
+            chunked_start: None,
             hash_end: "b".repeat(64),
             size_end: 12,
+            chunked_end: None,
+            member_changes: None,
             change_observed_during_period: false,
+            change_magnitude: None,
             engine: json!({ "score": 80 }),
         };
         let period = work_period::sign_period_record(inputs, &key).unwrap();
@@ -569,9 +645,12 @@ mod tests {
 
     // --- COMMIT 5B : arrêt de période ----------------------------------------
 
-    fn eph_sign(inputs: work_period::PeriodInputs) -> Result<ObservationPeriod, String> {
+    fn eph_sign(
+        inputs: work_period::PeriodInputs,
+        scheme: CanonScheme,
+    ) -> Result<ObservationPeriod, String> {
         let key = SigningKey::generate(&mut OsRng);
-        work_period::sign_period_record(inputs, &key)
+        work_period::sign_period_record_with(inputs, &key, scheme)
     }
 
     fn write_real(root: &Path, period: &ObservationPeriod) -> Result<PathBuf, String> {
@@ -711,7 +790,7 @@ mod tests {
             &state,
             wid.clone(),
             PasteStats::default(),
-            |_inputs| Err("signature KO".to_string()),
+            |_inputs, _scheme| Err("signature KO".to_string()),
             write_real,
         );
         assert!(res.is_err());
@@ -791,6 +870,42 @@ mod tests {
         cleanup(&base);
     }
 
+    #[test]
+    fn test_5b_8b_ampleur_du_changement_mesuree() {
+        let base = temp_base();
+        let (works, wid, state, doc) = start(&base, b"Premier paragraphe.");
+        let snapshots = works.join(wid.as_str()).join("snapshots");
+        assert_eq!(fs::read_dir(&snapshots).unwrap().count(), 1);
+        fs::write(
+            &doc,
+            "Premier paragraphe.\n\nUn second paragraphe entièrement nouveau.",
+        )
+        .unwrap();
+        stop_work_period_inner(
+            &works,
+            &state,
+            wid.clone(),
+            PasteStats::default(),
+            eph_sign,
+            write_real,
+        )
+        .unwrap();
+        let last = work_period::find_last_period(&works, &wid)
+            .unwrap()
+            .unwrap();
+        let m = last
+            .change_magnitude
+            .as_ref()
+            .expect("document texte -> ampleur mesurée");
+        assert_eq!(m.paragraphs_start, 1);
+        assert_eq!(m.paragraphs_end, 2);
+        assert!(!m.is_trivial());
+        assert!(work_period::verify_period_record(&last).is_ok());
+        // Version de départ purgée avec le pending.
+        assert!(!snapshots.exists());
+        cleanup(&base);
+    }
+
     #[test]
     fn test_5b_9_finalize_capture_bloc_paste() {
         // Exerce le bloc paste déplacé : collage dominant force gate=false + flag.
@@ -839,4 +954,88 @@ mod tests {
         assert!(!projects.join(wid.as_str()).exists());
         cleanup(&base);
     }
+
+    /// Simule une observation réelle : 200 frappes réparties sur ~2 min
+    /// (gate du moteur franchi), horodatées dans la capture en cours.
+    fn type_for_two_minutes(state: &AppState) {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut rt = state.runtime.lock().unwrap();
+        rt.start_timestamp = now - 120_000;
+        rt.keystroke_timestamps = (0..200i64)
+            .map(|i| now - 118_000 + i * 580 + (i * 37) % 160)
+            .collect();
+    }
+
+    /// Bout en bout : Work réglé en HO-JCS-V1 -> période scellée par le flux
+    /// d'arrêt réel puis certificat, tous deux HO-JCS-V1 et vérifiables après
+    /// relecture disque.
+    #[test]
+    fn test_5b_11_reglage_jcs_periode_et_certificat() {
+        let base = temp_base();
+        let (works, wid, doc) = make_work(&base, b"avant");
+        work_commands::set_work_canonicalization_core(&works, &wid, "HO-JCS-V1").unwrap();
+        let state = AppState::new_detached();
+        start_work_period_core(&works, &state, wid.clone()).unwrap();
+        type_for_two_minutes(&state);
+        fs::write(
+            &doc,
+            "avant\n\nUn paragraphe entier rédigé pendant l'observation, bien au-delà d'une retouche.",
+        )
+        .unwrap();
+        stop_work_period_inner(
+            &works,
+            &state,
+            wid.clone(),
+            PasteStats::default(),
+            eph_sign,
+            write_real,
+        )
+        .unwrap();
+
+        let period = work_period::find_last_period(&works, &wid)
+            .unwrap()
+            .unwrap();
+        assert_eq!(period.signature_metadata.canonicalization, "HO-JCS-V1");
+        assert!(crate::work_certificate::period_gate_passed(&period));
+        work_period::verify_period_record(&period).unwrap();
+
+        let key = SigningKey::generate(&mut OsRng);
+        let cert = crate::work_certificate::create_work_certificate_with(
+            &works,
+            &wid,
+            "2026-08-01T10:00:00Z",
+            &key,
+        )
+        .unwrap();
+        assert_eq!(
+            cert.signature_metadata.canonicalization.as_deref(),
+            Some("HO-JCS-V1")
+        );
+        let chain = crate::work_certificate::load_certificates(&works, &wid).unwrap();
+        assert_eq!(chain, vec![cert]);
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_5b_12_reglage_illisible_refuse_moteur_intact() {
+        let base = temp_base();
+        let (works, wid, state, _doc) = start(&base, b"x");
+        let mut record = work_store::read_work_metadata(&works, &wid).unwrap();
+        record.canonicalization = Some("RFC8785".to_string());
+        work_store::write_work_metadata_atomic(&works, &record).unwrap();
+        let res = stop_work_period_inner(
+            &works,
+            &state,
+            wid.clone(),
+            PasteStats::default(),
+            eph_sign,
+            write_real,
+        );
+        assert!(res.is_err());
+        assert!(is_capture_active(&state), "moteur intact");
+        assert!(work_period::find_last_period(&works, &wid)
+            .unwrap()
+            .is_none());
+        cleanup(&base);
+    }
 }
@@ -0,0 +1,917 @@
+//! work_image — Adaptateur image (V2-B) : copie PNG/JPEG labellisée + sidecar.
+//!
+//! Portée STRICTE : à partir d'une image source certifiée (PNG ou JPEG),
+//! produire dans le dossier de sortie :
+//! - `labeled_image.png|jpg` : l'image d'origine redressée selon son
+//!   orientation EXIF, pixels recopiés à l'identique en (0,0), avec une MARGE
+//!   AJOUTÉE à droite qui reçoit la cartouche Work (jamais de dessin dans la
+//!   zone utile) ; EXIF d'origine conservé (`ImageDescription` ajouté ou
+//!   remplacé, `Orientation` neutralisée) + XMP (`ho:CertificateId`,
+//!   `ho:VerifyUrl`, `ho:Verdict`, `ho:SourceSha256`) ; profil ICC conservé ;
+//! - `labeled_image.ho.json` : sidecar signé (`work_sidecar`) décrivant la copie
+//!   et les empreintes perceptuelles de la source et de la copie
+//!   (`image_fingerprint`).
+//!
+//! Le fichier source n'est JAMAIS modifié. Sortie PNG : copie sans perte (16 bits
+//! conservés). Sortie JPEG : ré-encodage qualité 95 (le JPEG n'a pas de mode sans
+//! perte) ; la source intacte reste la référence certifiée.
+//!
+//! AUCUNE commande Tauri (voir `work_publish`), AUCUNE UI, AUCUN réseau,
+//! AUCUN chemin local dans l'artefact, le XMP ou le sidecar.
+//!
+//! Garde-fous :
+//! - format détecté par signature (magic), pas par extension ;
+//! - valeurs XMP échappées (XML) ; segment JPEG APP1 borné à 64 Ko ;
+//! - le sidecar est signé par la clé du certificat (sinon échec du rendu).
+
+// Fondation (V2-B) : branché par `work_publish::create_native_image_package_core`.
+#![allow(dead_code)]
+
+use ed25519_dalek::SigningKey;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::imageops::{self, FilterType};
+use image::metadata::Orientation;
+use image::{
+    DynamicImage, ExtendedColorType, GenericImageView, ImageDecoder, ImageEncoder, ImageReader,
+    Rgba, RgbaImage,
+};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::evidence_kernel::{
+    ArtifactRenderError, DocumentAdapter, DocumentArtifactKind, LabeledArtifactRequest,
+    MediaSpecificMetadata, RenderedArtifactRef,
+};
+use crate::image_fingerprint;
+use crate::work_sidecar;
+
+const LABELED_PNG_FILENAME: &str = "labeled_image.png";
+const LABELED_JPEG_FILENAME: &str = "labeled_image.jpg";
+const SIDECAR_FILENAME: &str = "labeled_image.ho.json";
+const CARTOUCHE_FILENAME: &str = "cartouche.png";
+
+/// Proportions de la cartouche Work (viewBox 220x300).
+const CARTOUCHE_W: u32 = 220;
+const CARTOUCHE_H: u32 = 300;
+/// Fond de la marge (crème de la cartouche).
+const MARGIN_BG: [u8; 3] = [0xf8, 0xf4, 0xec];
+const JPEG_QUALITY: u8 = 95;
+
+const PNG_MAGIC: &[u8] = b"\x89PNG\r\n\x1a\n";
+const JPEG_MAGIC: &[u8] = &[0xFF, 0xD8, 0xFF];
+const XMP_PNG_KEYWORD: &[u8] = b"XML:com.adobe.xmp";
+const XMP_JPEG_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_NS: &str = "https://humanorigin.app/ns/work/1.0/";
+/// Tag TIFF `ImageDescription` (IFD0, ASCII).
+const EXIF_IMAGE_DESCRIPTION: u16 = 0x010E;
+/// EXIF max. d'un segment JPEG APP1 (longueur 2 + en-tête `Exif\0\0` 6).
+const EXIF_MAX_LEN: usize = u16::MAX as usize - 2 - 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ImageFormat {
+    Png,
+    Jpeg,
+}
+
+impl ImageFormat {
+    fn artifact_filename(self) -> &'static str {
+        match self {
+            ImageFormat::Png => LABELED_PNG_FILENAME,
+            ImageFormat::Jpeg => LABELED_JPEG_FILENAME,
+        }
+    }
+
+    fn mime_type(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+        }
+    }
+}
+
+/// Format d'une image d'après sa signature (magic).
+pub(crate) fn detect_image_format(bytes: &[u8]) -> Option<ImageFormat> {
+    if bytes.starts_with(PNG_MAGIC) {
+        Some(ImageFormat::Png)
+    } else if bytes.starts_with(JPEG_MAGIC) {
+        Some(ImageFormat::Jpeg)
+    } else {
+        None
+    }
+}
+
+// --- ADAPTATEUR ---------------------------------------------------------------
+
+/// Adaptateur image : signe le sidecar avec la clé du certificat.
+pub(crate) struct ImageDocumentAdapter<'a> {
+    signing_key: &'a SigningKey,
+}
+
+impl<'a> ImageDocumentAdapter<'a> {
+    pub(crate) fn new(signing_key: &'a SigningKey) -> Self {
+        Self { signing_key }
+    }
+}
+
+impl DocumentAdapter for ImageDocumentAdapter<'_> {
+    fn adapter_name(&self) -> &'static str {
+        "image"
+    }
+
+    fn artifact_kind(&self) -> DocumentArtifactKind {
+        DocumentArtifactKind::Image
+    }
+
+    fn default_artifact_filename(&self) -> &'static str {
+        LABELED_PNG_FILENAME
+    }
+
+    fn render_labeled_artifact(
+        &self,
+        request: &LabeledArtifactRequest,
+        source: &Path,
+        output_dir: &Path,
+    ) -> Result<RenderedArtifactRef, ArtifactRenderError> {
+        let source_bytes =
+            fs::read(source).map_err(|e| ArtifactRenderError::RenderFailed(e.to_string()))?;
+        let format = detect_image_format(&source_bytes).ok_or_else(|| {
+            ArtifactRenderError::RenderFailed("format image non supporté (PNG/JPEG)".to_string())
+        })?;
+
+        // Marquage : cartouche Work native, rastérisée dans le dossier de sortie.
+        let cartouche_path = output_dir.join(CARTOUCHE_FILENAME);
+        render_cartouche(request, &cartouche_path)?;
+        let cartouche = image::open(&cartouche_path)
+            .map_err(|e| ArtifactRenderError::MarkingFailed(e.to_string()))?
+            .to_rgba8();
+
+        let labeled_bytes = render_labeled_image(&source_bytes, format, &cartouche, request)?;
+        let artifact_name = format.artifact_filename();
+        fs::write(output_dir.join(artifact_name), &labeled_bytes)
+            .map_err(|e| ArtifactRenderError::RenderFailed(e.to_string()))?;
+
+        let artifact = RenderedArtifactRef {
+            artifact_name: artifact_name.to_string(),
+            media_type: self.artifact_kind(),
+            fingerprint_sha256: Some(sha256_hex_bytes(&labeled_bytes)),
+            size_bytes: Some(labeled_bytes.len() as u64),
+            mime_type: Some(format.mime_type().to_string()),
+        };
+
+        // Le sidecar porte l'empreinte perceptuelle de la SOURCE et le dHash de
+        // la copie labellisée (marge incluse) : une recompression en aval de
+        // l'une ou de l'autre reste reconnaissable (`image_fingerprint`).
+        let mut fingerprint = image_fingerprint::fingerprint_image_bytes(&source_bytes)
+            .map_err(ArtifactRenderError::RenderFailed)?;
+        fingerprint.labeled_perceptual_hash = Some(
+            image_fingerprint::perceptual_hash_bytes(&labeled_bytes)
+                .map_err(ArtifactRenderError::RenderFailed)?,
+        );
+        let mut sidecar_request = request.clone();
+        sidecar_request.source_version.media_specific =
+            Some(MediaSpecificMetadata::Image(fingerprint));
+        let sidecar =
+            work_sidecar::sign_sidecar(&sidecar_request, Some(artifact.clone()), self.signing_key)
+                .map_err(ArtifactRenderError::RenderFailed)?;
+        work_sidecar::write_sidecar(&sidecar, &output_dir.join(SIDECAR_FILENAME))
+            .map_err(ArtifactRenderError::RenderFailed)?;
+        Ok(artifact)
+    }
+
+    fn sidecar_filename(&self) -> Option<&'static str> {
+        Some(SIDECAR_FILENAME)
+    }
+}
+
+fn sha256_hex_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn render_cartouche(
+    request: &LabeledArtifactRequest,
+    out: &Path,
+) -> Result<(), ArtifactRenderError> {
+    let inputs = crate::work_cartouche::WorkCartoucheInputs {
+        certificate_id: request.certificate_id.clone(),
+        verdict: request.verdict_label.clone(),
+        identity_status: "LOCAL_DEVICE".to_string(),
+        verify_url: request.verify_url.clone(),
+        signing_key_id: request.signing_key_id.clone(),
+        created_at: request.certificate_created_at.clone(),
+    };
+    crate::work_cartouche::render_work_cartouche_png(&inputs, out)
+        .map_err(ArtifactRenderError::MarkingFailed)?;
+    if !out.is_file() {
+        return Err(ArtifactRenderError::MarkingNotProduced);
+    }
+    Ok(())
+}
+
+// --- COMPOSITION --------------------------------------------------------------
+
+/// Largeur de la marge : cartouche à pleine hauteur, proportions conservées.
+fn margin_width(height: u32) -> u32 {
+    ((u64::from(height) * u64::from(CARTOUCHE_W)).div_ceil(u64::from(CARTOUCHE_H)) as u32).max(1)
+}
+
+/// Toile `source | marge` : la source est recopiée telle quelle en (0,0), la
+/// cartouche est mise à l'échelle dans la marge ajoutée à droite.
+fn compose(source: &DynamicImage, cartouche: &RgbaImage, format: ImageFormat) -> DynamicImage {
+    let (w, h) = source.dimensions();
+    let margin = margin_width(h);
+    let mark =
+        DynamicImage::ImageRgba8(imageops::resize(cartouche, margin, h, FilterType::Lanczos3));
+    let bg = Rgba([MARGIN_BG[0], MARGIN_BG[1], MARGIN_BG[2], 0xFF]);
+
+    let is_16_bit = matches!(
+        source,
+        DynamicImage::ImageLuma16(_)
+            | DynamicImage::ImageLumaA16(_)
+            | DynamicImage::ImageRgb16(_)
+            | DynamicImage::ImageRgba16(_)
+    );
+    match format {
+        // JPEG : RGB 8 bits uniquement.
+        ImageFormat::Jpeg => {
+            let mut canvas = image::RgbImage::from_pixel(w + margin, h, image::Rgb(MARGIN_BG));
+            imageops::replace(&mut canvas, &source.to_rgb8(), 0, 0);
+            imageops::overlay(&mut canvas, &mark.to_rgb8(), i64::from(w), 0);
+            DynamicImage::ImageRgb8(canvas)
+        }
+        ImageFormat::Png if is_16_bit => {
+            let bg16 = Rgba(bg.0.map(|c| u16::from(c) * 257));
+            let mut canvas = image::ImageBuffer::from_pixel(w + margin, h, bg16);
+            imageops::replace(&mut canvas, &source.to_rgba16(), 0, 0);
+            imageops::overlay(&mut canvas, &mark.to_rgba16(), i64::from(w), 0);
+            DynamicImage::ImageRgba16(canvas)
+        }
+        ImageFormat::Png => {
+            let mut canvas = RgbaImage::from_pixel(w + margin, h, bg);
+            imageops::replace(&mut canvas, &source.to_rgba8(), 0, 0);
+            imageops::overlay(&mut canvas, &mark.to_rgba8(), i64::from(w), 0);
+            DynamicImage::ImageRgba8(canvas)
+        }
+    }
+}
+
+/// Décode la source (profil ICC et EXIF conservés, orientation EXIF appliquée
+/// aux pixels), compose, encode avec EXIF + XMP.
+fn render_labeled_image(
+    source_bytes: &[u8],
+    format: ImageFormat,
+    cartouche: &RgbaImage,
+    request: &LabeledArtifactRequest,
+) -> Result<Vec<u8>, ArtifactRenderError> {
+    let render_err = |e: image::ImageError| ArtifactRenderError::RenderFailed(e.to_string());
+    let mut decoder = ImageReader::new(Cursor::new(source_bytes))
+        .with_guessed_format()
+        .map_err(|e| ArtifactRenderError::RenderFailed(e.to_string()))?
+        .into_decoder()
+        .map_err(render_err)?;
+    let icc = decoder.icc_profile().map_err(render_err)?;
+    let mut source_exif = decoder.exif_metadata().map_err(render_err)?;
+    let orientation = decoder.orientation().map_err(render_err)?;
+    let mut source = DynamicImage::from_decoder(decoder).map_err(render_err)?;
+    // Pixels redressés : l'orientation de l'EXIF recopié devient neutre (1),
+    // sinon les lecteurs tourneraient la copie une seconde fois.
+    source.apply_orientation(orientation);
+    if let Some(chunk) = source_exif.as_mut() {
+        let _ = Orientation::remove_from_exif_chunk(chunk);
+    }
+
+    let labeled = compose(&source, cartouche, format);
+    let exif = exif_with_description(
+        source_exif.as_deref(),
+        &format!(
+            "HumanOrigin Work certificate {} - verify: {}",
+            request.certificate_id, request.verify_url
+        ),
+    );
+
+    let mut out = Vec::new();
+    let (w, h) = labeled.dimensions();
+    let color: ExtendedColorType = labeled.color().into();
+    match format {
+        ImageFormat::Png => {
+            let mut enc = PngEncoder::new(&mut out);
+            attach_metadata(&mut enc, icc, exif);
+            enc.write_image(labeled.as_bytes(), w, h, color)
+                .map_err(render_err)?;
+        }
+        ImageFormat::Jpeg => {
+            let mut enc = JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY);
+            attach_metadata(&mut enc, icc, exif);
+            enc.write_image(labeled.as_bytes(), w, h, color)
+                .map_err(render_err)?;
+        }
+    }
+
+    let xmp = build_xmp(request);
+    match format {
+        ImageFormat::Png => insert_png_xmp(&out, &xmp),
+        ImageFormat::Jpeg => insert_jpeg_xmp(&out, &xmp),
+    }
+}
+
+/// ICC + EXIF : best effort (un encodeur qui ne les gère pas n'échoue pas).
+fn attach_metadata<E: ImageEncoder>(enc: &mut E, icc: Option<Vec<u8>>, exif: Vec<u8>) {
+    if let Some(profile) = icc {
+        let _ = enc.set_icc_profile(profile);
+    }
+    let _ = enc.set_exif_metadata(exif);
+}
+
+// --- MÉTADONNÉES --------------------------------------------------------------
+
+/// Valeur ASCII terminée par NUL ; les caractères non ASCII deviennent `?`.
+fn exif_ascii(text: &str) -> Vec<u8> {
+    let mut value: Vec<u8> = text
+        .chars()
+        .map(|c| if c.is_ascii() { c as u8 } else { b'?' })
+        .collect();
+    value.push(0);
+    value
+}
+
+/// EXIF de la copie : EXIF source conservé, `ImageDescription` ajouté ou
+/// remplacé. Sans EXIF source exploitable (ou trop gros pour APP1) : IFD0
+/// minimal.
+fn exif_with_description(source: Option<&[u8]>, text: &str) -> Vec<u8> {
+    let value = exif_ascii(text);
+    source
+        .and_then(|tiff| merge_image_description(tiff, &value))
+        .filter(|tiff| tiff.len() <= EXIF_MAX_LEN)
+        .unwrap_or_else(|| exif_image_description(&value))
+}
+
+/// TIFF little-endian minimal : IFD0 avec le seul tag `ImageDescription`
+/// (0x010E, ASCII).
+fn exif_image_description(value: &[u8]) -> Vec<u8> {
+    // En-tête (8) + nombre d'entrées (2) + 1 entrée (12) + IFD suivant (4).
+    let data_offset: u32 = 8 + 2 + 12 + 4;
+    let mut tiff = Vec::with_capacity(data_offset as usize + value.len());
+    tiff.extend_from_slice(b"II*\0");
+    tiff.extend_from_slice(&8u32.to_le_bytes());
+    tiff.extend_from_slice(&1u16.to_le_bytes());
+    tiff.extend_from_slice(&EXIF_IMAGE_DESCRIPTION.to_le_bytes());
+    tiff.extend_from_slice(&2u16.to_le_bytes());
+    tiff.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    tiff.extend_from_slice(&data_offset.to_le_bytes());
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    tiff.extend_from_slice(value);
+    tiff
+}
+
+/// Réécrit l'IFD0 d'un TIFF EXIF avec `ImageDescription` = `value`.
+///
+/// Le blob d'origine est conservé octet pour octet (ses offsets absolus restent
+/// valides : sous-IFD EXIF/GPS, IFD1, valeurs longues) ; la valeur et le nouvel
+/// IFD0 sont ajoutés en fin, puis l'en-tête pointe sur ce nouvel IFD0.
+/// `None` si le TIFF est illisible.
+fn merge_image_description(tiff: &[u8], value: &[u8]) -> Option<Vec<u8>> {
+    let le = match tiff.get(..4)? {
+        b"II*\0" => true,
+        b"MM\0*" => false,
+        _ => return None,
+    };
+    let u16_at = |pos: usize| -> Option<u16> {
+        let b: [u8; 2] = tiff.get(pos..pos + 2)?.try_into().ok()?;
+        Some(if le {
+            u16::from_le_bytes(b)
+        } else {
+            u16::from_be_bytes(b)
+        })
+    };
+    let u32_at = |pos: usize| -> Option<u32> {
+        let b: [u8; 4] = tiff.get(pos..pos + 4)?.try_into().ok()?;
+        Some(if le {
+            u32::from_le_bytes(b)
+        } else {
+            u32::from_be_bytes(b)
+        })
+    };
+    let w16 = |v: u16| if le { v.to_le_bytes() } else { v.to_be_bytes() };
+    let w32 = |v: u32| if le { v.to_le_bytes() } else { v.to_be_bytes() };
+
+    let ifd0 = u32_at(4)? as usize;
+    let count = usize::from(u16_at(ifd0)?);
+    let next_ifd = u32_at(ifd0 + 2 + 12 * count)?;
+    let mut entries: Vec<(u16, [u8; 12])> = Vec::with_capacity(count + 1);
+    for i in 0..count {
+        let pos = ifd0 + 2 + 12 * i;
+        let tag = u16_at(pos)?;
+        if tag != EXIF_IMAGE_DESCRIPTION {
+            entries.push((tag, tiff[pos..pos + 12].try_into().ok()?));
+        }
+    }
+
+    let mut out = tiff.to_vec();
+    // Offsets TIFF alignés sur un mot.
+    if out.len() % 2 == 1 {
+        out.push(0);
+    }
+    let mut entry = [0u8; 12];
+    entry[..2].copy_from_slice(&w16(EXIF_IMAGE_DESCRIPTION));
+    entry[2..4].copy_from_slice(&w16(2));
+    entry[4..8].copy_from_slice(&w32(u32::try_from(value.len()).ok()?));
+    if value.len() <= 4 {
+        entry[8..8 + value.len()].copy_from_slice(value);
+    } else {
+        entry[8..].copy_from_slice(&w32(u32::try_from(out.len()).ok()?));
+        out.extend_from_slice(value);
+        if out.len() % 2 == 1 {
+            out.push(0);
+        }
+    }
+    entries.push((EXIF_IMAGE_DESCRIPTION, entry));
+    // Entrées d'un IFD triées par tag croissant.
+    entries.sort_by_key(|(tag, _)| *tag);
+
+    let new_ifd0 = u32::try_from(out.len()).ok()?;
+    out.extend_from_slice(&w16(u16::try_from(entries.len()).ok()?));
+    for (_, entry) in &entries {
+        out.extend_from_slice(entry);
+    }
+    out.extend_from_slice(&w32(next_ifd));
+    out[4..8].copy_from_slice(&w32(new_ifd0));
+    Some(out)
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Paquet XMP : espace `ho:` (identifiant, URL, verdict, empreinte source).
+fn build_xmp(request: &LabeledArtifactRequest) -> String {
+    format!(
+        concat!(
+            "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n",
+            "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n",
+            " <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n",
+            "  <rdf:Description rdf:about=\"\" xmlns:ho=\"{ns}\"\n",
+            "   ho:CertificateId=\"{id}\"\n",
+            "   ho:VerifyUrl=\"{url}\"\n",
+            "   ho:Verdict=\"{verdict}\"\n",
+            "   ho:SourceSha256=\"{sha}\"/>\n",
+            " </rdf:RDF>\n",
+            "</x:xmpmeta>\n",
+            "<?xpacket end=\"r\"?>"
+        ),
+        ns = XMP_NS,
+        id = xml_escape(&request.certificate_id),
+        url = xml_escape(&request.verify_url),
+        verdict = xml_escape(&request.verdict_label),
+        sha = xml_escape(&request.source_version.fingerprint_sha256),
+    )
+}
+
+/// CRC-32 (ISO-HDLC) des chunks PNG.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &b in bytes {
+        crc ^= u32::from(b);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Insère un chunk `iTXt` XMP (non compressé) juste après `IHDR`.
+fn insert_png_xmp(png: &[u8], xmp: &str) -> Result<Vec<u8>, ArtifactRenderError> {
+    // Signature (8) + IHDR : longueur (4) + type (4) + données (13) + CRC (4).
+    let ihdr_end = PNG_MAGIC.len() + 4 + 4 + 13 + 4;
+    if png.len() < ihdr_end || &png[12..16] != b"IHDR" {
+        return Err(ArtifactRenderError::RenderFailed(
+            "PNG encodé sans IHDR".to_string(),
+        ));
+    }
+    let mut chunk = Vec::with_capacity(4 + XMP_PNG_KEYWORD.len() + 5 + xmp.len());
+    chunk.extend_from_slice(b"iTXt");
+    chunk.extend_from_slice(XMP_PNG_KEYWORD);
+    // Séparateur, non compressé, méthode 0, langue vide, mot-clé traduit vide.
+    chunk.extend_from_slice(&[0, 0, 0, 0, 0]);
+    chunk.extend_from_slice(xmp.as_bytes());
+
+    let mut out = Vec::with_capacity(png.len() + chunk.len() + 8);
+    out.extend_from_slice(&png[..ihdr_end]);
+    out.extend_from_slice(&((chunk.len() - 4) as u32).to_be_bytes());
+    out.extend_from_slice(&chunk);
+    out.extend_from_slice(&crc32(&chunk).to_be_bytes());
+    out.extend_from_slice(&png[ihdr_end..]);
+    Ok(out)
+}
+
+/// Insère un segment APP1 XMP après SOI et les APP0/APP1 de tête (JFIF, EXIF).
+fn insert_jpeg_xmp(jpeg: &[u8], xmp: &str) -> Result<Vec<u8>, ArtifactRenderError> {
+    let seg_len = 2 + XMP_JPEG_HEADER.len() + xmp.len();
+    if seg_len > usize::from(u16::MAX) {
+        return Err(ArtifactRenderError::RenderFailed(
+            "XMP trop volumineux pour un segment APP1".to_string(),
+        ));
+    }
+    if !jpeg.starts_with(&[0xFF, 0xD8]) {
+        return Err(ArtifactRenderError::RenderFailed(
+            "JPEG encodé sans SOI".to_string(),
+        ));
+    }
+    let mut pos = 2;
+    while pos + 4 <= jpeg.len() && jpeg[pos] == 0xFF && matches!(jpeg[pos + 1], 0xE0 | 0xE1) {
+        let len = usize::from(u16::from_be_bytes([jpeg[pos + 2], jpeg[pos + 3]]));
+        pos += 2 + len;
+    }
+    if pos > jpeg.len() {
+        return Err(ArtifactRenderError::RenderFailed(
+            "JPEG encodé tronqué".to_string(),
+        ));
+    }
+
+    let mut out = Vec::with_capacity(jpeg.len() + seg_len + 2);
+    out.extend_from_slice(&jpeg[..pos]);
+    out.extend_from_slice(&[0xFF, 0xE1]);
+    out.extend_from_slice(&(seg_len as u16).to_be_bytes());
+    out.extend_from_slice(XMP_JPEG_HEADER);
+    out.extend_from_slice(xmp.as_bytes());
+    out.extend_from_slice(&jpeg[pos..]);
+    Ok(out)
+}
+
+/// Relit le paquet XMP embarqué (chunk `iTXt` PNG ou segment APP1 JPEG).
+pub(crate) fn embedded_xmp(bytes: &[u8]) -> Option<String> {
+    match detect_image_format(bytes)? {
+        ImageFormat::Png => {
+            let mut pos = PNG_MAGIC.len();
+            while pos + 8 <= bytes.len() {
+                let len = u32::from_be_bytes(bytes[pos..pos + 4].try_into().ok()?) as usize;
+                let data = bytes.get(pos + 8..(pos + 8).checked_add(len)?)?;
+                if &bytes[pos + 4..pos + 8] == b"iTXt" && data.starts_with(XMP_PNG_KEYWORD) {
+                    let text = data.get(XMP_PNG_KEYWORD.len() + 5..)?;
+                    return String::from_utf8(text.to_vec()).ok();
+                }
+                pos += 12 + len;
+            }
+            None
+        }
+        ImageFormat::Jpeg => {
+            let mut pos = 2;
+            // Segments de tête uniquement (jusqu'à SOS).
+            while pos + 4 <= bytes.len() && bytes[pos] == 0xFF && bytes[pos + 1] != 0xDA {
+                let len = usize::from(u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]));
+                let data = bytes.get(pos + 4..(pos + 2).checked_add(len)?)?;
+                if bytes[pos + 1] == 0xE1 && data.starts_with(XMP_JPEG_HEADER) {
+                    return String::from_utf8(data[XMP_JPEG_HEADER.len()..].to_vec()).ok();
+                }
+                pos += 2 + len;
+            }
+            None
+        }
+    }
+}
+
+// --- TESTS UNITAIRES ----------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evidence_kernel::{MediaKind, ObjectVersionRef, ObservedObjectRef};
+    use base64::{engine::general_purpose, Engine as _};
+    use image::{ImageBuffer, Rgb};
+    use rand_core::OsRng;
+    use uuid::Uuid;
+
+    fn tmp() -> std::path::PathBuf {
+        let d = std::env::temp_dir().join(format!("ho_work_image_test_{}", Uuid::new_v4()));
+        fs::create_dir_all(&d).unwrap();
+        d
+    }
+
+    fn cleanup(p: &Path) {
+        let _ = fs::remove_dir_all(p);
+    }
+
+    fn request(k: &SigningKey, source: &Path) -> LabeledArtifactRequest {
+        let pk = general_purpose::STANDARD.encode(k.verifying_key().to_bytes());
+        let bytes = fs::read(source).unwrap();
+        LabeledArtifactRequest {
+            source_object: ObservedObjectRef::new("work-img", MediaKind::Image),
+            source_version: ObjectVersionRef {
+                fingerprint_sha256: format!("{:x}", Sha256::digest(&bytes)),
+                size_bytes: Some(bytes.len() as u64),
+                mime_type: None,
+                chunked_fingerprint: None,
+                media_specific: None,
+            },
+            certificate_id: "cert-img-1".to_string(),
+            verify_url: "https://verify.humanorigin.app/r/img?a=1&b=<2>".to_string(),
+            verdict_label: "OBSERVED_WORK_CONSISTENT".to_string(),
+            signing_key_id: Some(format!("{:x}", Sha256::digest(pk.as_bytes()))),
+            certificate_created_at: Some("2026-01-01T00:00:00Z".to_string()),
+        }
+    }
+
+    /// Dégradé déterministe : chaque pixel est distinct.
+    fn sample_png(dir: &Path) -> std::path::PathBuf {
+        let img = ImageBuffer::from_fn(64, 48, |x, y| Rgb([(x * 4) as u8, (y * 5) as u8, 77u8]));
+        let p = dir.join("source.png");
+        img.save(&p).unwrap();
+        p
+    }
+
+    #[test]
+    fn test_1_detection_par_signature() {
+        assert_eq!(
+            detect_image_format(b"\x89PNG\r\n\x1a\nxx"),
+            Some(ImageFormat::Png)
+        );
+        assert_eq!(
+            detect_image_format(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some(ImageFormat::Jpeg)
+        );
+        assert_eq!(detect_image_format(b"%PDF-1.7"), None);
+    }
+
+    #[test]
+    fn test_2_png_pixels_source_intacts_marge_ajoutee() {
+        let dir = tmp();
+        let src = sample_png(&dir);
+        let before = fs::read(&src).unwrap();
+        let k = SigningKey::generate(&mut OsRng);
+        let out = dir.join("out");
+        fs::create_dir_all(&out).unwrap();
+
+        let a = ImageDocumentAdapter::new(&k);
+        let art = a
+            .render_labeled_artifact(&request(&k, &src), &src, &out)
+            .unwrap();
+        assert_eq!(art.artifact_name, "labeled_image.png");
+        assert_eq!(art.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(fs::read(&src).unwrap(), before, "source modifiée");
+
+        let original = image::open(&src).unwrap().to_rgba8();
+        let labeled = image::open(out.join(&art.artifact_name))
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(labeled.height(), 48);
+        assert_eq!(labeled.width(), 64 + margin_width(48));
+        for (x, y, px) in original.enumerate_pixels() {
+            assert_eq!(labeled.get_pixel(x, y), px, "pixel ({x},{y}) modifié");
+        }
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_3_png_xmp_exif_et_sidecar() {
+        let dir = tmp();
+        let src = sample_png(&dir);
+        let k = SigningKey::generate(&mut OsRng);
+        let req = request(&k, &src);
+        let out = dir.join("out");
+        fs::create_dir_all(&out).unwrap();
+
+        let a = ImageDocumentAdapter::new(&k);
+        let art = a.render_labeled_artifact(&req, &src, &out).unwrap();
+        let bytes = fs::read(out.join(&art.artifact_name)).unwrap();
+        let xmp = embedded_xmp(&bytes).expect("XMP absent");
+        assert!(xmp.contains("ho:CertificateId=\"cert-img-1\""));
+        assert!(
+            xmp.contains("a=1&amp;b=&lt;2&gt;"),
+            "URL non échappée : {xmp}"
+        );
+        assert!(xmp.contains(&req.source_version.fingerprint_sha256));
+        assert!(bytes.windows(4).any(|w| w == b"eXIf"), "EXIF absent");
+        // Le PNG reste décodable après insertion du chunk.
+        assert!(image::load_from_memory(&bytes).is_ok());
+
+        assert_eq!(a.sidecar_filename(), Some(SIDECAR_FILENAME));
+        assert_eq!(
+            SIDECAR_FILENAME,
+            work_sidecar::sidecar_filename("labeled_image")
+        );
+        let sidecar = work_sidecar::read_sidecar(&out.join(SIDECAR_FILENAME)).unwrap();
+        assert!(work_sidecar::verify_sidecar(&sidecar).is_ok());
+        let Some(MediaSpecificMetadata::Image(fp)) = &sidecar.source_version.media_specific else {
+            panic!("empreinte perceptuelle source absente du sidecar");
+        };
+        assert_eq!(
+            image_fingerprint::verify_image_file(fp, &src).unwrap(),
+            image_fingerprint::ImageMatch::PixelIdentical
+        );
+        // La copie labellisée recompressée en aval reste reconnue.
+        let mut recompressed = Vec::new();
+        JpegEncoder::new_with_quality(&mut recompressed, 70)
+            .encode_image(&image::load_from_memory(&bytes).unwrap().to_rgb8())
+            .unwrap();
+        assert!(fp.labeled_perceptual_hash.is_some());
+        assert!(matches!(
+            image_fingerprint::verify_image_bytes(fp, &recompressed).unwrap(),
+            image_fingerprint::ImageMatch::PerceptuallyEquivalent { .. }
+        ));
+        assert_eq!(sidecar.labeled_artifact.as_ref(), Some(&art));
+        assert_eq!(
+            art.fingerprint_sha256.as_deref(),
+            Some(format!("{:x}", Sha256::digest(&bytes)).as_str())
+        );
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_4_jpeg_sortie_jpeg_avec_xmp() {
+        let dir = tmp();
+        let img = ImageBuffer::from_fn(40, 30, |x, y| Rgb([x as u8 * 6, y as u8 * 8, 120u8]));
+        let src = dir.join("photo.jpg");
+        img.save(&src).unwrap();
+        let k = SigningKey::generate(&mut OsRng);
+        let out = dir.join("out");
+        fs::create_dir_all(&out).unwrap();
+
+        let a = ImageDocumentAdapter::new(&k);
+        let art = a
+            .render_labeled_artifact(&request(&k, &src), &src, &out)
+            .unwrap();
+        assert_eq!(art.artifact_name, "labeled_image.jpg");
+        assert_eq!(art.mime_type.as_deref(), Some("image/jpeg"));
+        let bytes = fs::read(out.join(&art.artifact_name)).unwrap();
+        assert!(embedded_xmp(&bytes).unwrap().contains("cert-img-1"));
+        let decoded = image::load_from_memory(&bytes).unwrap();
+        assert_eq!(decoded.dimensions(), (40 + margin_width(30), 30));
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_5_png_16_bits_conserve() {
+        let dir = tmp();
+        let img: ImageBuffer<Rgb<u16>, Vec<u16>> =
+            ImageBuffer::from_fn(8, 8, |x, y| Rgb([x as u16 * 4097, y as u16 * 4099, 1]));
+        let src = dir.join("deep.png");
+        img.save(&src).unwrap();
+        let k = SigningKey::generate(&mut OsRng);
+        let out = dir.join("out");
+        fs::create_dir_all(&out).unwrap();
+
+        let a = ImageDocumentAdapter::new(&k);
+        let art = a
+            .render_labeled_artifact(&request(&k, &src), &src, &out)
+            .unwrap();
+        let labeled = image::open(out.join(&art.artifact_name)).unwrap();
+        assert!(matches!(labeled, DynamicImage::ImageRgba16(_)));
+        let labeled = labeled.to_rgba16();
+        for (x, y, px) in img.enumerate_pixels() {
+            let l = labeled.get_pixel(x, y);
+            assert_eq!([l[0], l[1], l[2], l[3]], [px[0], px[1], px[2], u16::MAX]);
+        }
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_6_format_non_supporte_refuse() {
+        let dir = tmp();
+        let src = dir.join("doc.pdf");
+        fs::write(&src, b"%PDF-1.7 nope").unwrap();
+        let k = SigningKey::generate(&mut OsRng);
+        let a = ImageDocumentAdapter::new(&k);
+        let res = a.render_labeled_artifact(&request(&k, &src), &src, &dir);
+        assert!(matches!(res, Err(ArtifactRenderError::RenderFailed(_))));
+        assert!(!dir.join(SIDECAR_FILENAME).exists());
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_7_cle_differente_du_certificat_refusee() {
+        let dir = tmp();
+        let src = sample_png(&dir);
+        let k = SigningKey::generate(&mut OsRng);
+        let other = SigningKey::generate(&mut OsRng);
+        let a = ImageDocumentAdapter::new(&other);
+        let res = a.render_labeled_artifact(&request(&k, &src), &src, &dir);
+        assert!(matches!(res, Err(ArtifactRenderError::RenderFailed(_))));
+        cleanup(&dir);
+    }
+
+    /// TIFF big-endian : `Make`, `ImageDescription` (à remplacer),
+    /// `Orientation` = `orientation`, pointeur vers un sous-IFD EXIF (`UserComment`).
+    fn source_exif(orientation: u16) -> Vec<u8> {
+        let mut t = b"MM\0*".to_vec();
+        t.extend(8u32.to_be_bytes());
+        let entry = |t: &mut Vec<u8>, tag: u16, kind: u16, count: u32, value: [u8; 4]| {
+            t.extend(tag.to_be_bytes());
+            t.extend(kind.to_be_bytes());
+            t.extend(count.to_be_bytes());
+            t.extend(value);
+        };
+        // IFD0 : 4 entrées à l'offset 8 -> données à 8 + 2 + 48 + 4 = 62.
+        t.extend(4u16.to_be_bytes());
+        entry(&mut t, 0x010E, 2, 4, *b"old\0");
+        entry(&mut t, 0x010F, 2, 8, 62u32.to_be_bytes());
+        let mut orient = [0u8; 4];
+        orient[..2].copy_from_slice(&orientation.to_be_bytes());
+        entry(&mut t, 0x0112, 3, 1, orient);
+        entry(&mut t, 0x8769, 4, 1, 70u32.to_be_bytes());
+        t.extend(0u32.to_be_bytes());
+        t.extend(b"TestCam\0");
+        // Sous-IFD EXIF à 70 : UserComment (8 octets) à 70 + 2 + 12 + 4 = 88.
+        t.extend(1u16.to_be_bytes());
+        entry(&mut t, 0x9286, 7, 8, 88u32.to_be_bytes());
+        t.extend(0u32.to_be_bytes());
+        t.extend(b"ASCII\0\0\0");
+        t
+    }
+
+    #[test]
+    fn test_8_jpeg_oriente_redresse_et_exif_conserve() {
+        let dir = tmp();
+        // 40x30 : moitié gauche rouge, moitié droite bleue.
+        let img = ImageBuffer::from_fn(40, 30, |x, _| {
+            if x < 20 {
+                Rgb([230u8, 20, 20])
+            } else {
+                Rgb([20u8, 20, 230])
+            }
+        });
+        let src = dir.join("portrait.jpg");
+        let mut jpeg = Vec::new();
+        let mut enc = JpegEncoder::new_with_quality(&mut jpeg, 95);
+        enc.set_exif_metadata(source_exif(6)).unwrap();
+        enc.write_image(img.as_raw(), 40, 30, ExtendedColorType::Rgb8)
+            .unwrap();
+        fs::write(&src, &jpeg).unwrap();
+        let k = SigningKey::generate(&mut OsRng);
+        let out = dir.join("out");
+        fs::create_dir_all(&out).unwrap();
+
+        let a = ImageDocumentAdapter::new(&k);
+        let art = a
+            .render_labeled_artifact(&request(&k, &src), &src, &out)
+            .unwrap();
+        let bytes = fs::read(out.join(&art.artifact_name)).unwrap();
+
+        // Orientation 6 (rotation 90° horaire) appliquée : 30x40, le bord
+        // gauche rouge devient le haut.
+        let labeled = image::load_from_memory(&bytes).unwrap().to_rgb8();
+        assert_eq!(labeled.dimensions(), (30 + margin_width(40), 40));
+        let top = labeled.get_pixel(15, 5);
+        let bottom = labeled.get_pixel(15, 34);
+        assert!(top[0] > 180 && top[2] < 80, "haut : {top:?}");
+        assert!(bottom[2] > 180 && bottom[0] < 80, "bas : {bottom:?}");
+
+        // EXIF : orientation neutralisée, autres tags conservés, description remplacée.
+        let mut decoder = ImageReader::new(Cursor::new(&bytes))
+            .with_guessed_format()
+            .unwrap()
+            .into_decoder()
+            .unwrap();
+        let exif = decoder.exif_metadata().unwrap().expect("EXIF absent");
+        assert_eq!(
+            Orientation::from_exif_chunk(&exif),
+            Some(Orientation::NoTransforms)
+        );
+        assert!(exif.windows(8).any(|w| w == b"TestCam\0"));
+        assert!(exif.windows(8).any(|w| w == b"ASCII\0\0\0"));
+        assert!(exif.windows(10).any(|w| w == b"cert-img-1"));
+        cleanup(&dir);
+    }
+
+    #[test]
+    fn test_9_fusion_exif_ifd0_trie_et_offsets_conserves() {
+        let tiff = source_exif(1);
+        let merged = merge_image_description(&tiff, b"HumanOrigin\0").unwrap();
+        // Seul le pointeur d'IFD0 de l'en-tête change.
+        assert_eq!(&merged[..4], &tiff[..4]);
+        assert_eq!(&merged[8..tiff.len()], &tiff[8..], "blob source modifié");
+        let ifd0 = u32::from_be_bytes(merged[4..8].try_into().unwrap()) as usize;
+        assert_eq!(ifd0 % 2, 0);
+        let count = u16::from_be_bytes([merged[ifd0], merged[ifd0 + 1]]) as usize;
+        let tags: Vec<u16> = (0..count)
+            .map(|i| {
+                let p = ifd0 + 2 + 12 * i;
+                u16::from_be_bytes([merged[p], merged[p + 1]])
+            })
+            .collect();
+        assert_eq!(tags, vec![0x010E, 0x010F, 0x0112, 0x8769]);
+        let p = ifd0 + 2;
+        let off = u32::from_be_bytes(merged[p + 8..p + 12].try_into().unwrap()) as usize;
+        assert_eq!(&merged[off..off + 12], b"HumanOrigin\0");
+
+        // EXIF illisible : IFD0 minimal.
+        let fallback = exif_with_description(Some(b"junk"), "é");
+        assert!(fallback.starts_with(b"II*\0"));
+        assert!(fallback.ends_with(b"?\0"));
+    }
+}
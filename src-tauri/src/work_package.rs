@@ -1,11 +1,22 @@
 //! work_package — PackageManifest final signé (Commit 6C-1, backend pur).
 //!
-//! Portée STRICTE : à partir d'un `certificate_N.json` (6B) et d'un
-//! `labeled_document.pdf` DÉJÀ EXISTANTS, assembler un package reproductible :
+//! Portée STRICTE : à partir d'un `certificate_N.json` (6B) et d'un artefact
+//! labellisé DÉJÀ EXISTANT (`RenderedArtifactRef` d'un `DocumentAdapter`),
+//! assembler un package reproductible :
 //!   Works/{work_id}/packages/package_{N}/
 //!     certificate.json        (copie byte-identique du certificat)
-//!     labeled_document.pdf     (copie du PDF fourni)
+//!     labeled_document.pdf     (copie de l'artefact, sous SON nom relatif)
 //!     manifest.json            (PackageManifest signé)
+//!     source_document.docx     (option : source certifiée d'un rendu dérivé)
+//!     rendition.pdf            (option : rendu PDF non labellisé de la source)
+//!     conversion_record.json   (option : empreinte de conversion du sidecar)
+//!     labeled_image.ho.json    (option : sidecar `.ho.json` signé de l'artefact)
+//!
+//! Package « sidecar seul » (original livré intact, aucune marque visible) :
+//!   Works/{work_id}/packages/package_{N}/
+//!     certificate.json
+//!     original_document.ho.json (sidecar signé de l'original, sans copie)
+//!     manifest.json            (référence SHA256 de l'original, non embarqué)
 //!
 //! AUCUNE génération de PDF, AUCUN PDFium, AUCUN publish_pdf_core, AUCUNE
 //! cartouche, AUCUN DOCX->PDF, AUCUNE commande Tauri, AUCUNE UI, AUCUN verifier,
@@ -18,7 +29,23 @@
 //! - construction dans un dossier temporaire sibling, publication atomique par
 //!   `rename` seulement après `verify_manifest(temp)` OK ; cleanup sinon ;
 //! - refus d'écrasement d'un `package_{N}/` existant ;
-//! - `manifest.json` écrit EN DERNIER, signature après hash réel des copies.
+//! - `manifest.json` écrit EN DERNIER, signature après hash réel des copies ;
+//! - rendu dérivé (DOCX->PDF fait EN AMONT par work_publish) : le SHA256 de la
+//!   source embarquée DOIT égaler `document.hash_current` du certificat ; le
+//!   lien source -> rendu est porté par le manifest signé (`derived_rendition`) ;
+//! - `conversion_record.json` (si fourni) doit citer EXACTEMENT ces deux SHA256
+//!   (source, sortie) : un record d'une autre conversion est refusé.
+//! - PDF, source et rendu sont copiés et hachés EN FLUX (`file_hash`) : aucun
+//!   gros fichier n'est chargé entier en mémoire.
+//! - l'artefact labellisé porte un nom de fichier simple, distinct des fichiers
+//!   réservés du package ; un artefact PDF est sérialisé en `labeled_pdf` V1
+//!   (corps signé inchangé), tout autre média en `labeled_artifact`.
+//! - sidecar `.ho.json` (si l'adaptateur en produit un) : signé par la clé du
+//!   certificat, même `certificate_id`, SHA256 de l'artefact égal à la copie
+//!   embarquée ; revérifié par `verify_manifest`.
+//! - sidecar seul : SHA256 de l'original == `document.hash_current` du
+//!   certificat == `source_version` du sidecar (obligatoire, sans copie
+//!   labellisée) ; l'original n'est NI copié NI modifié.
 
 // Fondation (6C-1) : logique pure ; orchestration PDF/exposition = 6C-2/6D.
 #![allow(dead_code)]
@@ -32,10 +59,13 @@ use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use crate::evidence_kernel::{DocumentArtifactKind, RenderedArtifactRef};
+use crate::file_hash;
 use crate::work_certificate::{
     verify_certificate, CertificateVersion, ProofVerdict, WorkCertificate,
 };
 use crate::work_period;
+use crate::work_sidecar::{self, WorkSidecar};
 use crate::work_store::WorkId;
 
 pub(crate) const PACKAGE_MANIFEST_SCHEMA_VERSION: u32 = 1;
@@ -46,6 +76,9 @@ const PACKAGES_DIR: &str = "packages";
 const CERTIFICATE_FILENAME: &str = "certificate.json";
 const LABELED_PDF_FILENAME: &str = "labeled_document.pdf";
 const MANIFEST_FILENAME: &str = "manifest.json";
+const SOURCE_DOCUMENT_STEM: &str = "source_document";
+const RENDITION_PDF_FILENAME: &str = "rendition.pdf";
+const CONVERSION_RECORD_FILENAME: &str = "conversion_record.json";
 
 // --- TYPES --------------------------------------------------------------------
 
@@ -53,7 +86,13 @@ const MANIFEST_FILENAME: &str = "manifest.json";
 pub(crate) enum PackageError {
     CertificateUnavailable(String),
     CertificateInvalid(String),
-    LabeledPdfUnavailable(String),
+    LabeledArtifactUnavailable(String),
+    InvalidArtifactName(String),
+    RenditionUnavailable(String),
+    RenditionSourceMismatch,
+    ConversionRecordMismatch(String),
+    SidecarInvalid(String),
+    OriginalDocumentMismatch,
     KeyMismatch,
     AlreadyExists(String),
     VerifyFailed(String),
@@ -66,10 +105,183 @@ pub(crate) struct FileRef {
     pub sha256: String,
 }
 
+/// Original livré intact (package sidecar seul) : référencé, jamais embarqué.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct OriginalDocumentRef {
+    /// Empreinte du document certifié (`document.hash_current`).
+    pub sha256: String,
+    /// Taille en octets, optionnelle. Absente de la sérialisation si `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+}
+
+/// Nature du package : copie labellisée embarquée, ou original intact + sidecar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PackageKind {
+    Labeled,
+    SidecarOnly,
+}
+
+/// Fichiers signés du package : EXACTEMENT un de `labeled_artifact` (artefact
+/// décrit par l'adaptateur de rendu, `fingerprint_sha256` TOUJOURS présent) ou
+/// `original_document` (sidecar seul, `labeled_sidecar` alors obligatoire).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(try_from = "ManifestFilesWire", into = "ManifestFilesWire")]
 pub(crate) struct ManifestFiles {
     pub certificate: FileRef,
-    pub labeled_pdf: FileRef,
+    pub labeled_artifact: Option<RenderedArtifactRef>,
+    /// Original référencé par son SHA256 (package sidecar seul).
+    pub original_document: Option<OriginalDocumentRef>,
+    /// Sidecar `.ho.json` de l'artefact ou de l'original. Absent (non
+    /// sérialisé) si aucun.
+    pub labeled_sidecar: Option<FileRef>,
+}
+
+/// Forme sérialisée de `ManifestFiles` : exactement un de `labeled_pdf` (PDF,
+/// forme V1), `labeled_artifact` (tout autre artefact) ou `original_document`.
+#[derive(Serialize, Deserialize)]
+struct ManifestFilesWire {
+    certificate: FileRef,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    labeled_pdf: Option<FileRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    labeled_artifact: Option<RenderedArtifactRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original_document: Option<OriginalDocumentRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    labeled_sidecar: Option<FileRef>,
+}
+
+/// Un PDF sans taille ni MIME : exactement ce que porte `labeled_pdf` V1.
+fn is_v1_labeled_pdf(artifact: &RenderedArtifactRef) -> bool {
+    artifact.media_type == DocumentArtifactKind::Pdf
+        && artifact.size_bytes.is_none()
+        && artifact.mime_type.is_none()
+}
+
+impl From<ManifestFiles> for ManifestFilesWire {
+    fn from(files: ManifestFiles) -> Self {
+        let (labeled_pdf, labeled_artifact) = match files.labeled_artifact {
+            Some(a) if is_v1_labeled_pdf(&a) => (
+                Some(FileRef {
+                    filename: a.artifact_name,
+                    sha256: a.fingerprint_sha256.unwrap_or_default(),
+                }),
+                None,
+            ),
+            other => (None, other),
+        };
+        ManifestFilesWire {
+            certificate: files.certificate,
+            labeled_pdf,
+            labeled_artifact,
+            original_document: files.original_document,
+            labeled_sidecar: files.labeled_sidecar,
+        }
+    }
+}
+
+impl TryFrom<ManifestFilesWire> for ManifestFiles {
+    type Error = String;
+
+    fn try_from(wire: ManifestFilesWire) -> Result<Self, String> {
+        let (labeled_artifact, original_document) = match (
+            wire.labeled_pdf,
+            wire.labeled_artifact,
+            wire.original_document,
+        ) {
+            (Some(pdf), None, None) => (
+                Some(RenderedArtifactRef {
+                    artifact_name: pdf.filename,
+                    media_type: DocumentArtifactKind::Pdf,
+                    fingerprint_sha256: Some(pdf.sha256),
+                    size_bytes: None,
+                    mime_type: None,
+                }),
+                None,
+            ),
+            // Une forme V1 déguisée se resérialiserait autrement : refusée.
+            (None, Some(a), None) if a.fingerprint_sha256.is_some() && !is_v1_labeled_pdf(&a) => {
+                (Some(a), None)
+            }
+            (None, Some(_), None) => return Err("labeled_artifact incomplet ou ambigu".to_string()),
+            // Sidecar seul : la preuve EST le sidecar.
+            (None, None, Some(o)) if wire.labeled_sidecar.is_some() => (None, Some(o)),
+            (None, None, Some(_)) => return Err("original_document sans sidecar".to_string()),
+            _ => return Err("exactement un artefact labellisé ou un original attendu".to_string()),
+        };
+        Ok(ManifestFiles {
+            certificate: wire.certificate,
+            labeled_artifact,
+            original_document,
+            labeled_sidecar: wire.labeled_sidecar,
+        })
+    }
+}
+
+impl ManifestFiles {
+    /// Nature du package (invariant garanti par la désérialisation).
+    pub(crate) fn kind(&self) -> PackageKind {
+        if self.labeled_artifact.is_some() {
+            PackageKind::Labeled
+        } else {
+            PackageKind::SidecarOnly
+        }
+    }
+
+    /// SHA256 de l'artefact labellisé (toujours présent après désérialisation),
+    /// `None` pour un package sidecar seul.
+    pub(crate) fn labeled_artifact_sha256(&self) -> Option<&str> {
+        self.labeled_artifact
+            .as_ref()
+            .and_then(|a| a.fingerprint_sha256.as_deref())
+    }
+}
+
+/// Rendu dérivé : la source certifiée (ex. DOCX) et le PDF qui en a été tiré.
+/// `source.sha256` == `document.hash_current` du certificat (vérifié).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DerivedRendition {
+    pub source: FileRef,
+    pub rendition: FileRef,
+    pub converter: String,
+    /// Empreinte du convertisseur (moteur, version, options), si fournie.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversion_record: Option<FileRef>,
+}
+
+/// Entrée de construction d'un rendu dérivé (chemins locaux, jamais sérialisés).
+#[derive(Clone, Debug)]
+pub(crate) struct RenditionInput<'a> {
+    pub source_path: &'a Path,
+    pub rendition_pdf_path: &'a Path,
+    pub converter: &'a str,
+    pub conversion_record_path: Option<&'a Path>,
+}
+
+/// Entrée de construction de l'artefact labellisé (chemins locaux, jamais
+/// sérialisés) : fichier produit, description de l'adaptateur, sidecar éventuel.
+#[derive(Clone, Debug)]
+pub(crate) struct LabeledInput<'a> {
+    pub artifact_path: &'a Path,
+    pub artifact: &'a RenderedArtifactRef,
+    pub sidecar_path: Option<&'a Path>,
+}
+
+/// Entrée d'un package sidecar seul : empreinte de l'original (calculée par
+/// l'appelant : fichier ou arbre de dépôt) et sidecar signé à embarquer.
+#[derive(Clone, Debug)]
+pub(crate) struct OriginalInput<'a> {
+    pub sha256: &'a str,
+    pub size_bytes: Option<u64>,
+    pub sidecar_path: &'a Path,
+}
+
+/// Document couvert par le package à construire.
+#[derive(Clone, Debug)]
+pub(crate) enum PackageDocument<'a> {
+    Labeled(LabeledInput<'a>),
+    SidecarOnly(OriginalInput<'a>),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -81,7 +293,7 @@ pub(crate) struct ManifestSignatureMetadata {
     pub schema_version: u32,
 }
 
-/// Manifest final signé : lie `certificate.json` et `labeled_document.pdf` par
+/// Manifest final signé : lie `certificate.json` et l'artefact labellisé par
 /// leurs SHA256, sans aucun chemin absolu (filenames relatifs uniquement).
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub(crate) struct PackageManifest {
@@ -94,12 +306,86 @@ pub(crate) struct PackageManifest {
     pub certificate_version: CertificateVersion,
     pub verdict: ProofVerdict,
     pub files: ManifestFiles,
+    /// Absent (non sérialisé) pour un Work PDF : corps signé V1 inchangé.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derived_rendition: Option<DerivedRendition>,
     pub signature_metadata: ManifestSignatureMetadata,
     pub signature: String,
 }
 
 // --- HELPERS ------------------------------------------------------------------
 
+/// `conversion_record.json` du sidecar : `source.sha256` et `output.sha256`
+/// doivent être ceux de la source et du rendu embarqués.
+fn check_conversion_record(
+    record: &[u8],
+    source_sha256: &str,
+    rendition_sha256: &str,
+) -> Result<(), String> {
+    let value: serde_json::Value =
+        serde_json::from_slice(record).map_err(|e| format!("conversion_record invalide : {e}"))?;
+    let digest = |side: &str| {
+        value
+            .get(side)
+            .and_then(|v| v.get("sha256"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+    if digest("source").as_deref() != Some(source_sha256) {
+        return Err("conversion_record : source ≠ document source".to_string());
+    }
+    if digest("output").as_deref() != Some(rendition_sha256) {
+        return Err("conversion_record : sortie ≠ rendition.pdf".to_string());
+    }
+    Ok(())
+}
+
+/// Objet décrit par un sidecar : copie labellisée embarquée ou original intact
+/// (SHA256 attendu).
+#[derive(Clone, Copy)]
+enum SidecarSubject<'a> {
+    Artifact(&'a str),
+    Original(&'a str),
+}
+
+/// Sidecar `.ho.json` : signature valide, même certificat, même clé. Artefact :
+/// s'il décrit une copie labellisée, même SHA256 que la copie embarquée.
+/// Original : aucune copie décrite et `source_version` == original.
+fn check_sidecar(
+    bytes: &[u8],
+    certificate_id: &str,
+    signing_key_id: &str,
+    subject: SidecarSubject,
+) -> Result<(), String> {
+    let sidecar: WorkSidecar =
+        serde_json::from_slice(bytes).map_err(|e| format!("sidecar invalide : {e}"))?;
+    work_sidecar::verify_sidecar(&sidecar)?;
+    if sidecar.certificate_id != certificate_id {
+        return Err("sidecar : certificate_id ≠ certificat".to_string());
+    }
+    if sidecar.signature_metadata.signing_key_id != signing_key_id {
+        return Err("sidecar : clé ≠ clé du certificat".to_string());
+    }
+    match subject {
+        SidecarSubject::Artifact(artifact_sha256) => {
+            if let Some(a) = &sidecar.labeled_artifact {
+                if a.fingerprint_sha256.as_deref() != Some(artifact_sha256) {
+                    return Err("sidecar : artefact ≠ copie embarquée".to_string());
+                }
+            }
+        }
+        SidecarSubject::Original(original_sha256) => {
+            if sidecar.labeled_artifact.is_some() {
+                return Err("sidecar : copie labellisée dans un package sidecar seul".to_string());
+            }
+            if sidecar.source_version.fingerprint_sha256 != original_sha256 {
+                return Err("sidecar : source ≠ document original".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
 fn sha256_hex_str(s: &str) -> String {
     format!("{:x}", Sha256::digest(s.as_bytes()))
 }
@@ -108,11 +394,6 @@ fn sha256_hex_bytes(bytes: &[u8]) -> String {
     format!("{:x}", Sha256::digest(bytes))
 }
 
-fn sha256_file_hex(path: &Path) -> Result<String, String> {
-    let bytes = fs::read(path).map_err(|e| e.to_string())?;
-    Ok(sha256_hex_bytes(&bytes))
-}
-
 fn decode_b64_fixed<const N: usize>(s: &str) -> Result<[u8; N], String> {
     let bytes = general_purpose::STANDARD
         .decode(s)
@@ -125,6 +406,63 @@ fn decode_b64_fixed<const N: usize>(s: &str) -> Result<[u8; N], String> {
     Ok(arr)
 }
 
+/// Copie en flux (jamais le fichier entier en mémoire) puis fsync de la copie.
+fn copy_synced(src: &Path, dst: &Path) -> Result<(), PackageError> {
+    fs::copy(src, dst).map_err(|e| PackageError::Io(e.to_string()))?;
+    File::open(dst)
+        .and_then(|f| f.sync_all())
+        .map_err(|e| PackageError::Io(e.to_string()))
+}
+
+/// Nom d'artefact acceptable : fichier simple, hors fichiers réservés du package.
+fn is_valid_artifact_name(name: &str) -> bool {
+    let reserved = [
+        CERTIFICATE_FILENAME,
+        MANIFEST_FILENAME,
+        RENDITION_PDF_FILENAME,
+        CONVERSION_RECORD_FILENAME,
+    ];
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', ':'])
+        && !reserved.contains(&name)
+        && !name.starts_with(&format!("{SOURCE_DOCUMENT_STEM}."))
+}
+
+/// Référence signée de l'artefact copié : nom + type + SHA256 de la copie. Un
+/// PDF garde la forme V1 (ni taille ni MIME) ; les autres médias gardent leur
+/// MIME et portent leur taille réelle.
+fn manifest_artifact_ref(
+    produced: &RenderedArtifactRef,
+    sha256: String,
+    size: u64,
+) -> RenderedArtifactRef {
+    let pdf = produced.media_type == DocumentArtifactKind::Pdf;
+    RenderedArtifactRef {
+        artifact_name: produced.artifact_name.clone(),
+        media_type: produced.media_type.clone(),
+        fingerprint_sha256: Some(sha256),
+        size_bytes: (!pdf).then_some(size),
+        mime_type: if pdf {
+            None
+        } else {
+            produced.mime_type.clone()
+        },
+    }
+}
+
+/// Référence de l'artefact PDF labellisé historique (`labeled_document.pdf`).
+pub(crate) fn labeled_pdf_artifact() -> RenderedArtifactRef {
+    RenderedArtifactRef {
+        artifact_name: LABELED_PDF_FILENAME.to_string(),
+        media_type: DocumentArtifactKind::Pdf,
+        fingerprint_sha256: None,
+        size_bytes: None,
+        mime_type: None,
+    }
+}
+
 fn write_synced(path: &Path, bytes: &[u8]) -> Result<(), PackageError> {
     let mut f = File::create(path).map_err(|e| PackageError::Io(e.to_string()))?;
     f.write_all(bytes)
@@ -170,10 +508,14 @@ fn manifest_body_digest(manifest: &PackageManifest) -> Result<[u8; 32], String>
 /// Vérifie un package complet dans `package_dir` :
 /// 1. signature Ed25519 du manifest (corps hors `signature`), algo, identité,
 ///    `signing_key_id == SHA256(public_key)` ;
-/// 2. SHA256 recalculés des fichiers == `files.*.sha256` ;
+/// 2. SHA256 recalculés des fichiers == `files.*` (nom d'artefact simple) ;
 /// 3. `certificate.json` parsé + `verify_certificate` OK ;
 /// 4. cohérence manifest<->certificat : work_id, certificate_id,
-///    certificate_sequence, certificate_version, verdict, signing_key_id.
+///    certificate_sequence, certificate_version, verdict, signing_key_id ;
+/// 5. si `derived_rendition` : SHA256 source + rendu, et source == document certifié ;
+/// 6. sidecar seul : original référencé == document certifié, sidecar exigé ;
+/// 7. si `labeled_sidecar` : SHA256 + sidecar signé par la clé du certificat,
+///    lié à ce certificat et à l'artefact embarqué (ou à l'original).
 pub(crate) fn verify_manifest(package_dir: &Path) -> Result<(), String> {
     let manifest_raw = fs::read_to_string(package_dir.join(MANIFEST_FILENAME))
         .map_err(|e| format!("manifest illisible : {e}"))?;
@@ -201,15 +543,27 @@ pub(crate) fn verify_manifest(package_dir: &Path) -> Result<(), String> {
     vk.verify_strict(&digest, &sig)
         .map_err(|_| "signature de manifest invalide".to_string())?;
 
-    // 2) Hashes réels des fichiers copiés.
+    // 2) Hashes réels des fichiers copiés (l'original d'un package sidecar
+    //    seul n'est pas embarqué).
     let cert_path = package_dir.join(&manifest.files.certificate.filename);
-    let pdf_path = package_dir.join(&manifest.files.labeled_pdf.filename);
-    if sha256_file_hex(&cert_path)? != manifest.files.certificate.sha256 {
+    if file_hash::sha256_file(&cert_path)? != manifest.files.certificate.sha256 {
         return Err("certificate.json altéré (SHA256 ≠ manifest)".to_string());
     }
-    if sha256_file_hex(&pdf_path)? != manifest.files.labeled_pdf.sha256 {
-        return Err("labeled_document.pdf altéré (SHA256 ≠ manifest)".to_string());
-    }
+    let artifact_name = match &manifest.files.labeled_artifact {
+        Some(a) => {
+            if !is_valid_artifact_name(&a.artifact_name) {
+                return Err(format!("nom d'artefact invalide : {:?}", a.artifact_name));
+            }
+            let artifact_path = package_dir.join(&a.artifact_name);
+            if Some(file_hash::sha256_file(&artifact_path)?.as_str())
+                != manifest.files.labeled_artifact_sha256()
+            {
+                return Err(format!("{} altéré (SHA256 ≠ manifest)", a.artifact_name));
+            }
+            Some(a.artifact_name.as_str())
+        }
+        None => None,
+    };
 
     // 3) Le certificat embarqué doit être valide.
     let cert_raw = fs::read_to_string(&cert_path).map_err(|e| e.to_string())?;
@@ -236,6 +590,84 @@ pub(crate) fn verify_manifest(package_dir: &Path) -> Result<(), String> {
     if manifest.signature_metadata.signing_key_id != cert.signature_metadata.signing_key_id {
         return Err("signing_key_id manifest ≠ certificat".to_string());
     }
+
+    // 5) Rendu dérivé : source certifiée -> PDF rendu.
+    if let Some(r) = &manifest.derived_rendition {
+        if file_hash::sha256_file(&package_dir.join(&r.source.filename))? != r.source.sha256 {
+            return Err("document source altéré (SHA256 ≠ manifest)".to_string());
+        }
+        if file_hash::sha256_file(&package_dir.join(&r.rendition.filename))? != r.rendition.sha256 {
+            return Err("rendition.pdf altéré (SHA256 ≠ manifest)".to_string());
+        }
+        if r.source.sha256 != cert.public_core_evidence.document.hash_current {
+            return Err("document source ≠ document certifié".to_string());
+        }
+        if let Some(record) = &r.conversion_record {
+            let record_path = package_dir.join(&record.filename);
+            if file_hash::sha256_file(&record_path)? != record.sha256 {
+                return Err("conversion_record.json altéré (SHA256 ≠ manifest)".to_string());
+            }
+            let bytes = fs::read(&record_path).map_err(|e| e.to_string())?;
+            check_conversion_record(&bytes, &r.source.sha256, &r.rendition.sha256)?;
+        }
+    }
+
+    // 6) Sidecar seul : l'original référencé est le document certifié.
+    let subject = match (&manifest.files.original_document, artifact_name) {
+        (Some(original), None) => {
+            if original.sha256 != cert.public_core_evidence.document.hash_current {
+                return Err("document original ≠ document certifié".to_string());
+            }
+            if manifest.derived_rendition.is_some() {
+                return Err("rendu dérivé dans un package sidecar seul".to_string());
+            }
+            SidecarSubject::Original(&original.sha256)
+        }
+        (None, Some(_)) => {
+            SidecarSubject::Artifact(manifest.files.labeled_artifact_sha256().unwrap_or_default())
+        }
+        _ => return Err("exactement un artefact labellisé ou un original attendu".to_string()),
+    };
+
+    // 7) Sidecar `.ho.json` de l'artefact ou de l'original.
+    if let Some(sidecar) = &manifest.files.labeled_sidecar {
+        if !is_valid_artifact_name(&sidecar.filename)
+            || Some(sidecar.filename.as_str()) == artifact_name
+        {
+            return Err(format!("nom de sidecar invalide : {:?}", sidecar.filename));
+        }
+        let sidecar_path = package_dir.join(&sidecar.filename);
+        if file_hash::sha256_file(&sidecar_path)? != sidecar.sha256 {
+            return Err(format!("{} altéré (SHA256 ≠ manifest)", sidecar.filename));
+        }
+        let bytes = fs::read(&sidecar_path).map_err(|e| e.to_string())?;
+        check_sidecar(
+            &bytes,
+            &cert.certificate_id,
+            &cert.signature_metadata.signing_key_id,
+            subject,
+        )?;
+    } else if manifest.files.kind() == PackageKind::SidecarOnly {
+        return Err("package sidecar seul sans sidecar".to_string());
+    }
+    Ok(())
+}
+
+/// Vérifie le package PUIS que `original` (fichier livré à part) est bien
+/// l'original référencé par un package sidecar seul.
+pub(crate) fn verify_original_document(package_dir: &Path, original: &Path) -> Result<(), String> {
+    verify_manifest(package_dir)?;
+    let raw = fs::read_to_string(package_dir.join(MANIFEST_FILENAME))
+        .map_err(|e| format!("manifest illisible : {e}"))?;
+    let manifest: PackageManifest =
+        serde_json::from_str(&raw).map_err(|e| format!("manifest invalide : {e}"))?;
+    let expected = manifest
+        .files
+        .original_document
+        .ok_or_else(|| "package labellisé : aucun original référencé".to_string())?;
+    if file_hash::sha256_file(original)? != expected.sha256 {
+        return Err("fichier ≠ original certifié (SHA256)".to_string());
+    }
     Ok(())
 }
 
@@ -243,10 +675,12 @@ pub(crate) fn verify_manifest(package_dir: &Path) -> Result<(), String> {
 
 /// Cœur avec seam de test `on_before_publish` (appelé après `verify_manifest(temp)`
 /// OK et AVANT la publication atomique). En prod : no-op.
+/// `rendition` : source certifiée + rendu PDF à embarquer (Work DOCX), sinon `None`.
 fn create_work_package_inner<H>(
     works_root: &Path,
     certificate_path: &Path,
-    labeled_pdf_path: &Path,
+    document: &PackageDocument,
+    rendition: Option<&RenditionInput>,
     created_at: &str,
     signing_key: &SigningKey,
     on_before_publish: H,
@@ -261,9 +695,80 @@ where
         .map_err(|e| PackageError::CertificateInvalid(format!("JSON : {e}")))?;
     verify_certificate(&cert).map_err(PackageError::CertificateInvalid)?;
 
-    // PDF labellisé : doit exister (aucun package sinon).
-    let pdf_bytes = fs::read(labeled_pdf_path)
-        .map_err(|e| PackageError::LabeledPdfUnavailable(e.to_string()))?;
+    // Artefact labellisé : nom simple, fichier existant (aucun package sinon),
+    // copié en flux plus bas. Original (sidecar seul) : DOIT être le document
+    // certifié ; jamais copié, ni rendu dérivé.
+    let (labeled, sidecar_path) = match document {
+        PackageDocument::Labeled(l) => {
+            if !is_valid_artifact_name(&l.artifact.artifact_name) {
+                return Err(PackageError::InvalidArtifactName(
+                    l.artifact.artifact_name.clone(),
+                ));
+            }
+            File::open(l.artifact_path)
+                .map_err(|e| PackageError::LabeledArtifactUnavailable(e.to_string()))?;
+            (Some(l), l.sidecar_path)
+        }
+        PackageDocument::SidecarOnly(o) => {
+            if o.sha256 != cert.public_core_evidence.document.hash_current || rendition.is_some() {
+                return Err(PackageError::OriginalDocumentMismatch);
+            }
+            (None, Some(o.sidecar_path))
+        }
+    };
+    let artifact_name = labeled.map(|l| l.artifact.artifact_name.as_str());
+
+    // Sidecar : nom simple distinct de l'artefact ; contenu vérifié plus bas,
+    // une fois le SHA256 de la copie de l'artefact connu.
+    let sidecar_plan = match sidecar_path {
+        Some(path) => {
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default()
+                .to_string();
+            if !is_valid_artifact_name(&name) || Some(name.as_str()) == artifact_name {
+                return Err(PackageError::InvalidArtifactName(name));
+            }
+            let bytes = fs::read(path).map_err(|e| PackageError::SidecarInvalid(e.to_string()))?;
+            Some((name, bytes))
+        }
+        None => None,
+    };
+
+    // Rendu dérivé : la source DOIT être le document certifié, le record DOIT
+    // citer source et rendu (aucun package sinon). Source et rendu sont hachés
+    // en flux ; leurs copies sont re-hachées puis revérifiées par
+    // `verify_manifest(temp)` (un fichier modifié entre-temps est refusé).
+    let rendition_plan = match rendition {
+        Some(r) => {
+            let source_sha256 = file_hash::sha256_file(r.source_path)
+                .map_err(PackageError::RenditionUnavailable)?;
+            if source_sha256 != cert.public_core_evidence.document.hash_current {
+                return Err(PackageError::RenditionSourceMismatch);
+            }
+            let rendition_sha256 = file_hash::sha256_file(r.rendition_pdf_path)
+                .map_err(PackageError::RenditionUnavailable)?;
+            let ext = r
+                .source_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.to_ascii_lowercase())
+                .unwrap_or_else(|| "bin".to_string());
+            let record = match r.conversion_record_path {
+                Some(path) => {
+                    let bytes = fs::read(path)
+                        .map_err(|e| PackageError::RenditionUnavailable(e.to_string()))?;
+                    check_conversion_record(&bytes, &source_sha256, &rendition_sha256)
+                        .map_err(PackageError::ConversionRecordMismatch)?;
+                    Some(bytes)
+                }
+                None => None,
+            };
+            Some((r, format!("{SOURCE_DOCUMENT_STEM}.{ext}"), record))
+        }
+        None => None,
+    };
 
     // Garde-fou #2 : la clé du manifest doit être CELLE du certificat.
     let public_key = general_purpose::STANDARD.encode(signing_key.verifying_key().to_bytes());
@@ -288,13 +793,94 @@ where
     let built = (|| -> Result<PackageManifest, PackageError> {
         // Copies (certificate byte-identique).
         let cert_dst = temp_dir.join(CERTIFICATE_FILENAME);
-        let pdf_dst = temp_dir.join(LABELED_PDF_FILENAME);
         write_synced(&cert_dst, &cert_bytes)?;
-        write_synced(&pdf_dst, &pdf_bytes)?;
+        let certificate_sha256 = file_hash::sha256_file(&cert_dst).map_err(PackageError::Io)?;
 
         // Hashes calculés sur les fichiers RÉELLEMENT copiés.
-        let certificate_sha256 = sha256_file_hex(&cert_dst).map_err(PackageError::Io)?;
-        let labeled_pdf_sha256 = sha256_file_hex(&pdf_dst).map_err(PackageError::Io)?;
+        let labeled_artifact = match labeled {
+            Some(l) => {
+                let artifact_dst = temp_dir.join(&l.artifact.artifact_name);
+                copy_synced(l.artifact_path, &artifact_dst)?;
+                let artifact_sha256 =
+                    file_hash::sha256_file(&artifact_dst).map_err(PackageError::Io)?;
+                let artifact_size = fs::metadata(&artifact_dst)
+                    .map_err(|e| PackageError::Io(e.to_string()))?
+                    .len();
+                Some(manifest_artifact_ref(
+                    l.artifact,
+                    artifact_sha256,
+                    artifact_size,
+                ))
+            }
+            None => None,
+        };
+        let original_document = match document {
+            PackageDocument::SidecarOnly(o) => Some(OriginalDocumentRef {
+                sha256: o.sha256.to_string(),
+                size_bytes: o.size_bytes,
+            }),
+            PackageDocument::Labeled(_) => None,
+        };
+        let subject = match (&labeled_artifact, &original_document) {
+            (Some(a), _) => {
+                SidecarSubject::Artifact(a.fingerprint_sha256.as_deref().unwrap_or_default())
+            }
+            (None, Some(o)) => SidecarSubject::Original(&o.sha256),
+            (None, None) => unreachable!("document labellisé ou original"),
+        };
+
+        let labeled_sidecar = match &sidecar_plan {
+            Some((name, bytes)) => {
+                check_sidecar(
+                    bytes,
+                    &cert.certificate_id,
+                    &cert.signature_metadata.signing_key_id,
+                    subject,
+                )
+                .map_err(PackageError::SidecarInvalid)?;
+                let sidecar_dst = temp_dir.join(name);
+                write_synced(&sidecar_dst, bytes)?;
+                Some(FileRef {
+                    filename: name.clone(),
+                    sha256: file_hash::sha256_file(&sidecar_dst).map_err(PackageError::Io)?,
+                })
+            }
+            None => None,
+        };
+
+        let derived_rendition = match &rendition_plan {
+            Some((r, source_filename, record)) => {
+                let source_dst = temp_dir.join(source_filename);
+                let rendition_dst = temp_dir.join(RENDITION_PDF_FILENAME);
+                copy_synced(r.source_path, &source_dst)?;
+                copy_synced(r.rendition_pdf_path, &rendition_dst)?;
+                let conversion_record = match record {
+                    Some(bytes) => {
+                        let record_dst = temp_dir.join(CONVERSION_RECORD_FILENAME);
+                        write_synced(&record_dst, bytes)?;
+                        Some(FileRef {
+                            filename: CONVERSION_RECORD_FILENAME.to_string(),
+                            sha256: file_hash::sha256_file(&record_dst)
+                                .map_err(PackageError::Io)?,
+                        })
+                    }
+                    None => None,
+                };
+                Some(DerivedRendition {
+                    source: FileRef {
+                        filename: source_filename.clone(),
+                        sha256: file_hash::sha256_file(&source_dst).map_err(PackageError::Io)?,
+                    },
+                    rendition: FileRef {
+                        filename: RENDITION_PDF_FILENAME.to_string(),
+                        sha256: file_hash::sha256_file(&rendition_dst).map_err(PackageError::Io)?,
+                    },
+                    converter: r.converter.to_string(),
+                    conversion_record,
+                })
+            }
+            None => None,
+        };
 
         // Manifest (path-free ; filenames relatifs).
         let mut manifest = PackageManifest {
@@ -311,11 +897,11 @@ where
                     filename: CERTIFICATE_FILENAME.to_string(),
                     sha256: certificate_sha256,
                 },
-                labeled_pdf: FileRef {
-                    filename: LABELED_PDF_FILENAME.to_string(),
-                    sha256: labeled_pdf_sha256,
-                },
+                labeled_artifact,
+                original_document,
+                labeled_sidecar,
             },
+            derived_rendition,
             signature_metadata: ManifestSignatureMetadata {
                 signature_algorithm: SIGN_ALG.to_string(),
                 public_key,
@@ -365,42 +951,125 @@ where
     Ok(manifest)
 }
 
-/// Assemble le package avec une clé fournie (seam de test / injection).
-pub(crate) fn create_work_package_with(
+/// Assemble le package d'un artefact labellisé quelconque avec une clé fournie
+/// (seam de test / injection). `rendition` : source + rendu d'un Work DOCX.
+pub(crate) fn create_artifact_package_with(
     works_root: &Path,
     certificate_path: &Path,
-    labeled_pdf_path: &Path,
+    labeled: &LabeledInput,
+    rendition: Option<&RenditionInput>,
     created_at: &str,
     signing_key: &SigningKey,
 ) -> Result<PackageManifest, PackageError> {
     create_work_package_inner(
         works_root,
         certificate_path,
-        labeled_pdf_path,
+        &PackageDocument::Labeled(labeled.clone()),
+        rendition,
         created_at,
         signing_key,
         || Ok(()),
     )
 }
 
-/// Point d'entrée production : signe avec la clé device (`ensure_signing_key`).
-/// Non exposé en commande Tauri en 6C-1.
-pub(crate) fn create_work_package_core(
+/// Assemble un package sidecar seul avec une clé fournie : l'original reste
+/// intact hors du package, référencé par son SHA256 à côté de son sidecar.
+pub(crate) fn create_sidecar_package_with(
+    works_root: &Path,
+    certificate_path: &Path,
+    original: &OriginalInput,
+    created_at: &str,
+    signing_key: &SigningKey,
+) -> Result<PackageManifest, PackageError> {
+    create_work_package_inner(
+        works_root,
+        certificate_path,
+        &PackageDocument::SidecarOnly(original.clone()),
+        None,
+        created_at,
+        signing_key,
+        || Ok(()),
+    )
+}
+
+/// Assemble le package d'un PDF labellisé avec une clé fournie.
+pub(crate) fn create_work_package_with(
+    works_root: &Path,
+    certificate_path: &Path,
+    labeled_pdf_path: &Path,
+    created_at: &str,
+    signing_key: &SigningKey,
+) -> Result<PackageManifest, PackageError> {
+    create_artifact_package_with(
+        works_root,
+        certificate_path,
+        &LabeledInput {
+            artifact_path: labeled_pdf_path,
+            artifact: &labeled_pdf_artifact(),
+            sidecar_path: None,
+        },
+        None,
+        created_at,
+        signing_key,
+    )
+}
+
+/// Variante rendu dérivé (Work DOCX) avec une clé fournie : embarque aussi la
+/// source certifiée et le PDF rendu, liés dans `derived_rendition`.
+pub(crate) fn create_rendition_package_with(
     works_root: &Path,
     certificate_path: &Path,
     labeled_pdf_path: &Path,
+    rendition: &RenditionInput,
+    created_at: &str,
+    signing_key: &SigningKey,
+) -> Result<PackageManifest, PackageError> {
+    create_artifact_package_with(
+        works_root,
+        certificate_path,
+        &LabeledInput {
+            artifact_path: labeled_pdf_path,
+            artifact: &labeled_pdf_artifact(),
+            sidecar_path: None,
+        },
+        Some(rendition),
+        created_at,
+        signing_key,
+    )
+}
+
+/// Point d'entrée production : signe avec la clé device (`ensure_signing_key`).
+/// Appelé par work_publish avec l'artefact produit par son `DocumentAdapter` ;
+/// le rendu/la conversion ne sont PAS faits ici.
+pub(crate) fn create_artifact_package_core(
+    works_root: &Path,
+    certificate_path: &Path,
+    labeled: &LabeledInput,
+    rendition: Option<&RenditionInput>,
     created_at: &str,
 ) -> Result<PackageManifest, PackageError> {
     let key = crate::ensure_signing_key().map_err(PackageError::Io)?;
-    create_work_package_with(
+    create_artifact_package_with(
         works_root,
         certificate_path,
-        labeled_pdf_path,
+        labeled,
+        rendition,
         created_at,
         &key,
     )
 }
 
+/// Point d'entrée production du package sidecar seul (clé device).
+pub(crate) fn create_sidecar_package_core(
+    works_root: &Path,
+    certificate_path: &Path,
+    original: &OriginalInput,
+    created_at: &str,
+) -> Result<PackageManifest, PackageError> {
+    let key = crate::ensure_signing_key().map_err(PackageError::Io)?;
+    create_sidecar_package_with(works_root, certificate_path, original, created_at, &key)
+}
+
 // --- TESTS UNITAIRES ----------------------------------------------------------
 
 #[cfg(test)]
@@ -432,24 +1101,37 @@ mod tests {
     /// Fabrique un `WorkCertificate` valide signé par `k` (contenu minimal mais
     /// cohérent : `verify_certificate` le valide).
     fn make_cert(k: &SigningKey, work_id: &WorkId, sequence: u64) -> WorkCertificate {
+        make_cert_with_hash(k, work_id, sequence, &"a".repeat(64))
+    }
+
+    /// Idem avec un `hash_current` donné (source d'un rendu dérivé).
+    fn make_cert_with_hash(
+        k: &SigningKey,
+        work_id: &WorkId,
+        sequence: u64,
+        hash_current: &str,
+    ) -> WorkCertificate {
         let public = PublicCoreEvidence {
             schema_version: CORE_EVIDENCE_SCHEMA_VERSION,
             work_id: work_id.clone(),
             certificate_version: CertificateVersion::V1,
             document: PublicDocumentRef {
-                hash_current: "a".repeat(64),
+                hash_current: hash_current.to_string(),
                 size_current: 42,
+                members: None,
             },
             included_period_summaries: vec![IncludedPeriod {
                 period_id: Uuid::new_v4().to_string(),
                 sequence_number: 0,
                 hash_start: "b".repeat(64),
-                hash_end: "a".repeat(64),
+                hash_end: hash_current.to_string(),
                 size_start: 10,
                 size_end: 42,
                 net_document_change: true,
                 gate_passed: true,
                 qualifying: true,
+                change_magnitude: None,
+                member_changes: None,
                 period_record_sha256: "c".repeat(64),
             }],
             new_period_ids: vec![],
@@ -464,6 +1146,7 @@ mod tests {
                 total_active_seconds: 60,
                 continuity: ContinuityKind::Full,
                 verdict: ProofVerdict::ObservedWorkConsistent,
+                flags: vec![],
             },
             previous_certificate_id: None,
             previous_core_evidence_sha256: None,
@@ -488,6 +1171,7 @@ mod tests {
                 signing_key_id,
                 identity_status: "LOCAL_DEVICE".to_string(),
                 schema_version: CERTIFICATE_SCHEMA_VERSION,
+                canonicalization: None,
             },
             signature: String::new(),
         };
@@ -675,11 +1359,15 @@ mod tests {
             serde_json::from_slice(&fs::read(dir.join("manifest.json")).unwrap()).unwrap();
         assert_eq!(
             m.files.certificate.sha256,
-            sha256_file_hex(&dir.join("certificate.json")).unwrap()
+            file_hash::sha256_file(&dir.join("certificate.json")).unwrap()
         );
         assert_eq!(
-            m.files.labeled_pdf.sha256,
-            sha256_file_hex(&dir.join("labeled_document.pdf")).unwrap()
+            m.files.labeled_artifact_sha256(),
+            Some(
+                file_hash::sha256_file(&dir.join("labeled_document.pdf"))
+                    .unwrap()
+                    .as_str()
+            )
         );
         cleanup(&base);
     }
@@ -720,9 +1408,19 @@ mod tests {
         let base = temp_base();
         let (works, wid, cert_path, pdf_path, k) = setup(&base);
         // Injecte un échec juste avant la publication.
-        let res = create_work_package_inner(&works, &cert_path, &pdf_path, "t", &k, || {
-            Err(PackageError::Io("échec injecté".into()))
-        });
+        let res = create_work_package_inner(
+            &works,
+            &cert_path,
+            &PackageDocument::Labeled(LabeledInput {
+                artifact_path: &pdf_path,
+                artifact: &labeled_pdf_artifact(),
+                sidecar_path: None,
+            }),
+            None,
+            "t",
+            &k,
+            || Err(PackageError::Io("échec injecté".into())),
+        );
         assert!(res.is_err());
         // Aucun package_1 final.
         assert!(!final_dir(&works, &wid, 1).exists());
@@ -788,7 +1486,10 @@ mod tests {
         let (works, _wid, cert_path, _pdf_path, k) = setup(&base);
         let res =
             create_work_package_with(&works, &cert_path, &base.join("inbox/absent.pdf"), "t", &k);
-        assert!(matches!(res, Err(PackageError::LabeledPdfUnavailable(_))));
+        assert!(matches!(
+            res,
+            Err(PackageError::LabeledArtifactUnavailable(_))
+        ));
         assert!(!works.exists(), "aucun package si PDF absent");
         cleanup(&base);
     }
@@ -811,4 +1512,486 @@ mod tests {
         }
         cleanup(&base);
     }
+
+    /// Écrit un certificat lié à une source DOCX + un PDF rendu + un PDF
+    /// labellisé ; renvoie (works, wid, cert_path, labeled, source, rendition, k).
+    fn setup_rendition(
+        base: &Path,
+    ) -> (
+        PathBuf,
+        WorkId,
+        PathBuf,
+        PathBuf,
+        PathBuf,
+        PathBuf,
+        SigningKey,
+    ) {
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        let inbox = base.join("inbox");
+        fs::create_dir_all(&inbox).unwrap();
+        let source = inbox.join("Chapitre.DOCX");
+        fs::write(&source, b"PK fake docx bytes").unwrap();
+        let cert = make_cert_with_hash(&k, &wid, 1, &sha256_hex_bytes(b"PK fake docx bytes"));
+        let cert_path = inbox.join("certificate_1.json");
+        fs::write(&cert_path, serde_json::to_vec_pretty(&cert).unwrap()).unwrap();
+        let rendition = inbox.join("Chapitre.pdf");
+        fs::write(&rendition, b"%PDF-1.7 rendition").unwrap();
+        let labeled = inbox.join("labeled.pdf");
+        fs::write(&labeled, b"%PDF-1.7 labeled").unwrap();
+        (works, wid, cert_path, labeled, source, rendition, k)
+    }
+
+    #[test]
+    fn test_18_rendition_liee_et_verifiable() {
+        let base = temp_base();
+        let (works, wid, cert_path, labeled, source, rendition, k) = setup_rendition(&base);
+        let input = RenditionInput {
+            source_path: &source,
+            rendition_pdf_path: &rendition,
+            converter: "libreoffice-headless",
+            conversion_record_path: None,
+        };
+        let m =
+            create_rendition_package_with(&works, &cert_path, &labeled, &input, "t", &k).unwrap();
+        let dir = final_dir(&works, &wid, 1);
+        assert!(verify_manifest(&dir).is_ok());
+        let r = m.derived_rendition.expect("derived_rendition attendu");
+        assert_eq!(r.source.filename, "source_document.docx");
+        assert_eq!(r.source.sha256, sha256_hex_bytes(b"PK fake docx bytes"));
+        assert_eq!(r.rendition.filename, "rendition.pdf");
+        assert_eq!(r.rendition.sha256, sha256_hex_bytes(b"%PDF-1.7 rendition"));
+        assert_eq!(r.converter, "libreoffice-headless");
+        assert!(dir.join("source_document.docx").exists());
+        assert!(dir.join("rendition.pdf").exists());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_19_rendition_source_non_certifiee_refusee() {
+        let base = temp_base();
+        let (works, _wid, cert_path, labeled, source, rendition, k) = setup_rendition(&base);
+        fs::write(&source, b"PK autre contenu").unwrap();
+        let input = RenditionInput {
+            source_path: &source,
+            rendition_pdf_path: &rendition,
+            converter: "libreoffice-headless",
+            conversion_record_path: None,
+        };
+        let res = create_rendition_package_with(&works, &cert_path, &labeled, &input, "t", &k);
+        assert_eq!(res.err(), Some(PackageError::RenditionSourceMismatch));
+        assert!(
+            !works.exists(),
+            "aucun package si la source n'est pas certifiée"
+        );
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_20_rendition_pdf_altere_detecte() {
+        let base = temp_base();
+        let (works, wid, cert_path, labeled, source, rendition, k) = setup_rendition(&base);
+        let input = RenditionInput {
+            source_path: &source,
+            rendition_pdf_path: &rendition,
+            converter: "libreoffice-headless",
+            conversion_record_path: None,
+        };
+        create_rendition_package_with(&works, &cert_path, &labeled, &input, "t", &k).unwrap();
+        let dir = final_dir(&works, &wid, 1);
+        fs::write(dir.join("rendition.pdf"), b"%PDF-1.7 autre rendu").unwrap();
+        assert!(verify_manifest(&dir).is_err());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_21_sans_rendition_champ_absent_du_manifest() {
+        let base = temp_base();
+        let (works, wid, cert_path, pdf_path, k) = setup(&base);
+        let m = create_work_package_with(&works, &cert_path, &pdf_path, "t", &k).unwrap();
+        assert!(m.derived_rendition.is_none());
+        let json = fs::read_to_string(final_dir(&works, &wid, 1).join("manifest.json")).unwrap();
+        assert!(!json.contains("derived_rendition"), "corps V1 inchangé");
+        cleanup(&base);
+    }
+
+    fn write_record(path: &Path, source_sha256: &str, output_sha256: &str) {
+        let record = serde_json::json!({
+            "record_version": 1,
+            "source": { "filename": "Chapitre.DOCX", "sha256": source_sha256, "size": 18 },
+            "output": { "filename": "Chapitre.pdf", "sha256": output_sha256, "size": 18 },
+            "engine": { "name": "libreoffice-headless", "version": "LibreOffice 7.6.4.1",
+                        "converter_version": "0.1.0", "options": {} }
+        });
+        fs::write(path, serde_json::to_vec(&record).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_22_conversion_record_embarque_et_verifie() {
+        let base = temp_base();
+        let (works, wid, cert_path, labeled, source, rendition, k) = setup_rendition(&base);
+        let record = base.join("inbox").join("Chapitre.conversion_record.json");
+        write_record(
+            &record,
+            &sha256_hex_bytes(b"PK fake docx bytes"),
+            &sha256_hex_bytes(b"%PDF-1.7 rendition"),
+        );
+        let input = RenditionInput {
+            source_path: &source,
+            rendition_pdf_path: &rendition,
+            converter: "libreoffice-headless",
+            conversion_record_path: Some(&record),
+        };
+        let m =
+            create_rendition_package_with(&works, &cert_path, &labeled, &input, "t", &k).unwrap();
+        let dir = final_dir(&works, &wid, 1);
+        assert!(verify_manifest(&dir).is_ok());
+        let r = m.derived_rendition.unwrap().conversion_record.unwrap();
+        assert_eq!(r.filename, "conversion_record.json");
+        assert_eq!(r.sha256, file_hash::sha256_file(&record).unwrap());
+        assert_eq!(
+            fs::read(dir.join("conversion_record.json")).unwrap(),
+            fs::read(&record).unwrap()
+        );
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_23_conversion_record_d_un_autre_rendu_refuse() {
+        let base = temp_base();
+        let (works, _wid, cert_path, labeled, source, rendition, k) = setup_rendition(&base);
+        let record = base.join("inbox").join("Chapitre.conversion_record.json");
+        write_record(
+            &record,
+            &sha256_hex_bytes(b"PK fake docx bytes"),
+            &sha256_hex_bytes(b"%PDF-1.7 autre rendu"),
+        );
+        let input = RenditionInput {
+            source_path: &source,
+            rendition_pdf_path: &rendition,
+            converter: "libreoffice-headless",
+            conversion_record_path: Some(&record),
+        };
+        let res = create_rendition_package_with(&works, &cert_path, &labeled, &input, "t", &k);
+        assert!(matches!(
+            res,
+            Err(PackageError::ConversionRecordMismatch(_))
+        ));
+        assert!(!works.exists(), "aucun package sur record incohérent");
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_24_conversion_record_altere_detecte() {
+        let base = temp_base();
+        let (works, wid, cert_path, labeled, source, rendition, k) = setup_rendition(&base);
+        let record = base.join("inbox").join("Chapitre.conversion_record.json");
+        write_record(
+            &record,
+            &sha256_hex_bytes(b"PK fake docx bytes"),
+            &sha256_hex_bytes(b"%PDF-1.7 rendition"),
+        );
+        let input = RenditionInput {
+            source_path: &source,
+            rendition_pdf_path: &rendition,
+            converter: "libreoffice-headless",
+            conversion_record_path: Some(&record),
+        };
+        create_rendition_package_with(&works, &cert_path, &labeled, &input, "t", &k).unwrap();
+        let dir = final_dir(&works, &wid, 1);
+        write_record(
+            &dir.join("conversion_record.json"),
+            &sha256_hex_bytes(b"PK fake docx bytes"),
+            &sha256_hex_bytes(b"%PDF-1.7 autre rendu"),
+        );
+        assert!(verify_manifest(&dir).is_err());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_25_pdf_garde_la_forme_v1_labeled_pdf() {
+        let base = temp_base();
+        let (works, wid, cert_path, pdf_path, k) = setup(&base);
+        create_work_package_with(&works, &cert_path, &pdf_path, "t", &k).unwrap();
+        let raw = fs::read_to_string(final_dir(&works, &wid, 1).join("manifest.json")).unwrap();
+        let v: serde_json::Value = serde_json::from_str(&raw).unwrap();
+        assert_eq!(
+            v["files"]["labeled_pdf"]["filename"],
+            serde_json::json!("labeled_document.pdf")
+        );
+        assert!(v["files"].get("labeled_artifact").is_none());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_26_artefact_non_pdf_reference_et_verifiable() {
+        let base = temp_base();
+        let (works, wid, cert_path, _pdf_path, k) = setup(&base);
+        let png_path = base.join("inbox").join("labeled.png");
+        fs::write(&png_path, b"\x89PNG fake image bytes").unwrap();
+        let produced = RenderedArtifactRef {
+            artifact_name: "labeled_image.png".to_string(),
+            media_type: DocumentArtifactKind::Image,
+            fingerprint_sha256: None,
+            size_bytes: None,
+            mime_type: Some("image/png".to_string()),
+        };
+        let labeled = LabeledInput {
+            artifact_path: &png_path,
+            artifact: &produced,
+            sidecar_path: None,
+        };
+        let m = create_artifact_package_with(&works, &cert_path, &labeled, None, "t", &k).unwrap();
+        let dir = final_dir(&works, &wid, 1);
+        assert!(dir.join("labeled_image.png").exists());
+        assert!(!dir.join("labeled_document.pdf").exists());
+        assert!(verify_manifest(&dir).is_ok());
+
+        let a = m.files.labeled_artifact.as_ref().unwrap();
+        assert_eq!(a.media_type, DocumentArtifactKind::Image);
+        assert_eq!(a.size_bytes, Some(fs::metadata(&png_path).unwrap().len()));
+        assert_eq!(a.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(
+            m.files.labeled_artifact_sha256(),
+            Some(
+                file_hash::sha256_file(&dir.join("labeled_image.png"))
+                    .unwrap()
+                    .as_str()
+            )
+        );
+        let raw = fs::read_to_string(dir.join("manifest.json")).unwrap();
+        assert!(raw.contains("\"labeled_artifact\""));
+        assert!(!raw.contains("\"labeled_pdf\""));
+        let back: PackageManifest = serde_json::from_str(&raw).unwrap();
+        assert_eq!(back.files, m.files);
+
+        // Artefact remplacé après coup : détecté.
+        fs::write(dir.join("labeled_image.png"), b"autre image").unwrap();
+        assert!(verify_manifest(&dir).is_err());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_27_nom_d_artefact_invalide_refuse() {
+        let base = temp_base();
+        let (works, wid, cert_path, pdf_path, k) = setup(&base);
+        for name in [
+            "../evil.pdf",
+            "sub/x.pdf",
+            "manifest.json",
+            "source_document.docx",
+            "",
+        ] {
+            let mut produced = labeled_pdf_artifact();
+            produced.artifact_name = name.to_string();
+            let labeled = LabeledInput {
+                artifact_path: &pdf_path,
+                artifact: &produced,
+                sidecar_path: None,
+            };
+            let res = create_artifact_package_with(&works, &cert_path, &labeled, None, "t", &k);
+            assert!(
+                matches!(res, Err(PackageError::InvalidArtifactName(_))),
+                "nom accepté : {name:?}"
+            );
+        }
+        assert!(!final_dir(&works, &wid, 1).exists());
+        cleanup(&base);
+    }
+
+    /// Requête de sidecar pour le certificat de `cert_path`.
+    fn sidecar_request(cert_path: &Path) -> crate::evidence_kernel::LabeledArtifactRequest {
+        use crate::evidence_kernel::{
+            LabeledArtifactRequest, MediaKind, ObjectVersionRef, ObservedObjectRef,
+        };
+        let cert: WorkCertificate = serde_json::from_slice(&fs::read(cert_path).unwrap()).unwrap();
+        LabeledArtifactRequest {
+            source_object: ObservedObjectRef::new(cert.work_id.as_str(), MediaKind::Image),
+            source_version: ObjectVersionRef {
+                fingerprint_sha256: cert.public_core_evidence.document.hash_current.clone(),
+                size_bytes: None,
+                mime_type: None,
+                chunked_fingerprint: None,
+                media_specific: None,
+            },
+            certificate_id: cert.certificate_id.clone(),
+            verify_url: "https://verify.humanorigin.app/r/x".to_string(),
+            verdict_label: "OBSERVED_WORK_CONSISTENT".to_string(),
+            signing_key_id: Some(cert.signature_metadata.signing_key_id.clone()),
+            certificate_created_at: None,
+        }
+    }
+
+    /// Artefact image + sidecar signé pour le certificat de `cert_path`.
+    fn setup_sidecar(
+        base: &Path,
+        cert_path: &Path,
+        k: &SigningKey,
+    ) -> (PathBuf, RenderedArtifactRef, PathBuf) {
+        let png_path = base.join("inbox").join("labeled_image.png");
+        fs::write(&png_path, b"\x89PNG labeled image").unwrap();
+        let produced = RenderedArtifactRef {
+            artifact_name: "labeled_image.png".to_string(),
+            media_type: DocumentArtifactKind::Image,
+            fingerprint_sha256: Some(sha256_hex_bytes(b"\x89PNG labeled image")),
+            size_bytes: None,
+            mime_type: Some("image/png".to_string()),
+        };
+        let request = sidecar_request(cert_path);
+        let sidecar = work_sidecar::sign_sidecar(&request, Some(produced.clone()), k).unwrap();
+        let sidecar_path = base.join("inbox").join("labeled_image.ho.json");
+        work_sidecar::write_sidecar(&sidecar, &sidecar_path).unwrap();
+        (png_path, produced, sidecar_path)
+    }
+
+    #[test]
+    fn test_28_sidecar_embarque_et_verifie() {
+        let base = temp_base();
+        let (works, wid, cert_path, _pdf_path, k) = setup(&base);
+        let (png_path, produced, sidecar_path) = setup_sidecar(&base, &cert_path, &k);
+        let labeled = LabeledInput {
+            artifact_path: &png_path,
+            artifact: &produced,
+            sidecar_path: Some(&sidecar_path),
+        };
+        let m = create_artifact_package_with(&works, &cert_path, &labeled, None, "t", &k).unwrap();
+        let dir = final_dir(&works, &wid, 1);
+        let sidecar = m.files.labeled_sidecar.expect("labeled_sidecar attendu");
+        assert_eq!(sidecar.filename, "labeled_image.ho.json");
+        assert_eq!(
+            fs::read(dir.join("labeled_image.ho.json")).unwrap(),
+            fs::read(&sidecar_path).unwrap()
+        );
+        assert!(verify_manifest(&dir).is_ok());
+
+        // Sidecar remplacé après coup : détecté.
+        fs::write(dir.join("labeled_image.ho.json"), b"{}").unwrap();
+        assert!(verify_manifest(&dir).is_err());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_29_sidecar_d_un_autre_artefact_refuse() {
+        let base = temp_base();
+        let (works, wid, cert_path, _pdf_path, k) = setup(&base);
+        let (png_path, produced, sidecar_path) = setup_sidecar(&base, &cert_path, &k);
+        // L'artefact livré n'est plus celui que décrit le sidecar.
+        fs::write(&png_path, b"\x89PNG autre image").unwrap();
+        let labeled = LabeledInput {
+            artifact_path: &png_path,
+            artifact: &produced,
+            sidecar_path: Some(&sidecar_path),
+        };
+        let res = create_artifact_package_with(&works, &cert_path, &labeled, None, "t", &k);
+        assert!(matches!(res, Err(PackageError::SidecarInvalid(_))));
+        assert!(!final_dir(&works, &wid, 1).exists());
+        cleanup(&base);
+    }
+
+    /// Sidecar signé de l'original (aucune copie labellisée).
+    fn setup_original_sidecar(base: &Path, cert_path: &Path, k: &SigningKey) -> PathBuf {
+        let sidecar = work_sidecar::sign_sidecar(&sidecar_request(cert_path), None, k).unwrap();
+        let sidecar_path = base.join("inbox").join("original_document.ho.json");
+        work_sidecar::write_sidecar(&sidecar, &sidecar_path).unwrap();
+        sidecar_path
+    }
+
+    #[test]
+    fn test_30_sidecar_seul_original_reference_sans_copie() {
+        let base = temp_base();
+        let (works, wid, cert_path, _pdf_path, k) = setup(&base);
+        let sidecar_path = setup_original_sidecar(&base, &cert_path, &k);
+        let original = OriginalInput {
+            sha256: &"a".repeat(64),
+            size_bytes: Some(42),
+            sidecar_path: &sidecar_path,
+        };
+        let m = create_sidecar_package_with(&works, &cert_path, &original, "t", &k).unwrap();
+        assert_eq!(m.files.kind(), PackageKind::SidecarOnly);
+        assert!(m.files.labeled_artifact.is_none());
+        assert_eq!(m.files.labeled_artifact_sha256(), None);
+        assert_eq!(
+            m.files.original_document,
+            Some(OriginalDocumentRef {
+                sha256: "a".repeat(64),
+                size_bytes: Some(42),
+            })
+        );
+
+        let dir = final_dir(&works, &wid, 1);
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "certificate.json",
+                "manifest.json",
+                "original_document.ho.json"
+            ]
+        );
+        assert!(verify_manifest(&dir).is_ok());
+        let raw = fs::read_to_string(dir.join("manifest.json")).unwrap();
+        assert!(raw.contains("\"original_document\""));
+        assert!(!raw.contains("\"labeled_pdf\""));
+        assert!(!raw.contains("\"labeled_artifact\""));
+        let back: PackageManifest = serde_json::from_str(&raw).unwrap();
+        assert_eq!(back, m);
+
+        // Un fichier qui n'est pas l'original référencé : refusé.
+        let other_file = base.join("original.bin");
+        fs::write(&other_file, b"x").unwrap();
+        assert!(verify_original_document(&dir, &other_file).is_err());
+
+        // Sidecar retiré du manifest : un package sidecar seul sans sidecar est refusé.
+        let mut v: serde_json::Value = serde_json::from_str(&raw).unwrap();
+        v["files"]
+            .as_object_mut()
+            .unwrap()
+            .remove("labeled_sidecar");
+        assert!(serde_json::from_value::<PackageManifest>(v).is_err());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_31_sidecar_seul_original_ou_sidecar_incoherent_refuse() {
+        let base = temp_base();
+        let (works, wid, cert_path, _pdf_path, k) = setup(&base);
+        let sidecar_path = setup_original_sidecar(&base, &cert_path, &k);
+
+        // Original ≠ document certifié.
+        let other = "b".repeat(64);
+        let res = create_sidecar_package_with(
+            &works,
+            &cert_path,
+            &OriginalInput {
+                sha256: &other,
+                size_bytes: None,
+                sidecar_path: &sidecar_path,
+            },
+            "t",
+            &k,
+        );
+        assert!(matches!(res, Err(PackageError::OriginalDocumentMismatch)));
+
+        // Sidecar d'une copie labellisée : pas un sidecar d'original.
+        let (_png, _produced, labeled_sidecar) = setup_sidecar(&base, &cert_path, &k);
+        let res = create_sidecar_package_with(
+            &works,
+            &cert_path,
+            &OriginalInput {
+                sha256: &"a".repeat(64),
+                size_bytes: None,
+                sidecar_path: &labeled_sidecar,
+            },
+            "t",
+            &k,
+        );
+        assert!(matches!(res, Err(PackageError::SidecarInvalid(_))));
+        assert!(!final_dir(&works, &wid, 1).exists());
+        cleanup(&base);
+    }
 }
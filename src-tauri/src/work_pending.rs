@@ -13,7 +13,9 @@
 //! - un pending INTERRUPTED n'est jamais qualifiant pour fabriquer une période
 //!   signée ;
 //! - le pending n'est supprimé qu'APRÈS l'écriture réussie du `period.json`
-//!   immuable.
+//!   immuable ;
+//! - le snapshot chiffré de début de période (`work_snapshot`) ne survit pas à
+//!   un pending PENDING : purgé à la suppression, l'interruption et l'abandon.
 
 // Fondation (Commit 4) : API consommée par start/stop_work_period au Commit 5.
 #![allow(dead_code)]
@@ -24,7 +26,10 @@ use std::fs::{File, OpenOptions};
 use std::io::{ErrorKind, Write};
 use std::path::{Path, PathBuf};
 
+use crate::document_set::DocumentSetMember;
+use crate::evidence_kernel::ChunkedFingerprint;
 use crate::work_period;
+use crate::work_snapshot;
 use crate::work_store::WorkId;
 
 /// Version de schéma du format pending.
@@ -56,6 +61,14 @@ pub struct PendingPeriod {
     pub document_path: String,
     pub hash_start: String,
     pub size_start: u64,
+    /// Empreinte par blocs de départ (gros médias, `file_chunks`). Absente
+    /// sinon : pending V1 inchangé.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunked_start: Option<ChunkedFingerprint>,
+    /// Membres de départ d'un Work multi-fichiers (`document_set`), pour les
+    /// changements par fichier au stop. Absents sinon : pending V1 inchangé.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members_start: Option<Vec<DocumentSetMember>>,
     pub started_at: String,
     pub state: PendingState,
 }
@@ -210,6 +223,8 @@ pub fn mark_pending_interrupted(
         let tmp = write_temp(parent, PENDING_FILE, &json)?;
         publish_replace(&tmp, &path)?;
     }
+    // Une période interrompue ne sera jamais mesurée : version de départ inutile.
+    let _ = work_snapshot::purge_start_snapshots(works_root, work_id);
     Ok(pending)
 }
 
@@ -231,6 +246,7 @@ fn pending_matches_period(
         && pending.work_id == period.work_id
         && pending.sequence_number == period.sequence_number
         && pending.hash_start == period.hash_start
+        && pending.chunked_start == period.chunked_start
         && pending.previous_period_id == period.previous_period_id
         && pending.previous_period_record_sha256 == period.previous_period_record_sha256
 }
@@ -245,6 +261,7 @@ fn remove_pending_file(works_root: &Path, work_id: &WorkId) -> Result<(), String
             sync_dir(parent);
         }
     }
+    let _ = work_snapshot::purge_start_snapshots(works_root, work_id);
     Ok(())
 }
 
@@ -340,6 +357,7 @@ pub fn abort_pending_before_start(
     let src = pending_path(works_root, work_id);
     fs::rename(&src, &archive).map_err(|e| e.to_string())?;
     sync_dir(&dir);
+    let _ = work_snapshot::purge_start_snapshots(works_root, work_id);
     Ok(archive)
 }
 
@@ -399,6 +417,8 @@ mod tests {
             document_path: "/tmp/doc.txt".to_string(),
             hash_start: "a".repeat(64),
             size_start: 100,
+            chunked_start: None,
+            members_start: None,
             started_at: "2026-07-15T10:00:00Z".to_string(),
             state: PendingState::Pending,
         }
@@ -423,9 +443,13 @@ mod tests {
             document_path: pending.document_path.clone(),
             hash_start: pending.hash_start.clone(),
             size_start: pending.size_start,
+            chunked_start: pending.chunked_start.clone(),
             hash_end: "b".repeat(64),
             size_end: 120,
+            chunked_end: None,
+            member_changes: None,
             change_observed_during_period: true,
+            change_magnitude: None,
             engine: json!({ "score": 90 }),
         };
         let period = work_period::sign_period_record(inputs, &key).unwrap();
@@ -711,4 +735,40 @@ mod tests {
         assert!(!residual, "aucun fichier temporaire ne doit rester");
         cleanup(&root);
     }
+
+    #[test]
+    fn test_11_snapshot_de_depart_purge_avec_le_pending() {
+        let root = temp_root();
+        let works = root.join("Works");
+        let snapshot = |pending: &PendingPeriod| {
+            work_snapshot::write_start_snapshot(&works, &pending.work_id, &pending.period_id, b"v0")
+                .unwrap()
+        };
+
+        // Suppression après période signée.
+        let wid = WorkId::new();
+        let pending = sample_pending(&wid);
+        write_pending_atomic(&works, &pending).unwrap();
+        let snap = snapshot(&pending);
+        write_matching_period(&works, &pending, None);
+        remove_pending_after_period_success(&works, &wid, 0).unwrap();
+        assert!(!snap.exists());
+
+        // Interruption.
+        let wid = WorkId::new();
+        let pending = sample_pending(&wid);
+        write_pending_atomic(&works, &pending).unwrap();
+        let snap = snapshot(&pending);
+        recover_orphaned_pending(&works, &wid).unwrap();
+        assert!(!snap.exists());
+
+        // Abandon avant démarrage.
+        let wid = WorkId::new();
+        let pending = sample_pending(&wid);
+        write_pending_atomic(&works, &pending).unwrap();
+        let snap = snapshot(&pending);
+        abort_pending_before_start(&works, &wid, &pending.period_id).unwrap();
+        assert!(!snap.exists());
+        cleanup(&root);
+    }
 }
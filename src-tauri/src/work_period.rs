@@ -9,6 +9,11 @@
 //! certificat, aucune UI, aucun accès `Projects/`.
 //!
 //! Identité de signature : `LOCAL_DEVICE` uniquement.
+//!
+//! Plausibilité : quand l'ampleur du changement est mesurée, la croissance du
+//! texte est confrontée à la saisie du bloc `engine` AVANT signature
+//! (`engine.document_growth`) ; une croissance sans saisie correspondante fait
+//! échouer le gate de la période.
 
 // Fondation (Commit 3) : API consommée par start/stop_work_period au Commit 4.
 #![allow(dead_code)]
@@ -16,7 +21,7 @@
 use base64::{engine::general_purpose, Engine as _};
 use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
 use std::fs;
@@ -24,6 +29,9 @@ use std::fs::{File, OpenOptions};
 use std::io::{ErrorKind, Write};
 use std::path::{Path, PathBuf};
 
+use crate::document_set::MemberChange;
+use crate::evidence_kernel::ChunkedFingerprint;
+use crate::work_change::ChangeMagnitude;
 use crate::work_store::WorkId;
 
 /// Version de schéma du format période.
@@ -33,18 +41,54 @@ const SIGN_ALG: &str = "ed25519";
 const LOCAL_DEVICE_IDENTITY: &str = "LOCAL_DEVICE";
 const PERIODS_DIR: &str = "periods";
 
-/// Schéma de canonicalisation réellement appliqué au payload signé.
+/// Schéma de canonicalisation appliqué à un payload signé (lié au record via
+/// `SignatureMetadata::canonicalization`).
 ///
 /// HO-CANON-V1 = clés d'objets JSON triées récursivement (ordre d'octets UTF-8),
-/// sérialisation compacte `serde_json`, champs `signature` et
-/// `period_record_sha256` exclus du payload. Indépendant de l'ordre d'insertion
-/// des clés et de la feature `preserve_order`.
+/// sérialisation compacte `serde_json`. Indépendant de l'ordre d'insertion des
+/// clés et de la feature `preserve_order`. Ce N'EST PAS RFC 8785 : les nombres
+/// suivent `serde_json` (`5.0`, `1e+20`, `1e-6`), pas ECMAScript.
 ///
-/// NOTE : ce N'EST PAS RFC 8785 (JCS). La normalisation des nombres/chaînes
-/// suit `serde_json`, pas JCS. Suffisant pour l'intégrité et le round-trip
-/// (le bloc `engine` est capturé une fois puis re-sérialisé tel quel). Pour une
-/// interop JCS externe, une crate dédiée serait nécessaire.
-const CANON_SCHEME: &str = "HO-CANON-V1";
+/// HO-JCS-V1 = RFC 8785 (module `jcs`) : recalculable à l'identique par un
+/// vérificateur externe dans n'importe quel langage, flottants `engine` compris.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CanonScheme {
+    HoCanonV1,
+    HoJcsV1,
+}
+
+impl CanonScheme {
+    pub fn label(self) -> &'static str {
+        match self {
+            CanonScheme::HoCanonV1 => "HO-CANON-V1",
+            CanonScheme::HoJcsV1 => "HO-JCS-V1",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Result<Self, String> {
+        match label {
+            "HO-CANON-V1" => Ok(CanonScheme::HoCanonV1),
+            "HO-JCS-V1" => Ok(CanonScheme::HoJcsV1),
+            other => Err(format!("schéma de canonicalisation inattendu : {other}")),
+        }
+    }
+}
+
+/// Schéma des NOUVEAUX records par défaut. Les deux schémas restent vérifiables.
+pub const DEFAULT_CANON_SCHEME: CanonScheme = CanonScheme::HoCanonV1;
+
+/// Schéma des NOUVEAUX records d'un Work : réglage `WorkRecord::canonicalization`
+/// (`set_work_canonicalization`), sinon `DEFAULT_CANON_SCHEME`.
+pub(crate) fn work_canon_scheme(
+    works_root: &Path,
+    work_id: &WorkId,
+) -> Result<CanonScheme, String> {
+    let record = crate::work_store::read_work_metadata(works_root, work_id)?;
+    match record.canonicalization.as_deref() {
+        None => Ok(DEFAULT_CANON_SCHEME),
+        Some(label) => CanonScheme::from_label(label),
+    }
+}
 
 // --- TYPES --------------------------------------------------------------------
 
@@ -61,7 +105,7 @@ pub struct SignatureMetadata {
     pub signing_key_id: String,
     /// Identité du signataire. Toujours `LOCAL_DEVICE` en Alpha.
     pub identity: String,
-    /// Schéma de canonicalisation réellement utilisé (voir `CANON_SCHEME`).
+    /// Schéma de canonicalisation réellement utilisé (voir `CanonScheme`).
     pub canonicalization: String,
     pub schema_version: u32,
 }
@@ -78,13 +122,29 @@ pub struct ObservationPeriod {
     pub document_path: String,
     pub hash_start: String,
     pub size_start: u64,
+    /// Empreinte par blocs de départ (gros médias, `file_chunks`), en plus de
+    /// `hash_start`. Absente sinon : payload signé V1 inchangé.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunked_start: Option<ChunkedFingerprint>,
     pub hash_end: String,
     pub size_end: u64,
+    /// Empreinte par blocs de fin, en plus de `hash_end`. Absente sinon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunked_end: Option<ChunkedFingerprint>,
+    /// Work multi-fichiers : changements par fichier début -> fin (chemins
+    /// relatifs à la racine de l'ensemble). Absent sinon : payload signé V1
+    /// inchangé.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member_changes: Option<Vec<MemberChange>>,
     /// Le document a-t-il changé entre début et fin (hash_start != hash_end).
     pub net_document_change: bool,
     /// Signal complémentaire : des modifications ont-elles été observées
     /// pendant la période (issu du moteur). N'est pas un hard block.
     pub change_observed_during_period: bool,
+    /// Ampleur du changement de texte début -> fin (DOCX/TXT/MD, comptes
+    /// seulement). Absente si non mesurable : payload signé V1 inchangé.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_magnitude: Option<ChangeMagnitude>,
     /// Données moteur/scoring sous forme compatible et découplée du core.
     pub engine: Value,
     pub signature_metadata: SignatureMetadata,
@@ -104,9 +164,13 @@ pub struct PeriodInputs {
     pub document_path: String,
     pub hash_start: String,
     pub size_start: u64,
+    pub chunked_start: Option<ChunkedFingerprint>,
     pub hash_end: String,
     pub size_end: u64,
+    pub chunked_end: Option<ChunkedFingerprint>,
+    pub member_changes: Option<Vec<MemberChange>>,
     pub change_observed_during_period: bool,
+    pub change_magnitude: Option<ChangeMagnitude>,
     pub engine: Value,
 }
 
@@ -140,10 +204,21 @@ fn sha256_hex_str(s: &str) -> String {
     format!("{:x}", Sha256::digest(s.as_bytes()))
 }
 
-/// Payload canonique signé (schéma `HO-CANON-V1`) : tous les champs de la
-/// période — y compris `signature_metadata` — SAUF `signature` et
-/// `period_record_sha256`, avec clés triées récursivement.
+/// Octets canoniques d'une `Value` selon `scheme`.
+fn canonical_value_bytes(value: &Value, scheme: CanonScheme) -> Result<Vec<u8>, String> {
+    match scheme {
+        CanonScheme::HoCanonV1 => {
+            serde_json::to_vec(&canonicalize_value(value)).map_err(|e| e.to_string())
+        }
+        CanonScheme::HoJcsV1 => crate::jcs::jcs_bytes(value),
+    }
+}
+
+/// Payload canonique signé (schéma de `signature_metadata.canonicalization`) :
+/// tous les champs de la période — y compris `signature_metadata` — SAUF
+/// `signature` et `period_record_sha256`, avec clés triées récursivement.
 pub fn canonical_period_payload(period: &ObservationPeriod) -> Result<Vec<u8>, String> {
+    let scheme = CanonScheme::from_label(&period.signature_metadata.canonicalization)?;
     let mut value = serde_json::to_value(period).map_err(|e| e.to_string())?;
     match value.as_object_mut() {
         Some(map) => {
@@ -153,8 +228,7 @@ pub fn canonical_period_payload(period: &ObservationPeriod) -> Result<Vec<u8>, S
         }
         None => return Err("période non sérialisable en objet JSON".to_string()),
     }
-    let canonical = canonicalize_value(&value);
-    serde_json::to_vec(&canonical).map_err(|e| e.to_string())
+    canonical_value_bytes(&value, scheme)
 }
 
 /// SHA256 hex du payload canonique.
@@ -169,6 +243,15 @@ pub fn compute_period_record_sha256(period: &ObservationPeriod) -> Result<String
 pub(crate) fn canonical_bytes_excluding<T: Serialize>(
     value: &T,
     exclude_top_level: &[&str],
+) -> Result<Vec<u8>, String> {
+    canonical_bytes_with(value, exclude_top_level, CanonScheme::HoCanonV1)
+}
+
+/// Comme `canonical_bytes_excluding`, dans le schéma `scheme`.
+pub(crate) fn canonical_bytes_with<T: Serialize>(
+    value: &T,
+    exclude_top_level: &[&str],
+    scheme: CanonScheme,
 ) -> Result<Vec<u8>, String> {
     let mut v = serde_json::to_value(value).map_err(|e| e.to_string())?;
     if let Some(map) = v.as_object_mut() {
@@ -176,15 +259,22 @@ pub(crate) fn canonical_bytes_excluding<T: Serialize>(
             map.remove(*field);
         }
     }
-    let canonical = canonicalize_value(&v);
-    serde_json::to_vec(&canonical).map_err(|e| e.to_string())
+    canonical_value_bytes(&v, scheme)
 }
 
 /// Canonicalise (HO-CANON-V1 : clés d'objets triées récursivement) N'IMPORTE
 /// quelle valeur sérialisable puis renvoie son SHA256 hex. Source UNIQUE de
 /// canonicalisation pour la couche Work (réutilisée par la CoreEvidence).
 pub(crate) fn canonical_sha256<T: Serialize>(value: &T) -> Result<String, String> {
-    let bytes = canonical_bytes_excluding(value, &[])?;
+    canonical_sha256_with(value, CanonScheme::HoCanonV1)
+}
+
+/// Comme `canonical_sha256`, dans le schéma `scheme`.
+pub(crate) fn canonical_sha256_with<T: Serialize>(
+    value: &T,
+    scheme: CanonScheme,
+) -> Result<String, String> {
+    let bytes = canonical_bytes_with(value, &[], scheme)?;
     Ok(format!("{:x}", Sha256::digest(&bytes)))
 }
 
@@ -200,17 +290,129 @@ fn normalize_engine(engine: &Value) -> Result<Value, String> {
         .map_err(|e| e.to_string())
 }
 
-/// Bâtit une période complète, calcule son record SHA256 et la signe.
+// --- PLAUSIBILITÉ SAISIE / CROISSANCE -----------------------------------------
+
+/// En dessous, la croissance non collée s'explique par l'autocomplétion ou la
+/// correction automatique : aucun jugement.
+const GROWTH_MIN_UNEXPLAINED_CHARS: u64 = 500;
+/// Une frappe produit au plus un caractère ; marge pour l'autocomplétion.
+const GROWTH_MAX_CHARS_PER_KEYSTROKE: u64 = 3;
+
+/// Drapeau moteur/certificat : croissance du texte très au-delà de la saisie.
+pub(crate) const GROWTH_EXCEEDS_INPUT_FLAG: &str = "GROWTH_EXCEEDS_INPUT";
+
+/// Croissance confrontée à la saisie : caractères ajoutés quand le texte a été
+/// mesuré (`change_magnitude`), sinon octets gagnés par le fichier (PDF, format
+/// non mesuré, snapshot de début absent ou illisible). Jamais de période qui
+/// échappe au contrôle faute de mesure fine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Growth {
+    TextChars(u64),
+    FileBytes(u64),
+}
+
+impl Growth {
+    fn of(inputs: &PeriodInputs) -> Self {
+        match &inputs.change_magnitude {
+            Some(magnitude) => Growth::TextChars(magnitude.chars_added),
+            None => Growth::FileBytes(inputs.size_end.saturating_sub(inputs.size_start)),
+        }
+    }
+
+    fn added(self) -> u64 {
+        match self {
+            Growth::TextChars(n) | Growth::FileBytes(n) => n,
+        }
+    }
+}
+
+/// Confronte la croissance (hors collage) aux frappes observées et l'inscrit
+/// dans `engine.document_growth`. Croissance inexpliquée ->
+/// `analysis.gate_passed = false` et drapeau `GROWTH_EXCEEDS_INPUT`.
+fn apply_growth_check(engine: &mut Value, growth: Growth) {
+    let count = |block: &str, key: &str| {
+        engine
+            .get(block)
+            .and_then(|b| b.get(key))
+            .and_then(Value::as_u64)
+            .unwrap_or(0)
+    };
+    let keystrokes = count("keyboard_dynamics", "total_keystrokes");
+    let pasted_chars = count("paste_stats", "pasted_chars");
+
+    let unexplained = growth.added().saturating_sub(pasted_chars);
+    let ratio = unexplained as f64 / keystrokes.max(1) as f64;
+    let exceeds_input = unexplained >= GROWTH_MIN_UNEXPLAINED_CHARS
+        && unexplained > keystrokes.saturating_mul(GROWTH_MAX_CHARS_PER_KEYSTROKE);
+
+    let Some(map) = engine.as_object_mut() else {
+        return;
+    };
+    let (basis, added_key) = match growth {
+        Growth::TextChars(_) => ("text_chars", "chars_added"),
+        Growth::FileBytes(_) => ("file_bytes", "bytes_added"),
+    };
+    map.insert(
+        "document_growth".to_string(),
+        json!({
+            "basis": basis,
+            added_key: growth.added(),
+            "pasted_chars": pasted_chars,
+            "total_keystrokes": keystrokes,
+            "growth_vs_input_ratio": (ratio * 100.0).round() / 100.0,
+            "exceeds_input": exceeds_input,
+        }),
+    );
+    if !exceeds_input {
+        return;
+    }
+    if let Some(analysis) = map.get_mut("analysis").and_then(Value::as_object_mut) {
+        analysis.insert("gate_passed".to_string(), Value::Bool(false));
+        analysis.insert(
+            "gate_reason".to_string(),
+            Value::String(format!(
+                "Croissance du document sans saisie correspondante ({} {} ajoutés hors collage / {} frappes).",
+                unexplained,
+                match growth {
+                    Growth::TextChars(_) => "caractères",
+                    Growth::FileBytes(_) => "octets",
+                },
+                keystrokes
+            )),
+        );
+        match analysis.get_mut("flags").and_then(Value::as_array_mut) {
+            Some(flags) => flags.push(json!(GROWTH_EXCEEDS_INPUT_FLAG)),
+            None => {
+                analysis.insert("flags".to_string(), json!([GROWTH_EXCEEDS_INPUT_FLAG]));
+            }
+        }
+    }
+}
+
+/// Bâtit une période complète, calcule son record SHA256 et la signe
+/// (`DEFAULT_CANON_SCHEME`).
 /// `net_document_change` est dérivé objectivement de hash_start != hash_end.
 pub fn sign_period_record(
     inputs: PeriodInputs,
     signing_key: &SigningKey,
+) -> Result<ObservationPeriod, String> {
+    sign_period_record_with(inputs, signing_key, DEFAULT_CANON_SCHEME)
+}
+
+/// Comme `sign_period_record`, dans le schéma de canonicalisation `scheme`.
+pub fn sign_period_record_with(
+    inputs: PeriodInputs,
+    signing_key: &SigningKey,
+    scheme: CanonScheme,
 ) -> Result<ObservationPeriod, String> {
     let net_document_change = inputs.hash_start != inputs.hash_end;
     let public_key = general_purpose::STANDARD.encode(signing_key.verifying_key().to_bytes());
     let signing_key_id = sha256_hex_str(&public_key);
+    let growth = Growth::of(&inputs);
+    let mut engine = inputs.engine;
+    apply_growth_check(&mut engine, growth);
     // Point-fixe serde_json AVANT hash/signature/écriture (cf. normalize_engine).
-    let engine = normalize_engine(&inputs.engine)?;
+    let engine = normalize_engine(&engine)?;
 
     let mut period = ObservationPeriod {
         schema_version: PERIOD_SCHEMA_VERSION,
@@ -222,17 +424,21 @@ pub fn sign_period_record(
         document_path: inputs.document_path,
         hash_start: inputs.hash_start,
         size_start: inputs.size_start,
+        chunked_start: inputs.chunked_start,
         hash_end: inputs.hash_end,
         size_end: inputs.size_end,
+        chunked_end: inputs.chunked_end,
+        member_changes: inputs.member_changes,
         net_document_change,
         change_observed_during_period: inputs.change_observed_during_period,
+        change_magnitude: inputs.change_magnitude,
         engine,
         signature_metadata: SignatureMetadata {
             alg: SIGN_ALG.to_string(),
             public_key,
             signing_key_id,
             identity: LOCAL_DEVICE_IDENTITY.to_string(),
-            canonicalization: CANON_SCHEME.to_string(),
+            canonicalization: scheme.label().to_string(),
             schema_version: PERIOD_SCHEMA_VERSION,
         },
         signature: String::new(),
@@ -276,12 +482,7 @@ pub fn verify_period_record(period: &ObservationPeriod) -> Result<(), String> {
             meta.identity
         ));
     }
-    if meta.canonicalization != CANON_SCHEME {
-        return Err(format!(
-            "schéma de canonicalisation inattendu : {}",
-            meta.canonicalization
-        ));
-    }
+    CanonScheme::from_label(&meta.canonicalization)?;
     let expected_key_id = sha256_hex_str(&meta.public_key);
     if meta.signing_key_id != expected_key_id {
         return Err("signing_key_id incohérent avec la clé publique".to_string());
@@ -585,9 +786,13 @@ mod tests {
             document_path: "/tmp/doc.txt".to_string(),
             hash_start: "a".repeat(64),
             size_start: 100,
+            chunked_start: None,
             hash_end: "b".repeat(64),
             size_end: 120,
+            chunked_end: None,
+            member_changes: None,
             change_observed_during_period: true,
+            change_magnitude: None,
             engine: json!({ "score": 88, "gate_passed": true }),
         }
     }
@@ -602,9 +807,13 @@ mod tests {
             document_path: "/tmp/doc.txt".to_string(),
             hash_start: prev.hash_end.clone(),
             size_start: prev.size_end,
+            chunked_start: None,
             hash_end: "c".repeat(64),
             size_end: 130,
+            chunked_end: None,
+            member_changes: None,
             change_observed_during_period: true,
+            change_magnitude: None,
             engine: json!({ "score": 90, "gate_passed": true }),
         }
     }
@@ -657,6 +866,197 @@ mod tests {
         assert!(verify_period_record(&p2).is_err());
     }
 
+    #[test]
+    fn test_ampleur_signee_et_absente_si_non_mesuree() {
+        let k = key();
+        let wid = WorkId::new();
+        let p = sign_period_record(genesis_inputs(&wid), &k).unwrap();
+        let v = serde_json::to_value(&p).unwrap();
+        assert!(
+            v.get("change_magnitude").is_none(),
+            "None -> absent du JSON"
+        );
+
+        let mut inputs = genesis_inputs(&wid);
+        inputs.change_magnitude = Some(ChangeMagnitude {
+            format: "docx".to_string(),
+            chars_start: 100,
+            chars_end: 160,
+            chars_added: 70,
+            chars_removed: 10,
+            edit_distance_ratio_bps: 3077,
+            paragraphs_start: 2,
+            paragraphs_end: 3,
+            paragraphs_touched: 2,
+            exact: true,
+        });
+        let mut measured = sign_period_record(inputs, &k).unwrap();
+        let json = serde_json::to_string(&measured).unwrap();
+        let back: ObservationPeriod = serde_json::from_str(&json).unwrap();
+        assert!(verify_period_record(&back).is_ok());
+        assert_eq!(back.change_magnitude, measured.change_magnitude);
+        // L'ampleur fait partie du payload signé.
+        measured.change_magnitude.as_mut().unwrap().chars_added += 1;
+        assert!(verify_period_record(&measured).is_err());
+    }
+
+    #[test]
+    fn test_empreintes_par_blocs_signees_et_absentes_sinon() {
+        let k = key();
+        let wid = WorkId::new();
+        let p = sign_period_record(genesis_inputs(&wid), &k).unwrap();
+        let v = serde_json::to_value(&p).unwrap();
+        assert!(v.get("chunked_start").is_none());
+        assert!(v.get("chunked_end").is_none());
+
+        let chunked = |root: &str| ChunkedFingerprint {
+            scheme: "HO-CDC-GEAR-SHA256-MERKLE-V1".to_string(),
+            merkle_root: root.repeat(64),
+            chunk_count: 4,
+        };
+        let mut inputs = genesis_inputs(&wid);
+        inputs.chunked_start = Some(chunked("1"));
+        inputs.chunked_end = Some(chunked("2"));
+        let mut media = sign_period_record(inputs, &k).unwrap();
+        let back: ObservationPeriod =
+            serde_json::from_str(&serde_json::to_string(&media).unwrap()).unwrap();
+        assert!(verify_period_record(&back).is_ok());
+        assert_eq!(back.chunked_end, Some(chunked("2")));
+        // La racine Merkle fait partie du payload signé, à côté du SHA256 plat.
+        media.chunked_end.as_mut().unwrap().merkle_root = "3".repeat(64);
+        assert!(verify_period_record(&media).is_err());
+    }
+
+    #[test]
+    fn test_changements_par_fichier_signes_et_absents_sinon() {
+        use crate::document_set::MemberChangeKind;
+        let k = key();
+        let wid = WorkId::new();
+        let p = sign_period_record(genesis_inputs(&wid), &k).unwrap();
+        assert!(serde_json::to_value(&p)
+            .unwrap()
+            .get("member_changes")
+            .is_none());
+
+        let mut inputs = genesis_inputs(&wid);
+        inputs.member_changes = Some(vec![MemberChange {
+            path: "chapitres/02.md".to_string(),
+            change: MemberChangeKind::Modified,
+            hash_start: Some("a".repeat(64)),
+            hash_end: Some("b".repeat(64)),
+        }]);
+        let mut set = sign_period_record(inputs, &k).unwrap();
+        let back: ObservationPeriod =
+            serde_json::from_str(&serde_json::to_string(&set).unwrap()).unwrap();
+        assert!(verify_period_record(&back).is_ok());
+        // Le détail par fichier fait partie du payload signé.
+        set.member_changes.as_mut().unwrap()[0].change = MemberChangeKind::Added;
+        assert!(verify_period_record(&set).is_err());
+    }
+
+    fn measured_inputs(
+        work_id: &WorkId,
+        chars_added: u64,
+        keystrokes: u64,
+        pasted: u64,
+    ) -> PeriodInputs {
+        let mut inputs = genesis_inputs(work_id);
+        inputs.engine = json!({
+            "analysis": { "gate_passed": true, "flags": [] },
+            "keyboard_dynamics": { "total_keystrokes": keystrokes, "backspace_count": 0 },
+            "paste_stats": { "paste_events": 1, "pasted_chars": pasted, "max_paste_chars": pasted },
+        });
+        inputs.change_magnitude = Some(ChangeMagnitude {
+            format: "txt".to_string(),
+            chars_start: 0,
+            chars_end: chars_added,
+            chars_added,
+            chars_removed: 0,
+            edit_distance_ratio_bps: 10_000,
+            paragraphs_start: 0,
+            paragraphs_end: 1,
+            paragraphs_touched: 1,
+            exact: true,
+        });
+        inputs
+    }
+
+    #[test]
+    fn test_croissance_sans_saisie_fait_echouer_le_gate() {
+        let k = key();
+        let wid = WorkId::new();
+        let p = sign_period_record(measured_inputs(&wid, 40_000, 300, 0), &k).unwrap();
+        assert!(verify_period_record(&p).is_ok());
+        let growth = &p.engine["document_growth"];
+        assert_eq!(growth["exceeds_input"], json!(true));
+        assert_eq!(growth["growth_vs_input_ratio"], json!(133.33));
+        assert_eq!(p.engine["analysis"]["gate_passed"], json!(false));
+        assert_eq!(
+            p.engine["analysis"]["flags"],
+            json!([GROWTH_EXCEEDS_INPUT_FLAG])
+        );
+    }
+
+    #[test]
+    fn test_croissance_expliquee_par_saisie_ou_collage() {
+        let k = key();
+        let wid = WorkId::new();
+        // Saisie suffisante.
+        let typed = sign_period_record(measured_inputs(&wid, 900, 1_000, 0), &k).unwrap();
+        assert_eq!(
+            typed.engine["document_growth"]["exceeds_input"],
+            json!(false)
+        );
+        assert_eq!(typed.engine["analysis"]["gate_passed"], json!(true));
+        // Collage déclaré (relevé à part par le moteur) : pas de double peine.
+        let pasted = sign_period_record(measured_inputs(&wid, 40_000, 300, 39_800), &k).unwrap();
+        assert_eq!(
+            pasted.engine["document_growth"]["exceeds_input"],
+            json!(false)
+        );
+        // Sans mesure texte : la taille du fichier sert de croissance (+20 octets).
+        let unmeasured = sign_period_record(genesis_inputs(&wid), &k).unwrap();
+        let growth = &unmeasured.engine["document_growth"];
+        assert_eq!(growth["basis"], json!("file_bytes"));
+        assert_eq!(growth["bytes_added"], json!(20));
+        assert_eq!(growth["exceeds_input"], json!(false));
+    }
+
+    #[test]
+    fn test_croissance_sans_mesure_texte_controlee_sur_la_taille() {
+        let k = key();
+        let wid = WorkId::new();
+        // PDF, snapshot absent... : aucune mesure texte, 40k octets, 300 frappes.
+        let mut inputs = measured_inputs(&wid, 40_000, 300, 0);
+        inputs.change_magnitude = None;
+        inputs.size_start = 10_000;
+        inputs.size_end = 50_000;
+        let p = sign_period_record(inputs, &k).unwrap();
+        assert!(verify_period_record(&p).is_ok());
+        let growth = &p.engine["document_growth"];
+        assert_eq!(growth["basis"], json!("file_bytes"));
+        assert_eq!(growth["bytes_added"], json!(40_000));
+        assert_eq!(growth["exceeds_input"], json!(true));
+        assert_eq!(p.engine["analysis"]["gate_passed"], json!(false));
+        assert_eq!(
+            p.engine["analysis"]["flags"],
+            json!([GROWTH_EXCEEDS_INPUT_FLAG])
+        );
+        assert!(p.engine["analysis"]["gate_reason"]
+            .as_str()
+            .unwrap()
+            .contains("40000 octets"));
+
+        // Fichier qui rétrécit : aucune croissance, aucun jugement.
+        let mut shrunk = measured_inputs(&wid, 0, 0, 0);
+        shrunk.change_magnitude = None;
+        shrunk.size_start = 50_000;
+        shrunk.size_end = 10_000;
+        let p = sign_period_record(shrunk, &k).unwrap();
+        assert_eq!(p.engine["document_growth"]["bytes_added"], json!(0));
+        assert_eq!(p.engine["analysis"]["gate_passed"], json!(true));
+    }
+
     #[test]
     fn test_chainage_correct() {
         let k = key();
@@ -976,6 +1376,48 @@ mod tests {
         );
     }
 
+    /// HO-JCS-V1 : signée, vérifiable après relecture disque (flottants engine
+    /// compris), et liée à son schéma — relabelliser casse la signature.
+    #[test]
+    fn test_periode_jcs_signee_et_verifiable() {
+        let k = key();
+        let wid = WorkId::new();
+        let mut inputs = genesis_inputs(&wid);
+        inputs.engine = json!({
+            "analysis": { "effort_score": 241.33333333333334_f64, "ratio": 5.0, "score": 100 }
+        });
+        let p = sign_period_record_with(inputs, &k, CanonScheme::HoJcsV1).unwrap();
+        assert_eq!(p.signature_metadata.canonicalization, "HO-JCS-V1");
+        verify_period_record(&p).unwrap();
+
+        // Forme JCS : nombre ECMAScript (`5`), pas la forme serde (`5.0`).
+        let payload = String::from_utf8(canonical_period_payload(&p).unwrap()).unwrap();
+        assert!(payload.contains("\"ratio\":5,"), "payload : {payload}");
+
+        let bytes = serde_json::to_vec_pretty(&p).unwrap();
+        let reread: ObservationPeriod = serde_json::from_slice(&bytes).unwrap();
+        verify_period_record(&reread).expect("période JCS valide après round-trip disque");
+
+        let mut relabel = p.clone();
+        relabel.signature_metadata.canonicalization = "HO-CANON-V1".to_string();
+        assert!(verify_period_record(&relabel).is_err());
+        let mut inconnu = p;
+        inconnu.signature_metadata.canonicalization = "RFC8785".to_string();
+        assert!(verify_period_record(&inconnu)
+            .unwrap_err()
+            .contains("schéma de canonicalisation inattendu"));
+    }
+
+    /// Le schéma par défaut reste HO-CANON-V1 (records V1 inchangés).
+    #[test]
+    fn test_schema_par_defaut_ho_canon_v1() {
+        let p = sign_period_record(genesis_inputs(&WorkId::new()), &key()).unwrap();
+        assert_eq!(p.signature_metadata.canonicalization, "HO-CANON-V1");
+        for scheme in [CanonScheme::HoCanonV1, CanonScheme::HoJcsV1] {
+            assert_eq!(CanonScheme::from_label(scheme.label()).unwrap(), scheme);
+        }
+    }
+
     /// `normalize_engine` doit être idempotent : normalize(normalize(x)) == normalize(x).
     #[test]
     fn test_normalize_engine_idempotent() {
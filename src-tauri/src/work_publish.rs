@@ -1,36 +1,68 @@
 //! work_publish — Orchestration runtime PDF labellisé -> package (Commit 6C-2).
 //!
-//! Enchaîne : (créer OU réutiliser) le certificat Work -> valider que le PDF
-//! source correspond au document certifié -> produire `labeled_document.pdf`
-//! avec le pipeline existant (`publish_pdf_core`, appelé TEL QUEL) -> appeler
-//! `create_work_package_core` (6C-1) avec le PDF réellement écrit.
+//! Enchaîne : (créer OU réutiliser) le certificat Work -> valider que la
+//! source correspond au document certifié -> produire l'artefact labellisé via
+//! un `DocumentAdapter` (V2-M3C) -> appeler `create_artifact_package_core`
+//! (6C-1) avec l'artefact réellement écrit, décrit par son `RenderedArtifactRef`.
+//!
+//! Adaptateur de production : `PdfDocumentAdapter` (cartouche Work + pipeline
+//! existant `publish_pdf_core`, appelé TEL QUEL) -> `labeled_document.pdf`. Un
+//! nouveau type de média = un nouvel adaptateur ; le flux ne change pas.
+//! Work IMAGE (PNG/JPEG) : `work_image::ImageDocumentAdapter` -> copie labellisée
+//! et sidecar `.ho.json`, embarqués tels quels dans le package.
+//! Work AUDIO (WAV/FLAC/MP3) : `work_audio::AudioDocumentAdapter` -> copie dont
+//! seuls les tags (BWF `bext`, Vorbis, ID3) changent + sidecar `.ho.json`.
+//! Work DÉPÔT (dossier git) : empreinte d'arbre `repo_tree` comme hash source,
+//! `work_repo::RepoDocumentAdapter` -> liste d'arbre + sidecar `.ho.json`.
+//!
+//! Sidecar seul (aucune marque visible) : l'original reste byte-identique hors
+//! du package ; seul un sidecar `.ho.json` signé de l'original est packagé,
+//! avec sa référence SHA256 (`work_package::create_sidecar_package_core`).
+//!
+//! Work DOCX : la source certifiée est d'abord convertie en PDF par le sidecar
+//! `humanorigin-converter` (dans le temp), ce rendu est labellisé, puis le
+//! package embarque source + rendu liés dans `derived_rendition` (6C-1).
 //!
 //! N'ajoute AUCUN claim, ne modifie NI la cartouche NI `publication_core.rs`.
-//! AUCUNE commande Tauri, AUCUNE UI, AUCUN verifier, AUCUNE Supabase, AUCUN
-//! DOCX->PDF (PDF source déjà disponible), AUCUNE génération de cartouche.
+//! AUCUNE UI, AUCUN verifier, AUCUNE Supabase.
 //!
-//! Garde-fous : (1) SHA256(source_pdf) == certificat.document.hash_current ;
-//! (2) create-or-load du certificat + no-overwrite avant génération PDF ;
-//! (3) verify_url strictement public (http/https) ; (4) aucun package si PDFium
-//! échoue ou si le PDF n'existe pas ; (5) cartouche/PDF inchangés.
+//! Garde-fous : (1) SHA256(source) == certificat.document.hash_current (PDF ou
+//! DOCX, vérifié AVANT conversion) ; (2) create-or-load du certificat +
+//! no-overwrite avant génération PDF ; (3) verify_url strictement public
+//! (http/https) ; (4) aucun package si la conversion ou PDFium échoue ou si le
+//! PDF n'existe pas ; (5) cartouche/PDF inchangés ; (6) le rendu DOCX doit être
+//! un PDF produit dans le dossier temporaire du flux ; (7) l'artefact renvoyé
+//! par l'adaptateur (et son sidecar déclaré) doit exister dans ce même dossier
+//! temporaire.
 //!
-//! La partie PDFium/clé device est INTÉGRATION-only (bundle app). Les tests
-//! unitaires utilisent des seams (aucune couverture PDFium en `cargo test`).
+//! La partie PDFium/convertisseur/clé device est INTÉGRATION-only (bundle app).
+//! Les tests unitaires utilisent des seams (aucune couverture PDFium en `cargo test`).
 
 // Fondation (6C-2) : orchestration ; exposition Tauri/UI = 6D.
 #![allow(dead_code)]
 
-use sha2::{Digest, Sha256};
+use ed25519_dalek::SigningKey;
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
+use crate::evidence_kernel::{
+    ArtifactRenderError, DocumentAdapter, DocumentArtifactKind, LabeledArtifactRequest, MediaKind,
+    ObjectVersionRef, ObservedObjectRef, RenderedArtifactRef,
+};
+use crate::file_hash;
+use crate::publication_core::{PdfConformance, SignaturePolicy};
 use crate::work_certificate::{ProofVerdict, WorkCertificate};
-use crate::work_package::PackageManifest;
+use crate::work_package::{LabeledInput, OriginalInput, PackageManifest, RenditionInput};
 use crate::work_store::WorkId;
 
 const CERTIFICATES_DIR: &str = "certificates";
 const PACKAGES_DIR: &str = "packages";
 const LABELED_PDF_FILENAME: &str = "labeled_document.pdf";
+const CARTOUCHE_FILENAME: &str = "cartouche.png";
+const ORIGINAL_SIDECAR_FILENAME: &str = "original_document.ho.json";
+const RENDITION_DIR: &str = "rendition";
+const CONVERTER_SIDECAR: &str = "humanorigin-converter";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum PublishError {
@@ -42,11 +74,36 @@ pub(crate) enum PublishError {
     CartoucheNotProduced,
     PdfGenerationFailed(String),
     PdfNotProduced,
+    UnsupportedSourceFormat(String),
+    ConversionFailed(String),
+    RenditionNotProduced,
     Certificate(String),
     Package(String),
     Io(String),
 }
 
+/// PDF rendu depuis la source certifiée (non labellisé) + moteur qui l'a produit
+/// (+ `conversion_record.json` du convertisseur, s'il en a écrit un).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ConvertedRendition {
+    pub pdf_path: PathBuf,
+    pub converter: String,
+    pub conversion_record_path: Option<PathBuf>,
+}
+
+/// Retour d'un adaptateur : marquage -> `Cartouche*`, rendu -> `Pdf*` (noms
+/// historiques, conservés pour les appelants).
+impl From<ArtifactRenderError> for PublishError {
+    fn from(e: ArtifactRenderError) -> Self {
+        match e {
+            ArtifactRenderError::MarkingFailed(msg) => PublishError::CartoucheGenerationFailed(msg),
+            ArtifactRenderError::MarkingNotProduced => PublishError::CartoucheNotProduced,
+            ArtifactRenderError::RenderFailed(msg) => PublishError::PdfGenerationFailed(msg),
+            ArtifactRenderError::NotProduced => PublishError::PdfNotProduced,
+        }
+    }
+}
+
 // --- HELPERS ------------------------------------------------------------------
 
 fn certificate_json_path(works_root: &Path, work_id: &WorkId, sequence: u64) -> PathBuf {
@@ -64,11 +121,6 @@ fn package_dir(works_root: &Path, work_id: &WorkId, sequence: u64) -> PathBuf {
     packages_dir(works_root, work_id).join(format!("package_{sequence}"))
 }
 
-fn sha256_file_hex(path: &Path) -> Result<String, String> {
-    let bytes = fs::read(path).map_err(|e| e.to_string())?;
-    Ok(format!("{:x}", Sha256::digest(&bytes)))
-}
-
 /// Garde-fou #3 : verify_url strictement public.
 fn is_public_url(url: &str) -> bool {
     if url.is_empty() {
@@ -91,28 +143,313 @@ fn verdict_label(verdict: ProofVerdict) -> &'static str {
     }
 }
 
+fn has_extension(path: &Path, exts: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| exts.iter().any(|x| e.eq_ignore_ascii_case(x)))
+        .unwrap_or(false)
+}
+
+fn is_docx(path: &Path) -> bool {
+    has_extension(path, &["docx"])
+}
+
+fn is_image(path: &Path) -> bool {
+    has_extension(path, &["png", "jpg", "jpeg"])
+}
+
+fn is_audio(path: &Path) -> bool {
+    has_extension(path, &["wav", "flac", "mp3"])
+}
+
+/// Empreinte du document source : SHA256 du fichier, ou empreinte d'arbre
+/// (`repo_tree`) pour un dépôt git.
+fn source_document_hash(path: &Path) -> Result<String, String> {
+    if crate::repo_tree::is_repository(path) {
+        Ok(crate::repo_tree::repo_tree_digest(path)?.0)
+    } else {
+        file_hash::sha256_file(path)
+    }
+}
+
+/// Garde-fou #6 : le rendu est un vrai PDF (magic `%PDF-`) situé dans `out_dir`.
+fn is_rendition_pdf(rendition: &Path, out_dir: &Path) -> bool {
+    if !rendition.starts_with(out_dir) {
+        return false;
+    }
+    // Seul l'en-tête est lu : un rendu volumineux n'est jamais chargé en mémoire.
+    let mut magic = [0u8; 5];
+    fs::File::open(rendition)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .map(|()| &magic == b"%PDF-")
+        .unwrap_or(false)
+}
+
+// --- ADAPTATEUR PDF (V2-M3C) --------------------------------------------------
+
+/// Seam de marquage : écrit la cartouche de `request` dans le chemin donné.
+type CartoucheSeam<'a> =
+    Box<dyn Fn(&LabeledArtifactRequest, &Path) -> Result<(), PublishError> + 'a>;
+/// Seam de rendu : `(source_pdf, output_pdf, cartouche_png, certificate_id,
+/// verify_url, verdict)`.
+type GeneratePdfSeam<'a> =
+    Box<dyn Fn(&Path, &Path, &Path, &str, &str, &str) -> Result<(), PublishError> + 'a>;
+
+/// Adaptateur PDF : cartouche Work (native ou fournie) puis `publish_pdf_core`.
+/// Produit `labeled_document.pdf` dans le dossier de sortie.
+pub(crate) struct PdfDocumentAdapter<'a> {
+    cartouche_fn: CartoucheSeam<'a>,
+    generate_pdf_fn: GeneratePdfSeam<'a>,
+}
+
+impl<'a> PdfDocumentAdapter<'a> {
+    /// Production : cartouche Work native construite depuis la requête.
+    /// `signature_policy` : conduite si la source porte des signatures PDF.
+    pub(crate) fn native(conformance: PdfConformance, signature_policy: SignaturePolicy) -> Self {
+        Self::with_seams(
+            render_native_cartouche,
+            move |src, out, png, id, url, verdict| {
+                let job = publication_job(
+                    src,
+                    out,
+                    png,
+                    id,
+                    url,
+                    verdict,
+                    conformance,
+                    signature_policy,
+                );
+                generate_pdf_real(job)
+            },
+        )
+    }
+
+    /// Production, fallback manuel : la cartouche fournie est simplement copiée.
+    pub(crate) fn with_cartouche_file(
+        cartouche_png_path: &Path,
+        conformance: PdfConformance,
+        signature_policy: SignaturePolicy,
+    ) -> Self {
+        let cartouche_src = cartouche_png_path.to_path_buf();
+        Self::with_seams(
+            move |_request, out| {
+                fs::copy(&cartouche_src, out)
+                    .map(|_| ())
+                    .map_err(|e| PublishError::Io(e.to_string()))
+            },
+            move |src, out, png, id, url, verdict| {
+                let job = publication_job(
+                    src,
+                    out,
+                    png,
+                    id,
+                    url,
+                    verdict,
+                    conformance,
+                    signature_policy,
+                );
+                generate_pdf_real(job)
+            },
+        )
+    }
+
+    /// Seams explicites (tests : aucun PDFium).
+    pub(crate) fn with_seams<CT, GF>(cartouche_fn: CT, generate_pdf_fn: GF) -> Self
+    where
+        CT: Fn(&LabeledArtifactRequest, &Path) -> Result<(), PublishError> + 'a,
+        GF: Fn(&Path, &Path, &Path, &str, &str, &str) -> Result<(), PublishError> + 'a,
+    {
+        Self {
+            cartouche_fn: Box::new(cartouche_fn),
+            generate_pdf_fn: Box::new(generate_pdf_fn),
+        }
+    }
+}
+
+impl DocumentAdapter for PdfDocumentAdapter<'_> {
+    fn adapter_name(&self) -> &'static str {
+        "pdf"
+    }
+
+    fn artifact_kind(&self) -> DocumentArtifactKind {
+        DocumentArtifactKind::Pdf
+    }
+
+    fn default_artifact_filename(&self) -> &'static str {
+        LABELED_PDF_FILENAME
+    }
+
+    fn render_labeled_artifact(
+        &self,
+        request: &LabeledArtifactRequest,
+        source: &Path,
+        output_dir: &Path,
+    ) -> Result<RenderedArtifactRef, ArtifactRenderError> {
+        let cartouche_png = output_dir.join(CARTOUCHE_FILENAME);
+        let output_pdf = output_dir.join(LABELED_PDF_FILENAME);
+
+        // Cartouche AVANT toute génération PDF.
+        (self.cartouche_fn)(request, &cartouche_png).map_err(|e| match e {
+            PublishError::CartoucheGenerationFailed(msg) => ArtifactRenderError::MarkingFailed(msg),
+            PublishError::CartoucheNotProduced => ArtifactRenderError::MarkingNotProduced,
+            other => ArtifactRenderError::MarkingFailed(format!("{other:?}")),
+        })?;
+        // Garde-fou : la cartouche doit exister et être non vide.
+        let cartouche_ok = fs::metadata(&cartouche_png)
+            .map(|m| m.len() > 0)
+            .unwrap_or(false);
+        if !cartouche_ok {
+            return Err(ArtifactRenderError::MarkingNotProduced);
+        }
+        (self.generate_pdf_fn)(
+            source,
+            &output_pdf,
+            &cartouche_png,
+            &request.certificate_id,
+            &request.verify_url,
+            &request.verdict_label,
+        )
+        .map_err(|e| match e {
+            PublishError::PdfGenerationFailed(msg) => ArtifactRenderError::RenderFailed(msg),
+            PublishError::PdfNotProduced => ArtifactRenderError::NotProduced,
+            other => ArtifactRenderError::RenderFailed(format!("{other:?}")),
+        })?;
+        // Garde-fou #4 : le PDF doit exister réellement.
+        if !output_pdf.exists() {
+            return Err(ArtifactRenderError::NotProduced);
+        }
+        Ok(RenderedArtifactRef {
+            artifact_name: LABELED_PDF_FILENAME.to_string(),
+            media_type: DocumentArtifactKind::Pdf,
+            fingerprint_sha256: None,
+            size_bytes: None,
+            mime_type: Some("application/pdf".to_string()),
+        })
+    }
+}
+
+/// Requête d'adaptateur construite depuis le certificat réel (aucun chemin).
+fn labeled_artifact_request(
+    cert: &WorkCertificate,
+    source_path: &Path,
+    verify_url: &str,
+) -> LabeledArtifactRequest {
+    let media_type = if is_docx(source_path) {
+        MediaKind::Document
+    } else if crate::repo_tree::is_repository(source_path) {
+        MediaKind::Code
+    } else if is_image(source_path) {
+        MediaKind::Image
+    } else if is_audio(source_path) {
+        MediaKind::Audio
+    } else {
+        MediaKind::Pdf
+    };
+    LabeledArtifactRequest {
+        source_object: ObservedObjectRef::new(cert.work_id.as_str(), media_type),
+        source_version: ObjectVersionRef {
+            fingerprint_sha256: cert.public_core_evidence.document.hash_current.clone(),
+            size_bytes: Some(cert.public_core_evidence.document.size_current),
+            mime_type: None,
+            chunked_fingerprint: None,
+            media_specific: None,
+        },
+        certificate_id: cert.certificate_id.clone(),
+        verify_url: verify_url.to_string(),
+        verdict_label: verdict_label(cert.public_core_evidence.verdict.verdict).to_string(),
+        signing_key_id: Some(cert.signature_metadata.signing_key_id.clone()),
+        certificate_created_at: Some(cert.created_at.clone()),
+    }
+}
+
 // --- ORCHESTRATION (cœur seamed) ----------------------------------------------
 
-/// Cœur testable : les seams `create_cert_fn` / `generate_pdf_fn` /
-/// `make_package_fn` isolent PDFium et la clé device.
+/// Cœur testable : les seams `create_cert_fn` / `adapter` / `make_package_fn`
+/// isolent PDFium et la clé device.
 ///
 /// `create_cert_fn` n'est appelé QUE dans la branche « créer un nouveau
 /// certificat » (jamais lors d'une réutilisation).
-fn create_labeled_package_inner<CF, CT, GF, PF>(
+fn create_labeled_package_inner<CF, PF>(
     works_root: &Path,
     work_id: &WorkId,
     source_pdf_path: &Path,
     verify_url: &str,
     create_cert_fn: CF,
-    cartouche_fn: CT,
-    generate_pdf_fn: GF,
+    adapter: &dyn DocumentAdapter,
+    make_package_fn: PF,
+) -> Result<PackageManifest, PublishError>
+where
+    CF: FnOnce() -> Result<WorkCertificate, PublishError>,
+    PF: FnOnce(&Path, &LabeledInput) -> Result<PackageManifest, PublishError>,
+{
+    labeled_package_flow(
+        works_root,
+        work_id,
+        source_pdf_path,
+        verify_url,
+        create_cert_fn,
+        // Source déjà PDF : aucun rendu dérivé.
+        |_source, _out_dir| Ok(None),
+        adapter,
+        |certificate_path, labeled, _rendition| make_package_fn(certificate_path, labeled),
+    )
+}
+
+/// Cœur testable Work DOCX : `convert_fn(source_docx, out_dir)` isole le
+/// convertisseur ; il n'est appelé qu'APRÈS le hash check de la source DOCX.
+/// `make_package_fn` reçoit la source + le rendu à embarquer.
+#[allow(clippy::too_many_arguments)]
+fn create_docx_labeled_package_inner<CF, VF, PF>(
+    works_root: &Path,
+    work_id: &WorkId,
+    source_docx_path: &Path,
+    verify_url: &str,
+    create_cert_fn: CF,
+    convert_fn: VF,
+    adapter: &dyn DocumentAdapter,
     make_package_fn: PF,
 ) -> Result<PackageManifest, PublishError>
 where
     CF: FnOnce() -> Result<WorkCertificate, PublishError>,
-    CT: FnOnce(&WorkCertificate, &Path) -> Result<(), PublishError>,
-    GF: FnOnce(&Path, &Path, &Path, &str, &str, &str) -> Result<(), PublishError>,
-    PF: FnOnce(&Path, &Path) -> Result<PackageManifest, PublishError>,
+    VF: FnOnce(&Path, &Path) -> Result<ConvertedRendition, PublishError>,
+    PF: FnOnce(&Path, &LabeledInput, &RenditionInput) -> Result<PackageManifest, PublishError>,
+{
+    if !is_docx(source_docx_path) {
+        return Err(PublishError::UnsupportedSourceFormat(
+            source_docx_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("")
+                .to_string(),
+        ));
+    }
+    labeled_package_flow(
+        works_root,
+        work_id,
+        source_docx_path,
+        verify_url,
+        create_cert_fn,
+        |source, out_dir| convert_fn(source, out_dir).map(Some),
+        adapter,
+        |certificate_path, labeled, rendition| match rendition {
+            Some(r) => make_package_fn(certificate_path, labeled, r),
+            None => Err(PublishError::RenditionNotProduced),
+        },
+    )
+}
+
+/// Garde-fous communs à tous les flux, AVANT tout rendu : verify_url public,
+/// create-or-load du certificat, no-overwrite, source == document certifié.
+/// Renvoie le certificat et le chemin de son `certificate_N.json`.
+fn certified_source<CF>(
+    works_root: &Path,
+    work_id: &WorkId,
+    source_path: &Path,
+    verify_url: &str,
+    create_cert_fn: CF,
+) -> Result<(WorkCertificate, PathBuf), PublishError>
+where
+    CF: FnOnce() -> Result<WorkCertificate, PublishError>,
 {
     // Garde-fou #3 : verify_url public.
     if !is_public_url(verify_url) {
@@ -137,64 +474,233 @@ where
         return Err(PublishError::AlreadyExists(final_dir.display().to_string()));
     }
 
-    // Garde-fou #1 : le PDF source doit correspondre au document certifié.
+    // Garde-fou #1 : la source (PDF ou DOCX) doit correspondre au document certifié.
     let source_hash =
-        sha256_file_hex(source_pdf_path).map_err(PublishError::SourcePdfUnavailable)?;
+        source_document_hash(source_path).map_err(PublishError::SourcePdfUnavailable)?;
     if source_hash != cert.public_core_evidence.document.hash_current {
         return Err(PublishError::SourcePdfDoesNotMatchCertifiedDocument);
     }
+    Ok((cert, certificate_path))
+}
 
-    let verdict = verdict_label(cert.public_core_evidence.verdict.verdict);
+/// Flux commun PDF / DOCX. `render_fn(source, out_dir)` produit (ou non) un
+/// rendu PDF de la source dans le temp ; c'est ce rendu qui est labellisé par
+/// `adapter`.
+#[allow(clippy::too_many_arguments)]
+fn labeled_package_flow<CF, RF, PF>(
+    works_root: &Path,
+    work_id: &WorkId,
+    source_path: &Path,
+    verify_url: &str,
+    create_cert_fn: CF,
+    render_fn: RF,
+    adapter: &dyn DocumentAdapter,
+    make_package_fn: PF,
+) -> Result<PackageManifest, PublishError>
+where
+    CF: FnOnce() -> Result<WorkCertificate, PublishError>,
+    RF: FnOnce(&Path, &Path) -> Result<Option<ConvertedRendition>, PublishError>,
+    PF: FnOnce(
+        &Path,
+        &LabeledInput,
+        Option<&RenditionInput>,
+    ) -> Result<PackageManifest, PublishError>,
+{
+    let (cert, certificate_path) =
+        certified_source(works_root, work_id, source_path, verify_url, create_cert_fn)?;
+    let request = labeled_artifact_request(&cert, source_path, verify_url);
 
-    // PDF labellisé dans un dossier temporaire ; cleanup dans tous les cas.
+    // Artefact labellisé dans un dossier temporaire ; cleanup dans tous les cas.
     let pkgs = packages_dir(works_root, work_id);
     fs::create_dir_all(&pkgs).map_err(|e| PublishError::Io(e.to_string()))?;
     let temp_dir = pkgs.join(format!(".tmp_labeled_{}", uuid::Uuid::new_v4()));
     fs::create_dir_all(&temp_dir).map_err(|e| PublishError::Io(e.to_string()))?;
-    let cartouche_png = temp_dir.join("cartouche.png");
-    let output_pdf = temp_dir.join(LABELED_PDF_FILENAME);
 
     let result = (|| -> Result<PackageManifest, PublishError> {
-        // Cartouche (native ou fournie) générée dans le temp, APRÈS certificat +
-        // no-overwrite + hash check, AVANT toute génération PDF.
-        cartouche_fn(&cert, &cartouche_png)?;
-        // Garde-fou : la cartouche doit exister et être non vide.
-        let cartouche_ok = fs::metadata(&cartouche_png)
-            .map(|m| m.len() > 0)
-            .unwrap_or(false);
-        if !cartouche_ok {
-            return Err(PublishError::CartoucheNotProduced);
+        // Rendu dérivé (DOCX -> PDF) dans le temp, APRÈS le hash check.
+        let rendition_dir = temp_dir.join(RENDITION_DIR);
+        fs::create_dir_all(&rendition_dir).map_err(|e| PublishError::Io(e.to_string()))?;
+        let rendition = render_fn(source_path, &rendition_dir)?;
+        if let Some(r) = &rendition {
+            if !is_rendition_pdf(&r.pdf_path, &rendition_dir) {
+                return Err(PublishError::RenditionNotProduced);
+            }
+            // Le record doit venir de CETTE conversion (dossier temp du rendu).
+            if let Some(record) = &r.conversion_record_path {
+                if !record.starts_with(&rendition_dir) || !record.is_file() {
+                    return Err(PublishError::RenditionNotProduced);
+                }
+            }
         }
-        generate_pdf_fn(
-            source_pdf_path,
-            &output_pdf,
-            &cartouche_png,
-            &cert.certificate_id,
-            verify_url,
-            verdict,
-        )?;
-        // Garde-fou #4 : le PDF doit exister réellement.
-        if !output_pdf.exists() {
-            return Err(PublishError::PdfNotProduced);
+        let to_label = rendition
+            .as_ref()
+            .map(|r| r.pdf_path.clone())
+            .unwrap_or_else(|| source_path.to_path_buf());
+
+        // Marquage + rendu par l'adaptateur, dans le temp, APRÈS certificat +
+        // no-overwrite + hash check.
+        let labeled = adapter.render_labeled_artifact(&request, &to_label, &temp_dir)?;
+        // Garde-fou #7 : l'artefact (et son sidecar éventuel) est un fichier
+        // simple écrit dans le temp.
+        let produced_in_temp =
+            |name: &str| !name.contains(['/', '\\']) && temp_dir.join(name).is_file();
+        if !produced_in_temp(&labeled.artifact_name) {
+            return Err(ArtifactRenderError::NotProduced.into());
         }
+        let sidecar_path = match adapter.sidecar_filename() {
+            Some(name) if produced_in_temp(name) => Some(temp_dir.join(name)),
+            Some(_) => return Err(ArtifactRenderError::NotProduced.into()),
+            None => None,
+        };
+        let artifact_path = temp_dir.join(&labeled.artifact_name);
         // 6C-1 gère l'atomicité + no-overwrite + signature du manifest.
-        make_package_fn(&certificate_path, &output_pdf)
+        let rendition_input = rendition.as_ref().map(|r| RenditionInput {
+            source_path,
+            rendition_pdf_path: &r.pdf_path,
+            converter: &r.converter,
+            conversion_record_path: r.conversion_record_path.as_deref(),
+        });
+        let labeled_input = LabeledInput {
+            artifact_path: &artifact_path,
+            artifact: &labeled,
+            sidecar_path: sidecar_path.as_deref(),
+        };
+        make_package_fn(&certificate_path, &labeled_input, rendition_input.as_ref())
     })();
 
     let _ = fs::remove_dir_all(&temp_dir);
     result
 }
 
-/// Génération PDF réelle : appelle `publish_pdf_core` TEL QUEL (PDFium runtime).
-fn generate_pdf_real(
+/// Cœur testable du package sidecar seul : l'original n'est NI labellisé NI
+/// copié. Seul son sidecar signé (sans copie labellisée) est écrit, dans le
+/// temp, puis packagé avec la référence SHA256 de l'original.
+fn create_sidecar_package_inner<CF, PF>(
+    works_root: &Path,
+    work_id: &WorkId,
+    source_path: &Path,
+    verify_url: &str,
+    create_cert_fn: CF,
+    signing_key: &SigningKey,
+    make_package_fn: PF,
+) -> Result<PackageManifest, PublishError>
+where
+    CF: FnOnce() -> Result<WorkCertificate, PublishError>,
+    PF: FnOnce(&Path, &OriginalInput) -> Result<PackageManifest, PublishError>,
+{
+    let (cert, certificate_path) =
+        certified_source(works_root, work_id, source_path, verify_url, create_cert_fn)?;
+    let request = labeled_artifact_request(&cert, source_path, verify_url);
+
+    let pkgs = packages_dir(works_root, work_id);
+    fs::create_dir_all(&pkgs).map_err(|e| PublishError::Io(e.to_string()))?;
+    let temp_dir = pkgs.join(format!(".tmp_sidecar_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&temp_dir).map_err(|e| PublishError::Io(e.to_string()))?;
+
+    let result = (|| -> Result<PackageManifest, PublishError> {
+        let sidecar = crate::work_sidecar::sign_sidecar(&request, None, signing_key)
+            .map_err(|e| PublishError::Package(format!("sidecar : {e}")))?;
+        let sidecar_path = temp_dir.join(ORIGINAL_SIDECAR_FILENAME);
+        crate::work_sidecar::write_sidecar(&sidecar, &sidecar_path).map_err(PublishError::Io)?;
+        let original = OriginalInput {
+            sha256: &cert.public_core_evidence.document.hash_current,
+            size_bytes: Some(cert.public_core_evidence.document.size_current),
+            sidecar_path: &sidecar_path,
+        };
+        make_package_fn(&certificate_path, &original)
+    })();
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    result
+}
+
+/// Cartouche Work native, construite depuis la requête (certificat réel).
+fn render_native_cartouche(
+    request: &LabeledArtifactRequest,
+    out: &Path,
+) -> Result<(), PublishError> {
+    let inputs = crate::work_cartouche::WorkCartoucheInputs {
+        certificate_id: request.certificate_id.clone(),
+        verdict: request.verdict_label.clone(),
+        identity_status: "LOCAL_DEVICE".to_string(),
+        verify_url: request.verify_url.clone(),
+        signing_key_id: request.signing_key_id.clone(),
+        created_at: request.certificate_created_at.clone(),
+    };
+    crate::work_cartouche::render_work_cartouche_png(&inputs, out)
+        .map_err(PublishError::CartoucheGenerationFailed)
+}
+
+/// Conversion réelle DOCX -> PDF dans `out_dir` (temp) : bibliothèque
+/// `humanorigin_converter` en process si LibreOffice est trouvé, sinon sidecar
+/// `humanorigin-converter` (même contrat, relu en types).
+fn convert_docx_real(
+    source_docx: &Path,
+    out_dir: &Path,
+) -> Result<ConvertedRendition, PublishError> {
+    let outcome = if humanorigin_converter::find_soffice().is_some() {
+        humanorigin_converter::convert(
+            source_docx,
+            out_dir,
+            &humanorigin_converter::ConvertOptions::default(),
+        )
+    } else {
+        convert_docx_sidecar(source_docx, out_dir)?
+    };
+    let result = outcome.map_err(|e| {
+        PublishError::ConversionFailed(if e.message.is_empty() {
+            e.error_code
+        } else {
+            e.message
+        })
+    })?;
+    Ok(ConvertedRendition {
+        pdf_path: result.intermediate_pdf_path,
+        converter: result.engine,
+        conversion_record_path: Some(result.conversion_record_path),
+    })
+}
+
+fn convert_docx_sidecar(
+    source_docx: &Path,
+    out_dir: &Path,
+) -> Result<
+    Result<humanorigin_converter::ConversionResult, humanorigin_converter::ConversionError>,
+    PublishError,
+> {
+    let output = tauri::api::process::Command::new_sidecar(CONVERTER_SIDECAR)
+        .map_err(|e| PublishError::ConversionFailed(e.to_string()))?
+        .args([
+            "--input".to_string(),
+            source_docx.to_string_lossy().to_string(),
+            "--output-dir".to_string(),
+            out_dir.to_string_lossy().to_string(),
+        ])
+        .output()
+        .map_err(|e| PublishError::ConversionFailed(e.to_string()))?;
+
+    humanorigin_converter::parse_cli_output(&output.stdout).map_err(|e| {
+        PublishError::ConversionFailed(format!(
+            "sortie convertisseur illisible ({e}) : {}",
+            output.stderr.trim()
+        ))
+    })
+}
+
+/// Job `pdf_publication` passé à `publish_pdf_core` (rendu Work : cartouche
+/// compacte, première page).
+#[allow(clippy::too_many_arguments)]
+fn publication_job(
     source_pdf: &Path,
     output_pdf: &Path,
     cartouche_png: &Path,
     certificate_id: &str,
     verify_url: &str,
     verdict: &str,
-) -> Result<(), PublishError> {
-    let job = serde_json::json!({
+    conformance: PdfConformance,
+    signature_policy: SignaturePolicy,
+) -> serde_json::Value {
+    serde_json::json!({
         "job_version": "1.0",
         "job_type": "pdf_publication",
         "source_pdf_path": source_pdf.to_string_lossy(),
@@ -209,25 +715,43 @@ fn generate_pdf_real(
             "first_page_scale": 1.0,
             "other_pages_scale": 0.85,
             "anchor": "bottom_right",
-            "margin_pt": 34.0
+            "margin_pt": 34.0,
+            "conformance": conformance,
+            "signature_policy": signature_policy
         }
-    });
+    })
+}
+
+/// Échec `publish_pdf_core` -> `"<error_code>: <message>"` : l'UI reconnaît le
+/// code (ex. `SOURCE_PDF_SIGNED`, pour proposer `preserve`).
+fn publication_failure(res: &serde_json::Value) -> String {
+    let msg = res
+        .get("message")
+        .and_then(|v| v.as_str())
+        .unwrap_or("échec publication PDF");
+    match res.get("error_code").and_then(|v| v.as_str()) {
+        Some(code) => format!("{code}: {msg}"),
+        None => msg.to_string(),
+    }
+}
+
+/// Génération PDF réelle : appelle `publish_pdf_core` TEL QUEL (PDFium runtime).
+/// En `PdfConformance::Pdfa2b`, un échec du contrôle PDF/A fait échouer la
+/// génération, donc AUCUN package n'est créé ; de même pour une source signée
+/// refusée par la politique de signatures.
+fn generate_pdf_real(job: serde_json::Value) -> Result<(), PublishError> {
     let res = crate::publication_core::publish_pdf_core(job)
         .map_err(PublishError::PdfGenerationFailed)?;
     let ok = res.get("ok").and_then(|v| v.as_bool()).unwrap_or(false);
     if !ok {
-        let msg = res
-            .get("message")
-            .and_then(|v| v.as_str())
-            .unwrap_or("échec publication PDF")
-            .to_string();
-        return Err(PublishError::PdfGenerationFailed(msg));
+        return Err(PublishError::PdfGenerationFailed(publication_failure(&res)));
     }
     Ok(())
 }
 
 /// Point d'entrée production (INTÉGRATION-only : PDFium + clé device).
 /// Non exposé en commande Tauri en 6C-2.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn create_labeled_package_core(
     works_root: &Path,
     work_id: &WorkId,
@@ -235,8 +759,9 @@ pub(crate) fn create_labeled_package_core(
     cartouche_png_path: &Path,
     verify_url: &str,
     created_at: &str,
+    conformance: PdfConformance,
+    signature_policy: SignaturePolicy,
 ) -> Result<PackageManifest, PublishError> {
-    let cartouche_src = cartouche_png_path.to_path_buf();
     create_labeled_package_inner(
         works_root,
         work_id,
@@ -247,17 +772,13 @@ pub(crate) fn create_labeled_package_core(
                 .map_err(|e| PublishError::Certificate(format!("{e:?}")))
         },
         // Fallback manuel : la cartouche fournie est simplement copiée dans le temp.
-        move |_cert, out| {
-            fs::copy(&cartouche_src, out)
-                .map(|_| ())
-                .map_err(|e| PublishError::Io(e.to_string()))
-        },
-        generate_pdf_real,
-        |certificate_path, labeled_pdf_path| {
-            crate::work_package::create_work_package_core(
+        &PdfDocumentAdapter::with_cartouche_file(cartouche_png_path, conformance, signature_policy),
+        |certificate_path, labeled| {
+            crate::work_package::create_artifact_package_core(
                 works_root,
                 certificate_path,
-                labeled_pdf_path,
+                labeled,
+                None,
                 created_at,
             )
             .map_err(|e| PublishError::Package(format!("{e:?}")))
@@ -274,8 +795,9 @@ pub(crate) fn create_native_labeled_package_core(
     source_pdf_path: &Path,
     verify_url: &str,
     created_at: &str,
+    conformance: PdfConformance,
+    signature_policy: SignaturePolicy,
 ) -> Result<PackageManifest, PublishError> {
-    let verify_url_owned = verify_url.to_string();
     create_labeled_package_inner(
         works_root,
         work_id,
@@ -286,24 +808,13 @@ pub(crate) fn create_native_labeled_package_core(
                 .map_err(|e| PublishError::Certificate(format!("{e:?}")))
         },
         // Cartouche Work native : construite depuis le certificat réel.
-        move |cert, out| {
-            let inputs = crate::work_cartouche::WorkCartoucheInputs {
-                certificate_id: cert.certificate_id.clone(),
-                verdict: verdict_label(cert.public_core_evidence.verdict.verdict).to_string(),
-                identity_status: "LOCAL_DEVICE".to_string(),
-                verify_url: verify_url_owned.clone(),
-                signing_key_id: Some(cert.signature_metadata.signing_key_id.clone()),
-                created_at: Some(cert.created_at.clone()),
-            };
-            crate::work_cartouche::render_work_cartouche_png(&inputs, out)
-                .map_err(PublishError::CartoucheGenerationFailed)
-        },
-        generate_pdf_real,
-        |certificate_path, labeled_pdf_path| {
-            crate::work_package::create_work_package_core(
+        &PdfDocumentAdapter::native(conformance, signature_policy),
+        |certificate_path, labeled| {
+            crate::work_package::create_artifact_package_core(
                 works_root,
                 certificate_path,
-                labeled_pdf_path,
+                labeled,
+                None,
                 created_at,
             )
             .map_err(|e| PublishError::Package(format!("{e:?}")))
@@ -311,96 +822,411 @@ pub(crate) fn create_native_labeled_package_core(
     )
 }
 
-// --- COMMANDE TAURI (fine enveloppe 6D) --------------------------------------
-
-/// Commande dev/e2e : génère certificat + PDF labellisé + package pour un Work
-/// dont le document final est un PDF. `created_at` = maintenant (runtime).
-/// Retour : `package_dir` (chemin LOCAL, pour ouvrir le PDF côté app) + résumé
-/// du manifest. Aucune logique PDF nouvelle : délègue à `create_labeled_package_core`.
-#[tauri::command]
-pub fn create_labeled_work_package(
-    work_id: String,
-    source_pdf_path: String,
-    cartouche_png_path: String,
-    verify_url: String,
-) -> Result<serde_json::Value, String> {
-    let root = crate::work_store::works_root()?;
-    let wid = crate::work_store::WorkId(work_id);
-    let created_at = chrono::Utc::now().to_rfc3339();
-
-    let manifest = create_labeled_package_core(
-        &root,
-        &wid,
-        Path::new(&source_pdf_path),
-        Path::new(&cartouche_png_path),
-        &verify_url,
-        &created_at,
+/// Point d'entrée production Work DOCX (INTÉGRATION-only : convertisseur +
+/// PDFium + clé device). La source DOCX certifiée est convertie en PDF, ce rendu
+/// reçoit la cartouche native, et le package lie source -> rendu.
+pub(crate) fn create_native_docx_package_core(
+    works_root: &Path,
+    work_id: &WorkId,
+    source_docx_path: &Path,
+    verify_url: &str,
+    created_at: &str,
+    conformance: PdfConformance,
+) -> Result<PackageManifest, PublishError> {
+    create_docx_labeled_package_inner(
+        works_root,
+        work_id,
+        source_docx_path,
+        verify_url,
+        || {
+            crate::work_certificate::create_work_certificate_core(works_root, work_id, created_at)
+                .map_err(|e| PublishError::Certificate(format!("{e:?}")))
+        },
+        convert_docx_real,
+        // Rendu fraîchement converti : jamais signé, la politique est sans objet.
+        &PdfDocumentAdapter::native(conformance, SignaturePolicy::default()),
+        |certificate_path, labeled, rendition| {
+            crate::work_package::create_artifact_package_core(
+                works_root,
+                certificate_path,
+                labeled,
+                Some(rendition),
+                created_at,
+            )
+            .map_err(|e| PublishError::Package(format!("{e:?}")))
+        },
     )
-    .map_err(|e| format!("{e:?}"))?;
-
-    let dir = package_dir(&root, &manifest.work_id, manifest.certificate_sequence);
-    Ok(serde_json::json!({
-        "package_dir": dir.to_string_lossy(),
-        "manifest": {
-            "schema_version": manifest.schema_version,
-            "package_id": manifest.package_id,
-            "work_id": manifest.work_id,
-            "certificate_id": manifest.certificate_id,
-            "certificate_sequence": manifest.certificate_sequence,
-            "certificate_version": manifest.certificate_version,
-            "verdict": manifest.verdict,
-            "files": manifest.files,
-            "identity_status": manifest.signature_metadata.identity_status,
-            "signing_key_id": manifest.signature_metadata.signing_key_id,
-        }
-    }))
 }
 
-/// Commande dev/e2e NATIVE : identique à `create_labeled_work_package` mais la
-/// cartouche Work est générée nativement depuis le certificat (pas de PNG fourni).
-/// C'est le chemin principal du panneau dev.
-#[tauri::command]
-pub fn create_native_labeled_work_package(
-    work_id: String,
-    source_pdf_path: String,
-    verify_url: String,
-) -> Result<serde_json::Value, String> {
-    let root = crate::work_store::works_root()?;
-    let wid = crate::work_store::WorkId(work_id);
-    let created_at = chrono::Utc::now().to_rfc3339();
-
-    let manifest = create_native_labeled_package_core(
-        &root,
-        &wid,
-        Path::new(&source_pdf_path),
-        &verify_url,
-        &created_at,
+/// Point d'entrée production Work IMAGE (INTÉGRATION-only : clé device). La
+/// source PNG/JPEG certifiée reçoit la cartouche native dans une marge ajoutée
+/// (`work_image`) ; le package embarque la copie labellisée + son sidecar signé.
+pub(crate) fn create_native_image_package_core(
+    works_root: &Path,
+    work_id: &WorkId,
+    source_image_path: &Path,
+    verify_url: &str,
+    created_at: &str,
+) -> Result<PackageManifest, PublishError> {
+    let key = crate::ensure_signing_key().map_err(PublishError::Io)?;
+    create_labeled_package_inner(
+        works_root,
+        work_id,
+        source_image_path,
+        verify_url,
+        || {
+            crate::work_certificate::create_work_certificate_core(works_root, work_id, created_at)
+                .map_err(|e| PublishError::Certificate(format!("{e:?}")))
+        },
+        &crate::work_image::ImageDocumentAdapter::new(&key),
+        |certificate_path, labeled| {
+            crate::work_package::create_artifact_package_core(
+                works_root,
+                certificate_path,
+                labeled,
+                None,
+                created_at,
+            )
+            .map_err(|e| PublishError::Package(format!("{e:?}")))
+        },
     )
-    .map_err(|e| format!("{e:?}"))?;
-
-    let dir = package_dir(&root, &manifest.work_id, manifest.certificate_sequence);
-    Ok(serde_json::json!({
-        "package_dir": dir.to_string_lossy(),
-        "manifest": {
-            "schema_version": manifest.schema_version,
-            "package_id": manifest.package_id,
-            "work_id": manifest.work_id,
-            "certificate_id": manifest.certificate_id,
-            "certificate_sequence": manifest.certificate_sequence,
-            "certificate_version": manifest.certificate_version,
-            "verdict": manifest.verdict,
-            "files": manifest.files,
-            "identity_status": manifest.signature_metadata.identity_status,
-            "signing_key_id": manifest.signature_metadata.signing_key_id,
-        }
-    }))
 }
 
-// --- TESTS UNITAIRES (seams uniquement, aucun PDFium) -------------------------
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Point d'entrée production Work AUDIO (INTÉGRATION-only : clé device). Le
+/// signal de la source WAV/FLAC/MP3 certifiée n'est pas touché : seuls ses tags
+/// portent le certificat (`work_audio`) ; le package embarque copie + sidecar.
+pub(crate) fn create_native_audio_package_core(
+    works_root: &Path,
+    work_id: &WorkId,
+    source_audio_path: &Path,
+    verify_url: &str,
+    created_at: &str,
+) -> Result<PackageManifest, PublishError> {
+    let key = crate::ensure_signing_key().map_err(PublishError::Io)?;
+    create_labeled_package_inner(
+        works_root,
+        work_id,
+        source_audio_path,
+        verify_url,
+        || {
+            crate::work_certificate::create_work_certificate_core(works_root, work_id, created_at)
+                .map_err(|e| PublishError::Certificate(format!("{e:?}")))
+        },
+        &crate::work_audio::AudioDocumentAdapter::new(&key),
+        |certificate_path, labeled| {
+            crate::work_package::create_artifact_package_core(
+                works_root,
+                certificate_path,
+                labeled,
+                None,
+                created_at,
+            )
+            .map_err(|e| PublishError::Package(format!("{e:?}")))
+        },
+    )
+}
+
+/// Point d'entrée production Work DÉPÔT (INTÉGRATION-only : clé device). Le
+/// dépôt git certifié (empreinte d'arbre) n'est pas modifié : le package
+/// embarque sa liste d'arbre + le sidecar signé (`work_repo`).
+pub(crate) fn create_native_repo_package_core(
+    works_root: &Path,
+    work_id: &WorkId,
+    repo_path: &Path,
+    verify_url: &str,
+    created_at: &str,
+) -> Result<PackageManifest, PublishError> {
+    let key = crate::ensure_signing_key().map_err(PublishError::Io)?;
+    create_labeled_package_inner(
+        works_root,
+        work_id,
+        repo_path,
+        verify_url,
+        || {
+            crate::work_certificate::create_work_certificate_core(works_root, work_id, created_at)
+                .map_err(|e| PublishError::Certificate(format!("{e:?}")))
+        },
+        &crate::work_repo::RepoDocumentAdapter::new(&key),
+        |certificate_path, labeled| {
+            crate::work_package::create_artifact_package_core(
+                works_root,
+                certificate_path,
+                labeled,
+                None,
+                created_at,
+            )
+            .map_err(|e| PublishError::Package(format!("{e:?}")))
+        },
+    )
+}
+
+/// Point d'entrée production du package sidecar seul (INTÉGRATION-only : clé
+/// device). Toute source certifiée (fichier ou dépôt git) : l'original n'est
+/// ni copié ni modifié, le package embarque certificat + sidecar signé.
+pub(crate) fn create_sidecar_package_core(
+    works_root: &Path,
+    work_id: &WorkId,
+    source_path: &Path,
+    verify_url: &str,
+    created_at: &str,
+) -> Result<PackageManifest, PublishError> {
+    let key = crate::ensure_signing_key().map_err(PublishError::Io)?;
+    create_sidecar_package_inner(
+        works_root,
+        work_id,
+        source_path,
+        verify_url,
+        || {
+            crate::work_certificate::create_work_certificate_core(works_root, work_id, created_at)
+                .map_err(|e| PublishError::Certificate(format!("{e:?}")))
+        },
+        &key,
+        |certificate_path, original| {
+            crate::work_package::create_sidecar_package_core(
+                works_root,
+                certificate_path,
+                original,
+                created_at,
+            )
+            .map_err(|e| PublishError::Package(format!("{e:?}")))
+        },
+    )
+}
+
+// --- COMMANDE TAURI (fine enveloppe 6D) --------------------------------------
+
+/// Réponse commune des commandes : `package_dir` local + résumé du manifest.
+fn package_response(root: &Path, manifest: &PackageManifest) -> serde_json::Value {
+    let dir = package_dir(root, &manifest.work_id, manifest.certificate_sequence);
+    serde_json::json!({
+        "package_dir": dir.to_string_lossy(),
+        "manifest": {
+            "schema_version": manifest.schema_version,
+            "package_id": manifest.package_id,
+            "work_id": manifest.work_id,
+            "certificate_id": manifest.certificate_id,
+            "certificate_sequence": manifest.certificate_sequence,
+            "certificate_version": manifest.certificate_version,
+            "verdict": manifest.verdict,
+            "files": manifest.files,
+            "derived_rendition": manifest.derived_rendition,
+            "identity_status": manifest.signature_metadata.identity_status,
+            "signing_key_id": manifest.signature_metadata.signing_key_id,
+        }
+    })
+}
+
+/// Commande dev/e2e : génère certificat + PDF labellisé + package pour un Work
+/// dont le document final est un PDF. `created_at` = maintenant (runtime).
+/// Retour : `package_dir` (chemin LOCAL, pour ouvrir le PDF côté app) + résumé
+/// du manifest. Aucune logique PDF nouvelle : délègue à `create_labeled_package_core`.
+/// `signature_policy` absente = `refuse` : une source signée échoue avec
+/// `SOURCE_PDF_SIGNED` ; l'UI propose alors `preserve` (mise à jour incrémentale).
+#[tauri::command]
+pub async fn create_labeled_work_package(
+    work_id: String,
+    source_pdf_path: String,
+    cartouche_png_path: String,
+    verify_url: String,
+    conformance: Option<PdfConformance>,
+    signature_policy: Option<SignaturePolicy>,
+) -> Result<serde_json::Value, String> {
+    crate::run_blocking(move || {
+        let root = crate::work_store::works_root()?;
+        let wid = crate::work_store::WorkId(work_id);
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        let manifest = create_labeled_package_core(
+            &root,
+            &wid,
+            Path::new(&source_pdf_path),
+            Path::new(&cartouche_png_path),
+            &verify_url,
+            &created_at,
+            conformance.unwrap_or_default(),
+            signature_policy.unwrap_or_default(),
+        )
+        .map_err(|e| format!("{e:?}"))?;
+
+        Ok(package_response(&root, &manifest))
+    })
+    .await
+}
+
+/// Commande dev/e2e NATIVE : identique à `create_labeled_work_package` mais la
+/// cartouche Work est générée nativement depuis le certificat (pas de PNG fourni).
+/// C'est le chemin principal du panneau dev.
+#[tauri::command]
+pub async fn create_native_labeled_work_package(
+    work_id: String,
+    source_pdf_path: String,
+    verify_url: String,
+    conformance: Option<PdfConformance>,
+    signature_policy: Option<SignaturePolicy>,
+) -> Result<serde_json::Value, String> {
+    crate::run_blocking(move || {
+        let root = crate::work_store::works_root()?;
+        let wid = crate::work_store::WorkId(work_id);
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        let manifest = create_native_labeled_package_core(
+            &root,
+            &wid,
+            Path::new(&source_pdf_path),
+            &verify_url,
+            &created_at,
+            conformance.unwrap_or_default(),
+            signature_policy.unwrap_or_default(),
+        )
+        .map_err(|e| format!("{e:?}"))?;
+
+        Ok(package_response(&root, &manifest))
+    })
+    .await
+}
+
+/// Commande dev/e2e Work DOCX : conversion DOCX -> PDF (sidecar), cartouche
+/// native sur le rendu, package avec source + rendu liés (`derived_rendition`).
+#[tauri::command]
+pub async fn create_native_docx_work_package(
+    work_id: String,
+    source_docx_path: String,
+    verify_url: String,
+    conformance: Option<PdfConformance>,
+) -> Result<serde_json::Value, String> {
+    crate::run_blocking(move || {
+        let root = crate::work_store::works_root()?;
+        let wid = crate::work_store::WorkId(work_id);
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        let manifest = create_native_docx_package_core(
+            &root,
+            &wid,
+            Path::new(&source_docx_path),
+            &verify_url,
+            &created_at,
+            conformance.unwrap_or_default(),
+        )
+        .map_err(|e| format!("{e:?}"))?;
+
+        Ok(package_response(&root, &manifest))
+    })
+    .await
+}
+
+/// Commande dev/e2e Work IMAGE : copie PNG/JPEG labellisée (marge + XMP/EXIF)
+/// + sidecar `.ho.json` signé, packagés via le même manifest.
+#[tauri::command]
+pub async fn create_native_image_work_package(
+    work_id: String,
+    source_image_path: String,
+    verify_url: String,
+) -> Result<serde_json::Value, String> {
+    crate::run_blocking(move || {
+        let root = crate::work_store::works_root()?;
+        let wid = crate::work_store::WorkId(work_id);
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        let manifest = create_native_image_package_core(
+            &root,
+            &wid,
+            Path::new(&source_image_path),
+            &verify_url,
+            &created_at,
+        )
+        .map_err(|e| format!("{e:?}"))?;
+
+        Ok(package_response(&root, &manifest))
+    })
+    .await
+}
+
+/// Commande dev/e2e Work AUDIO : copie WAV/FLAC/MP3 aux tags HumanOrigin (signal
+/// intact) + sidecar `.ho.json` signé, packagés via le même manifest.
+#[tauri::command]
+pub async fn create_native_audio_work_package(
+    work_id: String,
+    source_audio_path: String,
+    verify_url: String,
+) -> Result<serde_json::Value, String> {
+    crate::run_blocking(move || {
+        let root = crate::work_store::works_root()?;
+        let wid = crate::work_store::WorkId(work_id);
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        let manifest = create_native_audio_package_core(
+            &root,
+            &wid,
+            Path::new(&source_audio_path),
+            &verify_url,
+            &created_at,
+        )
+        .map_err(|e| format!("{e:?}"))?;
+
+        Ok(package_response(&root, &manifest))
+    })
+    .await
+}
+
+/// Commande dev/e2e sidecar seul : l'original reste intact ; le package contient
+/// sa référence SHA256, `certificate.json`, le sidecar `.ho.json` et le manifest.
+#[tauri::command]
+pub async fn create_sidecar_work_package(
+    work_id: String,
+    source_path: String,
+    verify_url: String,
+) -> Result<serde_json::Value, String> {
+    crate::run_blocking(move || {
+        let root = crate::work_store::works_root()?;
+        let wid = crate::work_store::WorkId(work_id);
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        let manifest = create_sidecar_package_core(
+            &root,
+            &wid,
+            Path::new(&source_path),
+            &verify_url,
+            &created_at,
+        )
+        .map_err(|e| format!("{e:?}"))?;
+
+        Ok(package_response(&root, &manifest))
+    })
+    .await
+}
+
+/// Commande dev/e2e Work DÉPÔT : liste d'arbre + sidecar `.ho.json` signé d'un
+/// dépôt git certifié, packagés via le même manifest.
+#[tauri::command]
+pub async fn create_native_repo_work_package(
+    work_id: String,
+    repo_path: String,
+    verify_url: String,
+) -> Result<serde_json::Value, String> {
+    crate::run_blocking(move || {
+        let root = crate::work_store::works_root()?;
+        let wid = crate::work_store::WorkId(work_id);
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        let manifest = create_native_repo_package_core(
+            &root,
+            &wid,
+            Path::new(&repo_path),
+            &verify_url,
+            &created_at,
+        )
+        .map_err(|e| format!("{e:?}"))?;
+
+        Ok(package_response(&root, &manifest))
+    })
+    .await
+}
+
+// --- TESTS UNITAIRES (seams uniquement, aucun PDFium) -------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
     use crate::work_certificate::{
         compute_public_core_evidence_sha256, CertificateSignatureMetadata, CertificateVersion,
         Continuity, ContinuityKind, IncludedPeriod, PublicCoreEvidence, PublicDocumentRef,
@@ -410,6 +1236,7 @@ mod tests {
     use base64::{engine::general_purpose, Engine as _};
     use ed25519_dalek::{Signer, SigningKey};
     use rand_core::OsRng;
+    use sha2::{Digest, Sha256};
     use uuid::Uuid;
 
     const URL: &str = "https://verify.humanorigin.app/r/abc";
@@ -444,6 +1271,7 @@ mod tests {
             document: PublicDocumentRef {
                 hash_current: hash_current.to_string(),
                 size_current: 100,
+                members: None,
             },
             included_period_summaries: vec![IncludedPeriod {
                 period_id: Uuid::new_v4().to_string(),
@@ -455,6 +1283,8 @@ mod tests {
                 net_document_change: true,
                 gate_passed: true,
                 qualifying: true,
+                change_magnitude: None,
+                member_changes: None,
                 period_record_sha256: "c".repeat(64),
             }],
             new_period_ids: vec![],
@@ -469,6 +1299,7 @@ mod tests {
                 total_active_seconds: 60,
                 continuity: ContinuityKind::Full,
                 verdict: ProofVerdict::ObservedWorkConsistent,
+                flags: vec![],
             },
             previous_certificate_id: None,
             previous_core_evidence_sha256: None,
@@ -493,6 +1324,7 @@ mod tests {
                 signing_key_id,
                 identity_status: "LOCAL_DEVICE".to_string(),
                 schema_version: CERTIFICATE_SCHEMA_VERSION,
+                canonicalization: None,
             },
             signature: String::new(),
         };
@@ -530,12 +1362,12 @@ mod tests {
     }
 
     /// cartouche_fn de test : écrit une cartouche factice non vide dans le temp.
-    fn write_cartouche(_cert: &WorkCertificate, out: &Path) -> Result<(), PublishError> {
+    fn write_cartouche(_request: &LabeledArtifactRequest, out: &Path) -> Result<(), PublishError> {
         fs::write(out, b"PNG fake").map_err(|e| PublishError::Io(e.to_string()))
     }
 
     /// cartouche_fn de test : panique si appelée (chemin court-circuité avant).
-    fn panic_cartouche(_cert: &WorkCertificate, _out: &Path) -> Result<(), PublishError> {
+    fn panic_cartouche(_request: &LabeledArtifactRequest, _out: &Path) -> Result<(), PublishError> {
         panic!("la cartouche ne doit PAS être générée ici")
     }
 
@@ -543,10 +1375,12 @@ mod tests {
     fn pkg_fn<'a>(
         works: &'a Path,
         k: &'a SigningKey,
-    ) -> impl FnOnce(&Path, &Path) -> Result<PackageManifest, PublishError> + 'a {
-        move |cert_path: &Path, pdf_path: &Path| {
-            crate::work_package::create_work_package_with(works, cert_path, pdf_path, "t", k)
-                .map_err(|e| PublishError::Package(format!("{e:?}")))
+    ) -> impl FnOnce(&Path, &LabeledInput) -> Result<PackageManifest, PublishError> + 'a {
+        move |cert_path: &Path, labeled: &LabeledInput| {
+            crate::work_package::create_artifact_package_with(
+                works, cert_path, labeled, None, "t", k,
+            )
+            .map_err(|e| PublishError::Package(format!("{e:?}")))
         }
     }
 
@@ -558,7 +1392,7 @@ mod tests {
         !fs::read_dir(&pkgs)
             .unwrap()
             .filter_map(|e| e.ok())
-            .any(|e| e.file_name().to_string_lossy().starts_with(".tmp_labeled_"))
+            .any(|e| e.file_name().to_string_lossy().starts_with(".tmp_"))
     }
 
     #[test]
@@ -577,11 +1411,10 @@ mod tests {
             &src,
             URL,
             || panic!("ne doit PAS créer un certificat (réutilisation)"),
-            write_cartouche,
-            |_s, output, _c, _id, _u, _v| {
+            &PdfDocumentAdapter::with_seams(write_cartouche, |_s, output, _c, _id, _u, _v| {
                 fs::write(output, b"LABELED PDF").unwrap();
                 Ok(())
-            },
+            }),
             pkg_fn(&works, &k),
         );
         assert!(res.is_ok());
@@ -608,8 +1441,9 @@ mod tests {
             &src,
             URL,
             || panic!("réutilisation"),
-            panic_cartouche,
-            |_s, _o, _c, _id, _u, _v| panic!("le PDF ne doit PAS être généré sur mismatch"),
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| {
+                panic!("le PDF ne doit PAS être généré sur mismatch")
+            }),
             pkg_fn(&works, &k),
         );
         assert_eq!(
@@ -637,11 +1471,10 @@ mod tests {
             &src,
             URL,
             || panic!("doit réutiliser certificate_1.json"),
-            write_cartouche,
-            |_s, o, _c, _id, _u, _v| {
+            &PdfDocumentAdapter::with_seams(write_cartouche, |_s, o, _c, _id, _u, _v| {
                 fs::write(o, b"LABELED").unwrap();
                 Ok(())
-            },
+            }),
             pkg_fn(&works, &k),
         );
         assert!(res.is_ok());
@@ -667,8 +1500,9 @@ mod tests {
             &src,
             URL,
             || Ok(cert.clone()),
-            panic_cartouche,
-            |_s, _o, _c, _id, _u, _v| panic!("le PDF ne doit PAS être généré (package existe)"),
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| {
+                panic!("le PDF ne doit PAS être généré (package existe)")
+            }),
             pkg_fn(&works, &k),
         );
         assert!(matches!(res, Err(PublishError::AlreadyExists(_))));
@@ -689,8 +1523,7 @@ mod tests {
             &src,
             "file:///Users/x/doc.pdf",
             || panic!(),
-            panic_cartouche,
-            |_s, _o, _c, _id, _u, _v| panic!(),
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| panic!()),
             pkg_fn(&works, &k),
         );
         assert_eq!(res.err(), Some(PublishError::InvalidVerifyUrl));
@@ -711,8 +1544,7 @@ mod tests {
             &src,
             "https://x.app/Users/secret",
             || panic!(),
-            panic_cartouche,
-            |_s, _o, _c, _id, _u, _v| panic!(),
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| panic!()),
             pkg_fn(&works, &k),
         );
         assert_eq!(res.err(), Some(PublishError::InvalidVerifyUrl));
@@ -733,8 +1565,9 @@ mod tests {
             &src,
             URL,
             || panic!(),
-            write_cartouche,
-            |_s, _o, _c, _id, _u, _v| Err(PublishError::PdfGenerationFailed("KO".into())),
+            &PdfDocumentAdapter::with_seams(write_cartouche, |_s, _o, _c, _id, _u, _v| {
+                Err(PublishError::PdfGenerationFailed("KO".into()))
+            }),
             pkg_fn(&works, &k),
         );
         assert!(matches!(res, Err(PublishError::PdfGenerationFailed(_))));
@@ -761,8 +1594,7 @@ mod tests {
             &src,
             URL,
             || panic!(),
-            write_cartouche,
-            |_s, _o, _c, _id, _u, _v| Ok(()),
+            &PdfDocumentAdapter::with_seams(write_cartouche, |_s, _o, _c, _id, _u, _v| Ok(())),
             pkg_fn(&works, &k),
         );
         assert_eq!(res.err(), Some(PublishError::PdfNotProduced));
@@ -785,8 +1617,9 @@ mod tests {
             &src,
             URL,
             || Err(PublishError::Certificate("NoQualifyingNewPeriod".into())),
-            panic_cartouche,
-            |_s, _o, _c, _id, _u, _v| panic!("pas de PDF si le certificat échoue"),
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| {
+                panic!("pas de PDF si le certificat échoue")
+            }),
             pkg_fn(&works, &k),
         );
         assert!(matches!(res, Err(PublishError::Certificate(_))));
@@ -828,19 +1661,21 @@ mod tests {
             &src,
             URL,
             || panic!("réutilisation"),
-            move |cert, out| {
-                // La cartouche reçoit le certificat réel, APRÈS le hash check.
-                assert_eq!(cert.public_core_evidence.document.hash_current, expected_hash);
-                assert!(!cert.certificate_id.is_empty());
-                o_cart.borrow_mut().push("cartouche");
-                fs::write(out, b"PNG fake").unwrap();
-                Ok(())
-            },
-            move |_s, output, _c, _id, _u, _v| {
-                o_pdf.borrow_mut().push("pdf");
-                fs::write(output, b"LABELED").unwrap();
-                Ok(())
-            },
+            &PdfDocumentAdapter::with_seams(
+                move |request, out| {
+                    // La cartouche reçoit la requête du certificat réel, APRÈS le hash check.
+                    assert_eq!(request.source_version.fingerprint_sha256, expected_hash);
+                    assert!(!request.certificate_id.is_empty());
+                    o_cart.borrow_mut().push("cartouche");
+                    fs::write(out, b"PNG fake").unwrap();
+                    Ok(())
+                },
+                move |_s, output, _c, _id, _u, _v| {
+                    o_pdf.borrow_mut().push("pdf");
+                    fs::write(output, b"LABELED").unwrap();
+                    Ok(())
+                },
+            ),
             pkg_fn(&works, &k),
         );
         assert!(res.is_ok());
@@ -868,12 +1703,14 @@ mod tests {
             &src,
             URL,
             || panic!("réutilisation"),
-            move |_cert, out| {
-                c.set(true);
-                fs::write(out, b"PNG").unwrap();
-                Ok(())
-            },
-            |_s, _o, _c, _id, _u, _v| panic!("le PDF ne doit PAS être généré"),
+            &PdfDocumentAdapter::with_seams(
+                move |_request, out| {
+                    c.set(true);
+                    fs::write(out, b"PNG").unwrap();
+                    Ok(())
+                },
+                |_s, _o, _c, _id, _u, _v| panic!("le PDF ne doit PAS être généré"),
+            ),
             pkg_fn(&works, &k),
         );
         assert_eq!(
@@ -899,8 +1736,10 @@ mod tests {
             &src,
             URL,
             || panic!(),
-            |_cert, _out| Err(PublishError::CartoucheGenerationFailed("KO".into())),
-            |_s, _o, _c, _id, _u, _v| panic!("pas de PDF si la cartouche échoue"),
+            &PdfDocumentAdapter::with_seams(
+                |_request, _out| Err(PublishError::CartoucheGenerationFailed("KO".into())),
+                |_s, _o, _c, _id, _u, _v| panic!("pas de PDF si la cartouche échoue"),
+            ),
             pkg_fn(&works, &k),
         );
         assert!(matches!(res, Err(PublishError::CartoucheGenerationFailed(_))));
@@ -919,33 +1758,23 @@ mod tests {
         write_cert(&works, &wid, &build_cert(&k, &wid, 1, &h));
 
         // cartouche_fn NATIVE : identique au flux production (render réel, sans PDFium).
-        let verify_url_owned = URL.to_string();
         let res = create_labeled_package_inner(
             &works,
             &wid,
             &src,
             URL,
             || panic!("réutilisation"),
-            move |cert, out| {
-                let inputs = crate::work_cartouche::WorkCartoucheInputs {
-                    certificate_id: cert.certificate_id.clone(),
-                    verdict: verdict_label(cert.public_core_evidence.verdict.verdict).to_string(),
-                    identity_status: "LOCAL_DEVICE".to_string(),
-                    verify_url: verify_url_owned.clone(),
-                    signing_key_id: Some(cert.signature_metadata.signing_key_id.clone()),
-                    created_at: Some(cert.created_at.clone()),
-                };
-                crate::work_cartouche::render_work_cartouche_png(&inputs, out)
-                    .map_err(PublishError::CartoucheGenerationFailed)
-            },
-            // generate_pdf factice : vérifie que la cartouche native est un vrai PNG.
-            |_s, output, cartouche, _id, _u, _v| {
-                let bytes = fs::read(cartouche).unwrap();
-                assert!(bytes.len() > 100, "cartouche PNG vide");
-                assert_eq!(&bytes[..4], b"\x89PNG", "cartouche native n'est pas un PNG");
-                fs::write(output, b"LABELED PDF").unwrap();
-                Ok(())
-            },
+            &PdfDocumentAdapter::with_seams(
+                render_native_cartouche,
+                // generate_pdf factice : vérifie que la cartouche native est un vrai PNG.
+                |_s, output, cartouche, _id, _u, _v| {
+                    let bytes = fs::read(cartouche).unwrap();
+                    assert!(bytes.len() > 100, "cartouche PNG vide");
+                    assert_eq!(&bytes[..4], b"\x89PNG", "cartouche native n'est pas un PNG");
+                    fs::write(output, b"LABELED PDF").unwrap();
+                    Ok(())
+                },
+            ),
             pkg_fn(&works, &k),
         );
         assert!(res.is_ok(), "flux natif doit produire un package");
@@ -972,20 +1801,636 @@ mod tests {
             &src,
             URL,
             || panic!("réutilisation"),
-            move |_cert, out| {
-                fs::copy(&cart_src, out)
-                    .map(|_| ())
-                    .map_err(|e| PublishError::Io(e.to_string()))
+            &PdfDocumentAdapter::with_seams(
+                move |_request, out| {
+                    fs::copy(&cart_src, out)
+                        .map(|_| ())
+                        .map_err(|e| PublishError::Io(e.to_string()))
+                },
+                |_s, output, _c, _id, _u, _v| {
+                    fs::write(output, b"LABELED PDF").unwrap();
+                    Ok(())
+                },
+            ),
+            pkg_fn(&works, &k),
+        );
+        assert!(res.is_ok());
+        let dir = package_dir(&works, &wid, 1);
+        assert!(crate::work_package::verify_manifest(&dir).is_ok());
+        cleanup(&base);
+    }
+
+    /// Écrit une source DOCX de contenu donné ; renvoie (chemin, hash).
+    fn write_source_docx(base: &Path, content: &[u8]) -> (PathBuf, String) {
+        fs::create_dir_all(base).unwrap();
+        let p = base.join("chapitre.docx");
+        fs::write(&p, content).unwrap();
+        (p, sha_hex(content))
+    }
+
+    /// seam convert : écrit un PDF rendu factice dans le dossier de sortie.
+    fn fake_convert(_src: &Path, out_dir: &Path) -> Result<ConvertedRendition, PublishError> {
+        let pdf_path = out_dir.join("chapitre.pdf");
+        fs::write(&pdf_path, b"%PDF-1.7 rendition").unwrap();
+        Ok(ConvertedRendition {
+            pdf_path,
+            converter: "libreoffice-headless".to_string(),
+            conversion_record_path: None,
+        })
+    }
+
+    /// seam make_package rendu dérivé : 6C-1 réel avec la clé fournie.
+    fn rendition_pkg_fn<'a>(
+        works: &'a Path,
+        k: &'a SigningKey,
+    ) -> impl FnOnce(&Path, &LabeledInput, &RenditionInput) -> Result<PackageManifest, PublishError> + 'a
+    {
+        move |cert_path: &Path, labeled: &LabeledInput, rendition: &RenditionInput| {
+            crate::work_package::create_artifact_package_with(
+                works,
+                cert_path,
+                labeled,
+                Some(rendition),
+                "t",
+                k,
+            )
+            .map_err(|e| PublishError::Package(format!("{e:?}")))
+        }
+    }
+
+    #[test]
+    fn test_16_docx_rendu_labellise_et_lie_au_certificat() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        let (src, h) = write_source_docx(&base, b"PK DOCX CERTIFIE");
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &h));
+
+        let res = create_docx_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            fake_convert,
+            &PdfDocumentAdapter::with_seams(write_cartouche, |s, output, _c, _id, _u, _v| {
+                // C'est le RENDU PDF qui est labellisé, jamais le DOCX.
+                assert_eq!(fs::read(s).unwrap(), b"%PDF-1.7 rendition");
+                fs::write(output, b"LABELED PDF").unwrap();
+                Ok(())
+            }),
+            rendition_pkg_fn(&works, &k),
+        );
+        let manifest = res.unwrap();
+        let dir = package_dir(&works, &wid, 1);
+        assert!(crate::work_package::verify_manifest(&dir).is_ok());
+        let r = manifest
+            .derived_rendition
+            .expect("derived_rendition attendu");
+        assert_eq!(r.source.sha256, h);
+        assert_eq!(r.rendition.sha256, sha_hex(b"%PDF-1.7 rendition"));
+        assert!(dir.join("source_document.docx").exists());
+        assert!(dir.join("rendition.pdf").exists());
+        assert!(no_leftover_temp(&works, &wid));
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_17_docx_hash_mismatch_aucune_conversion() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        let (src, _h) = write_source_docx(&base, b"PK DOCX MODIFIE");
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &"a".repeat(64)));
+
+        let res = create_docx_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            |_s, _o| panic!("la conversion ne doit PAS être lancée sur mismatch"),
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| panic!()),
+            rendition_pkg_fn(&works, &k),
+        );
+        assert_eq!(
+            res.err(),
+            Some(PublishError::SourcePdfDoesNotMatchCertifiedDocument)
+        );
+        assert!(!package_dir(&works, &wid, 1).exists());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_18_docx_conversion_echoue_aucun_package() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        let (src, h) = write_source_docx(&base, b"PK DOCX");
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &h));
+
+        let res = create_docx_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            |_s, _o| Err(PublishError::ConversionFailed("soffice KO".into())),
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| {
+                panic!("pas de PDF si la conversion échoue")
+            }),
+            rendition_pkg_fn(&works, &k),
+        );
+        assert!(matches!(res, Err(PublishError::ConversionFailed(_))));
+        assert!(!package_dir(&works, &wid, 1).exists());
+        assert!(no_leftover_temp(&works, &wid));
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_19_docx_rendu_hors_temp_ou_non_pdf_refuse() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        let (src, h) = write_source_docx(&base, b"PK DOCX");
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &h));
+
+        // Rendu PDF valide mais HORS du dossier temporaire (fichier périmé).
+        let stale = base.join("stale.pdf");
+        fs::write(&stale, b"%PDF-1.4 ancien").unwrap();
+        let res = create_docx_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            move |_s, _o| {
+                Ok(ConvertedRendition {
+                    pdf_path: stale,
+                    converter: "libreoffice-headless".to_string(),
+                    conversion_record_path: None,
+                })
+            },
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| panic!()),
+            rendition_pkg_fn(&works, &k),
+        );
+        assert_eq!(res.err(), Some(PublishError::RenditionNotProduced));
+
+        // Rendu dans le temp mais qui n'est pas un PDF.
+        let res = create_docx_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            |_s, out_dir| {
+                let pdf_path = out_dir.join("chapitre.pdf");
+                fs::write(&pdf_path, b"not a pdf").unwrap();
+                Ok(ConvertedRendition {
+                    pdf_path,
+                    converter: "libreoffice-headless".to_string(),
+                    conversion_record_path: None,
+                })
             },
-            |_s, output, _c, _id, _u, _v| {
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| panic!()),
+            rendition_pkg_fn(&works, &k),
+        );
+        assert_eq!(res.err(), Some(PublishError::RenditionNotProduced));
+        assert!(!package_dir(&works, &wid, 1).exists());
+        assert!(no_leftover_temp(&works, &wid));
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_20_flux_docx_refuse_une_source_non_docx() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        let (src, h) = write_source_pdf(&base, b"PDF");
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &h));
+        let res = create_docx_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!(),
+            |_s, _o| panic!(),
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| panic!()),
+            rendition_pkg_fn(&works, &k),
+        );
+        assert_eq!(
+            res.err(),
+            Some(PublishError::UnsupportedSourceFormat("pdf".to_string()))
+        );
+        cleanup(&base);
+    }
+
+    /// seam convert : PDF rendu + record cohérent, comme le sidecar.
+    fn fake_convert_with_record(
+        src: &Path,
+        out_dir: &Path,
+    ) -> Result<ConvertedRendition, PublishError> {
+        let mut r = fake_convert(src, out_dir)?;
+        let record_path = out_dir.join("chapitre.conversion_record.json");
+        let record = serde_json::json!({
+            "record_version": 1,
+            "source": { "sha256": sha_hex(&fs::read(src).unwrap()) },
+            "output": { "sha256": sha_hex(&fs::read(&r.pdf_path).unwrap()) },
+            "engine": { "name": "libreoffice-headless" }
+        });
+        fs::write(&record_path, serde_json::to_vec(&record).unwrap()).unwrap();
+        r.conversion_record_path = Some(record_path);
+        Ok(r)
+    }
+
+    #[test]
+    fn test_21_docx_conversion_record_embarque() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        let (src, h) = write_source_docx(&base, b"PK DOCX CERTIFIE");
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &h));
+
+        let manifest = create_docx_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            fake_convert_with_record,
+            &PdfDocumentAdapter::with_seams(write_cartouche, |_s, output, _c, _id, _u, _v| {
                 fs::write(output, b"LABELED PDF").unwrap();
                 Ok(())
+            }),
+            rendition_pkg_fn(&works, &k),
+        )
+        .unwrap();
+        let dir = package_dir(&works, &wid, 1);
+        assert!(crate::work_package::verify_manifest(&dir).is_ok());
+        let record = manifest.derived_rendition.unwrap().conversion_record;
+        assert_eq!(record.unwrap().filename, "conversion_record.json");
+        assert!(dir.join("conversion_record.json").exists());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_22_docx_conversion_record_hors_temp_refuse() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        let (src, h) = write_source_docx(&base, b"PK DOCX CERTIFIE");
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &h));
+        let foreign = base.join("ailleurs.conversion_record.json");
+        fs::write(&foreign, b"{}").unwrap();
+
+        let res = create_docx_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            move |s, out_dir| {
+                let mut r = fake_convert(s, out_dir)?;
+                r.conversion_record_path = Some(foreign);
+                Ok(r)
+            },
+            &PdfDocumentAdapter::with_seams(panic_cartouche, |_s, _o, _c, _id, _u, _v| panic!()),
+            rendition_pkg_fn(&works, &k),
+        );
+        assert_eq!(res.err(), Some(PublishError::RenditionNotProduced));
+        assert!(!package_dir(&works, &wid, 1).exists());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_23_image_copie_labellisee_et_sidecar_embarques() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        fs::create_dir_all(&base).unwrap();
+        let src = base.join("illustration.png");
+        image::RgbImage::from_fn(32, 24, |x, y| image::Rgb([x as u8 * 7, y as u8 * 9, 50]))
+            .save(&src)
+            .unwrap();
+        let h = sha_hex(&fs::read(&src).unwrap());
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &h));
+
+        let manifest = create_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            &crate::work_image::ImageDocumentAdapter::new(&k),
+            pkg_fn(&works, &k),
+        )
+        .unwrap();
+        let art = manifest.files.labeled_artifact.as_ref().unwrap();
+        assert_eq!(art.artifact_name, "labeled_image.png");
+        assert_eq!(art.media_type, DocumentArtifactKind::Image);
+        let sidecar = manifest
+            .files
+            .labeled_sidecar
+            .as_ref()
+            .expect("sidecar absent");
+        assert_eq!(sidecar.filename, "labeled_image.ho.json");
+
+        let dir = package_dir(&works, &wid, 1);
+        assert!(crate::work_package::verify_manifest(&dir).is_ok());
+        let bytes = fs::read(dir.join(&art.artifact_name)).unwrap();
+        let xmp = crate::work_image::embedded_xmp(&bytes).unwrap();
+        assert!(xmp.contains(&manifest.certificate_id));
+        assert!(xmp.contains(URL));
+        assert!(no_leftover_temp(&works, &wid));
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_26_audio_wav_tags_et_sidecar_embarques_signal_intact() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        fs::create_dir_all(&base).unwrap();
+        // WAV PCM 16 bits mono 8 kHz minimal.
+        let pcm: Vec<u8> = (0..64i16).flat_map(|i| (i * 101).to_le_bytes()).collect();
+        let mut wav = b"RIFF\0\0\0\0WAVEfmt \x10\0\0\0\x01\0\x01\0".to_vec();
+        wav.extend_from_slice(&8_000u32.to_le_bytes());
+        wav.extend_from_slice(&16_000u32.to_le_bytes());
+        wav.extend_from_slice(&[2, 0, 16, 0]);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(pcm.len() as u32).to_le_bytes());
+        wav.extend_from_slice(&pcm);
+        let riff = (wav.len() - 8) as u32;
+        wav[4..8].copy_from_slice(&riff.to_le_bytes());
+        let src = base.join("prise.wav");
+        fs::write(&src, &wav).unwrap();
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &sha_hex(&wav)));
+
+        let manifest = create_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            &crate::work_audio::AudioDocumentAdapter::new(&k),
+            pkg_fn(&works, &k),
+        )
+        .unwrap();
+        let art = manifest.files.labeled_artifact.as_ref().unwrap();
+        assert_eq!(art.artifact_name, "labeled_audio.wav");
+        assert_eq!(art.media_type, DocumentArtifactKind::Audio);
+        assert_eq!(art.mime_type.as_deref(), Some("audio/wav"));
+        let sidecar = manifest
+            .files
+            .labeled_sidecar
+            .as_ref()
+            .expect("sidecar absent");
+        assert_eq!(sidecar.filename, "labeled_audio.ho.json");
+
+        let dir = package_dir(&works, &wid, 1);
+        assert!(crate::work_package::verify_manifest(&dir).is_ok());
+        let bytes = fs::read(dir.join(&art.artifact_name)).unwrap();
+        let labels =
+            crate::work_audio::embedded_labels(&mut std::io::Cursor::new(&bytes)).unwrap();
+        assert!(labels.contains(&("CertificateId".to_string(), manifest.certificate_id.clone())));
+        assert!(labels.contains(&("VerifyUrl".to_string(), URL.to_string())));
+        let fp = crate::audio_fingerprint::fingerprint_audio_bytes(&wav).unwrap();
+        assert_eq!(
+            crate::audio_fingerprint::verify_audio_bytes(&fp, &bytes).unwrap(),
+            crate::audio_fingerprint::AudioMatch::SignalIdentical
+        );
+        assert!(no_leftover_temp(&works, &wid));
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_27_sidecar_seul_original_intact() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        fs::create_dir_all(&base).unwrap();
+        let src = base.join("rapport.pdf");
+        fs::write(&src, b"%PDF-1.7 livrable final").unwrap();
+        let before = fs::read(&src).unwrap();
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &sha_hex(&before)));
+
+        let manifest = create_sidecar_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            &k,
+            |cert_path, original| {
+                crate::work_package::create_sidecar_package_with(
+                    &works, cert_path, original, "t", &k,
+                )
+                .map_err(|e| PublishError::Package(format!("{e:?}")))
             },
+        )
+        .unwrap();
+        assert!(manifest.files.labeled_artifact.is_none());
+        assert_eq!(
+            manifest.files.original_document.as_ref().unwrap().sha256,
+            sha_hex(&before)
+        );
+        let sidecar = manifest
+            .files
+            .labeled_sidecar
+            .as_ref()
+            .expect("sidecar absent");
+        assert_eq!(sidecar.filename, ORIGINAL_SIDECAR_FILENAME);
+
+        let dir = package_dir(&works, &wid, 1);
+        assert!(crate::work_package::verify_manifest(&dir).is_ok());
+        assert!(crate::work_package::verify_original_document(&dir, &src).is_ok());
+        assert_eq!(fs::read(&src).unwrap(), before, "original modifié");
+        let sc = crate::work_sidecar::read_sidecar(&dir.join(ORIGINAL_SIDECAR_FILENAME)).unwrap();
+        assert!(crate::work_sidecar::verify_sidecar(&sc).is_ok());
+        assert!(sc.labeled_artifact.is_none());
+        assert_eq!(sc.verify_url, URL);
+        assert!(no_leftover_temp(&works, &wid));
+
+        // Original retouché après coup : ne correspond plus au package.
+        fs::write(&src, b"%PDF-1.7 livrable retouche").unwrap();
+        assert!(crate::work_package::verify_original_document(&dir, &src).is_err());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_28_sidecar_seul_source_non_certifiee_refusee() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        fs::create_dir_all(&base).unwrap();
+        let src = base.join("rapport.pdf");
+        fs::write(&src, b"%PDF-1.7 autre version").unwrap();
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &"a".repeat(64)));
+
+        let res = create_sidecar_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            &k,
+            |_, _| panic!("aucun package attendu"),
+        );
+        assert!(matches!(
+            res,
+            Err(PublishError::SourcePdfDoesNotMatchCertifiedDocument)
+        ));
+        assert!(!package_dir(&works, &wid, 1).exists());
+        assert!(no_leftover_temp(&works, &wid));
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_25_depot_git_liste_et_sidecar_embarques() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        let repo = base.join("devoir");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::write(repo.join("solution.py"), b"def f():\n    return 42\n").unwrap();
+        let (tree, _) = crate::repo_tree::repo_tree_digest(&repo).unwrap();
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &tree));
+
+        let manifest = create_labeled_package_inner(
+            &works,
+            &wid,
+            &repo,
+            URL,
+            || panic!("réutilisation"),
+            &crate::work_repo::RepoDocumentAdapter::new(&k),
             pkg_fn(&works, &k),
+        )
+        .unwrap();
+        assert_eq!(
+            manifest.files.labeled_artifact.as_ref().unwrap().media_type,
+            DocumentArtifactKind::Code
         );
-        assert!(res.is_ok());
+        assert!(manifest.files.labeled_sidecar.is_some());
         let dir = package_dir(&works, &wid, 1);
         assert!(crate::work_package::verify_manifest(&dir).is_ok());
+
+        // Dépôt modifié après certification : refusé avant tout rendu.
+        let wid2 = WorkId::new();
+        write_cert(&works, &wid2, &build_cert(&k, &wid2, 1, &tree));
+        fs::write(repo.join("solution.py"), b"def f():\n    return 0\n").unwrap();
+        let res = create_labeled_package_inner(
+            &works,
+            &wid2,
+            &repo,
+            URL,
+            || panic!("réutilisation"),
+            &crate::work_repo::RepoDocumentAdapter::new(&k),
+            pkg_fn(&works, &k),
+        );
+        assert_eq!(
+            res.err(),
+            Some(PublishError::SourcePdfDoesNotMatchCertifiedDocument)
+        );
+        cleanup(&base);
+    }
+
+    /// Adaptateur qui déclare un sidecar mais ne l'écrit pas.
+    struct NoSidecarAdapter;
+    impl DocumentAdapter for NoSidecarAdapter {
+        fn adapter_name(&self) -> &'static str {
+            "no-sidecar"
+        }
+        fn artifact_kind(&self) -> DocumentArtifactKind {
+            DocumentArtifactKind::Image
+        }
+        fn default_artifact_filename(&self) -> &'static str {
+            "labeled_image.png"
+        }
+        fn render_labeled_artifact(
+            &self,
+            _request: &LabeledArtifactRequest,
+            _source: &Path,
+            output_dir: &Path,
+        ) -> Result<RenderedArtifactRef, ArtifactRenderError> {
+            fs::write(output_dir.join("labeled_image.png"), b"PNG fake").unwrap();
+            Ok(RenderedArtifactRef {
+                artifact_name: "labeled_image.png".to_string(),
+                media_type: DocumentArtifactKind::Image,
+                fingerprint_sha256: Some(sha_hex(b"PNG fake")),
+                size_bytes: Some(8),
+                mime_type: Some("image/png".to_string()),
+            })
+        }
+        fn sidecar_filename(&self) -> Option<&'static str> {
+            Some("labeled_image.ho.json")
+        }
+    }
+
+    #[test]
+    fn test_24_sidecar_declare_absent_aucun_package() {
+        let base = temp_base();
+        let works = base.join("Works");
+        let wid = WorkId::new();
+        let k = key();
+        let (src, h) = write_source_pdf(&base, b"SOURCE");
+        write_cert(&works, &wid, &build_cert(&k, &wid, 1, &h));
+
+        let res = create_labeled_package_inner(
+            &works,
+            &wid,
+            &src,
+            URL,
+            || panic!("réutilisation"),
+            &NoSidecarAdapter,
+            pkg_fn(&works, &k),
+        );
+        assert_eq!(res.err(), Some(PublishError::PdfNotProduced));
+        assert!(!package_dir(&works, &wid, 1).exists());
+        assert!(no_leftover_temp(&works, &wid));
         cleanup(&base);
     }
+
+    #[test]
+    fn test_29_job_porte_conformance_et_politique_signatures() {
+        let job = publication_job(
+            Path::new("/tmp/source.pdf"),
+            Path::new("/tmp/labeled_document.pdf"),
+            Path::new("/tmp/cartouche.png"),
+            "cert-1",
+            "https://humanorigin.io/verify/cert-1",
+            "human",
+            PdfConformance::Standard,
+            SignaturePolicy::Preserve,
+        );
+        let parsed = crate::publication_core::parse_publication_job(job).unwrap();
+        assert_eq!(parsed.render.signature_policy, SignaturePolicy::Preserve);
+        assert_eq!(parsed.render.conformance, PdfConformance::Standard);
+
+        // Le code d'erreur remonte jusqu'à l'UI (préfixe du message).
+        let refused = serde_json::json!({
+            "ok": false,
+            "error_code": "SOURCE_PDF_SIGNED",
+            "message": "Source PDF carries 1 signature(s)"
+        });
+        assert_eq!(
+            publication_failure(&refused),
+            "SOURCE_PDF_SIGNED: Source PDF carries 1 signature(s)"
+        );
+        assert_eq!(
+            publication_failure(&serde_json::json!({ "ok": false })),
+            "échec publication PDF"
+        );
+    }
 }
@@ -0,0 +1,195 @@
+//! work_repo — Adaptateur dépôt de code (V2 code) : liste d'arbre + sidecar.
+//!
+//! Portée STRICTE : un dépôt ne se « labellise » pas (aucune cartouche dans le
+//! code livré). L'artefact du package est la liste d'arbre `repo_tree`
+//! (`labeled_repository.json` : chemins RELATIFS, SHA256, tailles, empreinte),
+//! qui permet à un relecteur de confronter le dépôt reçu à l'état certifié ;
+//! le sidecar `labeled_repository.ho.json` (`work_sidecar`) signe le lien
+//! certificat <-> empreinte d'arbre <-> liste.
+//!
+//! Le dépôt n'est JAMAIS modifié (aucun fichier écrit dedans : l'empreinte
+//! changerait). AUCUNE commande Tauri (voir `work_publish`), AUCUNE UI,
+//! AUCUN appel à `git`, AUCUN chemin absolu dans la liste ou le sidecar.
+//!
+//! Garde-fou : l'arbre relu au rendu doit avoir l'empreinte de la requête
+//! (dépôt modifié entre le contrôle du flux et le rendu -> échec).
+
+// Fondation (V2 code) : branché par `work_publish::create_native_repo_package_core`.
+#![allow(dead_code)]
+
+use ed25519_dalek::SigningKey;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+use crate::evidence_kernel::{
+    ArtifactRenderError, DocumentAdapter, DocumentArtifactKind, LabeledArtifactRequest,
+    RenderedArtifactRef,
+};
+use crate::repo_tree;
+use crate::work_sidecar;
+
+const LISTING_FILENAME: &str = "labeled_repository.json";
+const SIDECAR_FILENAME: &str = "labeled_repository.ho.json";
+const LISTING_MIME: &str = "application/json";
+
+/// Adaptateur dépôt : signe le sidecar avec la clé du certificat.
+pub(crate) struct RepoDocumentAdapter<'a> {
+    signing_key: &'a SigningKey,
+}
+
+impl<'a> RepoDocumentAdapter<'a> {
+    pub(crate) fn new(signing_key: &'a SigningKey) -> Self {
+        Self { signing_key }
+    }
+}
+
+impl DocumentAdapter for RepoDocumentAdapter<'_> {
+    fn adapter_name(&self) -> &'static str {
+        "repository"
+    }
+
+    fn artifact_kind(&self) -> DocumentArtifactKind {
+        DocumentArtifactKind::Code
+    }
+
+    fn default_artifact_filename(&self) -> &'static str {
+        LISTING_FILENAME
+    }
+
+    fn render_labeled_artifact(
+        &self,
+        request: &LabeledArtifactRequest,
+        source: &Path,
+        output_dir: &Path,
+    ) -> Result<RenderedArtifactRef, ArtifactRenderError> {
+        let listing =
+            repo_tree::repo_tree_listing(source).map_err(ArtifactRenderError::RenderFailed)?;
+        if listing.tree_sha256 != request.source_version.fingerprint_sha256 {
+            return Err(ArtifactRenderError::RenderFailed(
+                "dépôt modifié depuis le contrôle d'empreinte".to_string(),
+            ));
+        }
+        let bytes = serde_json::to_vec_pretty(&listing)
+            .map_err(|e| ArtifactRenderError::RenderFailed(e.to_string()))?;
+        fs::write(output_dir.join(LISTING_FILENAME), &bytes)
+            .map_err(|e| ArtifactRenderError::RenderFailed(e.to_string()))?;
+
+        let artifact = RenderedArtifactRef {
+            artifact_name: LISTING_FILENAME.to_string(),
+            media_type: self.artifact_kind(),
+            fingerprint_sha256: Some(format!("{:x}", Sha256::digest(&bytes))),
+            size_bytes: Some(bytes.len() as u64),
+            mime_type: Some(LISTING_MIME.to_string()),
+        };
+
+        let mut sidecar_request = request.clone();
+        sidecar_request.source_version.size_bytes = Some(listing.total_bytes);
+        sidecar_request.source_version.mime_type = Some(repo_tree::REPO_MIME.to_string());
+        let sidecar =
+            work_sidecar::sign_sidecar(&sidecar_request, Some(artifact.clone()), self.signing_key)
+                .map_err(ArtifactRenderError::RenderFailed)?;
+        work_sidecar::write_sidecar(&sidecar, &output_dir.join(SIDECAR_FILENAME))
+            .map_err(ArtifactRenderError::RenderFailed)?;
+        Ok(artifact)
+    }
+
+    fn sidecar_filename(&self) -> Option<&'static str> {
+        Some(SIDECAR_FILENAME)
+    }
+}
+
+// --- TESTS UNITAIRES ----------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evidence_kernel::{MediaKind, ObjectVersionRef, ObservedObjectRef};
+    use base64::{engine::general_purpose, Engine as _};
+    use rand_core::OsRng;
+    use uuid::Uuid;
+
+    fn tmp_repo() -> std::path::PathBuf {
+        let d = std::env::temp_dir().join(format!("ho_work_repo_test_{}", Uuid::new_v4()));
+        fs::create_dir_all(d.join(".git")).unwrap();
+        fs::write(d.join("main.py"), b"print('ok')\n").unwrap();
+        d
+    }
+
+    fn cleanup(p: &Path) {
+        let _ = fs::remove_dir_all(p);
+    }
+
+    fn request(k: &SigningKey, tree_sha256: &str) -> LabeledArtifactRequest {
+        let pk = general_purpose::STANDARD.encode(k.verifying_key().to_bytes());
+        LabeledArtifactRequest {
+            source_object: ObservedObjectRef::new("work-repo", MediaKind::Code),
+            source_version: ObjectVersionRef {
+                fingerprint_sha256: tree_sha256.to_string(),
+                size_bytes: None,
+                mime_type: None,
+                chunked_fingerprint: None,
+                media_specific: None,
+            },
+            certificate_id: "cert-repo-1".to_string(),
+            verify_url: "https://verify.humanorigin.app/r/repo".to_string(),
+            verdict_label: "OBSERVED_WORK_CONSISTENT".to_string(),
+            signing_key_id: Some(format!("{:x}", Sha256::digest(pk.as_bytes()))),
+            certificate_created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_1_liste_et_sidecar_signes_depot_intact() {
+        let repo = tmp_repo();
+        let out = std::env::temp_dir().join(format!("ho_work_repo_out_{}", Uuid::new_v4()));
+        fs::create_dir_all(&out).unwrap();
+        let (tree, _) = repo_tree::repo_tree_digest(&repo).unwrap();
+        let k = SigningKey::generate(&mut OsRng);
+
+        let a = RepoDocumentAdapter::new(&k);
+        let art = a
+            .render_labeled_artifact(&request(&k, &tree), &repo, &out)
+            .unwrap();
+        assert_eq!(art.artifact_name, LISTING_FILENAME);
+        assert_eq!(art.media_type, DocumentArtifactKind::Code);
+
+        let listing: repo_tree::RepoTreeListing =
+            serde_json::from_slice(&fs::read(out.join(LISTING_FILENAME)).unwrap()).unwrap();
+        assert!(repo_tree::verify_listing(&listing).is_ok());
+        assert_eq!(listing.tree_sha256, tree);
+        let json = fs::read_to_string(out.join(LISTING_FILENAME)).unwrap();
+        assert!(
+            !json.contains(&*repo.to_string_lossy()),
+            "chemin absolu exposé"
+        );
+
+        let sidecar = work_sidecar::read_sidecar(&out.join(SIDECAR_FILENAME)).unwrap();
+        assert!(work_sidecar::verify_sidecar(&sidecar).is_ok());
+        assert_eq!(sidecar.source_version.fingerprint_sha256, tree);
+        assert_eq!(
+            sidecar.source_version.mime_type.as_deref(),
+            Some(repo_tree::REPO_MIME)
+        );
+        // Rien n'a été écrit dans le dépôt.
+        assert_eq!(repo_tree::repo_tree_digest(&repo).unwrap().0, tree);
+        cleanup(&repo);
+        cleanup(&out);
+    }
+
+    #[test]
+    fn test_2_depot_modifie_avant_rendu_refuse() {
+        let repo = tmp_repo();
+        let (tree, _) = repo_tree::repo_tree_digest(&repo).unwrap();
+        fs::write(repo.join("main.py"), b"print('change')\n").unwrap();
+        let out = std::env::temp_dir().join(format!("ho_work_repo_out_{}", Uuid::new_v4()));
+        fs::create_dir_all(&out).unwrap();
+        let k = SigningKey::generate(&mut OsRng);
+        let res =
+            RepoDocumentAdapter::new(&k).render_labeled_artifact(&request(&k, &tree), &repo, &out);
+        assert!(matches!(res, Err(ArtifactRenderError::RenderFailed(_))));
+        assert!(!out.join(SIDECAR_FILENAME).exists());
+        cleanup(&repo);
+        cleanup(&out);
+    }
+}
@@ -0,0 +1,267 @@
+//! work_sidecar — Sidecar `.ho.json` signé, posé à côté d'un fichier livré (V2-B).
+//!
+//! Portée STRICTE : décrire, sans le package, le lien entre un fichier livré
+//! (copie labellisée ou original intact) et son certificat Work :
+//! `certificate_id`, `verify_url`, verdict, objet/version source (empreinte)
+//! et, s'il existe, l'artefact labellisé (SHA256, taille, MIME). Le sidecar est
+//! signé Ed25519 par la clé device du certificat (corps HO-CANON-V1 hors
+//! `signature`), donc vérifiable seul.
+//!
+//! Media-agnostic : produit par les adaptateurs (`work_image`, …), embarqué par
+//! `work_package` (`files.labeled_sidecar`). AUCUN chemin local, AUCUN contenu
+//! média, AUCUNE commande Tauri, AUCUNE UI, AUCUN réseau.
+//!
+//! Garde-fous :
+//! - la clé signataire doit être CELLE du certificat (`signing_key_id` de la
+//!   requête, si fourni) ; `signing_key_id == SHA256(public_key)` ;
+//! - `verify_sidecar` refuse toute modification du corps signé ;
+//! - un `labeled_artifact` embarqué porte TOUJOURS son SHA256.
+
+// Fondation (V2-B) : consommé par l'adaptateur image et le package.
+#![allow(dead_code)]
+
+use base64::{engine::general_purpose, Engine as _};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+use crate::evidence_kernel::{
+    LabeledArtifactRequest, ObjectVersionRef, ObservedObjectRef, RenderedArtifactRef,
+};
+use crate::work_period;
+
+pub(crate) const SIDECAR_SCHEMA_VERSION: u32 = 1;
+pub(crate) const SIDECAR_FORMAT: &str = "humanorigin-work-sidecar";
+pub(crate) const SIDECAR_SUFFIX: &str = ".ho.json";
+
+const SIGN_ALG: &str = "ed25519";
+const LOCAL_DEVICE_IDENTITY: &str = "LOCAL_DEVICE";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SidecarSignatureMetadata {
+    pub signature_algorithm: String,
+    pub public_key: String,
+    pub signing_key_id: String,
+    pub identity_status: String,
+    pub schema_version: u32,
+}
+
+/// Sidecar signé : path-free, lisible par un tiers sans le package.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WorkSidecar {
+    pub schema_version: u32,
+    pub format: String,
+    pub certificate_id: String,
+    pub verify_url: String,
+    pub verdict: String,
+    pub source_object: ObservedObjectRef,
+    pub source_version: ObjectVersionRef,
+    /// Copie labellisée décrite par ce sidecar. Absente si l'original est livré
+    /// intact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labeled_artifact: Option<RenderedArtifactRef>,
+    pub signature_metadata: SidecarSignatureMetadata,
+    pub signature: String,
+}
+
+/// Nom du sidecar d'un fichier de radical `stem` (ex. `labeled_image.ho.json`).
+pub(crate) fn sidecar_filename(stem: &str) -> String {
+    format!("{stem}{SIDECAR_SUFFIX}")
+}
+
+fn sha256_hex_str(s: &str) -> String {
+    format!("{:x}", Sha256::digest(s.as_bytes()))
+}
+
+fn decode_b64_fixed<const N: usize>(s: &str) -> Result<[u8; N], String> {
+    let v = general_purpose::STANDARD
+        .decode(s)
+        .map_err(|e| format!("base64 invalide : {e}"))?;
+    v.try_into()
+        .map_err(|_| format!("longueur inattendue (attendu {N} octets)"))
+}
+
+/// Corps canonique signé (HO-CANON-V1, exclut UNIQUEMENT `signature`).
+fn sidecar_body_digest(sidecar: &WorkSidecar) -> Result<[u8; 32], String> {
+    let body = work_period::canonical_bytes_excluding(sidecar, &["signature"])?;
+    Ok(Sha256::digest(&body).into())
+}
+
+/// Construit et signe le sidecar de `request` (+ copie labellisée éventuelle).
+pub(crate) fn sign_sidecar(
+    request: &LabeledArtifactRequest,
+    labeled_artifact: Option<RenderedArtifactRef>,
+    signing_key: &SigningKey,
+) -> Result<WorkSidecar, String> {
+    let public_key = general_purpose::STANDARD.encode(signing_key.verifying_key().to_bytes());
+    let signing_key_id = sha256_hex_str(&public_key);
+    if let Some(expected) = &request.signing_key_id {
+        if *expected != signing_key_id {
+            return Err("clé du sidecar ≠ clé du certificat".to_string());
+        }
+    }
+    if let Some(a) = &labeled_artifact {
+        if a.fingerprint_sha256.is_none() {
+            return Err("labeled_artifact sans SHA256".to_string());
+        }
+    }
+    let mut sidecar = WorkSidecar {
+        schema_version: SIDECAR_SCHEMA_VERSION,
+        format: SIDECAR_FORMAT.to_string(),
+        certificate_id: request.certificate_id.clone(),
+        verify_url: request.verify_url.clone(),
+        verdict: request.verdict_label.clone(),
+        source_object: request.source_object.clone(),
+        source_version: request.source_version.clone(),
+        labeled_artifact,
+        signature_metadata: SidecarSignatureMetadata {
+            signature_algorithm: SIGN_ALG.to_string(),
+            public_key,
+            signing_key_id,
+            identity_status: LOCAL_DEVICE_IDENTITY.to_string(),
+            schema_version: SIDECAR_SCHEMA_VERSION,
+        },
+        signature: String::new(),
+    };
+    let digest = sidecar_body_digest(&sidecar)?;
+    sidecar.signature = general_purpose::STANDARD.encode(signing_key.sign(&digest).to_bytes());
+    Ok(sidecar)
+}
+
+/// Vérifie format, identité, `signing_key_id` et signature Ed25519 du sidecar.
+pub(crate) fn verify_sidecar(sidecar: &WorkSidecar) -> Result<(), String> {
+    if sidecar.format != SIDECAR_FORMAT {
+        return Err(format!("format de sidecar inattendu : {}", sidecar.format));
+    }
+    let meta = &sidecar.signature_metadata;
+    if meta.signature_algorithm != SIGN_ALG {
+        return Err(format!(
+            "algorithme inattendu : {}",
+            meta.signature_algorithm
+        ));
+    }
+    if meta.identity_status != LOCAL_DEVICE_IDENTITY {
+        return Err(format!("identité inattendue : {}", meta.identity_status));
+    }
+    if meta.signing_key_id != sha256_hex_str(&meta.public_key) {
+        return Err("signing_key_id incohérent avec la clé publique".to_string());
+    }
+    if let Some(a) = &sidecar.labeled_artifact {
+        if a.fingerprint_sha256.is_none() {
+            return Err("labeled_artifact sans SHA256".to_string());
+        }
+    }
+    let digest = sidecar_body_digest(sidecar)?;
+    let vk = VerifyingKey::from_bytes(&decode_b64_fixed::<32>(&meta.public_key)?)
+        .map_err(|e| e.to_string())?;
+    let sig = Signature::from_bytes(&decode_b64_fixed::<64>(&sidecar.signature)?);
+    vk.verify_strict(&digest, &sig)
+        .map_err(|_| "signature de sidecar invalide".to_string())
+}
+
+/// Écrit le sidecar (JSON indenté) dans `path`.
+pub(crate) fn write_sidecar(sidecar: &WorkSidecar, path: &Path) -> Result<(), String> {
+    let bytes = serde_json::to_vec_pretty(sidecar).map_err(|e| e.to_string())?;
+    fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+/// Lit et parse un sidecar (sans le vérifier).
+pub(crate) fn read_sidecar(path: &Path) -> Result<WorkSidecar, String> {
+    let raw = fs::read(path).map_err(|e| format!("sidecar illisible : {e}"))?;
+    serde_json::from_slice(&raw).map_err(|e| format!("sidecar invalide : {e}"))
+}
+
+// --- TESTS UNITAIRES ----------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evidence_kernel::{DocumentArtifactKind, MediaKind};
+    use rand_core::OsRng;
+
+    fn key() -> SigningKey {
+        SigningKey::generate(&mut OsRng)
+    }
+
+    fn request(k: &SigningKey) -> LabeledArtifactRequest {
+        let public_key = general_purpose::STANDARD.encode(k.verifying_key().to_bytes());
+        LabeledArtifactRequest {
+            source_object: ObservedObjectRef::new("work-1", MediaKind::Image),
+            source_version: ObjectVersionRef {
+                fingerprint_sha256: "a".repeat(64),
+                size_bytes: Some(42),
+                mime_type: None,
+                chunked_fingerprint: None,
+                media_specific: None,
+            },
+            certificate_id: "cert-1".to_string(),
+            verify_url: "https://verify.humanorigin.app/r/x".to_string(),
+            verdict_label: "OBSERVED_WORK_CONSISTENT".to_string(),
+            signing_key_id: Some(sha256_hex_str(&public_key)),
+            certificate_created_at: None,
+        }
+    }
+
+    fn artifact() -> RenderedArtifactRef {
+        RenderedArtifactRef {
+            artifact_name: "labeled_image.png".to_string(),
+            media_type: DocumentArtifactKind::Image,
+            fingerprint_sha256: Some("b".repeat(64)),
+            size_bytes: Some(100),
+            mime_type: Some("image/png".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_1_sidecar_signe_verifiable() {
+        let k = key();
+        let s = sign_sidecar(&request(&k), Some(artifact()), &k).unwrap();
+        assert_eq!(s.format, SIDECAR_FORMAT);
+        assert_eq!(s.certificate_id, "cert-1");
+        assert!(verify_sidecar(&s).is_ok());
+    }
+
+    #[test]
+    fn test_2_corps_modifie_refuse() {
+        let k = key();
+        let s = sign_sidecar(&request(&k), Some(artifact()), &k).unwrap();
+        let mut url = s.clone();
+        url.verify_url = "https://ailleurs.example/r/x".to_string();
+        assert!(verify_sidecar(&url).is_err());
+        let mut art = s.clone();
+        art.labeled_artifact.as_mut().unwrap().fingerprint_sha256 = Some("c".repeat(64));
+        assert!(verify_sidecar(&art).is_err());
+    }
+
+    #[test]
+    fn test_3_cle_differente_du_certificat_refusee() {
+        let k = key();
+        let other = key();
+        assert!(sign_sidecar(&request(&k), None, &other).is_err());
+    }
+
+    #[test]
+    fn test_4_sans_copie_labellisee_champ_absent() {
+        let k = key();
+        let s = sign_sidecar(&request(&k), None, &k).unwrap();
+        let json = serde_json::to_string(&s).unwrap();
+        assert!(!json.contains("labeled_artifact"));
+        assert!(!json.contains("null"));
+        assert!(verify_sidecar(&s).is_ok());
+    }
+
+    #[test]
+    fn test_5_ecriture_relecture_identique() {
+        let k = key();
+        let s = sign_sidecar(&request(&k), Some(artifact()), &k).unwrap();
+        let dir = std::env::temp_dir().join(format!("ho_sidecar_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(sidecar_filename("labeled_image"));
+        write_sidecar(&s, &path).unwrap();
+        assert_eq!(read_sidecar(&path).unwrap(), s);
+        assert!(path.to_string_lossy().ends_with("labeled_image.ho.json"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,261 @@
+//! work_snapshot — Version de DÉBUT du document, le temps d'une période.
+//!
+//! Portée STRICTE : conserver, chiffrée, la version du document au démarrage
+//! d'une période tant que son pending est ouvert, pour mesurer au stop
+//! l'ampleur du changement (`work_change`). Stockage :
+//! `Works/<work_id>/snapshots/start_<period_id>.bin`, même schéma AES-GCM que
+//! les drafts (`drafts::seal` / `drafts::open`, clé locale du Keychain) avec
+//! AAD = `work_id:period_id` (un snapshot ne peut pas être rejoué pour une
+//! autre période ou un autre Work).
+//!
+//! Garde-fous :
+//! - un snapshot ne survit pas à son pending : purgé à la suppression du
+//!   pending (période signée), à l'interruption et à l'abandon ;
+//! - formats mesurables uniquement (`work_change`), au plus
+//!   `MAX_SNAPSHOT_BYTES` : au-delà, aucun snapshot (période sans mesure) ;
+//! - les octets conservés doivent correspondre à `hash_start` (document
+//!   modifié entre le hash et la lecture -> aucun snapshot) ;
+//! - tout échec est non fatal pour la période (la mesure reste facultative).
+//!
+//! AUCUN contenu en clair sur disque, AUCUN contenu dans la période signée,
+//! AUCUNE commande Tauri, AUCUN accès `Projects/`.
+
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::drafts;
+use crate::work_change;
+use crate::work_store::WorkId;
+
+/// Taille maximale d'une version de départ conservée.
+pub(crate) const MAX_SNAPSHOT_BYTES: u64 = 64 * 1024 * 1024;
+
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+// --- CHEMINS ------------------------------------------------------------------
+
+fn ensure_valid_work_id(work_id: &WorkId) -> Result<(), String> {
+    if !work_id.is_valid() {
+        return Err(format!(
+            "WorkId invalide (UUID attendu) : {:?}",
+            work_id.as_str()
+        ));
+    }
+    Ok(())
+}
+
+fn ensure_valid_ids(work_id: &WorkId, period_id: &str) -> Result<(), String> {
+    ensure_valid_work_id(work_id)?;
+    // Le period_id entre dans un nom de fichier : UUID strict.
+    if uuid::Uuid::parse_str(period_id).is_err() {
+        return Err(format!(
+            "period_id invalide (UUID attendu) : {:?}",
+            period_id
+        ));
+    }
+    Ok(())
+}
+
+fn snapshots_dir(works_root: &Path, work_id: &WorkId) -> PathBuf {
+    works_root.join(work_id.as_str()).join(SNAPSHOTS_DIR)
+}
+
+fn snapshot_path(works_root: &Path, work_id: &WorkId, period_id: &str) -> PathBuf {
+    snapshots_dir(works_root, work_id).join(format!("start_{period_id}.bin"))
+}
+
+fn associated_data(work_id: &WorkId, period_id: &str) -> Vec<u8> {
+    format!("{}:{}", work_id.as_str(), period_id).into_bytes()
+}
+
+#[cfg(not(test))]
+fn snapshot_key() -> Result<[u8; drafts::KEY_LEN], String> {
+    drafts::local_key()
+}
+
+// Keychain indisponible en test : clé fixe, jamais compilée en prod.
+#[cfg(test)]
+fn snapshot_key() -> Result<[u8; drafts::KEY_LEN], String> {
+    Ok([0x5a; drafts::KEY_LEN])
+}
+
+// --- API ----------------------------------------------------------------------
+
+/// Lit la version de départ du document si elle peut être conservée : format
+/// mesurable, taille bornée, octets identiques à `hash_start`. `None` sinon.
+pub(crate) fn read_start_version(document_path: &Path, hash_start: &str) -> Option<Vec<u8>> {
+    if !work_change::is_measurable(document_path) {
+        return None;
+    }
+    let file = fs::File::open(document_path).ok()?;
+    let mut bytes = Vec::new();
+    // Lecture bornée : un octet de plus que la limite suffit à la détecter.
+    file.take(MAX_SNAPSHOT_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .ok()?;
+    if bytes.len() as u64 > MAX_SNAPSHOT_BYTES {
+        return None;
+    }
+    if format!("{:x}", Sha256::digest(&bytes)) != hash_start {
+        return None;
+    }
+    Some(bytes)
+}
+
+/// Chiffre et écrit la version de départ d'une période (remplace un éventuel
+/// snapshot de la même période). Écriture via temporaire + `rename`.
+pub(crate) fn write_start_snapshot(
+    works_root: &Path,
+    work_id: &WorkId,
+    period_id: &str,
+    bytes: &[u8],
+) -> Result<PathBuf, String> {
+    ensure_valid_ids(work_id, period_id)?;
+    let key = snapshot_key()?;
+    let sealed = drafts::seal(&key, &associated_data(work_id, period_id), bytes)?;
+
+    let dir = snapshots_dir(works_root, work_id);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = snapshot_path(works_root, work_id, period_id);
+    let tmp = dir.join(format!(".start_{}.tmp-{}", period_id, uuid::Uuid::new_v4()));
+    let res = (|| -> Result<(), String> {
+        let mut f = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp)
+            .map_err(|e| e.to_string())?;
+        f.write_all(&sealed).map_err(|e| e.to_string())?;
+        f.sync_all().map_err(|e| e.to_string())?;
+        fs::rename(&tmp, &path).map_err(|e| e.to_string())
+    })();
+    if let Err(e) = res {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    Ok(path)
+}
+
+/// Déchiffre la version de départ d'une période. `Ok(None)` si aucun snapshot ;
+/// erreur si le fichier est altéré ou appartient à une autre période.
+pub(crate) fn read_start_snapshot(
+    works_root: &Path,
+    work_id: &WorkId,
+    period_id: &str,
+) -> Result<Option<Vec<u8>>, String> {
+    ensure_valid_ids(work_id, period_id)?;
+    let path = snapshot_path(works_root, work_id, period_id);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let sealed = fs::read(&path).map_err(|e| e.to_string())?;
+    let key = snapshot_key()?;
+    drafts::open(&key, &associated_data(work_id, period_id), &sealed).map(Some)
+}
+
+/// Purge TOUS les snapshots d'un Work (un seul pending actif par Work : tout
+/// snapshot restant est périmé). Idempotente.
+pub(crate) fn purge_start_snapshots(works_root: &Path, work_id: &WorkId) -> Result<(), String> {
+    ensure_valid_work_id(work_id)?;
+    let dir = snapshots_dir(works_root, work_id);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// --- TESTS --------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_root() -> PathBuf {
+        std::env::temp_dir().join(format!("ho_snapshot_test_{}", Uuid::new_v4()))
+    }
+
+    fn cleanup(root: &Path) {
+        let _ = fs::remove_dir_all(root);
+    }
+
+    fn sha_hex(bytes: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(bytes))
+    }
+
+    #[test]
+    fn test_1_aller_retour_chiffre() {
+        let root = temp_root();
+        let wid = WorkId::new();
+        let pid = Uuid::new_v4().to_string();
+        let content = b"Texte de depart tres reconnaissable";
+        let path = write_start_snapshot(&root, &wid, &pid, content).unwrap();
+
+        let on_disk = fs::read(&path).unwrap();
+        assert!(!on_disk
+            .windows(content.len())
+            .any(|w| w == content.as_slice()));
+        assert_eq!(
+            read_start_snapshot(&root, &wid, &pid).unwrap(),
+            Some(content.to_vec())
+        );
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_2_snapshot_d_une_autre_periode_refuse() {
+        let root = temp_root();
+        let wid = WorkId::new();
+        let (pid_a, pid_b) = (Uuid::new_v4().to_string(), Uuid::new_v4().to_string());
+        let path_a = write_start_snapshot(&root, &wid, &pid_a, b"version A").unwrap();
+        // Substitution du fichier : l'AAD ne correspond plus.
+        fs::rename(&path_a, snapshot_path(&root, &wid, &pid_b)).unwrap();
+        assert!(read_start_snapshot(&root, &wid, &pid_b).is_err());
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_3_absent_puis_purge_idempotente() {
+        let root = temp_root();
+        let wid = WorkId::new();
+        let pid = Uuid::new_v4().to_string();
+        assert_eq!(read_start_snapshot(&root, &wid, &pid).unwrap(), None);
+
+        write_start_snapshot(&root, &wid, &pid, b"x").unwrap();
+        purge_start_snapshots(&root, &wid).unwrap();
+        assert!(!snapshots_dir(&root, &wid).exists());
+        assert_eq!(read_start_snapshot(&root, &wid, &pid).unwrap(), None);
+        purge_start_snapshots(&root, &wid).unwrap();
+        cleanup(&root);
+    }
+
+    #[test]
+    fn test_4_identifiants_invalides_refuses() {
+        let root = temp_root();
+        let wid = WorkId::new();
+        assert!(write_start_snapshot(&root, &wid, "../evasion", b"x").is_err());
+        let bad = WorkId("../evasion".to_string());
+        assert!(write_start_snapshot(&root, &bad, &Uuid::new_v4().to_string(), b"x").is_err());
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn test_5_version_de_depart_conforme_au_hash() {
+        let root = temp_root();
+        fs::create_dir_all(&root).unwrap();
+        let txt = root.join("doc.txt");
+        fs::write(&txt, b"contenu").unwrap();
+        assert_eq!(
+            read_start_version(&txt, &sha_hex(b"contenu")),
+            Some(b"contenu".to_vec())
+        );
+        // Document modifié depuis le hash de départ.
+        assert_eq!(read_start_version(&txt, &sha_hex(b"autre")), None);
+        // Format non mesurable : rien n'est conservé.
+        let pdf = root.join("doc.pdf");
+        fs::write(&pdf, b"contenu").unwrap();
+        assert_eq!(read_start_version(&pdf, &sha_hex(b"contenu")), None);
+        cleanup(&root);
+    }
+}
@@ -23,6 +23,8 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+use crate::document_set::DocumentSetSpec;
+
 /// Version de schéma du cache et des métadonnées Work.
 pub const WORK_SCHEMA_VERSION: u32 = 1;
 
@@ -105,6 +107,10 @@ pub struct WorkDocumentMetadata {
     /// Signaux d'identité de fichier pour la résolution de reprise (ultérieur).
     pub inode: Option<u64>,
     pub device_id: Option<u64>,
+    /// Work multi-fichiers : définition de l'ensemble (`document_set`). Le
+    /// hash est alors l'empreinte de manifeste. Absent : document unique.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_set: Option<DocumentSetSpec>,
 }
 
 /// Métadonnées locales éditables et NON signées (nom affiché, tags, notes).
@@ -129,6 +135,11 @@ pub struct WorkRecord {
     pub last_activity_utc: String,
     pub document: WorkDocumentMetadata,
     pub local_metadata: WorkLocalMetadata,
+    /// Réglage : schéma de canonicalisation des NOUVELLES périodes et
+    /// certificats (`HO-CANON-V1` | `HO-JCS-V1`). Absent = défaut
+    /// (`work_period::DEFAULT_CANON_SCHEME`) ; chaque record porte le sien.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonicalization: Option<String>,
 }
 
 impl WorkRecord {
@@ -142,6 +153,7 @@ impl WorkRecord {
             last_activity_utc: now_utc,
             document,
             local_metadata: WorkLocalMetadata::default(),
+            canonicalization: None,
         }
     }
 }
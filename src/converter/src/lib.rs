@@ -0,0 +1,690 @@
+//! humanorigin-converter — conversion d'un document source en PDF intermédiaire.
+//!
+//! Bibliothèque : `convert(input, output_dir, options)` renvoie un résultat
+//! typé (serde). Le binaire `humanorigin-converter` n'est qu'une enveloppe CLI
+//! qui imprime ce résultat en JSON ; l'app l'appelle en process quand
+//! LibreOffice est disponible, le sidecar reste le repli.
+//!
+//! Portée STRICTE :
+//! - formats bureautiques (.docx/.odt/.rtf/.doc) : LibreOffice headless isolé ;
+//! - formats texte (.md/.html/.txt) : rendu natif, sans LibreOffice ; texte
+//!   hors WinAnsi (grec, cyrillique, CJK, emoji…) : mêmes blocs réécrits en
+//!   HTML UTF-8 puis rendus par LibreOffice (polices embarquées) ;
+//! - `<stem>.conversion_record.json` écrit à côté de chaque `<stem>.pdf` produit.
+//!
+//! AUCUN label, AUCUNE cartouche, AUCUN certificat : le PDF produit n'est
+//! qu'un rendu, la preuve est construite en aval.
+
+mod libreoffice;
+mod markup;
+mod record;
+mod text_pdf;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+pub use record::{ConversionRecord, EngineInfo, FileDigest, RECORD_VERSION};
+
+pub const ENGINE_LIBREOFFICE: &str = "libreoffice-headless";
+pub const CONVERTER_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const DEFAULT_TIMEOUT_SECS: u64 = libreoffice::DEFAULT_TIMEOUT_SECS;
+
+/// Réglages d'une conversion. `soffice` : `None` -> `find_soffice()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertOptions {
+    pub timeout: Duration,
+    pub soffice: Option<PathBuf>,
+}
+
+impl Default for ConvertOptions {
+    fn default() -> Self {
+        ConvertOptions {
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            soffice: None,
+        }
+    }
+}
+
+/// Conversion réussie : PDF produit + empreinte (`<stem>.conversion_record.json`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConversionResult {
+    pub engine: String,
+    pub engine_version: Option<String>,
+    pub intermediate_pdf_path: PathBuf,
+    pub source_sha256: String,
+    pub output_sha256: String,
+    pub conversion_record_path: PathBuf,
+    pub warnings: Vec<String>,
+}
+
+/// Échec typé. `exit_code` : code de sortie du CLI (non sérialisé).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConversionError {
+    pub engine: String,
+    pub error_code: String,
+    pub message: String,
+    #[serde(skip)]
+    pub exit_code: i32,
+}
+
+impl ConversionError {
+    pub fn new(engine: &str, error_code: &str, message: impl Into<String>, exit_code: i32) -> Self {
+        ConversionError {
+            engine: engine.to_string(),
+            error_code: error_code.to_string(),
+            message: message.into(),
+            exit_code,
+        }
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.error_code, self.message)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+// --- CONTRAT JSON DU CLI ---------------------------------------------------------
+
+#[derive(Serialize, Deserialize)]
+struct CliOutput {
+    ok: bool,
+    engine: String,
+    #[serde(default)]
+    engine_version: Option<String>,
+    intermediate_pdf_path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    conversion_record_path: Option<PathBuf>,
+    #[serde(default)]
+    warnings: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+/// Ligne JSON imprimée par le CLI sur stdout (`ok` true/false).
+pub fn render_cli_output(outcome: &Result<ConversionResult, ConversionError>) -> String {
+    let out = match outcome {
+        Ok(r) => CliOutput {
+            ok: true,
+            engine: r.engine.clone(),
+            engine_version: r.engine_version.clone(),
+            intermediate_pdf_path: Some(r.intermediate_pdf_path.clone()),
+            source_sha256: Some(r.source_sha256.clone()),
+            output_sha256: Some(r.output_sha256.clone()),
+            conversion_record_path: Some(r.conversion_record_path.clone()),
+            warnings: r.warnings.clone(),
+            error_code: None,
+            message: None,
+        },
+        Err(e) => CliOutput {
+            ok: false,
+            engine: e.engine.clone(),
+            engine_version: None,
+            intermediate_pdf_path: None,
+            source_sha256: None,
+            output_sha256: None,
+            conversion_record_path: None,
+            warnings: vec![],
+            error_code: Some(e.error_code.clone()),
+            message: Some(e.message.clone()),
+        },
+    };
+    serde_json::to_string(&out).unwrap_or_default()
+}
+
+/// Relit la sortie du CLI (sidecar). `Err(String)` : sortie illisible ou
+/// incomplète ; sinon le résultat typé de la conversion.
+pub fn parse_cli_output(stdout: &str) -> Result<Result<ConversionResult, ConversionError>, String> {
+    let out: CliOutput = serde_json::from_str(stdout.trim())
+        .map_err(|e| format!("invalid converter output: {e}"))?;
+    if !out.ok {
+        return Ok(Err(ConversionError {
+            engine: out.engine,
+            error_code: out.error_code.unwrap_or_default(),
+            message: out.message.unwrap_or_default(),
+            exit_code: 0,
+        }));
+    }
+    let missing = |field: &str| format!("converter output has no {field}");
+    Ok(Ok(ConversionResult {
+        engine: out.engine,
+        engine_version: out.engine_version,
+        intermediate_pdf_path: out
+            .intermediate_pdf_path
+            .ok_or_else(|| missing("intermediate_pdf_path"))?,
+        source_sha256: out.source_sha256.ok_or_else(|| missing("source_sha256"))?,
+        output_sha256: out.output_sha256.ok_or_else(|| missing("output_sha256"))?,
+        conversion_record_path: out
+            .conversion_record_path
+            .ok_or_else(|| missing("conversion_record_path"))?,
+        warnings: out.warnings,
+    }))
+}
+
+// --- FORMATS -------------------------------------------------------------------
+
+/// Formats bureautiques : LibreOffice. Formats texte : rendu natif, sans LibreOffice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputKind {
+    Office,
+    Markdown,
+    Html,
+    PlainText,
+}
+
+impl InputKind {
+    fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "docx" | "odt" | "rtf" | "doc" => Some(InputKind::Office),
+            "md" | "markdown" => Some(InputKind::Markdown),
+            "html" | "htm" => Some(InputKind::Html),
+            "txt" | "text" => Some(InputKind::PlainText),
+            _ => None,
+        }
+    }
+
+    /// Rendu natif : la version du moteur est celle du convertisseur.
+    fn native_options(self) -> BTreeMap<String, String> {
+        [
+            ("page_size", "A4"),
+            ("fonts", "standard14-winansi"),
+            ("layout", "text_pdf"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+    }
+
+    fn native_engine(self) -> &'static str {
+        match self {
+            InputKind::Office => ENGINE_LIBREOFFICE,
+            InputKind::Markdown => "native-markdown",
+            InputKind::Html => "native-html",
+            InputKind::PlainText => "native-text",
+        }
+    }
+}
+
+/// `true` si l'extension de `input` est prise en charge (LibreOffice ou natif).
+pub fn is_supported_input(input: &Path) -> bool {
+    InputKind::from_extension(&extension_lower(input)).is_some()
+}
+
+fn extension_lower(input: &Path) -> String {
+    input
+        .extension()
+        .and_then(OsStr::to_str)
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+pub fn find_soffice() -> Option<PathBuf> {
+    if let Ok(custom) = env::var("HUMANORIGIN_SOFFICE") {
+        let p = PathBuf::from(custom);
+        if p.exists() {
+            return Some(p);
+        }
+    }
+
+    let candidates = [
+        "/Applications/LibreOffice.app/Contents/MacOS/soffice",
+        "/usr/local/bin/soffice",
+        "/opt/homebrew/bin/soffice",
+        "/usr/bin/soffice",
+        "/usr/local/bin/libreoffice",
+        "/opt/homebrew/bin/libreoffice",
+    ];
+
+    for c in candidates {
+        let p = PathBuf::from(c);
+        if p.exists() {
+            return Some(p);
+        }
+    }
+
+    None
+}
+
+// --- CONVERSION ----------------------------------------------------------------
+
+/// Décode l'entrée texte : UTF-8 (BOM retiré), sinon Latin-1 avec avertissement.
+fn read_text_input(input: &Path, warnings: &mut Vec<String>) -> Result<String, String> {
+    let bytes = fs::read(input).map_err(|e| format!("Cannot read input file: {}", e))?;
+    let bytes = bytes.strip_prefix(b"\xef\xbb\xbf").unwrap_or(&bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Ok(s.to_string()),
+        Err(_) => {
+            warnings.push(String::from(
+                "Input is not valid UTF-8; decoded as Latin-1.",
+            ));
+            Ok(bytes.iter().map(|&b| b as char).collect())
+        }
+    }
+}
+
+/// Succès : calcule l'empreinte (source, sortie, moteur) et écrit
+/// `<stem>.conversion_record.json` à côté du PDF.
+fn finish(
+    input: &Path,
+    output_pdf: &Path,
+    engine: EngineInfo,
+    warnings: Vec<String>,
+) -> Result<ConversionResult, ConversionError> {
+    let name = engine.name.clone();
+    let record = ConversionRecord::build(input, output_pdf, engine)
+        .and_then(|record| record.write_next_to(output_pdf).map(|path| (record, path)));
+    match record {
+        Ok((record, record_path)) => Ok(ConversionResult {
+            engine: record.engine.name,
+            engine_version: record.engine.version,
+            intermediate_pdf_path: output_pdf.to_path_buf(),
+            source_sha256: record.source.sha256,
+            output_sha256: record.output.sha256,
+            conversion_record_path: record_path,
+            warnings,
+        }),
+        Err(e) => Err(ConversionError::new(
+            &name,
+            "RECORD_WRITE_FAILED",
+            format!("Cannot write conversion record: {}", e),
+            14,
+        )),
+    }
+}
+
+/// Texte hors WinAnsi : les blocs déjà lus sont réécrits en `<stem>.html`
+/// UTF-8 (dossier temporaire propre, supprimé ensuite) et rendus par
+/// LibreOffice vers `<output_dir>/<stem>.pdf`.
+fn render_blocks_with_libreoffice(
+    blocks: &[markup::Block],
+    output_dir: &Path,
+    stem: &str,
+    soffice: &Path,
+    timeout: Duration,
+) -> Result<libreoffice::Converted, ConversionError> {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let html_dir = env::temp_dir().join(format!(
+        "ho_convert_html_{}_{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let html = html_dir.join(format!("{}.html", stem));
+    let converted = fs::create_dir_all(&html_dir)
+        .and_then(|()| fs::write(&html, markup::blocks_to_html(blocks)))
+        .map_err(|e| {
+            ConversionError::new(
+                ENGINE_LIBREOFFICE,
+                "SCRATCH_DIR_ERROR",
+                format!("Cannot write HTML for LibreOffice: {}", e),
+                13,
+            )
+        })
+        .and_then(|()| {
+            libreoffice::convert(soffice, &html, output_dir, stem, timeout).map_err(|f| {
+                ConversionError::new(ENGINE_LIBREOFFICE, f.code, f.message, f.exit_code)
+            })
+        });
+    let _ = fs::remove_dir_all(&html_dir);
+    converted
+}
+
+fn convert_native(
+    kind: InputKind,
+    input: &Path,
+    output_dir: &Path,
+    stem: &str,
+    options: &ConvertOptions,
+) -> Result<ConversionResult, ConversionError> {
+    let engine = kind.native_engine();
+    let output_pdf = &output_dir.join(format!("{}.pdf", stem));
+    let mut warnings = Vec::new();
+
+    let raw = read_text_input(input, &mut warnings)
+        .map_err(|e| ConversionError::new(engine, "INPUT_READ_FAILED", e, 10))?;
+
+    let blocks = match kind {
+        InputKind::Markdown => markup::parse_markdown(&raw),
+        InputKind::Html => markup::parse_html(&raw),
+        _ => markup::parse_plain_text(&raw),
+    };
+    if blocks.is_empty() {
+        warnings.push(String::from(
+            "Input has no text content; produced a blank page.",
+        ));
+    }
+
+    let unsupported = text_pdf::unsupported_chars(&blocks);
+    if !unsupported.is_empty() {
+        let listed: Vec<String> = unsupported
+            .iter()
+            .take(8)
+            .map(|c| format!("'{}' (U+{:04X})", c, *c as u32))
+            .collect();
+        let Some(soffice) = options.soffice.clone().or_else(find_soffice) else {
+            return Err(ConversionError::new(
+                engine,
+                "UNSUPPORTED_CHARACTERS",
+                format!(
+                    "Input contains {} character(s) outside the WinAnsi set, which the native renderer cannot draw: {}; LibreOffice, used for such text, was not found.",
+                    unsupported.len(),
+                    listed.join(", ")
+                ),
+                15,
+            ));
+        };
+        warnings.push(format!(
+            "Input contains {} character(s) outside the WinAnsi set ({}); rendered by LibreOffice instead of the native renderer.",
+            unsupported.len(),
+            listed.join(", ")
+        ));
+        let converted =
+            render_blocks_with_libreoffice(&blocks, output_dir, stem, &soffice, options.timeout)?;
+        warnings.extend(converted.warnings);
+        // Le record garde la source d'origine ; les options disent d'où vient le HTML.
+        let mut engine_options = libreoffice::options();
+        engine_options.insert("input".to_string(), "markup-blocks-html-utf8".to_string());
+        engine_options.insert("fallback_from".to_string(), engine.to_string());
+        let info = EngineInfo {
+            name: ENGINE_LIBREOFFICE.to_string(),
+            version: converted.engine_version,
+            converter_version: CONVERTER_VERSION.to_string(),
+            options: engine_options,
+        };
+        return finish(input, &converted.pdf_path, info, warnings);
+    }
+
+    text_pdf::render_blocks(&blocks, output_pdf)
+        .map_err(|e| ConversionError::new(engine, "NATIVE_RENDER_FAILED", e, 11))?;
+    let info = EngineInfo {
+        name: engine.to_string(),
+        version: Some(CONVERTER_VERSION.to_string()),
+        converter_version: CONVERTER_VERSION.to_string(),
+        options: kind.native_options(),
+    };
+    finish(input, output_pdf, info, warnings)
+}
+
+/// Convertit `input` en `<output_dir>/<stem>.pdf` et écrit
+/// `<output_dir>/<stem>.conversion_record.json`.
+pub fn convert(
+    input: &Path,
+    output_dir: &Path,
+    options: &ConvertOptions,
+) -> Result<ConversionResult, ConversionError> {
+    if !input.exists() {
+        return Err(ConversionError::new(
+            ENGINE_LIBREOFFICE,
+            "INPUT_NOT_FOUND",
+            "Input document file not found.",
+            3,
+        ));
+    }
+
+    let Some(kind) = InputKind::from_extension(&extension_lower(input)) else {
+        return Err(ConversionError::new(
+            ENGINE_LIBREOFFICE,
+            "UNSUPPORTED_INPUT",
+            "Supported inputs: .docx, .odt, .rtf, .doc (LibreOffice) and .md, .html, .txt (native).",
+            4,
+        ));
+    };
+
+    if let Err(err) = fs::create_dir_all(output_dir) {
+        return Err(ConversionError::new(
+            kind.native_engine(),
+            "OUTPUT_DIR_ERROR",
+            format!("Cannot create output directory: {}", err),
+            5,
+        ));
+    }
+
+    let stem = input
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or("document");
+
+    if kind != InputKind::Office {
+        return convert_native(kind, input, output_dir, stem, options);
+    }
+
+    let Some(soffice) = options.soffice.clone().or_else(find_soffice) else {
+        return Err(ConversionError::new(
+            ENGINE_LIBREOFFICE,
+            "CONVERTER_ENGINE_NOT_FOUND",
+            "LibreOffice soffice was not found. Final product should embed or provide a signed local converter pack.",
+            6,
+        ));
+    };
+
+    let converted = libreoffice::convert(&soffice, input, output_dir, stem, options.timeout)
+        .map_err(|f| ConversionError::new(ENGINE_LIBREOFFICE, f.code, f.message, f.exit_code))?;
+    let info = EngineInfo {
+        name: ENGINE_LIBREOFFICE.to_string(),
+        version: converted.engine_version,
+        converter_version: CONVERTER_VERSION.to_string(),
+        options: libreoffice::options(),
+    };
+    finish(input, &converted.pdf_path, info, converted.warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn temp_base() -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let dir = env::temp_dir().join(format!(
+            "ho_converter_test_{}_{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn cleanup(p: &Path) {
+        let _ = fs::remove_dir_all(p);
+    }
+
+    /// `soffice` factice : `--version` imprime une version, sinon écrit
+    /// `<stem>.pdf` dans `--outdir` (ou bloque si `body` le demande).
+    #[cfg(unix)]
+    fn fake_soffice(base: &Path, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let script = base.join("soffice");
+        fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\n\
+                 for a in \"$@\"; do [ \"$a\" = \"--version\" ] && echo \"LibreOffice 7.6.4.1 test\" && exit 0; done\n\
+                 for a in \"$@\"; do [ \"$prev\" = \"--outdir\" ] && dir=$a; prev=$a; last=$a; done\n\
+                 stem=$(basename \"$last\"); stem=${{stem%.*}}\n\
+                 {body}\n"
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        script
+    }
+
+    #[test]
+    fn test_1_markdown_natif_pdf_et_record() {
+        let base = temp_base();
+        let input = base.join("notes.md");
+        fs::write(&input, "# Titre\n\nUn paragraphe.\n").unwrap();
+        let out = base.join("out");
+
+        let r = convert(&input, &out, &ConvertOptions::default()).unwrap();
+        assert_eq!(r.engine, "native-markdown");
+        assert_eq!(r.intermediate_pdf_path, out.join("notes.pdf"));
+        assert!(fs::read(&r.intermediate_pdf_path)
+            .unwrap()
+            .starts_with(b"%PDF-"));
+
+        let record: ConversionRecord =
+            serde_json::from_slice(&fs::read(&r.conversion_record_path).unwrap()).unwrap();
+        assert_eq!(record.source.sha256, r.source_sha256);
+        assert_eq!(record.output.sha256, r.output_sha256);
+        assert_eq!(record.engine.options["page_size"], "A4");
+        cleanup(&base);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_2_soffice_factice_version_et_options_enregistrees() {
+        let base = temp_base();
+        let soffice = fake_soffice(&base, "printf '%%PDF-1.7 fake' > \"$dir/$stem.pdf\"");
+        let input = base.join("Chapitre.docx");
+        fs::write(&input, b"PK fake docx").unwrap();
+        let out = base.join("out");
+        let options = ConvertOptions {
+            soffice: Some(soffice),
+            ..ConvertOptions::default()
+        };
+
+        let r = convert(&input, &out, &options).unwrap();
+        assert_eq!(r.engine, ENGINE_LIBREOFFICE);
+        assert_eq!(
+            r.engine_version.as_deref(),
+            Some("LibreOffice 7.6.4.1 test")
+        );
+        assert_eq!(
+            fs::read(out.join("Chapitre.pdf")).unwrap(),
+            b"%PDF-1.7 fake"
+        );
+        assert!(r.warnings.is_empty());
+
+        let record: ConversionRecord =
+            serde_json::from_slice(&fs::read(&r.conversion_record_path).unwrap()).unwrap();
+        assert_eq!(record.engine.options, libreoffice::options());
+        assert_eq!(record.source.filename, "Chapitre.docx");
+        cleanup(&base);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_3_soffice_bloque_conversion_interrompue() {
+        let base = temp_base();
+        let soffice = fake_soffice(&base, "sleep 30");
+        let input = base.join("Chapitre.docx");
+        fs::write(&input, b"PK fake docx").unwrap();
+        let out = base.join("out");
+        let options = ConvertOptions {
+            timeout: Duration::from_secs(1),
+            soffice: Some(soffice),
+        };
+
+        let err = convert(&input, &out, &options).unwrap_err();
+        assert_eq!(err.error_code, "CONVERSION_TIMEOUT");
+        assert_eq!(err.exit_code, 12);
+        assert!(!out.join("Chapitre.pdf").exists());
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_4_format_non_supporte_refuse() {
+        let base = temp_base();
+        let input = base.join("image.png");
+        fs::write(&input, b"png").unwrap();
+        let err = convert(&input, &base.join("out"), &ConvertOptions::default()).unwrap_err();
+        assert_eq!(err.error_code, "UNSUPPORTED_INPUT");
+        assert!(!is_supported_input(&input));
+        cleanup(&base);
+    }
+
+    #[test]
+    fn test_5_contrat_cli_aller_retour() {
+        let ok = ConversionResult {
+            engine: "native-text".to_string(),
+            engine_version: None,
+            intermediate_pdf_path: PathBuf::from("out/a \"b\".pdf"),
+            source_sha256: "aa".to_string(),
+            output_sha256: "bb".to_string(),
+            conversion_record_path: PathBuf::from("out/a.conversion_record.json"),
+            warnings: vec!["w\n1".to_string()],
+        };
+        let line = render_cli_output(&Ok(ok.clone()));
+        assert!(!line.contains('\n'));
+        assert_eq!(parse_cli_output(&line).unwrap(), Ok(ok));
+
+        let err = ConversionError::new(ENGINE_LIBREOFFICE, "PDF_NOT_CREATED", "none", 9);
+        let line = render_cli_output(&Err(err));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["ok"], false);
+        assert!(value["intermediate_pdf_path"].is_null());
+        match parse_cli_output(&line).unwrap() {
+            Err(e) => assert_eq!(
+                (e.error_code.as_str(), e.message.as_str()),
+                ("PDF_NOT_CREATED", "none")
+            ),
+            Ok(_) => panic!("échec attendu"),
+        }
+        assert!(parse_cli_output("not json").is_err());
+    }
+
+    #[test]
+    fn test_6_texte_hors_win_ansi_refuse_sans_pdf() {
+        if find_soffice().is_some() {
+            return; // LibreOffice installé : repli, couvert par test_7.
+        }
+        let base = temp_base();
+        let input = base.join("grec.md");
+        fs::write(&input, "# Titre\n\n\u{3b1}\u{3b2}\u{3b3} et \u{1f600}\n").unwrap();
+        let out = base.join("out");
+
+        let err = convert(&input, &out, &ConvertOptions::default()).unwrap_err();
+        assert_eq!(err.error_code, "UNSUPPORTED_CHARACTERS");
+        assert_eq!(err.exit_code, 15);
+        assert!(err.message.contains("U+03B1"), "{}", err.message);
+        assert!(!out.join("grec.pdf").exists());
+        cleanup(&base);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_7_markdown_non_latin_rendu_par_libreoffice() {
+        let base = temp_base();
+        // Le `soffice` factice recopie le HTML reçu : on vérifie ce qu'il rend.
+        let soffice = fake_soffice(&base, "cp \"$last\" \"$dir/$stem.pdf\"");
+        let input = base.join("\u{3c0}\u{3bf}\u{3af}\u{3b7}\u{3bc}\u{3b1}.md");
+        let text = "# \u{41f}\u{440}\u{438}\u{432}\u{435}\u{442}\n\n\u{3b1}\u{3b2}\u{3b3}, \u{6f22}\u{5b57} et \u{1f600}\n";
+        fs::write(&input, text).unwrap();
+        let out = base.join("out");
+        let options = ConvertOptions {
+            soffice: Some(soffice),
+            ..ConvertOptions::default()
+        };
+
+        let r = convert(&input, &out, &options).unwrap();
+        assert_eq!(r.engine, ENGINE_LIBREOFFICE);
+        let stem = "\u{3c0}\u{3bf}\u{3af}\u{3b7}\u{3bc}\u{3b1}";
+        assert_eq!(r.intermediate_pdf_path, out.join(format!("{stem}.pdf")));
+        let rendered = fs::read_to_string(&r.intermediate_pdf_path).unwrap();
+        assert!(rendered.contains("<h1>\u{41f}\u{440}\u{438}\u{432}\u{435}\u{442}</h1>"));
+        assert!(rendered.contains("\u{3b1}\u{3b2}\u{3b3}, \u{6f22}\u{5b57} et \u{1f600}"));
+        assert!(r.warnings[0].contains("U+03B1"), "{:?}", r.warnings);
+
+        let record: ConversionRecord =
+            serde_json::from_slice(&fs::read(&r.conversion_record_path).unwrap()).unwrap();
+        assert_eq!(record.source.filename, format!("{stem}.md"));
+        assert_eq!(record.engine.options["fallback_from"], "native-markdown");
+        assert_eq!(record.engine.options["input"], "markup-blocks-html-utf8");
+        cleanup(&base);
+    }
+}
@@ -0,0 +1,470 @@
+//! Conversion LibreOffice isolée : une conversion = un dossier de travail
+//! unique (profil `-env:UserInstallation` jetable + sortie vierge), un délai
+//! maximal au-delà duquel tout l'arbre de processus `soffice` est tué.
+//!
+//! Garde-fous :
+//! - jamais le profil LibreOffice de l'utilisateur (verrous, macros, config) ;
+//! - sortie détectée dans un dossier qui était VIDE avant la conversion :
+//!   aucun PDF périmé ne peut être repris ;
+//! - le PDF est ensuite déplacé vers `<output_dir>/<stem>.pdf` ;
+//! - le dossier de travail est supprimé dans tous les cas (`Drop`).
+
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const VERSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub struct Failure {
+    pub code: &'static str,
+    pub message: String,
+    pub exit_code: i32,
+}
+
+impl Failure {
+    fn new(code: &'static str, message: String, exit_code: i32) -> Self {
+        Failure {
+            code,
+            message,
+            exit_code,
+        }
+    }
+}
+
+/// Dossier de travail propre à une conversion, supprimé au `Drop`.
+struct Scratch {
+    root: PathBuf,
+}
+
+impl Scratch {
+    fn create() -> Result<Self, String> {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        for _ in 0..16 {
+            let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let root = std::env::temp_dir().join(format!(
+                "humanorigin-convert-{}-{}-{}",
+                std::process::id(),
+                nanos,
+                n
+            ));
+            // `create_dir` (et non `_all`) : échoue si le dossier existe déjà.
+            match fs::create_dir(&root) {
+                Ok(()) => {
+                    let scratch = Scratch { root };
+                    fs::create_dir(scratch.profile_dir()).map_err(|e| e.to_string())?;
+                    fs::create_dir(scratch.out_dir()).map_err(|e| e.to_string())?;
+                    return Ok(scratch);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+        Err("Cannot allocate a unique scratch directory".to_string())
+    }
+
+    fn profile_dir(&self) -> PathBuf {
+        self.root.join("profile")
+    }
+
+    fn out_dir(&self) -> PathBuf {
+        self.root.join("out")
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// URL `file://` attendue par `-env:UserInstallation` (séparateurs `/`,
+/// caractères hors URL percent-encodés).
+fn file_url(path: &Path) -> String {
+    let raw = path.to_string_lossy().replace('\\', "/");
+    let mut encoded = String::with_capacity(raw.len());
+    for b in raw.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                encoded.push(b as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    if encoded.starts_with('/') {
+        format!("file://{}", encoded)
+    } else {
+        format!("file:///{}", encoded)
+    }
+}
+
+fn spawn_reader<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut p) = pipe {
+            let _ = p.read_to_end(&mut buf);
+        }
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+#[cfg(unix)]
+fn isolate_process_tree(cmd: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    // Nouveau groupe de processus : `soffice` relance `soffice.bin`, les deux
+    // doivent pouvoir être tués ensemble.
+    cmd.process_group(0);
+}
+
+#[cfg(not(unix))]
+fn isolate_process_tree(_cmd: &mut Command) {}
+
+#[cfg(unix)]
+fn kill_process_tree(child: &mut Child) {
+    let _ = Command::new("kill")
+        .arg("-KILL")
+        .arg("--")
+        .arg(format!("-{}", child.id()))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(windows)]
+fn kill_process_tree(child: &mut Child) {
+    let _ = Command::new("taskkill")
+        .arg("/PID")
+        .arg(child.id().to_string())
+        .arg("/T")
+        .arg("/F")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(not(any(unix, windows)))]
+fn kill_process_tree(child: &mut Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// PDF produit : exactement `<stem>.pdf`, sinon l'unique PDF du dossier de
+/// sortie (avertissement). Plusieurs PDF ou aucun : échec.
+fn detect_output(out_dir: &Path, stem: &str) -> Result<(PathBuf, Vec<String>), Failure> {
+    let expected = out_dir.join(format!("{}.pdf", stem));
+    if expected.is_file() {
+        return Ok((expected, vec![]));
+    }
+
+    let pdfs: Vec<PathBuf> = fs::read_dir(out_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.is_file()
+                        && p.extension()
+                            .and_then(OsStr::to_str)
+                            .map(|e| e.eq_ignore_ascii_case("pdf"))
+                            .unwrap_or(false)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    match pdfs.as_slice() {
+        [only] => Ok((
+            only.clone(),
+            vec![String::from(
+                "PDF filename differed from expected input stem.",
+            )],
+        )),
+        [] => Err(Failure::new(
+            "PDF_NOT_CREATED",
+            "Conversion finished but no PDF was produced.".to_string(),
+            9,
+        )),
+        _ => Err(Failure::new(
+            "PDF_NOT_CREATED",
+            format!(
+                "Conversion produced {} PDF files; refusing to guess which one is the output.",
+                pdfs.len()
+            ),
+            9,
+        )),
+    }
+}
+
+/// Déplace le PDF produit vers sa destination finale (copie si `rename`
+/// échoue, ex. dossier temporaire sur un autre volume).
+fn publish_output(produced: &Path, destination: &Path) -> Result<(), Failure> {
+    if fs::rename(produced, destination).is_ok() {
+        return Ok(());
+    }
+    fs::copy(produced, destination).map(|_| ()).map_err(|e| {
+        Failure::new(
+            "OUTPUT_DIR_ERROR",
+            format!("Cannot write converted PDF to output directory: {}", e),
+            5,
+        )
+    })
+}
+
+/// Lance `cmd` (stdout/stderr capturés) et attend au plus `timeout` ; au-delà,
+/// l'arbre de processus est tué et `on_timeout` est renvoyé.
+fn run_with_timeout(
+    mut cmd: Command,
+    timeout: Duration,
+    on_timeout: Failure,
+) -> Result<(ExitStatus, String, String), Failure> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    isolate_process_tree(&mut cmd);
+
+    let mut child = cmd.spawn().map_err(|e| {
+        Failure::new(
+            "CONVERTER_LAUNCH_FAILED",
+            format!("Failed to launch converter engine: {}", e),
+            8,
+        )
+    })?;
+    let stdout = spawn_reader(child.stdout.take());
+    let stderr = spawn_reader(child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                kill_process_tree(&mut child);
+                return Err(on_timeout);
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                kill_process_tree(&mut child);
+                return Err(Failure::new(
+                    "CONVERSION_FAILED",
+                    format!("Cannot wait for LibreOffice: {}", e),
+                    7,
+                ));
+            }
+        }
+    };
+    Ok((
+        status,
+        stdout.join().unwrap_or_default(),
+        stderr.join().unwrap_or_default(),
+    ))
+}
+
+/// `soffice --version` (même profil isolé), ex. « LibreOffice 7.6.4.1 … ».
+/// `None` si la version n'a pas pu être lue : la conversion n'est pas bloquée.
+fn query_version(soffice: &Path, profile_arg: &str) -> Option<String> {
+    let mut cmd = Command::new(soffice);
+    cmd.arg(profile_arg).arg("--headless").arg("--version");
+    let timeout = Failure::new("CONVERSION_TIMEOUT", String::new(), 12);
+    match run_with_timeout(cmd, VERSION_TIMEOUT, timeout) {
+        Ok((status, stdout, _)) if status.success() => stdout
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Options LibreOffice fixes, reportées telles quelles dans le record.
+pub fn options() -> BTreeMap<String, String> {
+    [
+        ("convert_to", "pdf"),
+        ("pdf_export_filter", "default"),
+        ("profile", "isolated-per-conversion"),
+        ("headless", "true"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect()
+}
+
+#[derive(Debug)]
+pub struct Converted {
+    pub pdf_path: PathBuf,
+    pub warnings: Vec<String>,
+    pub engine_version: Option<String>,
+}
+
+/// Convertit `input` en `<output_dir>/<stem>.pdf`.
+pub fn convert(
+    soffice: &Path,
+    input: &Path,
+    output_dir: &Path,
+    stem: &str,
+    timeout: Duration,
+) -> Result<Converted, Failure> {
+    let scratch = Scratch::create().map_err(|e| {
+        Failure::new(
+            "SCRATCH_DIR_ERROR",
+            format!("Cannot create conversion scratch directory: {}", e),
+            13,
+        )
+    })?;
+    let profile_arg = format!("-env:UserInstallation={}", file_url(&scratch.profile_dir()));
+
+    let mut warnings = Vec::new();
+    let engine_version = query_version(soffice, &profile_arg);
+    if engine_version.is_none() {
+        warnings.push(String::from("LibreOffice version could not be determined."));
+    }
+
+    let mut cmd = Command::new(soffice);
+    cmd.arg(&profile_arg)
+        .arg("--headless")
+        .arg("--norestore")
+        .arg("--nolockcheck")
+        .arg("--convert-to")
+        .arg("pdf")
+        .arg("--outdir")
+        .arg(scratch.out_dir())
+        .arg(input);
+
+    let timed_out = Failure::new(
+        "CONVERSION_TIMEOUT",
+        format!(
+            "LibreOffice did not finish within {} s; the conversion was aborted.",
+            timeout.as_secs()
+        ),
+        12,
+    );
+    let (status, stdout, stderr) = run_with_timeout(cmd, timeout, timed_out)?;
+
+    if !status.success() {
+        return Err(Failure::new(
+            "CONVERSION_FAILED",
+            format!(
+                "LibreOffice conversion failed with status: {}. stdout: {} stderr: {}",
+                status,
+                stdout.trim(),
+                stderr.trim()
+            ),
+            7,
+        ));
+    }
+
+    let (produced, detect_warnings) = detect_output(&scratch.out_dir(), stem)?;
+    warnings.extend(detect_warnings);
+    let destination = output_dir.join(format!("{}.pdf", stem));
+    publish_output(&produced, &destination)?;
+    Ok(Converted {
+        pdf_path: destination,
+        warnings,
+        engine_version,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_out() -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "ho_lo_detect_{}_{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn detected(out_dir: &Path, stem: &str) -> (PathBuf, Vec<String>) {
+        match detect_output(out_dir, stem) {
+            Ok(found) => found,
+            Err(f) => panic!("{}: {}", f.code, f.message),
+        }
+    }
+
+    fn detect_error(out_dir: &Path, stem: &str) -> Failure {
+        match detect_output(out_dir, stem) {
+            Ok((path, _)) => panic!("sortie inattendue : {}", path.display()),
+            Err(f) => f,
+        }
+    }
+
+    #[test]
+    fn test_1_file_url_unix_espaces_diese_non_ascii() {
+        assert_eq!(
+            file_url(Path::new("/tmp/ho/Mon dossier #2/été")),
+            "file:///tmp/ho/Mon%20dossier%20%232/%C3%A9t%C3%A9"
+        );
+        // `%` et `?` sont eux-mêmes encodés : aucune séquence ambiguë.
+        assert_eq!(file_url(Path::new("/a%20b?c")), "file:///a%2520b%3Fc");
+    }
+
+    #[test]
+    fn test_2_file_url_lettre_de_lecteur_windows() {
+        assert_eq!(
+            file_url(Path::new(r"C:\Users\Jean Dupont\AppData\Local\Temp\ho")),
+            "file:///C:/Users/Jean%20Dupont/AppData/Local/Temp/ho"
+        );
+        assert_eq!(file_url(Path::new("D:/x")), "file:///D:/x");
+    }
+
+    #[test]
+    fn test_3_detect_output_nom_attendu_prioritaire() {
+        let dir = temp_out();
+        fs::write(dir.join("Chapitre.pdf"), b"%PDF-").unwrap();
+        fs::write(dir.join("autre.pdf"), b"%PDF-").unwrap();
+        let (path, warnings) = detected(&dir, "Chapitre");
+        assert_eq!(path, dir.join("Chapitre.pdf"));
+        assert!(warnings.is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_4_detect_output_unique_pdf_renomme_avertit() {
+        let dir = temp_out();
+        fs::write(dir.join("Chapitre_1.PDF"), b"%PDF-").unwrap();
+        fs::write(dir.join("trace.log"), b"").unwrap();
+        fs::create_dir(dir.join("dossier.pdf")).unwrap();
+        let (path, warnings) = detected(&dir, "Chapitre");
+        assert_eq!(path, dir.join("Chapitre_1.PDF"));
+        assert_eq!(warnings.len(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_5_detect_output_aucun_ou_plusieurs_refuse() {
+        let dir = temp_out();
+        fs::write(dir.join("Chapitre.odt"), b"").unwrap();
+        let none = detect_error(&dir, "Chapitre");
+        assert_eq!((none.code, none.exit_code), ("PDF_NOT_CREATED", 9));
+
+        fs::write(dir.join("a.pdf"), b"%PDF-").unwrap();
+        fs::write(dir.join("b.pdf"), b"%PDF-").unwrap();
+        let many = detect_error(&dir, "Chapitre");
+        assert_eq!(many.code, "PDF_NOT_CREATED");
+        assert!(many.message.contains("2 PDF"));
+
+        // Dossier de sortie absent : même échec, pas de panique.
+        let missing = detect_error(&dir.join("absent"), "Chapitre");
+        assert_eq!(missing.code, "PDF_NOT_CREATED");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,597 @@
+//! Markdown / HTML / texte brut -> blocs simples, mis en page par `text_pdf`.
+//!
+//! Rendu volontairement sobre, sans LibreOffice : titres, paragraphes, listes,
+//! citations, code, filets. Images ignorées (texte alternatif conservé),
+//! tableaux linéarisés (cellules séparées par ` | `), aucun style inline.
+//! Texte hors WinAnsi : les MÊMES blocs sont réécrits en HTML UTF-8
+//! (`blocks_to_html`) et rendus par LibreOffice.
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    Heading(u8),
+    Paragraph,
+    ListItem(u8),
+    Quote,
+    Code,
+    Rule,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub kind: BlockKind,
+    pub text: String,
+}
+
+/// Accumulateur commun aux deux lecteurs (Markdown, HTML).
+struct Builder {
+    blocks: Vec<Block>,
+    kind: BlockKind,
+    text: String,
+    quote_depth: u32,
+    lists: Vec<Option<u64>>,
+    marker_len: usize,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Builder {
+            blocks: Vec::new(),
+            kind: BlockKind::Paragraph,
+            text: String::new(),
+            quote_depth: 0,
+            lists: Vec::new(),
+            marker_len: 0,
+        }
+    }
+
+    fn default_kind(&self) -> BlockKind {
+        if self.quote_depth > 0 {
+            BlockKind::Quote
+        } else {
+            BlockKind::Paragraph
+        }
+    }
+
+    fn flush(&mut self) {
+        let text = if self.kind == BlockKind::Code {
+            self.text.trim_end().to_string()
+        } else {
+            self.text.trim().to_string()
+        };
+        if !text.is_empty() {
+            self.blocks.push(Block {
+                kind: self.kind,
+                text,
+            });
+        }
+        self.text.clear();
+        self.marker_len = 0;
+        self.kind = self.default_kind();
+    }
+
+    /// Item de liste ouvert dont seule la puce est posée.
+    fn item_is_empty(&self) -> bool {
+        matches!(self.kind, BlockKind::ListItem(_)) && self.text.len() == self.marker_len
+    }
+
+    fn start(&mut self, kind: BlockKind) {
+        self.flush();
+        self.kind = kind;
+    }
+
+    fn start_item(&mut self) {
+        self.flush();
+        let depth = self.lists.len().clamp(1, 8) as u8;
+        let marker = match self.lists.last_mut() {
+            Some(Some(n)) => {
+                let m = format!("{n}. ");
+                *n += 1;
+                m
+            }
+            _ => "\u{2022} ".to_string(),
+        };
+        self.kind = BlockKind::ListItem(depth);
+        self.marker_len = marker.len();
+        self.text.push_str(&marker);
+    }
+
+    fn rule(&mut self) {
+        self.flush();
+        self.blocks.push(Block {
+            kind: BlockKind::Rule,
+            text: String::new(),
+        });
+    }
+
+    fn push_text(&mut self, t: &str) {
+        self.text.push_str(t);
+    }
+
+    fn finish(mut self) -> Vec<Block> {
+        self.flush();
+        self.blocks
+    }
+}
+
+// --- TEXTE BRUT ---------------------------------------------------------------
+
+/// Un paragraphe par groupe de lignes séparé par une ligne vide ; les retours
+/// à la ligne internes sont conservés (poésie, adresses, listes manuelles).
+pub fn parse_plain_text(raw: &str) -> Vec<Block> {
+    let normalized = raw
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\t', "    ");
+    let mut blocks = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for line in normalized.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(Block {
+                    kind: BlockKind::Paragraph,
+                    text: current.join("\n"),
+                });
+                current.clear();
+            }
+        } else {
+            current.push(line.trim_end());
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(Block {
+            kind: BlockKind::Paragraph,
+            text: current.join("\n"),
+        });
+    }
+    blocks
+}
+
+// --- MARKDOWN -----------------------------------------------------------------
+
+pub fn parse_markdown(raw: &str) -> Vec<Block> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut b = Builder::new();
+    for event in Parser::new_ext(raw, options) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => b.start(BlockKind::Heading(level as u8)),
+            // Premier paragraphe d'un item : reste dans l'item (puce déjà posée).
+            Event::Start(Tag::Paragraph) if !b.item_is_empty() => b.start(b.default_kind()),
+            Event::Start(Tag::CodeBlock(_)) => b.start(BlockKind::Code),
+            Event::Start(Tag::BlockQuote(_)) => {
+                b.flush();
+                b.quote_depth += 1;
+                b.kind = b.default_kind();
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                b.flush();
+                b.quote_depth = b.quote_depth.saturating_sub(1);
+                b.kind = b.default_kind();
+            }
+            Event::Start(Tag::List(first)) => {
+                b.flush();
+                b.lists.push(first);
+            }
+            Event::End(TagEnd::List(_)) => {
+                b.flush();
+                b.lists.pop();
+            }
+            Event::Start(Tag::Item) => b.start_item(),
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::CodeBlock
+                | TagEnd::Item
+                | TagEnd::TableHead
+                | TagEnd::TableRow,
+            ) => b.flush(),
+            Event::End(TagEnd::TableCell) => b.push_text(" | "),
+            Event::Text(t) | Event::Code(t) => b.push_text(&t),
+            Event::SoftBreak => {
+                if b.kind == BlockKind::Code {
+                    b.push_text("\n");
+                } else {
+                    b.push_text(" ");
+                }
+            }
+            Event::HardBreak => b.push_text("\n"),
+            Event::Rule => b.rule(),
+            Event::TaskListMarker(done) => b.push_text(if done { "[x] " } else { "[ ] " }),
+            _ => {}
+        }
+    }
+    b.finish()
+        .into_iter()
+        .map(|mut block| {
+            // Cellule finale d'une ligne de tableau : séparateur pendant retiré.
+            if let Some(stripped) = block.text.strip_suffix(" |") {
+                block.text = stripped.trim_end().to_string();
+            }
+            block
+        })
+        .collect()
+}
+
+// --- HTML ---------------------------------------------------------------------
+
+/// Lecteur HTML tolérant (balises mal fermées acceptées) : structure de bloc
+/// uniquement. `<head>`, `<script>`, `<style>` et commentaires sont ignorés.
+pub fn parse_html(raw: &str) -> Vec<Block> {
+    let mut b = Builder::new();
+    let mut pre_depth = 0u32;
+    let mut rest = raw;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map(|i| &after[i + 3..]).unwrap_or("");
+            continue;
+        }
+        if starts_tag(rest) {
+            let Some(end) = rest.find('>') else {
+                b.push_text(&collapse_ws(&decode_entities(rest), &b.text));
+                break;
+            };
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+
+            let closing = tag.starts_with('/');
+            let name: String = tag
+                .trim_start_matches('/')
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect::<String>()
+                .to_ascii_lowercase();
+
+            if !closing && matches!(name.as_str(), "script" | "style" | "head" | "title") {
+                let close = format!("</{name}");
+                rest = find_ci(rest, &close)
+                    .and_then(|i| rest[i..].find('>').map(|j| &rest[i + j + 1..]))
+                    .unwrap_or("");
+                continue;
+            }
+
+            match (name.as_str(), closing) {
+                ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                    let level = name[1..].parse::<u8>().unwrap_or(1);
+                    b.start(BlockKind::Heading(level));
+                }
+                ("pre", false) => {
+                    pre_depth += 1;
+                    b.start(BlockKind::Code);
+                }
+                ("pre", true) => {
+                    pre_depth = pre_depth.saturating_sub(1);
+                    b.flush();
+                }
+                ("blockquote", false) => {
+                    b.flush();
+                    b.quote_depth += 1;
+                    b.kind = b.default_kind();
+                }
+                ("blockquote", true) => {
+                    b.flush();
+                    b.quote_depth = b.quote_depth.saturating_sub(1);
+                    b.kind = b.default_kind();
+                }
+                ("ul", false) => {
+                    b.flush();
+                    b.lists.push(None);
+                }
+                ("ol", false) => {
+                    b.flush();
+                    b.lists.push(Some(1));
+                }
+                ("ul" | "ol", true) => {
+                    b.flush();
+                    b.lists.pop();
+                }
+                ("li", false) => b.start_item(),
+                ("p" | "div", false) if b.item_is_empty() => {}
+                ("br", _) => b.push_text("\n"),
+                ("hr", _) => b.rule(),
+                ("td" | "th", true) => b.push_text(" | "),
+                (
+                    "p" | "div" | "section" | "article" | "header" | "footer" | "main" | "nav"
+                    | "aside" | "figure" | "figcaption" | "table" | "tr" | "dl" | "dt" | "dd"
+                    | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "li" | "body",
+                    _,
+                ) => b.flush(),
+                _ => {}
+            }
+            continue;
+        }
+
+        let end = next_tag(rest);
+        let text = decode_entities(&rest[..end]);
+        if pre_depth > 0 {
+            b.push_text(&text);
+        } else {
+            let collapsed = collapse_ws(&text, &b.text);
+            b.push_text(&collapsed);
+        }
+        rest = &rest[end..];
+    }
+
+    b.finish()
+        .into_iter()
+        .map(|mut block| {
+            if let Some(stripped) = block.text.strip_suffix(" |") {
+                block.text = stripped.trim_end().to_string();
+            }
+            block
+        })
+        .collect()
+}
+
+/// `<` n'ouvre une balise que suivi de `/`, `!` ou d'une lettre ASCII :
+/// `a < b`, `<3`, `<=` restent du texte.
+fn starts_tag(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next() == Some('<')
+        && chars
+            .next()
+            .is_some_and(|c| c == '/' || c == '!' || c.is_ascii_alphabetic())
+}
+
+/// Début de la prochaine balise (ou fin du texte). `s` ne commence pas par
+/// une balise : un `<` littéral en tête est sauté.
+fn next_tag(s: &str) -> usize {
+    s.char_indices()
+        .skip(1)
+        .find(|&(i, c)| c == '<' && starts_tag(&s[i..]))
+        .map_or(s.len(), |(i, _)| i)
+}
+
+/// Espaces HTML : toute suite de blancs devient un espace, sans doubler
+/// l'espace déjà présent en fin de bloc (ni après un `<br>`).
+fn collapse_ws(text: &str, current: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut prev_space = current.is_empty() || current.ends_with([' ', '\n']);
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !prev_space {
+                out.push(' ');
+                prev_space = true;
+            }
+        } else {
+            out.push(c);
+            prev_space = false;
+        }
+    }
+    out
+}
+
+fn find_ci(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .to_ascii_lowercase()
+        .find(&needle.to_ascii_lowercase())
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        let after = &rest[i + 1..];
+        let decoded = after
+            .find(';')
+            .filter(|&j| j <= 10)
+            .and_then(|j| entity_char(&after[..j]).map(|c| (c, j)));
+        match decoded {
+            Some((c, j)) => {
+                out.push(c);
+                rest = &after[j + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn entity_char(name: &str) -> Option<char> {
+    if let Some(num) = name.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse::<u32>().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    let c = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "laquo" => '«',
+        "raquo" => '»',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "bull" => '•',
+        "euro" => '€',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "deg" => '°',
+        "eacute" => 'é',
+        "egrave" => 'è',
+        "ecirc" => 'ê',
+        "euml" => 'ë',
+        "agrave" => 'à',
+        "acirc" => 'â',
+        "ccedil" => 'ç',
+        "icirc" => 'î',
+        "iuml" => 'ï',
+        "ocirc" => 'ô',
+        "ugrave" => 'ù',
+        "ucirc" => 'û',
+        "uuml" => 'ü',
+        "oelig" => 'œ',
+        "Eacute" => 'É',
+        "Egrave" => 'È',
+        "Agrave" => 'À',
+        "Ccedil" => 'Ç',
+        "OElig" => 'Œ',
+        _ => return None,
+    };
+    Some(c)
+}
+
+// --- HTML (repli LibreOffice) ------------------------------------------------
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Blocs -> document HTML UTF-8 autonome, même structure que `text_pdf` :
+/// rendu identique quel que soit le format d'origine, aucun contenu actif.
+pub fn blocks_to_html(blocks: &[Block]) -> String {
+    let mut html =
+        String::from("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"></head><body>\n");
+    for block in blocks {
+        let text = escape_html(&block.text);
+        let line = match block.kind {
+            BlockKind::Heading(level) => {
+                let level = level.clamp(1, 6);
+                format!("<h{level}>{text}</h{level}>")
+            }
+            BlockKind::Paragraph => format!("<p>{}</p>", text.replace('\n', "<br>")),
+            BlockKind::ListItem(depth) => format!(
+                "<p style=\"margin-left: {}em\">{}</p>",
+                u32::from(depth) * 2,
+                text.replace('\n', "<br>")
+            ),
+            BlockKind::Quote => {
+                format!(
+                    "<blockquote><p>{}</p></blockquote>",
+                    text.replace('\n', "<br>")
+                )
+            }
+            BlockKind::Code => format!("<pre>{text}</pre>"),
+            BlockKind::Rule => "<hr>".to_string(),
+        };
+        html.push_str(&line);
+        html.push('\n');
+    }
+    html.push_str("</body></html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(blocks: &[Block]) -> Vec<(BlockKind, &str)> {
+        blocks.iter().map(|b| (b.kind, b.text.as_str())).collect()
+    }
+
+    #[test]
+    fn test_1_chevron_litteral_reste_du_texte() {
+        let blocks = parse_html("<p>a < b et 3<4, <3 ou <= 2</p><p>x <-> y</p>");
+        assert_eq!(
+            texts(&blocks),
+            vec![
+                (BlockKind::Paragraph, "a < b et 3<4, <3 ou <= 2"),
+                (BlockKind::Paragraph, "x <-> y"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_2_balises_lettre_slash_et_bang_reconnues() {
+        let blocks = parse_html(
+            "<!DOCTYPE html><html><head><title>T</title></head><body>\
+             <!-- note --><H2>Titre</H2><p>un<br>deux</p><hr/></body></html>",
+        );
+        assert_eq!(
+            texts(&blocks),
+            vec![
+                (BlockKind::Heading(2), "Titre"),
+                (BlockKind::Paragraph, "un\ndeux"),
+                (BlockKind::Rule, ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_3_chevron_non_ferme_en_fin_de_texte() {
+        let blocks = parse_html("<p>fin <b");
+        assert_eq!(texts(&blocks), vec![(BlockKind::Paragraph, "fin <b")]);
+        let blocks = parse_html("<p>fin <</p>");
+        assert_eq!(texts(&blocks), vec![(BlockKind::Paragraph, "fin <")]);
+    }
+
+    #[test]
+    fn test_4_listes_entites_et_pre() {
+        let blocks = parse_html(
+            "<ol><li>un &amp; deux</li><li>&lt;tag&gt;</li></ol>\
+             <ul><li>puce</li></ul><pre>  a  <  b\n  c</pre>",
+        );
+        assert_eq!(
+            texts(&blocks),
+            vec![
+                (BlockKind::ListItem(1), "1. un & deux"),
+                (BlockKind::ListItem(1), "2. <tag>"),
+                (BlockKind::ListItem(1), "\u{2022} puce"),
+                (BlockKind::Code, "  a  <  b\n  c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_5_markdown_et_texte_brut() {
+        let blocks = parse_markdown("# T\n\na < b\n\n| x | y |\n|---|---|\n| 1 | 2 |\n");
+        assert_eq!(
+            texts(&blocks),
+            vec![
+                (BlockKind::Heading(1), "T"),
+                (BlockKind::Paragraph, "a < b"),
+                (BlockKind::Paragraph, "x | y"),
+                (BlockKind::Paragraph, "1 | 2"),
+            ]
+        );
+        let blocks = parse_plain_text("a\r\nb\n\n\tc\n");
+        assert_eq!(
+            texts(&blocks),
+            vec![
+                (BlockKind::Paragraph, "a\nb"),
+                (BlockKind::Paragraph, "    c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_6_blocs_vers_html_echappe() {
+        let blocks = parse_markdown(
+            "# \u{3a4}\u{3af}\u{3c4}\u{3bb}\u{3bf}\u{3c2}\n\n- a < b\n\n```\nx && y\n```\n\n---\n",
+        );
+        let html = blocks_to_html(&blocks);
+        assert!(html.contains("<meta charset=\"utf-8\">"));
+        assert!(html.contains("<h1>\u{3a4}\u{3af}\u{3c4}\u{3bb}\u{3bf}\u{3c2}</h1>"));
+        assert!(html.contains("<p style=\"margin-left: 2em\">\u{2022} a &lt; b</p>"));
+        assert!(html.contains("<pre>x &amp;&amp; y</pre>"));
+        assert!(html.contains("<hr>"));
+        assert_eq!(
+            blocks_to_html(&parse_plain_text("un\ndeux"))
+                .matches("<p>un<br>deux</p>")
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! `<stem>.conversion_record.json` : empreinte d'une conversion, écrite à côté
+//! de `<stem>.pdf` (le package Work l'embarque sous `conversion_record.json`).
+//!
+//! Lie SHA256(source) -> SHA256(sortie) au moteur exact (nom, version,
+//! options) : le record atteste CE rendu, pas sa reproductibilité. LibreOffice
+//! inscrit dans le PDF des dates et un `/ID` propres à chaque exécution : deux
+//! conversions du même fichier, même moteur, donnent des SHA256 de sortie
+//! différents.
+//!
+//! Aucune date, aucun chemin absolu (noms de fichiers seuls), options triées
+//! par clé.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const RECORD_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FileDigest {
+    pub filename: String,
+    pub sha256: String,
+    pub size: u64,
+}
+
+impl FileDigest {
+    pub fn of(path: &Path) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        let mut hasher = Sha256::new();
+        let size = io::copy(&mut file, &mut hasher)?;
+        Ok(FileDigest {
+            filename: path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            sha256: format!("{:x}", hasher.finalize()),
+            size,
+        })
+    }
+}
+
+/// Moteur ayant produit le PDF. `version` : sortie de `soffice --version`
+/// (LibreOffice) ou version du convertisseur (rendu natif) ; `None` si inconnue.
+/// `options` : map triée, donc sérialisation stable.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EngineInfo {
+    pub name: String,
+    pub version: Option<String>,
+    pub converter_version: String,
+    pub options: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConversionRecord {
+    pub record_version: u32,
+    pub source: FileDigest,
+    pub output: FileDigest,
+    pub engine: EngineInfo,
+}
+
+impl ConversionRecord {
+    pub fn build(source: &Path, output: &Path, engine: EngineInfo) -> io::Result<Self> {
+        Ok(ConversionRecord {
+            record_version: RECORD_VERSION,
+            source: FileDigest::of(source)?,
+            output: FileDigest::of(output)?,
+            engine,
+        })
+    }
+
+    /// Écrit `<stem>.conversion_record.json` à côté du PDF produit.
+    pub fn write_next_to(&self, output_pdf: &Path) -> io::Result<PathBuf> {
+        let stem = output_pdf
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "document".to_string());
+        let path = output_pdf.with_file_name(format!("{}.conversion_record.json", stem));
+        fs::write(&path, serde_json::to_vec(self)?)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn temp_base() -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let dir = env::temp_dir().join(format!(
+            "ho_record_test_{}_{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn engine() -> EngineInfo {
+        EngineInfo {
+            name: "libreoffice-headless".to_string(),
+            version: Some("LibreOffice 7.6.4.1".to_string()),
+            converter_version: "0.1.0".to_string(),
+            options: BTreeMap::from([
+                ("timeout_secs".to_string(), "120".to_string()),
+                ("filter".to_string(), "writer_pdf_Export".to_string()),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_1_serialisation_figee() {
+        let record = ConversionRecord {
+            record_version: RECORD_VERSION,
+            source: FileDigest {
+                filename: "Chapitre.docx".to_string(),
+                sha256: "a".repeat(64),
+                size: 3,
+            },
+            output: FileDigest {
+                filename: "Chapitre.pdf".to_string(),
+                sha256: "b".repeat(64),
+                size: 5,
+            },
+            engine: EngineInfo {
+                version: None,
+                ..engine()
+            },
+        };
+        // Forme exacte lue par le package Work : ordre des champs figé,
+        // options triées par clé, `version` inconnue = `null`.
+        let expected = format!(
+            concat!(
+                r#"{{"record_version":1,"#,
+                r#""source":{{"filename":"Chapitre.docx","sha256":"{a}","size":3}},"#,
+                r#""output":{{"filename":"Chapitre.pdf","sha256":"{b}","size":5}},"#,
+                r#""engine":{{"name":"libreoffice-headless","version":null,"#,
+                r#""converter_version":"0.1.0","#,
+                r#""options":{{"filter":"writer_pdf_Export","timeout_secs":"120"}}}}}}"#
+            ),
+            a = "a".repeat(64),
+            b = "b".repeat(64)
+        );
+        assert_eq!(serde_json::to_string(&record).unwrap(), expected);
+        let back: ConversionRecord = serde_json::from_str(&expected).unwrap();
+        assert_eq!(back, record);
+    }
+
+    #[test]
+    fn test_2_ecrit_a_cote_du_pdf_et_relu() {
+        let base = temp_base();
+        let source = base.join("Chapitre.docx");
+        let pdf = base.join("Chapitre.pdf");
+        fs::write(&source, b"abc").unwrap();
+        fs::write(&pdf, b"%PDF-").unwrap();
+
+        let record = ConversionRecord::build(&source, &pdf, engine()).unwrap();
+        assert_eq!(
+            record.source.sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!((record.source.size, record.output.size), (3, 5));
+        assert_eq!(record.output.filename, "Chapitre.pdf");
+
+        let path = record.write_next_to(&pdf).unwrap();
+        assert_eq!(path, base.join("Chapitre.conversion_record.json"));
+        let raw = fs::read_to_string(&path).unwrap();
+        // Noms de fichiers seuls : aucun chemin du dossier de sortie.
+        assert!(!raw.contains(&*base.to_string_lossy()));
+        let back: ConversionRecord = serde_json::from_str(&raw).unwrap();
+        assert_eq!(back, record);
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_3_fichier_absent_erreur() {
+        let base = temp_base();
+        assert!(FileDigest::of(&base.join("absent.pdf")).is_err());
+        let _ = fs::remove_dir_all(&base);
+    }
+}
@@ -0,0 +1,468 @@
+//! Mise en page des blocs `markup` en PDF (A4, polices standard 14).
+//!
+//! Aucune police embarquée : Helvetica / Helvetica-Bold / Helvetica-Oblique /
+//! Courier en `WinAnsiEncoding` (un tel rendu ne peut donc jamais devenir
+//! PDF/A : la publication `pdfa_2b` le refuse d'emblée, avant tout rendu, en
+//! `PDFA_SOURCE_UNSUPPORTED`). Un texte contenant un caractère hors WinAnsi
+//! est REFUSÉ (`unsupported_chars`) : jamais de `?` substitué en silence dans
+//! un rendu qui sert de base à la preuve ; l'appelant confie alors les mêmes
+//! blocs à LibreOffice.
+//! Aucune date, aucun identifiant aléatoire écrit par ce module.
+
+use crate::markup::{Block, BlockKind};
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Document, Object, ObjectId, Stream, StringFormat};
+use std::path::Path;
+
+const PAGE_W: f32 = 595.28;
+const PAGE_H: f32 = 841.89;
+const MARGIN: f32 = 56.7;
+const BODY_SIZE: f32 = 11.0;
+const CODE_SIZE: f32 = 9.5;
+const LEADING: f32 = 1.35;
+const INDENT_STEP: f32 = 14.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Font {
+    Regular,
+    Bold,
+    Oblique,
+    Mono,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Oblique => "F3",
+            Font::Mono => "F4",
+        }
+    }
+}
+
+/// Largeurs AFM (1/1000 em) des glyphes ASCII 32..=126.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Code WinAnsi d'un caractère Unicode (`None` si absent de l'encodage).
+fn win_ansi(c: char) -> Option<u8> {
+    let code = c as u32;
+    match code {
+        0x20..=0x7e | 0xa0..=0xff => Some(code as u8),
+        _ => Some(match c {
+            '€' => 0x80,
+            '‚' => 0x82,
+            'ƒ' => 0x83,
+            '„' => 0x84,
+            '…' => 0x85,
+            '†' => 0x86,
+            '‡' => 0x87,
+            'ˆ' => 0x88,
+            '‰' => 0x89,
+            'Š' => 0x8a,
+            '‹' => 0x8b,
+            'Œ' => 0x8c,
+            'Ž' => 0x8e,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '˜' => 0x98,
+            '™' => 0x99,
+            'š' => 0x9a,
+            '›' => 0x9b,
+            'œ' => 0x9c,
+            'ž' => 0x9e,
+            'Ÿ' => 0x9f,
+            _ => return None,
+        }),
+    }
+}
+
+fn glyph_width(font: Font, byte: u8) -> f32 {
+    let table = match font {
+        Font::Mono => return 600.0,
+        Font::Bold => &HELVETICA_BOLD_WIDTHS,
+        Font::Regular | Font::Oblique => &HELVETICA_WIDTHS,
+    };
+    match byte {
+        0x20..=0x7e => table[(byte - 0x20) as usize] as f32,
+        // Hors ASCII : largeur prudente (lettres accentuées ~ lettre de base).
+        _ if font == Font::Bold => 611.0,
+        _ => 556.0,
+    }
+}
+
+fn text_width(font: Font, size: f32, bytes: &[u8]) -> f32 {
+    bytes.iter().map(|&b| glyph_width(font, b)).sum::<f32>() * size / 1000.0
+}
+
+struct Style {
+    font: Font,
+    size: f32,
+    indent: f32,
+    space_before: f32,
+    space_after: f32,
+}
+
+fn style_for(kind: BlockKind) -> Style {
+    match kind {
+        BlockKind::Heading(level) => {
+            let size = match level {
+                1 => 20.0,
+                2 => 16.0,
+                3 => 13.5,
+                _ => 12.0,
+            };
+            Style {
+                font: Font::Bold,
+                size,
+                indent: 0.0,
+                space_before: size * 0.8,
+                space_after: size * 0.35,
+            }
+        }
+        BlockKind::Paragraph | BlockKind::Rule => Style {
+            font: Font::Regular,
+            size: BODY_SIZE,
+            indent: 0.0,
+            space_before: 0.0,
+            space_after: BODY_SIZE * 0.6,
+        },
+        BlockKind::ListItem(depth) => Style {
+            font: Font::Regular,
+            size: BODY_SIZE,
+            indent: INDENT_STEP * depth as f32,
+            space_before: 0.0,
+            space_after: BODY_SIZE * 0.25,
+        },
+        BlockKind::Quote => Style {
+            font: Font::Oblique,
+            size: BODY_SIZE,
+            indent: INDENT_STEP * 1.5,
+            space_before: 0.0,
+            space_after: BODY_SIZE * 0.6,
+        },
+        BlockKind::Code => Style {
+            font: Font::Mono,
+            size: CODE_SIZE,
+            indent: INDENT_STEP,
+            space_before: 0.0,
+            space_after: BODY_SIZE * 0.6,
+        },
+    }
+}
+
+/// Césure au mot (`\n` = saut forcé) ; un mot plus large que la ligne est
+/// coupé au caractère. En code, les espaces de tête sont conservés.
+fn wrap(font: Font, size: f32, max_w: f32, text: &[u8]) -> Vec<Vec<u8>> {
+    let mut lines = Vec::new();
+    for raw_line in text.split(|&b| b == b'\n') {
+        let mut line: Vec<u8> = Vec::new();
+        let mut line_w = 0.0;
+        let space_w = glyph_width(font, b' ') * size / 1000.0;
+        let mut words = raw_line.split(|&b| b == b' ').peekable();
+        // Indentation de tête (code, texte brut) conservée telle quelle.
+        while words.peek().is_some_and(|w| w.is_empty()) {
+            words.next();
+            line.push(b' ');
+            line_w += space_w;
+        }
+        for word in words {
+            let w = text_width(font, size, word);
+            let sep = if line.is_empty() || line.iter().all(|&b| b == b' ') {
+                0.0
+            } else {
+                space_w
+            };
+            if line_w + sep + w <= max_w {
+                if sep > 0.0 {
+                    line.push(b' ');
+                }
+                line.extend_from_slice(word);
+                line_w += sep + w;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                line_w = 0.0;
+            }
+            // Mot seul trop large : coupe au glyphe.
+            for &b in word {
+                let gw = glyph_width(font, b) * size / 1000.0;
+                if line_w + gw > max_w && !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                    line_w = 0.0;
+                }
+                line.push(b);
+                line_w += gw;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+struct PageWriter {
+    pages: Vec<Vec<Operation>>,
+    y: f32,
+}
+
+impl PageWriter {
+    fn new() -> Self {
+        PageWriter {
+            pages: vec![Vec::new()],
+            y: PAGE_H - MARGIN,
+        }
+    }
+
+    fn ensure_room(&mut self, h: f32) {
+        if self.y - h < MARGIN {
+            self.pages.push(Vec::new());
+            self.y = PAGE_H - MARGIN;
+        }
+    }
+
+    fn ops(&mut self) -> &mut Vec<Operation> {
+        self.pages.last_mut().expect("at least one page")
+    }
+
+    fn text_line(&mut self, font: Font, size: f32, x: f32, line: Vec<u8>) {
+        let leading = size * LEADING;
+        self.ensure_room(leading);
+        self.y -= leading;
+        let y = self.y + (leading - size);
+        let ops = self.ops();
+        ops.push(Operation::new("BT", vec![]));
+        ops.push(Operation::new(
+            "Tf",
+            vec![font.resource().into(), size.into()],
+        ));
+        ops.push(Operation::new("Td", vec![x.into(), y.into()]));
+        ops.push(Operation::new(
+            "Tj",
+            vec![Object::String(line, StringFormat::Literal)],
+        ));
+        ops.push(Operation::new("ET", vec![]));
+    }
+
+    fn rule(&mut self) {
+        self.ensure_room(BODY_SIZE);
+        self.y -= BODY_SIZE * 0.5;
+        let y = self.y;
+        let ops = self.ops();
+        ops.push(Operation::new("w", vec![0.6.into()]));
+        ops.push(Operation::new("m", vec![MARGIN.into(), y.into()]));
+        ops.push(Operation::new(
+            "l",
+            vec![(PAGE_W - MARGIN).into(), y.into()],
+        ));
+        ops.push(Operation::new("S", vec![]));
+        self.y -= BODY_SIZE * 0.5;
+    }
+}
+
+/// Encode un texte de bloc en WinAnsi (`\n` conservé, autres contrôles
+/// retirés). `Err(c)` : premier caractère hors de l'encodage.
+fn encode_win_ansi(text: &str) -> Result<Vec<u8>, char> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        if c == '\n' {
+            bytes.push(b'\n');
+        } else if !c.is_control() {
+            bytes.push(win_ansi(c).ok_or(c)?);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Caractères distincts hors WinAnsi (ordre d'apparition) : non vide ->
+/// le rendu natif ne peut pas représenter fidèlement le texte.
+pub fn unsupported_chars(blocks: &[Block]) -> Vec<char> {
+    let mut found = Vec::new();
+    for c in blocks.iter().flat_map(|b| b.text.chars()) {
+        if c != '\n' && !c.is_control() && win_ansi(c).is_none() && !found.contains(&c) {
+            found.push(c);
+        }
+    }
+    found
+}
+
+/// Rendu final. Renvoie le nombre de pages ; refuse tout caractère hors
+/// WinAnsi (à vérifier en amont avec `unsupported_chars`).
+pub fn render_blocks(blocks: &[Block], output: &Path) -> Result<u32, String> {
+    let mut writer = PageWriter::new();
+
+    for block in blocks {
+        if block.kind == BlockKind::Rule {
+            writer.rule();
+            continue;
+        }
+        let style = style_for(block.kind);
+        let bytes = encode_win_ansi(&block.text)
+            .map_err(|c| format!("Character U+{:04X} is outside WinAnsi", c as u32))?;
+
+        if writer.y < PAGE_H - MARGIN {
+            writer.y -= style.space_before;
+        }
+        let x = MARGIN + style.indent;
+        let max_w = PAGE_W - MARGIN - x;
+        // Items de liste : retrait suspendu après la puce.
+        let hang = match block.kind {
+            BlockKind::ListItem(_) => {
+                let marker_end = bytes.iter().position(|&b| b == b' ').map_or(0, |i| i + 1);
+                text_width(style.font, style.size, &bytes[..marker_end])
+            }
+            _ => 0.0,
+        };
+        for (i, line) in wrap(style.font, style.size, max_w - hang, &bytes)
+            .into_iter()
+            .enumerate()
+        {
+            let lx = if i == 0 { x } else { x + hang };
+            writer.text_line(style.font, style.size, lx, line);
+        }
+        writer.y -= style.space_after;
+    }
+
+    let page_count = writer.pages.len() as u32;
+    write_document(writer.pages, output)?;
+    Ok(page_count)
+}
+
+fn font_dict(doc: &mut Document, base_font: &str) -> ObjectId {
+    doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => base_font,
+        "Encoding" => "WinAnsiEncoding",
+    })
+}
+
+fn write_document(pages: Vec<Vec<Operation>>, output: &Path) -> Result<(), String> {
+    let mut doc = Document::with_version("1.7");
+    let pages_id = doc.new_object_id();
+
+    let fonts = dictionary! {
+        Font::Regular.resource() => font_dict(&mut doc, "Helvetica"),
+        Font::Bold.resource() => font_dict(&mut doc, "Helvetica-Bold"),
+        Font::Oblique.resource() => font_dict(&mut doc, "Helvetica-Oblique"),
+        Font::Mono.resource() => font_dict(&mut doc, "Courier"),
+    };
+    let resources_id = doc.add_object(dictionary! { "Font" => fonts });
+
+    let mut kids: Vec<Object> = Vec::with_capacity(pages.len());
+    for operations in pages {
+        let content = Content { operations }
+            .encode()
+            .map_err(|e| format!("Cannot encode page content: {e}"))?;
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        });
+        kids.push(page_id.into());
+    }
+
+    let count = kids.len() as i64;
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids,
+            "Count" => count,
+            "Resources" => resources_id,
+            "MediaBox" => vec![0.into(), 0.into(), PAGE_W.into(), PAGE_H.into()],
+        }),
+    );
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+    let info_id = doc.add_object(dictionary! {
+        "Producer" => Object::string_literal("HumanOrigin Converter"),
+    });
+    doc.trailer.set("Info", info_id);
+
+    doc.save(output)
+        .map(|_| ())
+        .map_err(|e| format!("Cannot write PDF: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn paragraph(text: &str) -> Block {
+        Block {
+            kind: BlockKind::Paragraph,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_1_encodage_win_ansi() {
+        assert_eq!(encode_win_ansi("Aé€—"), Ok(vec![b'A', 0xe9, 0x80, 0x97]));
+        assert_eq!(encode_win_ansi("a\nb\u{7}c"), Ok(b"a\nbc".to_vec()));
+        assert_eq!(encode_win_ansi("ok \u{3b1} \u{1f600}"), Err('\u{3b1}'));
+        // Trous de la plage 0x80..0x9f : absents de WinAnsi.
+        assert_eq!(win_ansi('\u{81}'), None);
+        assert_eq!(win_ansi('\u{152}'), Some(0x8c));
+    }
+
+    #[test]
+    fn test_2_caracteres_non_supportes_distincts_et_ordonnes() {
+        let blocks = [paragraph("ж a ж"), paragraph("\u{1f600} é ж\n")];
+        assert_eq!(unsupported_chars(&blocks), vec!['ж', '\u{1f600}']);
+        assert!(unsupported_chars(&[paragraph("Œuvre « cité » – 10 €")]).is_empty());
+    }
+
+    #[test]
+    fn test_3_rendu_refuse_hors_win_ansi_sans_ecrire() {
+        let out = std::env::temp_dir().join(format!("ho_text_pdf_{}.pdf", std::process::id()));
+        let _ = fs::remove_file(&out);
+        let err = render_blocks(&[paragraph("Привет")], &out).unwrap_err();
+        assert!(err.contains("U+041F"), "{err}");
+        assert!(!out.exists());
+
+        let pages = render_blocks(&[paragraph("Été déjà là")], &out).unwrap();
+        assert_eq!(pages, 1);
+        let doc = lopdf::Document::load(&out).unwrap();
+        let page = *doc.get_pages().values().next().unwrap();
+        let content = doc.get_page_content(page).unwrap();
+        assert!(content
+            .windows(11)
+            .any(|w| w == b"\xc9t\xe9 d\xe9j\xe0 l\xe0"));
+        let _ = fs::remove_file(&out);
+    }
+
+    #[test]
+    fn test_4_cesure_au_mot_et_mot_trop_long() {
+        let size = 10.0;
+        let max_w = text_width(Font::Regular, size, b"aaaa bbbb") + 0.01;
+        let lines = wrap(Font::Regular, size, max_w, b"aaaa bbbb cccc");
+        assert_eq!(lines, vec![b"aaaa bbbb".to_vec(), b"cccc".to_vec()]);
+        let lines = wrap(Font::Mono, size, 6.0 * 3.0, b"abcdefg");
+        assert_eq!(lines, vec![b"abc".to_vec(), b"def".to_vec(), b"g".to_vec()]);
+    }
+}
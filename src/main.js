@@ -730,7 +730,9 @@ function buildObjectEvidence(boundDoc, finalBind, finalSizeNum, finalSelectedAt,
     text_length: boundDoc.structure_t0?.text_length ?? null,
     word_count: boundDoc.structure_t0?.word_count ?? null,
     text_extract_hash: boundDoc.structure_t0?.text_extract_hash ?? null,
+    metrics_version: boundDoc.structure_t0?.metrics_version ?? null,
     extraction_status: boundDoc.structure_t0?.extraction_status ?? "not_attempted",
+    docx_structure: boundDoc.structure_t0?.docx_structure ?? null,
   };
 
   const objectStateFinal = {
@@ -742,11 +744,17 @@ function buildObjectEvidence(boundDoc, finalBind, finalSizeNum, finalSelectedAt,
     text_length: finalStructure?.text_length ?? null,
     word_count: finalStructure?.word_count ?? null,
     text_extract_hash: finalStructure?.text_extract_hash ?? null,
+    metrics_version: finalStructure?.metrics_version ?? null,
     extraction_status: finalStructure?.extraction_status ?? "not_attempted",
+    docx_structure: finalStructure?.docx_structure ?? null,
   };
 
   // P1 : delta structurel — métriques texte/page
-  const textExtractionOk = objectStateInitial.extraction_status === "ok" && objectStateFinal.extraction_status === "ok";
+  // Versions d'extraction différentes (ex. DOCX lié avant une mise à jour) :
+  // mots/hash non comparables -> repli sur le delta taille (P0).
+  const metricsVersionMismatch = objectStateInitial.metrics_version !== objectStateFinal.metrics_version;
+  const textExtractionOk = !metricsVersionMismatch
+    && objectStateInitial.extraction_status === "ok" && objectStateFinal.extraction_status === "ok";
   const pdfExtractionOk  = objectStateInitial.extraction_status === "page_count_only" && objectStateFinal.extraction_status === "page_count_only";
   const textHashChanged = textExtractionOk
     ? (!!objectStateInitial.text_extract_hash && !!objectStateFinal.text_extract_hash
@@ -764,7 +772,8 @@ function buildObjectEvidence(boundDoc, finalBind, finalSizeNum, finalSelectedAt,
   const textDeltaRatio = (textExtractionOk && (objectStateInitial.text_length || 0) > 0)
     ? Math.round(Math.abs((objectStateFinal.text_length || 0) - (objectStateInitial.text_length || 0)) / Math.max(objectStateInitial.text_length, 1) * 10000) / 10000
     : null;
-  const extractionConfidence = textExtractionOk ? "ok" : pdfExtractionOk ? "page_count_only" : "none";
+  const extractionConfidence = textExtractionOk ? "ok" : pdfExtractionOk ? "page_count_only"
+    : metricsVersionMismatch ? "metrics_version_mismatch" : "none";
 
   const hashChanged = !!objectStateInitial.sha256 && !!objectStateFinal.sha256
     && objectStateInitial.sha256 !== objectStateFinal.sha256;
@@ -948,7 +957,9 @@ async function extractDocumentStructure(path, extension) {
         text_length: r.text_length ?? null,
         word_count: r.word_count ?? null,
         text_extract_hash: r.text_extract_hash ?? null,
+        metrics_version: r.metrics_version ?? null,
         extraction_status: r.extraction_status || "ok",
+        docx_structure: r.docx_structure ?? null,
       };
     }
     if (["txt", "md", "rst", "text"].includes(ext)) {
@@ -1002,7 +1013,11 @@ function fileExtLower(filename) {
   return ext || "bin";
 }
 
-const SUPPORTED_DOC_EXTENSIONS = new Set(["pdf", "docx", "doc", "txt", "rtf"]);
+const SUPPORTED_DOC_EXTENSIONS = new Set(["pdf", "docx", "doc", "odt", "txt", "rtf", "md", "markdown", "html", "htm"]);
+
+// Formats convertis en PDF par le sidecar humanorigin-converter avant publication
+// (LibreOffice pour docx/doc/odt/rtf, rendu natif pour md/html/txt).
+const CONVERTER_INPUT_EXTENSIONS = new Set(["docx", "doc", "odt", "rtf", "md", "markdown", "html", "htm", "txt"]);
 
 function isSupportedDocumentFile(filename) {
   const ext = (String(filename || "").split(".").pop() || "").toLowerCase();
@@ -1049,7 +1064,7 @@ async function pickDocumentToBind() {
   const selected = await open({
     multiple: false,
     directory: false,
-    filters: [{ name: "Documents supportés", extensions: ["pdf", "docx", "doc", "txt", "rtf"] }],
+    filters: [{ name: "Documents supportés", extensions: [...SUPPORTED_DOC_EXTENSIONS] }],
   });
 
   if (!selected) return null;
@@ -2431,6 +2446,37 @@ async function setupDeepLinkListeners() {
   }
 }
 
+// Progression du hachage des gros fichiers (événement Rust "file-hash-progress").
+// Affichée dans le toast tant que le fichier n'est pas entièrement haché.
+let __hashProgressListenerReady = false;
+let __hashProgressHideTimer = null;
+
+async function setupHashProgressListener() {
+  if (__hashProgressListenerReady) return;
+  __hashProgressListenerReady = true;
+
+  await listen("file-hash-progress", (ev) => {
+    const p = ev?.payload || {};
+    const total = Number(p.total_bytes) || 0;
+    const done = Number(p.bytes_hashed) || 0;
+    const pct = total > 0 ? Math.min(100, Math.floor((done * 100) / total)) : 0;
+    const el = $("toast");
+    if (!el) return;
+
+    clearTimeout(__hashProgressHideTimer);
+    el.innerText = hoPerm(
+      `Calcul de l'empreinte de ${p.file_name || "fichier"} : ${pct} %`,
+      `Hashing ${p.file_name || "file"}: ${pct}%`
+    );
+    el.style.display = "block";
+    if (total > 0 && done >= total) {
+      __hashProgressHideTimer = setTimeout(() => {
+        el.style.display = "none";
+      }, 1500);
+    }
+  }).catch(() => {});
+}
+
 // =========================================================
 // PROJECTS
 // =========================================================
@@ -3212,6 +3258,7 @@ function buildPublicationJob({
   certificateId,
   verifyUrl,
   verdict,
+  signaturePolicy,
 } = {}) {
   const job = {
     job_version: "1.0",
@@ -3237,10 +3284,31 @@ function buildPublicationJob({
       margin_pt: 34.0,
     },
   };
+  if (signaturePolicy) job.render.signature_policy = signaturePolicy;
 
   return JSON.stringify(job, null, 2);
 }
 
+// Source PDF signée (contrat e-signé…) : la publication la refuse par défaut
+// (SOURCE_PDF_SIGNED). L'utilisateur peut alors choisir "preserve" : la
+// cartouche est ajoutée en révision incrémentale, chaque signature reste valide.
+function confirmPreserveSignatures() {
+  return confirm(
+    "Ce PDF porte une signature électronique. Ajouter la cartouche HumanOrigin sans invalider la signature (révision ajoutée à la fin du fichier) ?"
+  );
+}
+
+// Commande de package Work PDF : relancée en signaturePolicy "preserve" si la
+// source est signée et que l'utilisateur l'accepte ; sinon l'erreur remonte.
+async function invokeWorkPdfPackage(command, args) {
+  try {
+    return await invoke(command, args);
+  } catch (e) {
+    if (!String(e || "").includes("SOURCE_PDF_SIGNED") || !confirmPreserveSignatures()) throw e;
+    return await invoke(command, { ...args, signaturePolicy: "preserve" });
+  }
+}
+
 async function runPublisherSidecar({ jobPath, fallbackInput } = {}) {
   if (!jobPath) {
     throw new Error("Publisher sidecar requires jobPath");
@@ -3853,7 +3921,7 @@ async function exportFinalProjectCertificate() {
     const publishedPdfFilename = "HumanOrigin_PUBLISHED.pdf";
     const publishedPdfPath = `${dir}${sep}${publishedPdfFilename}`;
       const isWindowsExport = (navigator.platform || "").toLowerCase().includes("win");
-      const canGeneratePublishedPdf = !isWindowsExport && (bind.mime === "application/pdf" || bindExtLower === "docx");
+      const canGeneratePublishedPdf = !isWindowsExport && (bind.mime === "application/pdf" || CONVERTER_INPUT_EXTENSIONS.has(bindExtLower));
 
     const publicationJobPath = `${dir}${sep}HumanOrigin_PUBLICATION_JOB.json`;
 
@@ -4570,7 +4638,7 @@ async function exportFinalProjectCertificate() {
         let sourcePdfForPublishing = publishedDocumentPath;
 
         // ── Garde PDF magic bytes — vérifier %PDF- avant d'appeler Pdfium ─────
-        if (bindExtLower !== "docx") {
+        if (!CONVERTER_INPUT_EXTENSIONS.has(bindExtLower)) {
           console.log("[PUBLISHER GUARD] source:", sourcePdfForPublishing, "ext:", bindExtLower);
           let _magic = null;
           try { _magic = await readBinaryFile(sourcePdfForPublishing); } catch {}
@@ -4585,7 +4653,7 @@ async function exportFinalProjectCertificate() {
           }
         }
 
-        if (bindExtLower === "docx") {
+        if (CONVERTER_INPUT_EXTENSIONS.has(bindExtLower)) {
           const converterOutputDir = `${dir}${sep}HumanOrigin_CONVERTED`;
 
           await createDir(converterOutputDir, { recursive: true });
@@ -4613,9 +4681,26 @@ async function exportFinalProjectCertificate() {
         await writeTextFile(publicationJobPath, publicationJobJson);
 
         console.log("[PUBLISHER GUARD] calling publish_pdf_core, source:", sourcePdfForPublishing);
-        const publishResult = await invoke("publish_pdf_core", {
+        let publishResult = await invoke("publish_pdf_core", {
           input: JSON.parse(publicationJobJson),
         });
+        if (publishResult?.error_code === "SOURCE_PDF_SIGNED" && confirmPreserveSignatures()) {
+          const preserveJobJson = buildPublicationJob({
+            sourcePdfPath: sourcePdfForPublishing,
+            outputPdfPath: publishedPdfPath,
+            cartouchePngPath: cartoucheCompactPngPath,
+            certificateJsonPath: hoPath,
+            verifyTxtPath,
+            certificateId,
+            verifyUrl: verifierUrl,
+            verdict,
+            signaturePolicy: "preserve",
+          });
+          await writeTextFile(publicationJobPath, preserveJobJson);
+          publishResult = await invoke("publish_pdf_core", {
+            input: JSON.parse(preserveJobJson),
+          });
+        }
 
         console.log("[PUBLISHER RESULT]", publishResult);
 
@@ -4626,8 +4711,8 @@ async function exportFinalProjectCertificate() {
 
         hoDocV1.payload.publication = {
           status: "visible_published_copy_included",
-          relationship: bindExtLower === "docx"
-            ? "published_pdf_generated_from_bound_docx_then_marked_by_humanorigin"
+          relationship: CONVERTER_INPUT_EXTENSIONS.has(bindExtLower)
+            ? `published_pdf_generated_from_bound_${bindExtLower}_then_marked_by_humanorigin`
             : "published_pdf_generated_from_bound_pdf_then_marked_by_humanorigin",
           source: {
             role: "bound_source_document",
@@ -7163,6 +7248,7 @@ window.tauriCheckAndInstallUpdate = tauriCheckAndInstallUpdate;
 // BOOT
 // =========================================================
 setupDeepLinkListeners().catch(() => {});
+setupHashProgressListener().catch(() => {});
 
 window.addEventListener("focus", () => {
   if (isPermissionsScreenVisible()) {
@@ -7175,6 +7261,7 @@ window.addEventListener("focus", () => {
 window.addEventListener("DOMContentLoaded", async () => {
   hoBootMark("dom:loaded");
   setupDeepLinkListeners().catch(() => {});
+  setupHashProgressListener().catch(() => {});
 
   const __hoLoginTitle = document.querySelector("#login-screen .brand-title");
   if (__hoLoginTitle) __hoLoginTitle.innerText = "Accédez à votre espace HumanOrigin";
@@ -7520,7 +7607,7 @@ window.addEventListener("DOMContentLoaded", async () => {
       try {
         if (!workId) return log("Créer un Work d'abord.");
         if (!sourcePdfPath) return log("Choisir un PDF d'abord.");
-        const r = await invoke("create_native_labeled_work_package", {
+        const r = await invokeWorkPdfPackage("create_native_labeled_work_package", {
           workId,
           sourcePdfPath,
           verifyUrl: VERIFY_URL,
@@ -7539,7 +7626,7 @@ window.addEventListener("DOMContentLoaded", async () => {
         if (!workId) return log("Créer un Work d'abord.");
         if (!sourcePdfPath) return log("Choisir un PDF d'abord.");
         if (!cartouchePngPath) return log("Choisir une cartouche PNG d'abord.");
-        const r = await invoke("create_labeled_work_package", {
+        const r = await invokeWorkPdfPackage("create_labeled_work_package", {
           workId,
           sourcePdfPath,
           cartouchePngPath,
@@ -7998,7 +8085,7 @@ window.addEventListener("DOMContentLoaded", async () => {
         enable("alpha-create", false);
         status("Création du document HumanOrigin…");
         try {
-          const r = await invoke("create_native_labeled_work_package", {
+          const r = await invokeWorkPdfPackage("create_native_labeled_work_package", {
             workId,
             sourcePdfPath: selectedPdfPath,
             verifyUrl: ALPHA_VERIFY_URL,
@@ -8031,6 +8118,12 @@ window.addEventListener("DOMContentLoaded", async () => {
             msg = "Le document a été modifié après la fin de l'observation.";
           else if (raw.includes("AlreadyExists"))
             msg = "Ce document HumanOrigin existe déjà.";
+          else if (raw.includes("SOURCE_PDF_SIGNED"))
+            msg =
+              "Ce PDF est signé électroniquement : document HumanOrigin non créé pour ne pas invalider la signature.";
+          else if (raw.includes("SIGNATURE_FORBIDS_STAMP"))
+            msg =
+              "La signature certifiante de ce PDF interdit tout ajout : la cartouche ne peut pas y être apposée.";
           status(msg);
           if (!raw.includes("AlreadyExists")) enable("alpha-create", true);
         } finally {
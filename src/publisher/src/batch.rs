@@ -0,0 +1,314 @@
+//! Mode batch : plusieurs jobs, une seule liaison PDFium.
+//!
+//! Entrée : un dossier (tous les `*.json`, triés par nom) ou un fichier
+//! JSONL (un job par ligne non vide).
+//! Sortie : exactement un `PublicationResult` JSON par job sur stdout,
+//! dans l'ordre d'entrée ; un échec n'interrompt pas le lot.
+//! Résumé (compteurs) sur stderr uniquement, stdout reste parsable ligne à ligne.
+//!
+//! Liaison PDFium, publication et émission des résultats passent par des seams
+//! (`run_batch_with`) : les tests unitaires n'ont besoin d'aucun PDFium.
+
+use crate::job::{self, PublicationJob};
+use crate::pdf_publish;
+use crate::result::PublicationResult;
+use std::fs;
+use std::path::Path;
+
+/// Tous les jobs ont réussi.
+pub const EXIT_ALL_OK: i32 = 0;
+/// Le lot n'a pas pu démarrer (arguments, lecture de l'entrée, PDFium).
+pub const EXIT_BATCH_FAILED: i32 = 1;
+/// Le lot a été traité mais au moins un job a échoué.
+pub const EXIT_SOME_FAILED: i32 = 2;
+
+/// Une entrée du lot : origine lisible + contenu JSON brut du job.
+struct BatchEntry {
+    source: String,
+    raw: Result<String, String>,
+}
+
+fn collect_entries(input: &Path) -> Result<Vec<BatchEntry>, String> {
+    if input.is_dir() {
+        let mut paths: Vec<_> = fs::read_dir(input)
+            .map_err(|e| format!("Unable to read batch directory: {e}"))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.is_file()
+                    && p.extension()
+                        .map(|x| x.eq_ignore_ascii_case("json"))
+                        .unwrap_or(false)
+            })
+            .collect();
+        paths.sort();
+
+        Ok(paths
+            .into_iter()
+            .map(|p| BatchEntry {
+                source: p.to_string_lossy().to_string(),
+                raw: fs::read_to_string(&p)
+                    .map_err(|e| format!("Unable to read publication job file: {e}")),
+            })
+            .collect())
+    } else {
+        let content =
+            fs::read_to_string(input).map_err(|e| format!("Unable to read batch file: {e}"))?;
+
+        Ok(content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| BatchEntry {
+                source: format!("{}:{}", input.to_string_lossy(), i + 1),
+                raw: Ok(line.to_string()),
+            })
+            .collect())
+    }
+}
+
+fn run_entry<E, P>(engine: &E, entry: &BatchEntry, publish: &P) -> PublicationResult
+where
+    P: Fn(&E, &PublicationJob) -> PublicationResult,
+{
+    let raw = match &entry.raw {
+        Ok(v) => v,
+        Err(msg) => return PublicationResult::err("JOB_READ_FAILED", msg),
+    };
+
+    let job = match job::parse_job(raw) {
+        Ok(v) => v,
+        Err(e) => return PublicationResult::err(e.code, &e.message),
+    };
+
+    if job.job_type != "pdf_publication" {
+        return PublicationResult::err(
+            "JOB_TYPE_UNSUPPORTED",
+            &format!("Unsupported job type: {}", job.job_type),
+        );
+    }
+
+    publish(engine, &job)
+}
+
+/// Exécute le lot et retourne le code de sortie du process.
+pub fn run_batch(input: &Path) -> i32 {
+    run_batch_with(
+        input,
+        pdf_publish::bind_pdfium,
+        pdf_publish::run_pdf_publication_with,
+        crate::print_result,
+    )
+}
+
+/// `run_batch` avec ses seams : `bind` (liaison unique du moteur), `publish`
+/// (un job) et `emit` (un résultat, dans l'ordre d'entrée).
+fn run_batch_with<E, B, P, O>(input: &Path, bind: B, publish: P, mut emit: O) -> i32
+where
+    B: FnOnce() -> Result<E, String>,
+    P: Fn(&E, &PublicationJob) -> PublicationResult,
+    O: FnMut(&PublicationResult),
+{
+    let entries = match collect_entries(input) {
+        Ok(v) => v,
+        Err(msg) => {
+            emit(&PublicationResult::err("BATCH_READ_FAILED", &msg));
+            return EXIT_BATCH_FAILED;
+        }
+    };
+
+    if entries.is_empty() {
+        emit(&PublicationResult::err(
+            "BATCH_EMPTY",
+            "Batch input contains no publication job",
+        ));
+        return EXIT_BATCH_FAILED;
+    }
+
+    let engine = match bind() {
+        Ok(v) => v,
+        Err(msg) => {
+            emit(&PublicationResult::err("PDFIUM_BIND_FAILED", &msg));
+            return EXIT_BATCH_FAILED;
+        }
+    };
+
+    let total = entries.len();
+    let mut failed = 0usize;
+
+    for entry in &entries {
+        let result = run_entry(&engine, entry, &publish).with_job_source(entry.source.clone());
+        if !result.ok {
+            failed += 1;
+        }
+        emit(&result);
+    }
+
+    eprintln!(
+        "batch summary: total={} ok={} failed={}",
+        total,
+        total - failed,
+        failed
+    );
+
+    if failed == 0 {
+        EXIT_ALL_OK
+    } else {
+        EXIT_SOME_FAILED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn temp_base() -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "ho_batch_test_{}_{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Job valide sur une ligne ; `certificate_id` sert de marqueur aux stubs.
+    fn job_line(certificate_id: &str) -> String {
+        serde_json::json!({
+            "job_version": "1.0",
+            "job_type": "pdf_publication",
+            "source_pdf_path": "/tmp/source.pdf",
+            "output_pdf_path": format!("/tmp/{certificate_id}.pdf"),
+            "cartouche_png_path": "/tmp/cartouche.png",
+            "certificate_json_path": null,
+            "verify_txt_path": null,
+            "certificate_id": certificate_id,
+            "verify_url": "https://humanorigin.io/verify/x",
+            "verdict": "human",
+            "render": {
+                "mode": "compact_cartouche",
+                "pages": "first",
+                "first_page_scale": 1.0,
+                "other_pages_scale": 0.85,
+                "anchor": "bottom_right",
+                "margin_pt": 34.0
+            }
+        })
+        .to_string()
+    }
+
+    /// Stub de publication : échoue pour les certificats `fail*`.
+    fn stub_publish(_engine: &(), job: &PublicationJob) -> PublicationResult {
+        if job.certificate_id.starts_with("fail") {
+            PublicationResult::err("PDF_OPEN_FAILED", "stub failure")
+        } else {
+            PublicationResult::ok(job.output_pdf_path.clone(), 1, "stub", vec![])
+        }
+    }
+
+    fn run_stub(input: &Path) -> (i32, Vec<PublicationResult>) {
+        let mut results = Vec::new();
+        let code = run_batch_with(input, || Ok(()), stub_publish, |r| results.push(r.clone()));
+        (code, results)
+    }
+
+    #[test]
+    fn test_1_dossier_seuls_json_tries() {
+        let dir = temp_base();
+        fs::write(dir.join("b.json"), job_line("b")).unwrap();
+        fs::write(dir.join("a.JSON"), job_line("a")).unwrap();
+        fs::write(dir.join("notes.txt"), "pas un job").unwrap();
+        fs::create_dir(dir.join("c.json")).unwrap();
+
+        let entries = collect_entries(&dir).unwrap();
+        let sources: Vec<_> = entries.iter().map(|e| e.source.clone()).collect();
+        assert_eq!(
+            sources,
+            vec![
+                dir.join("a.JSON").to_string_lossy().to_string(),
+                dir.join("b.json").to_string_lossy().to_string(),
+            ]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_2_jsonl_lignes_vides_ignorees_ligne_invalide_localisee() {
+        let dir = temp_base();
+        let jsonl = dir.join("jobs.jsonl");
+        fs::write(
+            &jsonl,
+            format!(
+                "{}\n\n   \n{{ pas du json\n{}\n",
+                job_line("a"),
+                job_line("b")
+            ),
+        )
+        .unwrap();
+
+        let (code, results) = run_stub(&jsonl);
+        assert_eq!(code, EXIT_SOME_FAILED);
+        let sources: Vec<_> = results
+            .iter()
+            .map(|r| r.job_source.clone().unwrap())
+            .collect();
+        let prefix = jsonl.to_string_lossy();
+        assert_eq!(
+            sources,
+            vec![
+                format!("{prefix}:1"),
+                format!("{prefix}:4"),
+                format!("{prefix}:5")
+            ]
+        );
+        assert_eq!(results[1].error_code.as_deref(), Some("JOB_PARSE_FAILED"));
+        assert!(results[0].ok && results[2].ok);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_3_un_echec_n_interrompt_pas_le_lot() {
+        let dir = temp_base();
+        for (name, id) in [("1.json", "ok-1"), ("2.json", "fail-2"), ("3.json", "ok-3")] {
+            fs::write(dir.join(name), job_line(id)).unwrap();
+        }
+        let (code, results) = run_stub(&dir);
+        assert_eq!(code, EXIT_SOME_FAILED);
+        let oks: Vec<_> = results.iter().map(|r| r.ok).collect();
+        assert_eq!(oks, vec![true, false, true]);
+        assert_eq!(results[1].error_code.as_deref(), Some("PDF_OPEN_FAILED"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_4_codes_de_sortie() {
+        let dir = temp_base();
+        fs::write(dir.join("1.json"), job_line("ok-1")).unwrap();
+        assert_eq!(run_stub(&dir).0, EXIT_ALL_OK);
+
+        // Lot lui-même en échec : entrée illisible, vide, ou moteur non lié.
+        let (code, results) = run_stub(&dir.join("absent.jsonl"));
+        assert_eq!(code, EXIT_BATCH_FAILED);
+        assert_eq!(results[0].error_code.as_deref(), Some("BATCH_READ_FAILED"));
+
+        let empty = dir.join("empty.jsonl");
+        fs::write(&empty, "\n\n").unwrap();
+        let (code, results) = run_stub(&empty);
+        assert_eq!(code, EXIT_BATCH_FAILED);
+        assert_eq!(results[0].error_code.as_deref(), Some("BATCH_EMPTY"));
+
+        let mut results = Vec::new();
+        let code = run_batch_with(
+            &dir,
+            || Err::<(), _>("no pdfium".to_string()),
+            stub_publish,
+            |r| results.push(r.clone()),
+        );
+        assert_eq!(code, EXIT_BATCH_FAILED);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].error_code.as_deref(), Some("PDFIUM_BIND_FAILED"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
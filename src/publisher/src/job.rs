@@ -1,18 +1,90 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Versions de job acceptées par ce publisher.
+/// Toute autre valeur est rejetée AVANT désérialisation complète
+/// (code `JOB_VERSION_UNSUPPORTED`), jamais interprétée "au mieux".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum JobVersion {
+    #[serde(rename = "1.0")]
+    V1_0,
+}
+
+impl JobVersion {
+    pub const SUPPORTED: &'static [&'static str] = &["1.0"];
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "1.0" => Some(JobVersion::V1_0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderMode {
+    CompactCartouche,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PageSelection {
+    First,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Anchor {
+    BottomRight,
+}
+
+/// Conformité du PDF produit. `pdfa_2b` : post-traitement PDF/A-2b puis
+/// contrôle bloquant (`PDFA_VALIDATION_FAILED` si non conforme). Aucune police
+/// n'est embarquée : une source à polices non embarquées (ex. rendu natif du
+/// convertisseur, polices standard 14) ou chiffrée est refusée AVANT tout
+/// rendu, en `PDFA_SOURCE_UNSUPPORTED`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PdfConformance {
+    #[default]
+    Standard,
+    #[serde(rename = "pdfa_2b")]
+    Pdfa2b,
+}
+
+/// Politique si la source porte des signatures PDF (contrat e-signé, etc.).
+/// `refuse` (défaut) : `SOURCE_PDF_SIGNED`. `warn` : réécriture complète,
+/// signatures invalidées + avertissement. `preserve` : mise à jour incrémentale.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SignaturePolicy {
+    #[default]
+    Refuse,
+    Warn,
+    Preserve,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct RenderOptions {
-    pub mode: String,
-    pub pages: String,
+    pub mode: RenderMode,
+    pub pages: PageSelection,
     pub first_page_scale: f32,
     pub other_pages_scale: f32,
-    pub anchor: String,
+    pub anchor: Anchor,
     pub margin_pt: f32,
+    #[serde(default)]
+    pub conformance: PdfConformance,
+    #[serde(default)]
+    pub signature_policy: SignaturePolicy,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct PublicationJob {
-    pub job_version: String,
+    pub job_version: JobVersion,
     pub job_type: String,
 
     pub source_pdf_path: String,
@@ -27,4 +99,151 @@ pub struct PublicationJob {
     pub verdict: String,
 
     pub render: RenderOptions,
-}
\ No newline at end of file
+}
+
+/// Erreur de lecture d'un job : code stable + message lisible.
+#[derive(Debug, Clone)]
+pub struct JobError {
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// Parse un job JSON en deux temps :
+/// 1. `job_version` est lu seul et confronté à `JobVersion::SUPPORTED`
+///    (code `JOB_VERSION_UNSUPPORTED`) ;
+/// 2. le job complet est désérialisé strictement (enums fermés, champs
+///    inconnus refusés, code `JOB_PARSE_FAILED`).
+pub fn parse_job(raw: &str) -> Result<PublicationJob, JobError> {
+    let value: serde_json::Value = serde_json::from_str(raw).map_err(|e| JobError {
+        code: "JOB_PARSE_FAILED",
+        message: format!("Unable to parse publication job file: {e}"),
+    })?;
+
+    match value.get("job_version").and_then(|v| v.as_str()) {
+        Some(v) if JobVersion::parse(v).is_some() => {}
+        Some(v) => {
+            return Err(JobError {
+                code: "JOB_VERSION_UNSUPPORTED",
+                message: format!(
+                    "Unsupported job version: {v} (supported: {})",
+                    JobVersion::SUPPORTED.join(", ")
+                ),
+            });
+        }
+        None => {
+            return Err(JobError {
+                code: "JOB_VERSION_UNSUPPORTED",
+                message: format!(
+                    "Missing or non-string job_version (supported: {})",
+                    JobVersion::SUPPORTED.join(", ")
+                ),
+            });
+        }
+    }
+
+    serde_json::from_value(value).map_err(|e| JobError {
+        code: "JOB_PARSE_FAILED",
+        message: format!("Unable to parse publication job file: {e}"),
+    })
+}
+
+/// JSON Schema du job, généré depuis les types ci-dessus.
+/// Copie publiée : `src/publisher/schema/publication_job.schema.json`
+/// (régénérer via `humanorigin-publisher --print-schema`).
+pub fn job_schema_json() -> String {
+    let schema = schemars::schema_for!(PublicationJob);
+    serde_json::to_string_pretty(&schema).unwrap_or_else(|_| "{}".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_job() -> serde_json::Value {
+        serde_json::json!({
+            "job_version": "1.0",
+            "job_type": "pdf_publication",
+            "source_pdf_path": "/tmp/source.pdf",
+            "output_pdf_path": "/tmp/published.pdf",
+            "cartouche_png_path": "/tmp/cartouche.png",
+            "certificate_json_path": null,
+            "verify_txt_path": null,
+            "certificate_id": "cert-1",
+            "verify_url": "https://humanorigin.io/verify/cert-1",
+            "verdict": "human",
+            "render": {
+                "mode": "compact_cartouche",
+                "pages": "first",
+                "first_page_scale": 1.0,
+                "other_pages_scale": 0.85,
+                "anchor": "bottom_right",
+                "margin_pt": 34.0
+            }
+        })
+    }
+
+    fn parse_error(value: &serde_json::Value) -> JobError {
+        match parse_job(&value.to_string()) {
+            Ok(_) => panic!("job accepté à tort : {value}"),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn test_1_job_valide() {
+        let job = parse_job(&sample_job().to_string()).unwrap();
+        assert_eq!(job.job_version, JobVersion::V1_0);
+        assert_eq!(job.render.pages, PageSelection::First);
+        assert_eq!(job.render.conformance, PdfConformance::Standard);
+        assert_eq!(job.render.signature_policy, SignaturePolicy::Refuse);
+    }
+
+    #[test]
+    fn test_2_version_inconnue_ou_absente() {
+        for version in [serde_json::json!("2.0"), serde_json::json!(1.0)] {
+            let mut v = sample_job();
+            v["job_version"] = version;
+            assert_eq!(parse_error(&v).code, "JOB_VERSION_UNSUPPORTED");
+        }
+        let mut v = sample_job();
+        v.as_object_mut().unwrap().remove("job_version");
+        assert_eq!(parse_error(&v).code, "JOB_VERSION_UNSUPPORTED");
+    }
+
+    #[test]
+    fn test_3_enums_fermes_et_champs_inconnus() {
+        for (field, bad) in [
+            ("mode", "full_page"),
+            ("pages", "last"),
+            ("anchor", "top_left"),
+            ("conformance", "pdfa"),
+            ("signature_policy", "ignore"),
+        ] {
+            let mut v = sample_job();
+            v["render"][field] = serde_json::json!(bad);
+            assert_eq!(parse_error(&v).code, "JOB_PARSE_FAILED", "{field}={bad}");
+        }
+
+        let mut v = sample_job();
+        v["watermark"] = serde_json::json!(true);
+        assert_eq!(parse_error(&v).code, "JOB_PARSE_FAILED");
+        let mut v = sample_job();
+        v["render"]["opacity"] = serde_json::json!(0.5);
+        assert_eq!(parse_error(&v).code, "JOB_PARSE_FAILED");
+
+        assert_eq!(
+            parse_job("{ not json").err().map(|e| e.code),
+            Some("JOB_PARSE_FAILED")
+        );
+    }
+
+    #[test]
+    fn test_4_schema_publie_a_jour() {
+        let published = include_str!("../schema/publication_job.schema.json");
+        assert_eq!(
+            job_schema_json(),
+            published.trim_end(),
+            "régénérer schema/publication_job.schema.json via --print-schema"
+        );
+    }
+}
@@ -1,30 +1,49 @@
+mod batch;
 mod job;
 mod pdf_publish;
+// Implémentations PDF/A et signatures uniques, partagées avec l'app.
+#[path = "../../../src-tauri/src/pdf_signatures.rs"]
+mod pdf_signatures;
+#[path = "../../../src-tauri/src/pdfa.rs"]
+mod pdfa;
 mod result;
 
-use job::PublicationJob;
 use result::PublicationResult;
 use std::env;
 use std::fs;
+use std::path::Path;
 
-fn print_and_exit(result: PublicationResult, code: i32) -> ! {
+fn print_result(result: &PublicationResult) {
     println!(
         "{}",
-        serde_json::to_string(&result).unwrap_or_else(|_| {
+        serde_json::to_string(result).unwrap_or_else(|_| {
             "{\"ok\":false,\"error_code\":\"SERIALIZE_FAILED\",\"message\":\"Unable to serialize result\"}".to_string()
         })
     );
+}
+
+fn print_and_exit(result: PublicationResult, code: i32) -> ! {
+    print_result(&result);
     std::process::exit(code);
 }
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
+    if args.len() == 2 && args[1] == "--print-schema" {
+        println!("{}", job::job_schema_json());
+        std::process::exit(0);
+    }
+
+    if args.len() == 3 && args[1] == "--batch" {
+        std::process::exit(batch::run_batch(Path::new(&args[2])));
+    }
+
     if args.len() < 3 || args[1] != "--job" {
         print_and_exit(
             PublicationResult::err(
                 "BAD_ARGS",
-                "Usage: humanorigin-publisher --job <path-to-publication-job.json>",
+                "Usage: humanorigin-publisher --job <path-to-publication-job.json> | --batch <dir|jobs.jsonl> | --print-schema",
             ),
             1,
         );
@@ -45,16 +64,10 @@ fn main() {
         }
     };
 
-    let job: PublicationJob = match serde_json::from_str(&raw) {
+    let job = match job::parse_job(&raw) {
         Ok(v) => v,
         Err(e) => {
-            print_and_exit(
-                PublicationResult::err(
-                    "JOB_PARSE_FAILED",
-                    &format!("Unable to parse publication job file: {e}"),
-                ),
-                1,
-            );
+            print_and_exit(PublicationResult::err(e.code, &e.message), 1);
         }
     };
 
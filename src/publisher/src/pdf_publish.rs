@@ -1,4 +1,5 @@
-use crate::job::PublicationJob;
+use crate::job::{PageSelection, PdfConformance, PublicationJob, SignaturePolicy};
+use crate::pdf_signatures::SignatureScan;
 use crate::result::PublicationResult;
 use image::GenericImageView;
 use image::ImageReader;
@@ -60,16 +61,15 @@ fn add_clickable_link_on_page(
     Ok(())
 }
 
-fn render_cartouche_on_page(
-    page: &mut PdfPage,
-    cartouche: &image::DynamicImage,
+/// Géométrie de la cartouche (coin bas-droit), partagée entre le rendu PDFium
+/// et l'estampille incrémentale (`pdf_signatures`).
+fn cartouche_placement(
+    page_w: f32,
+    (img_w, img_h): (u32, u32),
     scale: f32,
     margin_pt: f32,
     is_first_page: bool,
-) -> Result<CartouchePlacement, PdfiumError> {
-    let page_w = page.width().value;
-
-    let (img_w, img_h) = cartouche.dimensions();
+) -> CartouchePlacement {
     let image_ratio = img_h as f32 / img_w as f32;
 
     let (base_w_mm, base_h_mm) = if is_first_page {
@@ -102,33 +102,187 @@ fn render_cartouche_on_page(
     let x = (page_w - margin - target_w).max(0.0);
     let y = (margin).max(0.0);
 
+    CartouchePlacement {
+        x,
+        y,
+        w: target_w,
+        h: target_h,
+    }
+}
+
+/// Pages recevant la cartouche : la première seule (`first`) ou toutes (`all`).
+fn page_is_marked(selection: PageSelection, index: usize) -> bool {
+    selection == PageSelection::All || index == 0
+}
+
+fn render_cartouche_on_page(
+    page: &mut PdfPage,
+    cartouche: &image::DynamicImage,
+    scale: f32,
+    margin_pt: f32,
+    is_first_page: bool,
+) -> Result<CartouchePlacement, PdfiumError> {
+    let placement = cartouche_placement(
+        page.width().value,
+        cartouche.dimensions(),
+        scale,
+        margin_pt,
+        is_first_page,
+    );
+
     page.objects_mut().create_image_object(
-        PdfPoints::new(x),
-        PdfPoints::new(y),
+        PdfPoints::new(placement.x),
+        PdfPoints::new(placement.y),
         cartouche,
-        Some(PdfPoints::new(target_w)),
-        Some(PdfPoints::new(target_h)),
+        Some(PdfPoints::new(placement.w)),
+        Some(PdfPoints::new(placement.h)),
     )?;
 
     page.regenerate_content()?;
 
-    Ok(CartouchePlacement {
-        x,
-        y,
-        w: target_w,
-        h: target_h,
-    })
+    Ok(placement)
 }
 
-pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
-    let pdfium = match bind_pdfium_silent() {
-        Ok(v) => v,
+fn decode_cartouche(path: &str) -> Result<image::DynamicImage, (&'static str, String)> {
+    match ImageReader::open(path) {
+        Ok(r) => r.decode().map_err(|e| {
+            (
+                "CARTOUCHE_DECODE_FAILED",
+                format!("Unable to decode cartouche PNG: {e}"),
+            )
+        }),
+        Err(e) => Err((
+            "CARTOUCHE_OPEN_FAILED",
+            format!("Unable to open cartouche PNG: {e}"),
+        )),
+    }
+}
+
+/// Politique `preserve` : cartouche ajoutée en mise à jour incrémentale,
+/// sans PDFium, pour que les signatures existantes restent vérifiables.
+fn run_incremental_publication(job: &PublicationJob, scan: &SignatureScan) -> PublicationResult {
+    if job.render.conformance == PdfConformance::Pdfa2b {
+        return PublicationResult::err(
+            "JOB_OPTIONS_CONFLICT",
+            "signature_policy=preserve cannot be combined with conformance=pdfa_2b \
+             (PDF/A conversion rewrites the whole file)",
+        );
+    }
+    if !scan.allows_annotations() {
+        return PublicationResult::err(
+            "SIGNATURE_FORBIDS_STAMP",
+            &format!(
+                "Certifying signature (DocMDP P={}) forbids adding annotations",
+                scan.docmdp_permission.unwrap_or_default()
+            ),
+        );
+    }
+
+    let cartouche = match decode_cartouche(&job.cartouche_png_path) {
+        Ok(img) => img,
+        Err((code, msg)) => return PublicationResult::err(code, &msg),
+    };
+    let margin_pt = if job.render.margin_pt > 0.0 {
+        job.render.margin_pt
+    } else {
+        mm_to_pt(12.0)
+    };
+    let dims = cartouche.dimensions();
+
+    let output = std::path::Path::new(&job.output_pdf_path);
+    let stamped = crate::pdf_signatures::stamp_incremental(
+        std::path::Path::new(&job.source_pdf_path),
+        output,
+        &cartouche,
+        &job.verify_url,
+        |index, page_w, _page_h| {
+            if !page_is_marked(job.render.pages, index) {
+                return None;
+            }
+            let raw_scale = if index == 0 {
+                job.render.first_page_scale
+            } else {
+                job.render.other_pages_scale
+            };
+            let scale = clamp_f32(raw_scale, 0.72, 1.55);
+            let p = cartouche_placement(page_w, dims, scale, margin_pt, index == 0);
+            Some([p.x, p.y, p.w, p.h])
+        },
+    );
+
+    match stamped {
+        Ok(pages_marked) => PublicationResult::ok(
+            job.output_pdf_path.clone(),
+            pages_marked,
+            "lopdf-incremental",
+            vec![format!(
+                "incremental update: {} existing signature(s) preserved",
+                scan.signed_fields.len()
+            )],
+        ),
         Err(e) => {
-            return PublicationResult::err(
-                "PDFIUM_BIND_FAILED",
-                &format!("Unable to bind PDFium: {e}"),
-            );
+            let _ = std::fs::remove_file(output);
+            PublicationResult::err("INCREMENTAL_UPDATE_FAILED", &e)
         }
+    }
+}
+
+/// Signatures existantes : `Ok(warnings)` pour poursuivre le rendu PDFium,
+/// `Err(result)` pour s'arrêter (refus, ou publication incrémentale déjà
+/// effectuée en politique `preserve`). Seules des signatures effectivement
+/// trouvées bloquent : un source que lopdf ne sait pas lire (mais que PDFium
+/// ouvre) est publié avec un avertissement, comme avant le scan.
+fn apply_signature_policy(job: &PublicationJob) -> Result<Vec<String>, Box<PublicationResult>> {
+    let scan = crate::pdf_signatures::scan_signatures(std::path::Path::new(&job.source_pdf_path));
+    match (scan, job.render.signature_policy) {
+        (Ok(scan), policy) if scan.is_signed() => {
+            let fields = scan.signed_fields.join(", ");
+            match policy {
+                SignaturePolicy::Refuse => Err(Box::new(PublicationResult::err(
+                    "SOURCE_PDF_SIGNED",
+                    &format!(
+                        "Source PDF carries {} signature(s) ({fields}); set \
+                         render.signature_policy to \"warn\" or \"preserve\"",
+                        scan.signed_fields.len()
+                    ),
+                ))),
+                SignaturePolicy::Warn => Ok(vec![format!(
+                    "{} existing signature(s) invalidated by full rewrite: {fields}",
+                    scan.signed_fields.len()
+                )]),
+                SignaturePolicy::Preserve => Err(Box::new(run_incremental_publication(job, &scan))),
+            }
+        }
+        (Ok(_), _) => Ok(vec![]),
+        (Err(e), _) => Ok(vec![format!("signature scan unavailable: {e}")]),
+    }
+}
+
+pub fn bind_pdfium() -> Result<Pdfium, String> {
+    bind_pdfium_silent().map_err(|e| format!("Unable to bind PDFium: {e}"))
+}
+
+pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
+    match bind_pdfium() {
+        Ok(pdfium) => run_pdf_publication_with(&pdfium, job),
+        Err(msg) => PublicationResult::err("PDFIUM_BIND_FAILED", &msg),
+    }
+}
+
+/// Publication avec une instance PDFium fournie par l'appelant
+/// (mode batch : une seule liaison pour tous les jobs).
+pub fn run_pdf_publication_with(pdfium: &Pdfium, job: &PublicationJob) -> PublicationResult {
+    if job.render.conformance == PdfConformance::Pdfa2b {
+        // Refus avant tout rendu : le post-traitement PDF/A n'embarque aucune police.
+        let source = std::path::Path::new(&job.source_pdf_path);
+        if let Err(e) = crate::pdfa::check_pdfa2b_source(source) {
+            return PublicationResult::err("PDFA_SOURCE_UNSUPPORTED", &e);
+        }
+    }
+
+    let mut warnings = match apply_signature_policy(job) {
+        Ok(w) => w,
+        Err(result) => return *result,
     };
 
     let mut document = match pdfium.load_pdf_from_file(&job.source_pdf_path, None) {
@@ -147,22 +301,9 @@ pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
         return PublicationResult::err("EMPTY_PDF", "Source PDF contains no pages");
     }
 
-    let cartouche = match ImageReader::open(&job.cartouche_png_path) {
-        Ok(r) => match r.decode() {
-            Ok(img) => img,
-            Err(e) => {
-                return PublicationResult::err(
-                    "CARTOUCHE_DECODE_FAILED",
-                    &format!("Unable to decode cartouche PNG: {e}"),
-                );
-            }
-        },
-        Err(e) => {
-            return PublicationResult::err(
-                "CARTOUCHE_OPEN_FAILED",
-                &format!("Unable to open cartouche PNG: {e}"),
-            );
-        }
+    let cartouche = match decode_cartouche(&job.cartouche_png_path) {
+        Ok(img) => img,
+        Err((code, msg)) => return PublicationResult::err(code, &msg),
     };
 
     let margin_pt = if job.render.margin_pt > 0.0 {
@@ -171,9 +312,12 @@ pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
         mm_to_pt(12.0)
     };
 
-    let mut warnings = vec![];
+    let mut pages_marked = 0u32;
 
     for index in 0..page_count {
+        if !page_is_marked(job.render.pages, index as usize) {
+            break;
+        }
         let page_res = document.pages_mut().get(index);
 
         let mut page = match page_res {
@@ -213,6 +357,7 @@ pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
         if let Err(e) = add_clickable_link_on_page(&mut page, &job.verify_url, placement) {
             warnings.push(format!("Page {} link annotation failed: {}", index + 1, e));
         }
+        pages_marked += 1;
     }
 
     if let Err(e) = document.save_to_file(&job.output_pdf_path) {
@@ -222,10 +367,142 @@ pub fn run_pdf_publication(job: &PublicationJob) -> PublicationResult {
         );
     }
 
+    if job.render.conformance == PdfConformance::Pdfa2b {
+        // PDF/A-2b : un fichier non conforme n'est jamais laissé en sortie.
+        let output = std::path::Path::new(&job.output_pdf_path);
+        if let Err(e) = crate::pdfa::convert_to_pdfa2b(output) {
+            let _ = std::fs::remove_file(output);
+            return PublicationResult::err("PDFA_CONVERSION_FAILED", &e);
+        }
+        if let Err(issues) = crate::pdfa::validate_pdfa2b(output) {
+            let _ = std::fs::remove_file(output);
+            return PublicationResult::err(
+                "PDFA_VALIDATION_FAILED",
+                &format!("PDF/A-2b validation failed: {}", issues.join("; ")),
+            );
+        }
+    }
+
     PublicationResult::ok(
         job.output_pdf_path.clone(),
-        page_count as u32,
+        pages_marked,
         "pdfium-auto",
         warnings,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::parse_job;
+    use lopdf::{dictionary, Document, Object};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn temp_path(tag: &str, ext: &str) -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        std::env::temp_dir().join(format!(
+            "ho_publish_test_{tag}_{}_{}.{ext}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    /// PDF signé de `pages` pages (champ /Sig portant une valeur).
+    fn write_signed_pdf(path: &Path, pages: usize) {
+        let mut doc = Document::with_version("1.7");
+        let pages_id = doc.new_object_id();
+        let kids: Vec<Object> = (0..pages)
+            .map(|_| {
+                Object::Reference(doc.add_object(dictionary! {
+                    "Type" => "Page",
+                    "Parent" => pages_id,
+                    "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+                }))
+            })
+            .collect();
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => pages as i64,
+            }),
+        );
+        let sig_id = doc.add_object(dictionary! {
+            "Type" => "Sig",
+            "Filter" => "Adobe.PPKLite",
+            "ByteRange" => vec![0.into(), 10.into(), 20.into(), 30.into()],
+            "Contents" => Object::String(vec![0u8; 16], lopdf::StringFormat::Hexadecimal),
+        });
+        let field_id = doc.add_object(dictionary! {
+            "FT" => "Sig",
+            "T" => Object::string_literal("Signature1"),
+            "V" => sig_id,
+        });
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+            "AcroForm" => dictionary! { "Fields" => vec![Object::Reference(field_id)] },
+        });
+        doc.trailer.set("Root", catalog_id);
+        doc.save(path).unwrap();
+    }
+
+    fn preserve_job(source: &Path, output: &Path, cartouche: &Path, pages: &str) -> PublicationJob {
+        let raw = serde_json::json!({
+            "job_version": "1.0",
+            "job_type": "pdf_publication",
+            "source_pdf_path": source.to_string_lossy(),
+            "output_pdf_path": output.to_string_lossy(),
+            "cartouche_png_path": cartouche.to_string_lossy(),
+            "certificate_json_path": null,
+            "verify_txt_path": null,
+            "certificate_id": "cert-1",
+            "verify_url": "https://humanorigin.io/verify/cert-1",
+            "verdict": "human",
+            "render": {
+                "mode": "compact_cartouche",
+                "pages": pages,
+                "first_page_scale": 1.0,
+                "other_pages_scale": 0.85,
+                "anchor": "bottom_right",
+                "margin_pt": 34.0,
+                "signature_policy": "preserve"
+            }
+        });
+        parse_job(&raw.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_1_selection_first_marque_la_seule_page_0() {
+        assert!(page_is_marked(PageSelection::First, 0));
+        assert!(!page_is_marked(PageSelection::First, 1));
+        assert!((0..4).all(|i| page_is_marked(PageSelection::All, i)));
+    }
+
+    #[test]
+    fn test_2_pages_marked_compte_reel() {
+        let source = temp_path("src", "pdf");
+        let cartouche = temp_path("cartouche", "png");
+        write_signed_pdf(&source, 3);
+        image::RgbaImage::from_pixel(8, 12, image::Rgba([20, 40, 80, 255]))
+            .save(&cartouche)
+            .unwrap();
+
+        for (pages, expected) in [("first", 1), ("all", 3)] {
+            let output = temp_path("out", "pdf");
+            let job = preserve_job(&source, &output, &cartouche, pages);
+            let result = match apply_signature_policy(&job) {
+                Err(result) => *result,
+                Ok(_) => panic!("preserve doit publier en incrémental"),
+            };
+            assert!(result.ok, "{:?}", result.message);
+            assert_eq!(result.pages_marked, expected, "pages={pages}");
+            let _ = fs::remove_file(&output);
+        }
+        let _ = fs::remove_file(&source);
+        let _ = fs::remove_file(&cartouche);
+    }
+}
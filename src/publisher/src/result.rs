@@ -9,6 +9,9 @@ pub struct PublicationResult {
     pub warnings: Vec<String>,
     pub error_code: Option<String>,
     pub message: Option<String>,
+    /// Mode batch uniquement : fichier ou ligne JSONL d'origine du job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_source: Option<String>,
 }
 
 impl PublicationResult {
@@ -21,6 +24,7 @@ impl PublicationResult {
             warnings,
             error_code: None,
             message: None,
+            job_source: None,
         }
     }
 
@@ -33,6 +37,12 @@ impl PublicationResult {
             warnings: vec![],
             error_code: Some(code.to_string()),
             message: Some(message.to_string()),
+            job_source: None,
         }
     }
+
+    pub fn with_job_source(mut self, source: String) -> Self {
+        self.job_source = Some(source);
+        self
+    }
 }
\ No newline at end of file
@@ -33,7 +33,11 @@ function json(body: unknown, status = 200): Response {
   });
 }
 
-/** Recursive lexicographic key sort (RFC 8785 JCS). */
+/**
+ * Recursive key sort, serialized with JSON.stringify. NOT strict RFC 8785:
+ * integer-like keys ("1") are enumerated first by JS objects. Strict JCS is
+ * HO-JCS-V1 (docs/v2/V2_HO_JCS_V1_SPEC.md).
+ */
 function canonicalize(obj: unknown): unknown {
   if (obj === null || typeof obj !== "object") return obj;
   if (Array.isArray(obj)) return obj.map(canonicalize);